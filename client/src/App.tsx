@@ -89,7 +89,7 @@ function App() {
   const currentInputRef = useRef<InputState>({
     forward: false, backward: false, left: false, right: false,
     sprint: false, jump: false, attack: false, castSpell: false,
-    sequence: 0,
+    crouch: false, sequence: 0,
   });
   const lastSentInputState = useRef<Partial<InputState>>({});
   const animationFrameIdRef = useRef<number | null>(null); // For game loop
@@ -248,7 +248,7 @@ function App() {
   // --- Input State Management ---
   const keyMap: { [key: string]: keyof Omit<InputState, 'sequence' | 'castSpell'> } = {
     KeyW: 'forward', KeyS: 'backward', KeyA: 'left', KeyD: 'right',
-    ShiftLeft: 'sprint', Space: 'jump',
+    ShiftLeft: 'sprint', Space: 'jump', ControlLeft: 'crouch', KeyC: 'crouch',
   };
 
   const determineAnimation = useCallback((input: InputState): string => {
@@ -309,6 +309,7 @@ function App() {
       jump: !!currentInputState.jump,
       attack: !!currentInputState.attack,
       castSpell: !!currentInputState.castSpell,
+      crouch: !!currentInputState.crouch,
       sequence: currentInputState.sequence || 0,
     };
 
@@ -344,14 +345,13 @@ function App() {
       // Record timestamp for RTT calculation
       inputTimestampsRef.current.set(safeInputState.sequence, performance.now());
 
+      // The server rebuilds the aim direction from yaw and pitch
+      const pitchToSend = Math.asin(THREE.MathUtils.clamp(forwardVectorRef.current.y, -1, 1));
+
       conn.reducers.updatePlayerInput({
         input: safeInputState,
         clientYaw: yawToSend,
-        forwardVector: {
-          x: forwardVectorRef.current.x,
-          y: forwardVectorRef.current.y,
-          z: forwardVectorRef.current.z
-        },
+        clientPitch: pitchToSend,
         clientAnimation: currentAnimation
       });
       lastSentInputState.current = { ...safeInputState };
@@ -31,69 +31,1840 @@ import {
 } from "spacetimedb";
 
 // Import and reexport all reducer arg types
+import AbandonQuest from "./abandon_quest_reducer";
+export { AbandonQuest };
+import AcceptDuel from "./accept_duel_reducer";
+export { AcceptDuel };
+import AcceptFriendRequest from "./accept_friend_request_reducer";
+export { AcceptFriendRequest };
+import AcceptGuildInvite from "./accept_guild_invite_reducer";
+export { AcceptGuildInvite };
+import AcceptPartyInvite from "./accept_party_invite_reducer";
+export { AcceptPartyInvite };
+import AcceptQuest from "./accept_quest_reducer";
+export { AcceptQuest };
+import AddBoss from "./add_boss_reducer";
+export { AddBoss };
+import AddChest from "./add_chest_reducer";
+export { AddChest };
+import AddControlPoint from "./add_control_point_reducer";
+export { AddControlPoint };
+import AddDestructible from "./add_destructible_reducer";
+export { AddDestructible };
+import AddDoor from "./add_door_reducer";
+export { AddDoor };
+import AddItem from "./add_item_reducer";
+export { AddItem };
+import AddLever from "./add_lever_reducer";
+export { AddLever };
+import AddMovingPlatform from "./add_moving_platform_reducer";
+export { AddMovingPlatform };
+import AddNpcSpawner from "./add_npc_spawner_reducer";
+export { AddNpcSpawner };
+import AddResourceNode from "./add_resource_node_reducer";
+export { AddResourceNode };
+import AddSpawnPoint from "./add_spawn_point_reducer";
+export { AddSpawnPoint };
+import AddStaticCollider from "./add_static_collider_reducer";
+export { AddStaticCollider };
+import AddTradeItem from "./add_trade_item_reducer";
+export { AddTradeItem };
+import AddVehicle from "./add_vehicle_reducer";
+export { AddVehicle };
+import AddWaterVolume from "./add_water_volume_reducer";
+export { AddWaterVolume };
+import BanPlayer from "./ban_player_reducer";
+export { BanPlayer };
+import Blink from "./blink_reducer";
+export { Blink };
+import BlockPlayer from "./block_player_reducer";
+export { BlockPlayer };
+import BroadcastMessage from "./broadcast_message_reducer";
+export { BroadcastMessage };
+import BuyItem from "./buy_item_reducer";
+export { BuyItem };
+import CancelTrade from "./cancel_trade_reducer";
+export { CancelTrade };
+import CastHeal from "./cast_heal_reducer";
+export { CastHeal };
+import CastRejuvenate from "./cast_rejuvenate_reducer";
+export { CastRejuvenate };
+import CastShield from "./cast_shield_reducer";
+export { CastShield };
+import CastSpell from "./cast_spell_reducer";
+export { CastSpell };
+import CastUltimate from "./cast_ultimate_reducer";
+export { CastUltimate };
+import ChangeTeam from "./change_team_reducer";
+export { ChangeTeam };
+import ChangeUsername from "./change_username_reducer";
+export { ChangeUsername };
+import CheckDungeons from "./check_dungeons_reducer";
+export { CheckDungeons };
+import ClaimDailyReward from "./claim_daily_reward_reducer";
+export { ClaimDailyReward };
+import ClearAnticheatFlags from "./clear_anticheat_flags_reducer";
+export { ClearAnticheatFlags };
+import ConfirmTrade from "./confirm_trade_reducer";
+export { ConfirmTrade };
+import CraftItem from "./craft_item_reducer";
+export { CraftItem };
+import CreateGuild from "./create_guild_reducer";
+export { CreateGuild };
+import CreateInstance from "./create_instance_reducer";
+export { CreateInstance };
+import CustomizeCharacter from "./customize_character_reducer";
+export { CustomizeCharacter };
+import Dash from "./dash_reducer";
+export { Dash };
+import DeclineFriendRequest from "./decline_friend_request_reducer";
+export { DeclineFriendRequest };
+import DestroyInstance from "./destroy_instance_reducer";
+export { DestroyInstance };
+import DisbandGuild from "./disband_guild_reducer";
+export { DisbandGuild };
+import DismissPet from "./dismiss_pet_reducer";
+export { DismissPet };
+import Dismount from "./dismount_reducer";
+export { Dismount };
+import DriveInput from "./drive_input_reducer";
+export { DriveInput };
+import DropItem from "./drop_item_reducer";
+export { DropItem };
+import DuelRequest from "./duel_request_reducer";
+export { DuelRequest };
+import EndMatch from "./end_match_reducer";
+export { EndMatch };
+import EnterInstance from "./enter_instance_reducer";
+export { EnterInstance };
+import EnterVehicle from "./enter_vehicle_reducer";
+export { EnterVehicle };
+import EquipItem from "./equip_item_reducer";
+export { EquipItem };
+import ExitVehicle from "./exit_vehicle_reducer";
+export { ExitVehicle };
+import FireHitscan from "./fire_hitscan_reducer";
+export { FireHitscan };
+import FireProjectile from "./fire_projectile_reducer";
+export { FireProjectile };
 import GameTick from "./game_tick_reducer";
 export { GameTick };
+import GatherResource from "./gather_resource_reducer";
+export { GatherResource };
+import GenerateDungeon from "./generate_dungeon_reducer";
+export { GenerateDungeon };
 import IdentityConnected from "./identity_connected_reducer";
 export { IdentityConnected };
 import IdentityDisconnected from "./identity_disconnected_reducer";
 export { IdentityDisconnected };
+import InflictStatusEffect from "./inflict_status_effect_reducer";
+export { InflictStatusEffect };
+import InitiateTrade from "./initiate_trade_reducer";
+export { InitiateTrade };
+import Interact from "./interact_reducer";
+export { Interact };
+import InviteToGuild from "./invite_to_guild_reducer";
+export { InviteToGuild };
+import InviteToParty from "./invite_to_party_reducer";
+export { InviteToParty };
+import KickFromGuild from "./kick_from_guild_reducer";
+export { KickFromGuild };
+import KickPlayer from "./kick_player_reducer";
+export { KickPlayer };
+import LeaveGuild from "./leave_guild_reducer";
+export { LeaveGuild };
+import LeaveJoinQueue from "./leave_join_queue_reducer";
+export { LeaveJoinQueue };
+import LeaveParty from "./leave_party_reducer";
+export { LeaveParty };
+import LeaveQueue from "./leave_queue_reducer";
+export { LeaveQueue };
+import MeleeAttack from "./melee_attack_reducer";
+export { MeleeAttack };
+import Mount from "./mount_reducer";
+export { Mount };
+import MovePlayerToInstance from "./move_player_to_instance_reducer";
+export { MovePlayerToInstance };
+import MutePlayer from "./mute_player_reducer";
+export { MutePlayer };
+import OpenChest from "./open_chest_reducer";
+export { OpenChest };
+import PickupItem from "./pickup_item_reducer";
+export { PickupItem };
+import PromoteGuildMember from "./promote_guild_member_reducer";
+export { PromoteGuildMember };
+import PruneAuditLog from "./prune_audit_log_reducer";
+export { PruneAuditLog };
+import PruneChatMessages from "./prune_chat_messages_reducer";
+export { PruneChatMessages };
+import PruneLoggedOutPlayers from "./prune_logged_out_players_reducer";
+export { PruneLoggedOutPlayers };
+import PurgeLoggedOutPlayer from "./purge_logged_out_player_reducer";
+export { PurgeLoggedOutPlayer };
+import QueueForMatch from "./queue_for_match_reducer";
+export { QueueForMatch };
+import RecomputeLeaderboards from "./recompute_leaderboards_reducer";
+export { RecomputeLeaderboards };
+import RefillNpcSpawners from "./refill_npc_spawners_reducer";
+export { RefillNpcSpawners };
 import RegisterPlayer from "./register_player_reducer";
 export { RegisterPlayer };
+import RemoveBot from "./remove_bot_reducer";
+export { RemoveBot };
+import RemoveControlPoint from "./remove_control_point_reducer";
+export { RemoveControlPoint };
+import RemoveCraftingRecipe from "./remove_crafting_recipe_reducer";
+export { RemoveCraftingRecipe };
+import RemoveDestructible from "./remove_destructible_reducer";
+export { RemoveDestructible };
+import RemoveFriend from "./remove_friend_reducer";
+export { RemoveFriend };
+import RemoveInteractable from "./remove_interactable_reducer";
+export { RemoveInteractable };
+import RemoveMovingPlatform from "./remove_moving_platform_reducer";
+export { RemoveMovingPlatform };
+import RemoveNpcSpawner from "./remove_npc_spawner_reducer";
+export { RemoveNpcSpawner };
+import RemovePortal from "./remove_portal_reducer";
+export { RemovePortal };
+import RemoveResourceNode from "./remove_resource_node_reducer";
+export { RemoveResourceNode };
+import RemoveSpawnPoint from "./remove_spawn_point_reducer";
+export { RemoveSpawnPoint };
+import RemoveStaticCollider from "./remove_static_collider_reducer";
+export { RemoveStaticCollider };
+import RemoveVehicle from "./remove_vehicle_reducer";
+export { RemoveVehicle };
+import RemoveWaterVolume from "./remove_water_volume_reducer";
+export { RemoveWaterVolume };
+import RemoveZone from "./remove_zone_reducer";
+export { RemoveZone };
+import RunBossAbility from "./run_boss_ability_reducer";
+export { RunBossAbility };
+import RunMatchmaker from "./run_matchmaker_reducer";
+export { RunMatchmaker };
+import SellItem from "./sell_item_reducer";
+export { SellItem };
+import SendChat from "./send_chat_reducer";
+export { SendChat };
+import SendChatMessage from "./send_chat_message_reducer";
+export { SendChatMessage };
+import SendFriendRequest from "./send_friend_request_reducer";
+export { SendFriendRequest };
+import SetAfkThresholds from "./set_afk_thresholds_reducer";
+export { SetAfkThresholds };
+import SetBotDifficulty from "./set_bot_difficulty_reducer";
+export { SetBotDifficulty };
+import SetCraftingRecipe from "./set_crafting_recipe_reducer";
+export { SetCraftingRecipe };
+import SetFlagHome from "./set_flag_home_reducer";
+export { SetFlagHome };
+import SetFriendlyFire from "./set_friendly_fire_reducer";
+export { SetFriendlyFire };
+import SetGameMode from "./set_game_mode_reducer";
+export { SetGameMode };
+import SetGravity from "./set_gravity_reducer";
+export { SetGravity };
+import SetLoggedOutRetention from "./set_logged_out_retention_reducer";
+export { SetLoggedOutRetention };
+import SetLootTable from "./set_loot_table_reducer";
+export { SetLootTable };
+import SetMaxNpcs from "./set_max_npcs_reducer";
+export { SetMaxNpcs };
+import SetMaxPartySize from "./set_max_party_size_reducer";
+export { SetMaxPartySize };
+import SetMaxPlayers from "./set_max_players_reducer";
+export { SetMaxPlayers };
+import SetPlayerHealth from "./set_player_health_reducer";
+export { SetPlayerHealth };
+import SetRegenConfig from "./set_regen_config_reducer";
+export { SetRegenConfig };
+import SetSpawnStrategy from "./set_spawn_strategy_reducer";
+export { SetSpawnStrategy };
+import SetTeamSwapRules from "./set_team_swap_rules_reducer";
+export { SetTeamSwapRules };
+import SetTickRate from "./set_tick_rate_reducer";
+export { SetTickRate };
+import SetTradeGold from "./set_trade_gold_reducer";
+export { SetTradeGold };
+import SetWinConditions from "./set_win_conditions_reducer";
+export { SetWinConditions };
+import SpawnBot from "./spawn_bot_reducer";
+export { SpawnBot };
 import SpawnProjectile from "./spawn_projectile_reducer";
 export { SpawnProjectile };
+import StartMatch from "./start_match_reducer";
+export { StartMatch };
+import StartRankedSeason from "./start_ranked_season_reducer";
+export { StartRankedSeason };
+import SummonPet from "./summon_pet_reducer";
+export { SummonPet };
+import Taunt from "./taunt_reducer";
+export { Taunt };
+import TeleportPlayer from "./teleport_player_reducer";
+export { TeleportPlayer };
+import ToggleStealth from "./toggle_stealth_reducer";
+export { ToggleStealth };
+import UnbanPlayer from "./unban_player_reducer";
+export { UnbanPlayer };
+import UnblockPlayer from "./unblock_player_reducer";
+export { UnblockPlayer };
+import UnequipItem from "./unequip_item_reducer";
+export { UnequipItem };
+import UnmutePlayer from "./unmute_player_reducer";
+export { UnmutePlayer };
 import UpdatePlayerInput from "./update_player_input_reducer";
 export { UpdatePlayerInput };
+import UseAbility from "./use_ability_reducer";
+export { UseAbility };
 
 // Import and reexport all procedure arg types
 
 // Import and reexport all table handle types
+import AbilityCooldownRow from "./ability_cooldown_table";
+export { AbilityCooldownRow };
+import AchievementCounterRow from "./achievement_counter_table";
+export { AchievementCounterRow };
+import AchievementDefinitionRow from "./achievement_definition_table";
+export { AchievementDefinitionRow };
+import AchievementUnlockedEventRow from "./achievement_unlocked_event_table";
+export { AchievementUnlockedEventRow };
+import AdminRow from "./admin_table";
+export { AdminRow };
+import AfkStatusEventRow from "./afk_status_event_table";
+export { AfkStatusEventRow };
+import AnticheatEventRow from "./anticheat_event_table";
+export { AnticheatEventRow };
+import AnticheatFlagRow from "./anticheat_flag_table";
+export { AnticheatFlagRow };
+import ArenaRow from "./arena_table";
+export { ArenaRow };
+import AuditCleanupScheduleRow from "./audit_cleanup_schedule_table";
+export { AuditCleanupScheduleRow };
+import AuditLogRow from "./audit_log_table";
+export { AuditLogRow };
+import BanRow from "./ban_table";
+export { BanRow };
+import BossAbilityScheduleRow from "./boss_ability_schedule_table";
+export { BossAbilityScheduleRow };
+import BossContributionRow from "./boss_contribution_table";
+export { BossContributionRow };
+import BossEncounterRow from "./boss_encounter_table";
+export { BossEncounterRow };
+import BossEventRow from "./boss_event_table";
+export { BossEventRow };
+import BotRow from "./bot_table";
+export { BotRow };
+import CareerStatsRow from "./career_stats_table";
+export { CareerStatsRow };
+import ChatCleanupScheduleRow from "./chat_cleanup_schedule_table";
+export { ChatCleanupScheduleRow };
+import ChatDeliveryRow from "./chat_delivery_table";
+export { ChatDeliveryRow };
+import ChatMessageRow from "./chat_message_table";
+export { ChatMessageRow };
+import ClassDefinitionRow from "./class_definition_table";
+export { ClassDefinitionRow };
+import CombatEventRow from "./combat_event_table";
+export { CombatEventRow };
+import ControlPointRow from "./control_point_table";
+export { ControlPointRow };
+import CraftingRecipeRow from "./crafting_recipe_table";
+export { CraftingRecipeRow };
+import CustomizationCooldownRow from "./customization_cooldown_table";
+export { CustomizationCooldownRow };
+import DamageEventRow from "./damage_event_table";
+export { DamageEventRow };
+import DestructibleRow from "./destructible_table";
+export { DestructibleRow };
+import DroppedItemRow from "./dropped_item_table";
+export { DroppedItemRow };
+import DuelRow from "./duel_table";
+export { DuelRow };
+import DungeonRow from "./dungeon_table";
+export { DungeonRow };
+import DungeonChestRow from "./dungeon_chest_table";
+export { DungeonChestRow };
+import DungeonScheduleRow from "./dungeon_schedule_table";
+export { DungeonScheduleRow };
+import ExplosionEventRow from "./explosion_event_table";
+export { ExplosionEventRow };
+import FirePatchRow from "./fire_patch_table";
+export { FirePatchRow };
+import FlagRow from "./flag_table";
+export { FlagRow };
+import FlagCapturedEventRow from "./flag_captured_event_table";
+export { FlagCapturedEventRow };
+import FlagTakenEventRow from "./flag_taken_event_table";
+export { FlagTakenEventRow };
+import FriendRequestRow from "./friend_request_table";
+export { FriendRequestRow };
+import FriendStatusRow from "./friend_status_table";
+export { FriendStatusRow };
+import FriendshipRow from "./friendship_table";
+export { FriendshipRow };
+import GameConfigRow from "./game_config_table";
+export { GameConfigRow };
 import GameTickScheduleRow from "./game_tick_schedule_table";
 export { GameTickScheduleRow };
+import GuildRow from "./guild_table";
+export { GuildRow };
+import GuildInviteRow from "./guild_invite_table";
+export { GuildInviteRow };
+import GuildMemberRow from "./guild_member_table";
+export { GuildMemberRow };
+import HealEventRow from "./heal_event_table";
+export { HealEventRow };
+import HitscanTraceRow from "./hitscan_trace_table";
+export { HitscanTraceRow };
+import InstanceRow from "./instance_table";
+export { InstanceRow };
+import InteractableRow from "./interactable_table";
+export { InteractableRow };
+import ItemDefinitionRow from "./item_definition_table";
+export { ItemDefinitionRow };
+import JoinQueueRow from "./join_queue_table";
+export { JoinQueueRow };
+import JumpPadRow from "./jump_pad_table";
+export { JumpPadRow };
+import KillFeedRow from "./kill_feed_table";
+export { KillFeedRow };
+import LeaderboardEntryRow from "./leaderboard_entry_table";
+export { LeaderboardEntryRow };
+import LeaderboardScheduleRow from "./leaderboard_schedule_table";
+export { LeaderboardScheduleRow };
+import LoggedOutCleanupScheduleRow from "./logged_out_cleanup_schedule_table";
+export { LoggedOutCleanupScheduleRow };
 import LoggedOutPlayerRow from "./logged_out_player_table";
 export { LoggedOutPlayerRow };
+import LoginStreakRow from "./login_streak_table";
+export { LoginStreakRow };
+import LootTableRow from "./loot_table_table";
+export { LootTableRow };
+import MatchHistoryRow from "./match_history_table";
+export { MatchHistoryRow };
+import MatchInfoRow from "./match_info_table";
+export { MatchInfoRow };
+import MatchInstanceRow from "./match_instance_table";
+export { MatchInstanceRow };
+import MatchResultRow from "./match_result_table";
+export { MatchResultRow };
+import MatchStateRow from "./match_state_table";
+export { MatchStateRow };
+import MatchmakingQueueRow from "./matchmaking_queue_table";
+export { MatchmakingQueueRow };
+import MatchmakingScheduleRow from "./matchmaking_schedule_table";
+export { MatchmakingScheduleRow };
+import MinimapPingRow from "./minimap_ping_table";
+export { MinimapPingRow };
+import MovingPlatformRow from "./moving_platform_table";
+export { MovingPlatformRow };
+import MuteRow from "./mute_table";
+export { MuteRow };
+import MyBlocksRow from "./my_blocks_table";
+export { MyBlocksRow };
+import MyChatRow from "./my_chat_table";
+export { MyChatRow };
+import NavGridRow from "./nav_grid_table";
+export { NavGridRow };
+import NpcRow from "./npc_table";
+export { NpcRow };
+import NpcSpawnerRow from "./npc_spawner_table";
+export { NpcSpawnerRow };
+import NpcSpawnerScheduleRow from "./npc_spawner_schedule_table";
+export { NpcSpawnerScheduleRow };
+import OneWayPlatformRow from "./one_way_platform_table";
+export { OneWayPlatformRow };
+import OpsLogRow from "./ops_log_table";
+export { OpsLogRow };
+import PartyRow from "./party_table";
+export { PartyRow };
+import PartyInviteRow from "./party_invite_table";
+export { PartyInviteRow };
+import PartyMemberRow from "./party_member_table";
+export { PartyMemberRow };
+import PendingDuelRow from "./pending_duel_table";
+export { PendingDuelRow };
+import PetRow from "./pet_table";
+export { PetRow };
+import PickupRow from "./pickup_table";
+export { PickupRow };
 import PlayerRow from "./player_table";
 export { PlayerRow };
+import PlayerAchievementRow from "./player_achievement_table";
+export { PlayerAchievementRow };
+import PlayerBlockRow from "./player_block_table";
+export { PlayerBlockRow };
+import PlayerInventoryRow from "./player_inventory_table";
+export { PlayerInventoryRow };
+import PlayerQuestProgressRow from "./player_quest_progress_table";
+export { PlayerQuestProgressRow };
+import PlayerStatsRow from "./player_stats_table";
+export { PlayerStatsRow };
+import PortalRow from "./portal_table";
+export { PortalRow };
 import ProjectileRow from "./projectile_table";
 export { ProjectileRow };
+import ProjectileImpactRow from "./projectile_impact_table";
+export { ProjectileImpactRow };
+import ProjectileSpawnLogRow from "./projectile_spawn_log_table";
+export { ProjectileSpawnLogRow };
+import QuestDefinitionRow from "./quest_definition_table";
+export { QuestDefinitionRow };
+import RankedResultRow from "./ranked_result_table";
+export { RankedResultRow };
+import ResourceNodeRow from "./resource_node_table";
+export { ResourceNodeRow };
+import ShieldBreakEventRow from "./shield_break_event_table";
+export { ShieldBreakEventRow };
+import ShopListingRow from "./shop_listing_table";
+export { ShopListingRow };
+import ShopTransactionRow from "./shop_transaction_table";
+export { ShopTransactionRow };
+import SpawnPointRow from "./spawn_point_table";
+export { SpawnPointRow };
+import SpellCooldownRow from "./spell_cooldown_table";
+export { SpellCooldownRow };
+import SpellDefinitionRow from "./spell_definition_table";
+export { SpellDefinitionRow };
+import StaticColliderRow from "./static_collider_table";
+export { StaticColliderRow };
+import StatusEffectRow from "./status_effect_table";
+export { StatusEffectRow };
+import TeamScoreRow from "./team_score_table";
+export { TeamScoreRow };
+import TeleportEventRow from "./teleport_event_table";
+export { TeleportEventRow };
+import ThreatRow from "./threat_table";
+export { ThreatRow };
+import TickStateRow from "./tick_state_table";
+export { TickStateRow };
+import TradeSessionRow from "./trade_session_table";
+export { TradeSessionRow };
+import VehicleRow from "./vehicle_table";
+export { VehicleRow };
+import VendorRow from "./vendor_table";
+export { VendorRow };
+import WaterVolumeRow from "./water_volume_table";
+export { WaterVolumeRow };
+import ZoneRow from "./zone_table";
+export { ZoneRow };
 
 // Import and reexport all types
+import AbilityCooldown from "./ability_cooldown_type";
+export { AbilityCooldown };
+import AchievementCounter from "./achievement_counter_type";
+export { AchievementCounter };
+import AchievementDefinition from "./achievement_definition_type";
+export { AchievementDefinition };
+import AchievementStat from "./achievement_stat_type";
+export { AchievementStat };
+import AchievementUnlockedEvent from "./achievement_unlocked_event_type";
+export { AchievementUnlockedEvent };
+import Admin from "./admin_type";
+export { Admin };
+import AfkStatusEvent from "./afk_status_event_type";
+export { AfkStatusEvent };
+import AnticheatEvent from "./anticheat_event_type";
+export { AnticheatEvent };
+import AnticheatFlag from "./anticheat_flag_type";
+export { AnticheatFlag };
+import AnticheatReason from "./anticheat_reason_type";
+export { AnticheatReason };
+import Appearance from "./appearance_type";
+export { Appearance };
+import Arena from "./arena_type";
+export { Arena };
+import AuditCategory from "./audit_category_type";
+export { AuditCategory };
+import AuditCleanupSchedule from "./audit_cleanup_schedule_type";
+export { AuditCleanupSchedule };
+import AuditLog from "./audit_log_type";
+export { AuditLog };
+import Ban from "./ban_type";
+export { Ban };
+import BossAbility from "./boss_ability_type";
+export { BossAbility };
+import BossAbilitySchedule from "./boss_ability_schedule_type";
+export { BossAbilitySchedule };
+import BossContribution from "./boss_contribution_type";
+export { BossContribution };
+import BossEncounter from "./boss_encounter_type";
+export { BossEncounter };
+import BossEvent from "./boss_event_type";
+export { BossEvent };
+import BossEventKind from "./boss_event_kind_type";
+export { BossEventKind };
+import Bot from "./bot_type";
+export { Bot };
+import BufferedInput from "./buffered_input_type";
+export { BufferedInput };
+import CareerStats from "./career_stats_type";
+export { CareerStats };
+import ChatChannel from "./chat_channel_type";
+export { ChatChannel };
+import ChatCleanupSchedule from "./chat_cleanup_schedule_type";
+export { ChatCleanupSchedule };
+import ChatDelivery from "./chat_delivery_type";
+export { ChatDelivery };
+import ChatMessage from "./chat_message_type";
+export { ChatMessage };
+import ClassDefinition from "./class_definition_type";
+export { ClassDefinition };
+import CombatAction from "./combat_action_type";
+export { CombatAction };
+import CombatEvent from "./combat_event_type";
+export { CombatEvent };
+import ControlPoint from "./control_point_type";
+export { ControlPoint };
+import CraftingRecipe from "./crafting_recipe_type";
+export { CraftingRecipe };
+import CustomizationCooldown from "./customization_cooldown_type";
+export { CustomizationCooldown };
+import DamageEvent from "./damage_event_type";
+export { DamageEvent };
+import Destructible from "./destructible_type";
+export { Destructible };
+import DroppedItem from "./dropped_item_type";
+export { DroppedItem };
+import Duel from "./duel_type";
+export { Duel };
+import Dungeon from "./dungeon_type";
+export { Dungeon };
+import DungeonChest from "./dungeon_chest_type";
+export { DungeonChest };
+import DungeonSchedule from "./dungeon_schedule_type";
+export { DungeonSchedule };
+import DungeonState from "./dungeon_state_type";
+export { DungeonState };
+import EquipSlot from "./equip_slot_type";
+export { EquipSlot };
+import ExplosionEvent from "./explosion_event_type";
+export { ExplosionEvent };
+import FirePatch from "./fire_patch_type";
+export { FirePatch };
+import Flag from "./flag_type";
+export { Flag };
+import FlagCapturedEvent from "./flag_captured_event_type";
+export { FlagCapturedEvent };
+import FlagTakenEvent from "./flag_taken_event_type";
+export { FlagTakenEvent };
+import FriendRequest from "./friend_request_type";
+export { FriendRequest };
+import FriendStatus from "./friend_status_type";
+export { FriendStatus };
+import Friendship from "./friendship_type";
+export { Friendship };
+import GameConfig from "./game_config_type";
+export { GameConfig };
+import GameMode from "./game_mode_type";
+export { GameMode };
 import GameTickSchedule from "./game_tick_schedule_type";
 export { GameTickSchedule };
+import Guild from "./guild_type";
+export { Guild };
+import GuildInvite from "./guild_invite_type";
+export { GuildInvite };
+import GuildMember from "./guild_member_type";
+export { GuildMember };
+import GuildRole from "./guild_role_type";
+export { GuildRole };
+import HealEvent from "./heal_event_type";
+export { HealEvent };
+import HitscanTraceEvent from "./hitscan_trace_event_type";
+export { HitscanTraceEvent };
+import ImpactBehavior from "./impact_behavior_type";
+export { ImpactBehavior };
+import ImpactTarget from "./impact_target_type";
+export { ImpactTarget };
 import InputState from "./input_state_type";
 export { InputState };
+import Instance from "./instance_type";
+export { Instance };
+import InstanceKind from "./instance_kind_type";
+export { InstanceKind };
+import Interactable from "./interactable_type";
+export { Interactable };
+import InteractableKind from "./interactable_kind_type";
+export { InteractableKind };
+import ItemDefinition from "./item_definition_type";
+export { ItemDefinition };
+import JoinQueueEntry from "./join_queue_entry_type";
+export { JoinQueueEntry };
+import JumpPad from "./jump_pad_type";
+export { JumpPad };
+import KillFeedEntry from "./kill_feed_entry_type";
+export { KillFeedEntry };
+import LeaderboardCategory from "./leaderboard_category_type";
+export { LeaderboardCategory };
+import LeaderboardEntry from "./leaderboard_entry_type";
+export { LeaderboardEntry };
+import LeaderboardSchedule from "./leaderboard_schedule_type";
+export { LeaderboardSchedule };
+import LoggedOutCleanupSchedule from "./logged_out_cleanup_schedule_type";
+export { LoggedOutCleanupSchedule };
 import LoggedOutPlayerData from "./logged_out_player_data_type";
 export { LoggedOutPlayerData };
+import LoginStreak from "./login_streak_type";
+export { LoginStreak };
+import LootTable from "./loot_table_type";
+export { LootTable };
+import LootTableEntry from "./loot_table_entry_type";
+export { LootTableEntry };
+import MatchEndReason from "./match_end_reason_type";
+export { MatchEndReason };
+import MatchHistory from "./match_history_type";
+export { MatchHistory };
+import MatchInfo from "./match_info_type";
+export { MatchInfo };
+import MatchInstance from "./match_instance_type";
+export { MatchInstance };
+import MatchInstanceState from "./match_instance_state_type";
+export { MatchInstanceState };
+import MatchPhase from "./match_phase_type";
+export { MatchPhase };
+import MatchPlayerResult from "./match_player_result_type";
+export { MatchPlayerResult };
+import MatchResult from "./match_result_type";
+export { MatchResult };
+import MatchState from "./match_state_type";
+export { MatchState };
+import MatchmakingQueue from "./matchmaking_queue_type";
+export { MatchmakingQueue };
+import MatchmakingSchedule from "./matchmaking_schedule_type";
+export { MatchmakingSchedule };
+import MinimapPing from "./minimap_ping_type";
+export { MinimapPing };
+import MovingPlatform from "./moving_platform_type";
+export { MovingPlatform };
+import Mute from "./mute_type";
+export { Mute };
+import NavGrid from "./nav_grid_type";
+export { NavGrid };
+import Npc from "./npc_type";
+export { Npc };
+import NpcSpawner from "./npc_spawner_type";
+export { NpcSpawner };
+import NpcSpawnerSchedule from "./npc_spawner_schedule_type";
+export { NpcSpawnerSchedule };
+import NpcState from "./npc_state_type";
+export { NpcState };
+import NpcType from "./npc_type_type";
+export { NpcType };
+import OneWayPlatform from "./one_way_platform_type";
+export { OneWayPlatform };
+import OpsAction from "./ops_action_type";
+export { OpsAction };
+import OpsLog from "./ops_log_type";
+export { OpsLog };
+import Party from "./party_type";
+export { Party };
+import PartyInvite from "./party_invite_type";
+export { PartyInvite };
+import PartyMember from "./party_member_type";
+export { PartyMember };
+import PendingDuel from "./pending_duel_type";
+export { PendingDuel };
+import PersistentPlayerState from "./persistent_player_state_type";
+export { PersistentPlayerState };
+import Pet from "./pet_type";
+export { Pet };
+import PetTarget from "./pet_target_type";
+export { PetTarget };
+import PetType from "./pet_type_type";
+export { PetType };
+import PickupData from "./pickup_data_type";
+export { PickupData };
+import PickupKind from "./pickup_kind_type";
+export { PickupKind };
+import PlayerAchievement from "./player_achievement_type";
+export { PlayerAchievement };
+import PlayerBlock from "./player_block_type";
+export { PlayerBlock };
 import PlayerData from "./player_data_type";
 export { PlayerData };
+import PlayerInventory from "./player_inventory_type";
+export { PlayerInventory };
+import PlayerQuestProgress from "./player_quest_progress_type";
+export { PlayerQuestProgress };
+import PlayerStats from "./player_stats_type";
+export { PlayerStats };
+import Portal from "./portal_type";
+export { Portal };
 import ProjectileData from "./projectile_data_type";
 export { ProjectileData };
+import ProjectileImpactEvent from "./projectile_impact_event_type";
+export { ProjectileImpactEvent };
+import ProjectileKind from "./projectile_kind_type";
+export { ProjectileKind };
+import ProjectileSpawnLog from "./projectile_spawn_log_type";
+export { ProjectileSpawnLog };
+import QuestDefinition from "./quest_definition_type";
+export { QuestDefinition };
+import QuestItemReward from "./quest_item_reward_type";
+export { QuestItemReward };
+import QuestObjective from "./quest_objective_type";
+export { QuestObjective };
+import QuestObjectiveKind from "./quest_objective_kind_type";
+export { QuestObjectiveKind };
+import QuestStatus from "./quest_status_type";
+export { QuestStatus };
+import RankedResult from "./ranked_result_type";
+export { RankedResult };
+import RecipeIngredient from "./recipe_ingredient_type";
+export { RecipeIngredient };
+import ResourceKind from "./resource_kind_type";
+export { ResourceKind };
+import ResourceNode from "./resource_node_type";
+export { ResourceNode };
+import ShieldBreakEvent from "./shield_break_event_type";
+export { ShieldBreakEvent };
+import ShopAction from "./shop_action_type";
+export { ShopAction };
+import ShopListing from "./shop_listing_type";
+export { ShopListing };
+import ShopTransaction from "./shop_transaction_type";
+export { ShopTransaction };
+import SpawnPoint from "./spawn_point_type";
+export { SpawnPoint };
+import SpawnStrategy from "./spawn_strategy_type";
+export { SpawnStrategy };
+import SpellCooldown from "./spell_cooldown_type";
+export { SpellCooldown };
+import SpellDefinition from "./spell_definition_type";
+export { SpellDefinition };
+import StaticCollider from "./static_collider_type";
+export { StaticCollider };
+import StatusEffect from "./status_effect_type";
+export { StatusEffect };
+import StatusEffectKind from "./status_effect_kind_type";
+export { StatusEffectKind };
+import StatusOnHit from "./status_on_hit_type";
+export { StatusOnHit };
+import TeamScore from "./team_score_type";
+export { TeamScore };
+import TeleportEvent from "./teleport_event_type";
+export { TeleportEvent };
+import Threat from "./threat_type";
+export { Threat };
+import TickState from "./tick_state_type";
+export { TickState };
+import TradeItem from "./trade_item_type";
+export { TradeItem };
+import TradeOffer from "./trade_offer_type";
+export { TradeOffer };
+import TradePhase from "./trade_phase_type";
+export { TradePhase };
+import TradeSession from "./trade_session_type";
+export { TradeSession };
 import Vector3 from "./vector_3_type";
 export { Vector3 };
+import Vehicle from "./vehicle_type";
+export { Vehicle };
+import VehicleType from "./vehicle_type_type";
+export { VehicleType };
+import Vendor from "./vendor_type";
+export { Vendor };
+import WaterVolume from "./water_volume_type";
+export { WaterVolume };
+import Zone from "./zone_type";
+export { Zone };
 
 /** The schema information for all tables in this module. This is defined the same was as the tables would have been defined in the server. */
 const tablesSchema = __schema(
+  __table({
+    name: 'ability_cooldown',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+      { name: 'identity', algorithm: 'btree', columns: [
+        'identity',
+      ] },
+    ],
+    constraints: [
+      { name: 'ability_cooldown_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, AbilityCooldownRow),
+  __table({
+    name: 'achievement_counter',
+    indexes: [
+      { name: 'identity', algorithm: 'btree', columns: [
+        'identity',
+      ] },
+    ],
+    constraints: [
+      { name: 'achievement_counter_identity_key', constraint: 'unique', columns: ['identity'] },
+    ],
+  }, AchievementCounterRow),
+  __table({
+    name: 'achievement_definition',
+    indexes: [
+      { name: 'achievement_id', algorithm: 'btree', columns: [
+        'achievementId',
+      ] },
+    ],
+    constraints: [
+      { name: 'achievement_definition_achievement_id_key', constraint: 'unique', columns: ['achievementId'] },
+    ],
+  }, AchievementDefinitionRow),
+  __table({
+    name: 'achievement_unlocked_event',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+    ],
+    constraints: [
+      { name: 'achievement_unlocked_event_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, AchievementUnlockedEventRow),
+  __table({
+    name: 'admin',
+    indexes: [
+      { name: 'identity', algorithm: 'btree', columns: [
+        'identity',
+      ] },
+    ],
+    constraints: [
+      { name: 'admin_identity_key', constraint: 'unique', columns: ['identity'] },
+    ],
+  }, AdminRow),
+  __table({
+    name: 'afk_status_event',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+    ],
+    constraints: [
+      { name: 'afk_status_event_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, AfkStatusEventRow),
+  __table({
+    name: 'anticheat_event',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+    ],
+    constraints: [
+      { name: 'anticheat_event_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, AnticheatEventRow),
+  __table({
+    name: 'anticheat_flag',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+      { name: 'identity', algorithm: 'btree', columns: [
+        'identity',
+      ] },
+    ],
+    constraints: [
+      { name: 'anticheat_flag_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, AnticheatFlagRow),
+  __table({
+    name: 'arena',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+    ],
+    constraints: [
+      { name: 'arena_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, ArenaRow),
+  __table({
+    name: 'audit_cleanup_schedule',
+    indexes: [
+      { name: 'scheduled_id', algorithm: 'btree', columns: [
+        'scheduledId',
+      ] },
+    ],
+    constraints: [
+      { name: 'audit_cleanup_schedule_scheduled_id_key', constraint: 'unique', columns: ['scheduledId'] },
+    ],
+  }, AuditCleanupScheduleRow),
+  __table({
+    name: 'audit_log',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+    ],
+    constraints: [
+      { name: 'audit_log_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, AuditLogRow),
+  __table({
+    name: 'ban',
+    indexes: [
+      { name: 'identity', algorithm: 'btree', columns: [
+        'identity',
+      ] },
+    ],
+    constraints: [
+      { name: 'ban_identity_key', constraint: 'unique', columns: ['identity'] },
+    ],
+  }, BanRow),
+  __table({
+    name: 'boss_ability_schedule',
+    indexes: [
+      { name: 'scheduled_id', algorithm: 'btree', columns: [
+        'scheduledId',
+      ] },
+      { name: 'npc_id', algorithm: 'btree', columns: [
+        'npcId',
+      ] },
+    ],
+    constraints: [
+      { name: 'boss_ability_schedule_scheduled_id_key', constraint: 'unique', columns: ['scheduledId'] },
+    ],
+  }, BossAbilityScheduleRow),
+  __table({
+    name: 'boss_contribution',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+      { name: 'npc_id', algorithm: 'btree', columns: [
+        'npcId',
+      ] },
+    ],
+    constraints: [
+      { name: 'boss_contribution_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, BossContributionRow),
+  __table({
+    name: 'boss_encounter',
+    indexes: [
+      { name: 'npc_id', algorithm: 'btree', columns: [
+        'npcId',
+      ] },
+      { name: 'instance_id', algorithm: 'btree', columns: [
+        'instanceId',
+      ] },
+    ],
+    constraints: [
+      { name: 'boss_encounter_npc_id_key', constraint: 'unique', columns: ['npcId'] },
+    ],
+  }, BossEncounterRow),
+  __table({
+    name: 'boss_event',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+    ],
+    constraints: [
+      { name: 'boss_event_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, BossEventRow),
+  __table({
+    name: 'bot',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+    ],
+    constraints: [
+      { name: 'bot_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, BotRow),
+  __table({
+    name: 'career_stats',
+    indexes: [
+      { name: 'identity', algorithm: 'btree', columns: [
+        'identity',
+      ] },
+    ],
+    constraints: [
+      { name: 'career_stats_identity_key', constraint: 'unique', columns: ['identity'] },
+    ],
+  }, CareerStatsRow),
+  __table({
+    name: 'chat_cleanup_schedule',
+    indexes: [
+      { name: 'scheduled_id', algorithm: 'btree', columns: [
+        'scheduledId',
+      ] },
+    ],
+    constraints: [
+      { name: 'chat_cleanup_schedule_scheduled_id_key', constraint: 'unique', columns: ['scheduledId'] },
+    ],
+  }, ChatCleanupScheduleRow),
+  __table({
+    name: 'chat_delivery',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+      { name: 'recipient', algorithm: 'btree', columns: [
+        'recipient',
+      ] },
+    ],
+    constraints: [
+      { name: 'chat_delivery_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, ChatDeliveryRow),
+  __table({
+    name: 'chat_message',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+      { name: 'sender', algorithm: 'btree', columns: [
+        'sender',
+      ] },
+    ],
+    constraints: [
+      { name: 'chat_message_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, ChatMessageRow),
+  __table({
+    name: 'class_definition',
+    indexes: [
+      { name: 'name', algorithm: 'btree', columns: [
+        'name',
+      ] },
+    ],
+    constraints: [
+      { name: 'class_definition_name_key', constraint: 'unique', columns: ['name'] },
+    ],
+  }, ClassDefinitionRow),
+  __table({
+    name: 'combat_event',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+    ],
+    constraints: [
+      { name: 'combat_event_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, CombatEventRow),
+  __table({
+    name: 'control_point',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+    ],
+    constraints: [
+      { name: 'control_point_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, ControlPointRow),
+  __table({
+    name: 'crafting_recipe',
+    indexes: [
+      { name: 'recipe_id', algorithm: 'btree', columns: [
+        'recipeId',
+      ] },
+    ],
+    constraints: [
+      { name: 'crafting_recipe_recipe_id_key', constraint: 'unique', columns: ['recipeId'] },
+    ],
+  }, CraftingRecipeRow),
+  __table({
+    name: 'customization_cooldown',
+    indexes: [
+      { name: 'identity', algorithm: 'btree', columns: [
+        'identity',
+      ] },
+    ],
+    constraints: [
+      { name: 'customization_cooldown_identity_key', constraint: 'unique', columns: ['identity'] },
+    ],
+  }, CustomizationCooldownRow),
+  __table({
+    name: 'damage_event',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+    ],
+    constraints: [
+      { name: 'damage_event_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, DamageEventRow),
+  __table({
+    name: 'destructible',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+    ],
+    constraints: [
+      { name: 'destructible_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, DestructibleRow),
+  __table({
+    name: 'dropped_item',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+    ],
+    constraints: [
+      { name: 'dropped_item_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, DroppedItemRow),
+  __table({
+    name: 'duel',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+      { name: 'arena_id', algorithm: 'btree', columns: [
+        'arenaId',
+      ] },
+    ],
+    constraints: [
+      { name: 'duel_id_key', constraint: 'unique', columns: ['id'] },
+      { name: 'duel_arena_id_key', constraint: 'unique', columns: ['arenaId'] },
+    ],
+  }, DuelRow),
+  __table({
+    name: 'dungeon',
+    indexes: [
+      { name: 'instance_id', algorithm: 'btree', columns: [
+        'instanceId',
+      ] },
+    ],
+    constraints: [
+      { name: 'dungeon_instance_id_key', constraint: 'unique', columns: ['instanceId'] },
+    ],
+  }, DungeonRow),
+  __table({
+    name: 'dungeon_chest',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+      { name: 'instance_id', algorithm: 'btree', columns: [
+        'instanceId',
+      ] },
+    ],
+    constraints: [
+      { name: 'dungeon_chest_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, DungeonChestRow),
+  __table({
+    name: 'dungeon_schedule',
+    indexes: [
+      { name: 'scheduled_id', algorithm: 'btree', columns: [
+        'scheduledId',
+      ] },
+    ],
+    constraints: [
+      { name: 'dungeon_schedule_scheduled_id_key', constraint: 'unique', columns: ['scheduledId'] },
+    ],
+  }, DungeonScheduleRow),
+  __table({
+    name: 'explosion_event',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+    ],
+    constraints: [
+      { name: 'explosion_event_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, ExplosionEventRow),
+  __table({
+    name: 'fire_patch',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+    ],
+    constraints: [
+      { name: 'fire_patch_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, FirePatchRow),
+  __table({
+    name: 'flag',
+    indexes: [
+      { name: 'team', algorithm: 'btree', columns: [
+        'team',
+      ] },
+    ],
+    constraints: [
+      { name: 'flag_team_key', constraint: 'unique', columns: ['team'] },
+    ],
+  }, FlagRow),
+  __table({
+    name: 'flag_captured_event',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+    ],
+    constraints: [
+      { name: 'flag_captured_event_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, FlagCapturedEventRow),
+  __table({
+    name: 'flag_taken_event',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+    ],
+    constraints: [
+      { name: 'flag_taken_event_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, FlagTakenEventRow),
+  __table({
+    name: 'friend_request',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+      { name: 'sender', algorithm: 'btree', columns: [
+        'sender',
+      ] },
+      { name: 'recipient', algorithm: 'btree', columns: [
+        'recipient',
+      ] },
+    ],
+    constraints: [
+      { name: 'friend_request_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, FriendRequestRow),
+  __table({
+    name: 'friend_status',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+      { name: 'owner', algorithm: 'btree', columns: [
+        'owner',
+      ] },
+      { name: 'friend', algorithm: 'btree', columns: [
+        'friend',
+      ] },
+    ],
+    constraints: [
+      { name: 'friend_status_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, FriendStatusRow),
+  __table({
+    name: 'friendship',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+      { name: 'a', algorithm: 'btree', columns: [
+        'a',
+      ] },
+      { name: 'b', algorithm: 'btree', columns: [
+        'b',
+      ] },
+    ],
+    constraints: [
+      { name: 'friendship_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, FriendshipRow),
+  __table({
+    name: 'game_config',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+    ],
+    constraints: [
+      { name: 'game_config_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, GameConfigRow),
   __table({
     name: 'game_tick_schedule',
     indexes: [
-      { name: 'scheduled_id', algorithm: 'btree', columns: [
-        'scheduledId',
+      { name: 'scheduled_id', algorithm: 'btree', columns: [
+        'scheduledId',
+      ] },
+    ],
+    constraints: [
+      { name: 'game_tick_schedule_scheduled_id_key', constraint: 'unique', columns: ['scheduledId'] },
+    ],
+  }, GameTickScheduleRow),
+  __table({
+    name: 'guild',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+    ],
+    constraints: [
+      { name: 'guild_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, GuildRow),
+  __table({
+    name: 'guild_invite',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+      { name: 'invitee', algorithm: 'btree', columns: [
+        'invitee',
+      ] },
+    ],
+    constraints: [
+      { name: 'guild_invite_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, GuildInviteRow),
+  __table({
+    name: 'guild_member',
+    indexes: [
+      { name: 'identity', algorithm: 'btree', columns: [
+        'identity',
+      ] },
+      { name: 'guild_id', algorithm: 'btree', columns: [
+        'guildId',
+      ] },
+    ],
+    constraints: [
+      { name: 'guild_member_identity_key', constraint: 'unique', columns: ['identity'] },
+    ],
+  }, GuildMemberRow),
+  __table({
+    name: 'heal_event',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+    ],
+    constraints: [
+      { name: 'heal_event_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, HealEventRow),
+  __table({
+    name: 'hitscan_trace',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+    ],
+    constraints: [
+      { name: 'hitscan_trace_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, HitscanTraceRow),
+  __table({
+    name: 'instance',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+    ],
+    constraints: [
+      { name: 'instance_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, InstanceRow),
+  __table({
+    name: 'interactable',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+    ],
+    constraints: [
+      { name: 'interactable_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, InteractableRow),
+  __table({
+    name: 'item_definition',
+    indexes: [
+      { name: 'item_id', algorithm: 'btree', columns: [
+        'itemId',
+      ] },
+    ],
+    constraints: [
+      { name: 'item_definition_item_id_key', constraint: 'unique', columns: ['itemId'] },
+    ],
+  }, ItemDefinitionRow),
+  __table({
+    name: 'join_queue',
+    indexes: [
+      { name: 'identity', algorithm: 'btree', columns: [
+        'identity',
+      ] },
+    ],
+    constraints: [
+      { name: 'join_queue_identity_key', constraint: 'unique', columns: ['identity'] },
+    ],
+  }, JoinQueueRow),
+  __table({
+    name: 'jump_pad',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+    ],
+    constraints: [
+      { name: 'jump_pad_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, JumpPadRow),
+  __table({
+    name: 'kill_feed',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+    ],
+    constraints: [
+      { name: 'kill_feed_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, KillFeedRow),
+  __table({
+    name: 'leaderboard_entry',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+      { name: 'category', algorithm: 'btree', columns: [
+        'category',
+      ] },
+    ],
+    constraints: [
+      { name: 'leaderboard_entry_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, LeaderboardEntryRow),
+  __table({
+    name: 'leaderboard_schedule',
+    indexes: [
+      { name: 'scheduled_id', algorithm: 'btree', columns: [
+        'scheduledId',
+      ] },
+    ],
+    constraints: [
+      { name: 'leaderboard_schedule_scheduled_id_key', constraint: 'unique', columns: ['scheduledId'] },
+    ],
+  }, LeaderboardScheduleRow),
+  __table({
+    name: 'logged_out_cleanup_schedule',
+    indexes: [
+      { name: 'scheduled_id', algorithm: 'btree', columns: [
+        'scheduledId',
+      ] },
+    ],
+    constraints: [
+      { name: 'logged_out_cleanup_schedule_scheduled_id_key', constraint: 'unique', columns: ['scheduledId'] },
+    ],
+  }, LoggedOutCleanupScheduleRow),
+  __table({
+    name: 'logged_out_player',
+    indexes: [
+      { name: 'identity', algorithm: 'btree', columns: [
+        'identity',
+      ] },
+    ],
+    constraints: [
+      { name: 'logged_out_player_identity_key', constraint: 'unique', columns: ['identity'] },
+    ],
+  }, LoggedOutPlayerRow),
+  __table({
+    name: 'login_streak',
+    indexes: [
+      { name: 'identity', algorithm: 'btree', columns: [
+        'identity',
+      ] },
+    ],
+    constraints: [
+      { name: 'login_streak_identity_key', constraint: 'unique', columns: ['identity'] },
+    ],
+  }, LoginStreakRow),
+  __table({
+    name: 'loot_table',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+    ],
+    constraints: [
+      { name: 'loot_table_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, LootTableRow),
+  __table({
+    name: 'match_history',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+    ],
+    constraints: [
+      { name: 'match_history_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, MatchHistoryRow),
+  __table({
+    name: 'match_info',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+    ],
+    constraints: [
+      { name: 'match_info_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, MatchInfoRow),
+  __table({
+    name: 'match_instance',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+    ],
+    constraints: [
+      { name: 'match_instance_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, MatchInstanceRow),
+  __table({
+    name: 'match_result',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+    ],
+    constraints: [
+      { name: 'match_result_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, MatchResultRow),
+  __table({
+    name: 'match_state',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+    ],
+    constraints: [
+      { name: 'match_state_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, MatchStateRow),
+  __table({
+    name: 'matchmaking_queue',
+    indexes: [
+      { name: 'identity', algorithm: 'btree', columns: [
+        'identity',
+      ] },
+      { name: 'group_id', algorithm: 'btree', columns: [
+        'groupId',
+      ] },
+    ],
+    constraints: [
+      { name: 'matchmaking_queue_identity_key', constraint: 'unique', columns: ['identity'] },
+    ],
+  }, MatchmakingQueueRow),
+  __table({
+    name: 'matchmaking_schedule',
+    indexes: [
+      { name: 'scheduled_id', algorithm: 'btree', columns: [
+        'scheduledId',
+      ] },
+    ],
+    constraints: [
+      { name: 'matchmaking_schedule_scheduled_id_key', constraint: 'unique', columns: ['scheduledId'] },
+    ],
+  }, MatchmakingScheduleRow),
+  __table({
+    name: 'minimap_ping',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+      { name: 'viewer', algorithm: 'btree', columns: [
+        'viewer',
+      ] },
+    ],
+    constraints: [
+      { name: 'minimap_ping_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, MinimapPingRow),
+  __table({
+    name: 'moving_platform',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+    ],
+    constraints: [
+      { name: 'moving_platform_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, MovingPlatformRow),
+  __table({
+    name: 'mute',
+    indexes: [
+      { name: 'identity', algorithm: 'btree', columns: [
+        'identity',
+      ] },
+    ],
+    constraints: [
+      { name: 'mute_identity_key', constraint: 'unique', columns: ['identity'] },
+    ],
+  }, MuteRow),
+  __table({
+    name: 'my_blocks',
+    indexes: [
+    ],
+    constraints: [
+    ],
+  }, MyBlocksRow),
+  __table({
+    name: 'my_chat',
+    indexes: [
+    ],
+    constraints: [
+    ],
+  }, MyChatRow),
+  __table({
+    name: 'nav_grid',
+    indexes: [
+      { name: 'instance_id', algorithm: 'btree', columns: [
+        'instanceId',
+      ] },
+    ],
+    constraints: [
+      { name: 'nav_grid_instance_id_key', constraint: 'unique', columns: ['instanceId'] },
+    ],
+  }, NavGridRow),
+  __table({
+    name: 'npc',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+      { name: 'spawner_id', algorithm: 'btree', columns: [
+        'spawnerId',
+      ] },
+    ],
+    constraints: [
+      { name: 'npc_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, NpcRow),
+  __table({
+    name: 'npc_spawner',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+    ],
+    constraints: [
+      { name: 'npc_spawner_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, NpcSpawnerRow),
+  __table({
+    name: 'npc_spawner_schedule',
+    indexes: [
+      { name: 'scheduled_id', algorithm: 'btree', columns: [
+        'scheduledId',
+      ] },
+    ],
+    constraints: [
+      { name: 'npc_spawner_schedule_scheduled_id_key', constraint: 'unique', columns: ['scheduledId'] },
+    ],
+  }, NpcSpawnerScheduleRow),
+  __table({
+    name: 'one_way_platform',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+    ],
+    constraints: [
+      { name: 'one_way_platform_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, OneWayPlatformRow),
+  __table({
+    name: 'ops_log',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+    ],
+    constraints: [
+      { name: 'ops_log_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, OpsLogRow),
+  __table({
+    name: 'party',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+    ],
+    constraints: [
+      { name: 'party_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, PartyRow),
+  __table({
+    name: 'party_invite',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+      { name: 'invitee', algorithm: 'btree', columns: [
+        'invitee',
+      ] },
+    ],
+    constraints: [
+      { name: 'party_invite_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, PartyInviteRow),
+  __table({
+    name: 'party_member',
+    indexes: [
+      { name: 'identity', algorithm: 'btree', columns: [
+        'identity',
+      ] },
+      { name: 'party_id', algorithm: 'btree', columns: [
+        'partyId',
+      ] },
+    ],
+    constraints: [
+      { name: 'party_member_identity_key', constraint: 'unique', columns: ['identity'] },
+    ],
+  }, PartyMemberRow),
+  __table({
+    name: 'pending_duel',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+      { name: 'target', algorithm: 'btree', columns: [
+        'target',
       ] },
     ],
     constraints: [
-      { name: 'game_tick_schedule_scheduled_id_key', constraint: 'unique', columns: ['scheduledId'] },
+      { name: 'pending_duel_id_key', constraint: 'unique', columns: ['id'] },
     ],
-  }, GameTickScheduleRow),
+  }, PendingDuelRow),
   __table({
-    name: 'logged_out_player',
+    name: 'pet',
     indexes: [
-      { name: 'identity', algorithm: 'btree', columns: [
-        'identity',
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+      { name: 'owner', algorithm: 'btree', columns: [
+        'owner',
       ] },
     ],
     constraints: [
-      { name: 'logged_out_player_identity_key', constraint: 'unique', columns: ['identity'] },
+      { name: 'pet_id_key', constraint: 'unique', columns: ['id'] },
+      { name: 'pet_owner_key', constraint: 'unique', columns: ['owner'] },
     ],
-  }, LoggedOutPlayerRow),
+  }, PetRow),
+  __table({
+    name: 'pickup',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+    ],
+    constraints: [
+      { name: 'pickup_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, PickupRow),
   __table({
     name: 'player',
     indexes: [
@@ -105,6 +1876,84 @@ const tablesSchema = __schema(
       { name: 'player_identity_key', constraint: 'unique', columns: ['identity'] },
     ],
   }, PlayerRow),
+  __table({
+    name: 'player_achievement',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+      { name: 'owner', algorithm: 'btree', columns: [
+        'owner',
+      ] },
+    ],
+    constraints: [
+      { name: 'player_achievement_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, PlayerAchievementRow),
+  __table({
+    name: 'player_block',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+      { name: 'blocker', algorithm: 'btree', columns: [
+        'blocker',
+      ] },
+    ],
+    constraints: [
+      { name: 'player_block_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, PlayerBlockRow),
+  __table({
+    name: 'player_inventory',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+      { name: 'owner', algorithm: 'btree', columns: [
+        'owner',
+      ] },
+    ],
+    constraints: [
+      { name: 'player_inventory_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, PlayerInventoryRow),
+  __table({
+    name: 'player_quest_progress',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+      { name: 'owner', algorithm: 'btree', columns: [
+        'owner',
+      ] },
+    ],
+    constraints: [
+      { name: 'player_quest_progress_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, PlayerQuestProgressRow),
+  __table({
+    name: 'player_stats',
+    indexes: [
+      { name: 'identity', algorithm: 'btree', columns: [
+        'identity',
+      ] },
+    ],
+    constraints: [
+      { name: 'player_stats_identity_key', constraint: 'unique', columns: ['identity'] },
+    ],
+  }, PlayerStatsRow),
+  __table({
+    name: 'portal',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+    ],
+    constraints: [
+      { name: 'portal_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, PortalRow),
   __table({
     name: 'projectile',
     indexes: [
@@ -116,14 +1965,414 @@ const tablesSchema = __schema(
       { name: 'projectile_id_key', constraint: 'unique', columns: ['id'] },
     ],
   }, ProjectileRow),
+  __table({
+    name: 'projectile_impact',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+    ],
+    constraints: [
+      { name: 'projectile_impact_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, ProjectileImpactRow),
+  __table({
+    name: 'projectile_spawn_log',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+    ],
+    constraints: [
+      { name: 'projectile_spawn_log_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, ProjectileSpawnLogRow),
+  __table({
+    name: 'quest_definition',
+    indexes: [
+      { name: 'quest_id', algorithm: 'btree', columns: [
+        'questId',
+      ] },
+    ],
+    constraints: [
+      { name: 'quest_definition_quest_id_key', constraint: 'unique', columns: ['questId'] },
+    ],
+  }, QuestDefinitionRow),
+  __table({
+    name: 'ranked_result',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+      { name: 'identity', algorithm: 'btree', columns: [
+        'identity',
+      ] },
+    ],
+    constraints: [
+      { name: 'ranked_result_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, RankedResultRow),
+  __table({
+    name: 'resource_node',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+    ],
+    constraints: [
+      { name: 'resource_node_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, ResourceNodeRow),
+  __table({
+    name: 'shield_break_event',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+    ],
+    constraints: [
+      { name: 'shield_break_event_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, ShieldBreakEventRow),
+  __table({
+    name: 'shop_listing',
+    indexes: [
+      { name: 'item_id', algorithm: 'btree', columns: [
+        'itemId',
+      ] },
+    ],
+    constraints: [
+      { name: 'shop_listing_item_id_key', constraint: 'unique', columns: ['itemId'] },
+    ],
+  }, ShopListingRow),
+  __table({
+    name: 'shop_transaction',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+      { name: 'player', algorithm: 'btree', columns: [
+        'player',
+      ] },
+    ],
+    constraints: [
+      { name: 'shop_transaction_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, ShopTransactionRow),
+  __table({
+    name: 'spawn_point',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+    ],
+    constraints: [
+      { name: 'spawn_point_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, SpawnPointRow),
+  __table({
+    name: 'spell_cooldown',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+      { name: 'caster', algorithm: 'btree', columns: [
+        'caster',
+      ] },
+    ],
+    constraints: [
+      { name: 'spell_cooldown_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, SpellCooldownRow),
+  __table({
+    name: 'spell_definition',
+    indexes: [
+      { name: 'spell_id', algorithm: 'btree', columns: [
+        'spellId',
+      ] },
+    ],
+    constraints: [
+      { name: 'spell_definition_spell_id_key', constraint: 'unique', columns: ['spellId'] },
+    ],
+  }, SpellDefinitionRow),
+  __table({
+    name: 'static_collider',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+    ],
+    constraints: [
+      { name: 'static_collider_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, StaticColliderRow),
+  __table({
+    name: 'status_effect',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+      { name: 'target_identity', algorithm: 'btree', columns: [
+        'targetIdentity',
+      ] },
+    ],
+    constraints: [
+      { name: 'status_effect_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, StatusEffectRow),
+  __table({
+    name: 'team_score',
+    indexes: [
+      { name: 'team', algorithm: 'btree', columns: [
+        'team',
+      ] },
+    ],
+    constraints: [
+      { name: 'team_score_team_key', constraint: 'unique', columns: ['team'] },
+    ],
+  }, TeamScoreRow),
+  __table({
+    name: 'teleport_event',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+    ],
+    constraints: [
+      { name: 'teleport_event_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, TeleportEventRow),
+  __table({
+    name: 'threat',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+      { name: 'npc_id', algorithm: 'btree', columns: [
+        'npcId',
+      ] },
+    ],
+    constraints: [
+      { name: 'threat_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, ThreatRow),
+  __table({
+    name: 'tick_state',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+    ],
+    constraints: [
+      { name: 'tick_state_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, TickStateRow),
+  __table({
+    name: 'trade_session',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+      { name: 'initiator', algorithm: 'btree', columns: [
+        'initiator',
+      ] },
+      { name: 'partner', algorithm: 'btree', columns: [
+        'partner',
+      ] },
+    ],
+    constraints: [
+      { name: 'trade_session_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, TradeSessionRow),
+  __table({
+    name: 'vehicle',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+    ],
+    constraints: [
+      { name: 'vehicle_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, VehicleRow),
+  __table({
+    name: 'vendor',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+    ],
+    constraints: [
+      { name: 'vendor_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, VendorRow),
+  __table({
+    name: 'water_volume',
+    indexes: [
+      { name: 'id', algorithm: 'btree', columns: [
+        'id',
+      ] },
+    ],
+    constraints: [
+      { name: 'water_volume_id_key', constraint: 'unique', columns: ['id'] },
+    ],
+  }, WaterVolumeRow),
+  __table({
+    name: 'zone',
+    indexes: [
+      { name: 'name', algorithm: 'btree', columns: [
+        'name',
+      ] },
+    ],
+    constraints: [
+      { name: 'zone_name_key', constraint: 'unique', columns: ['name'] },
+    ],
+  }, ZoneRow),
 );
 
 /** The schema information for all reducers in this module. This is defined the same way as the reducers would have been defined in the server, except the body of the reducer is omitted in code generation. */
 const reducersSchema = __reducers(
+  __reducerSchema("abandon_quest", AbandonQuest),
+  __reducerSchema("accept_duel", AcceptDuel),
+  __reducerSchema("accept_friend_request", AcceptFriendRequest),
+  __reducerSchema("accept_guild_invite", AcceptGuildInvite),
+  __reducerSchema("accept_party_invite", AcceptPartyInvite),
+  __reducerSchema("accept_quest", AcceptQuest),
+  __reducerSchema("add_boss", AddBoss),
+  __reducerSchema("add_chest", AddChest),
+  __reducerSchema("add_control_point", AddControlPoint),
+  __reducerSchema("add_destructible", AddDestructible),
+  __reducerSchema("add_door", AddDoor),
+  __reducerSchema("add_item", AddItem),
+  __reducerSchema("add_lever", AddLever),
+  __reducerSchema("add_moving_platform", AddMovingPlatform),
+  __reducerSchema("add_npc_spawner", AddNpcSpawner),
+  __reducerSchema("add_resource_node", AddResourceNode),
+  __reducerSchema("add_spawn_point", AddSpawnPoint),
+  __reducerSchema("add_static_collider", AddStaticCollider),
+  __reducerSchema("add_trade_item", AddTradeItem),
+  __reducerSchema("add_vehicle", AddVehicle),
+  __reducerSchema("add_water_volume", AddWaterVolume),
+  __reducerSchema("ban_player", BanPlayer),
+  __reducerSchema("blink", Blink),
+  __reducerSchema("block_player", BlockPlayer),
+  __reducerSchema("broadcast_message", BroadcastMessage),
+  __reducerSchema("buy_item", BuyItem),
+  __reducerSchema("cancel_trade", CancelTrade),
+  __reducerSchema("cast_heal", CastHeal),
+  __reducerSchema("cast_rejuvenate", CastRejuvenate),
+  __reducerSchema("cast_shield", CastShield),
+  __reducerSchema("cast_spell", CastSpell),
+  __reducerSchema("cast_ultimate", CastUltimate),
+  __reducerSchema("change_team", ChangeTeam),
+  __reducerSchema("change_username", ChangeUsername),
+  __reducerSchema("check_dungeons", CheckDungeons),
+  __reducerSchema("claim_daily_reward", ClaimDailyReward),
+  __reducerSchema("clear_anticheat_flags", ClearAnticheatFlags),
+  __reducerSchema("confirm_trade", ConfirmTrade),
+  __reducerSchema("craft_item", CraftItem),
+  __reducerSchema("create_guild", CreateGuild),
+  __reducerSchema("create_instance", CreateInstance),
+  __reducerSchema("customize_character", CustomizeCharacter),
+  __reducerSchema("dash", Dash),
+  __reducerSchema("decline_friend_request", DeclineFriendRequest),
+  __reducerSchema("destroy_instance", DestroyInstance),
+  __reducerSchema("disband_guild", DisbandGuild),
+  __reducerSchema("dismiss_pet", DismissPet),
+  __reducerSchema("dismount", Dismount),
+  __reducerSchema("drive_input", DriveInput),
+  __reducerSchema("drop_item", DropItem),
+  __reducerSchema("duel_request", DuelRequest),
+  __reducerSchema("end_match", EndMatch),
+  __reducerSchema("enter_instance", EnterInstance),
+  __reducerSchema("enter_vehicle", EnterVehicle),
+  __reducerSchema("equip_item", EquipItem),
+  __reducerSchema("exit_vehicle", ExitVehicle),
+  __reducerSchema("fire_hitscan", FireHitscan),
+  __reducerSchema("fire_projectile", FireProjectile),
   __reducerSchema("game_tick", GameTick),
+  __reducerSchema("gather_resource", GatherResource),
+  __reducerSchema("generate_dungeon", GenerateDungeon),
+  __reducerSchema("inflict_status_effect", InflictStatusEffect),
+  __reducerSchema("initiate_trade", InitiateTrade),
+  __reducerSchema("interact", Interact),
+  __reducerSchema("invite_to_guild", InviteToGuild),
+  __reducerSchema("invite_to_party", InviteToParty),
+  __reducerSchema("kick_from_guild", KickFromGuild),
+  __reducerSchema("kick_player", KickPlayer),
+  __reducerSchema("leave_guild", LeaveGuild),
+  __reducerSchema("leave_join_queue", LeaveJoinQueue),
+  __reducerSchema("leave_party", LeaveParty),
+  __reducerSchema("leave_queue", LeaveQueue),
+  __reducerSchema("melee_attack", MeleeAttack),
+  __reducerSchema("mount", Mount),
+  __reducerSchema("move_player_to_instance", MovePlayerToInstance),
+  __reducerSchema("mute_player", MutePlayer),
+  __reducerSchema("open_chest", OpenChest),
+  __reducerSchema("pickup_item", PickupItem),
+  __reducerSchema("promote_guild_member", PromoteGuildMember),
+  __reducerSchema("prune_audit_log", PruneAuditLog),
+  __reducerSchema("prune_chat_messages", PruneChatMessages),
+  __reducerSchema("prune_logged_out_players", PruneLoggedOutPlayers),
+  __reducerSchema("purge_logged_out_player", PurgeLoggedOutPlayer),
+  __reducerSchema("queue_for_match", QueueForMatch),
+  __reducerSchema("recompute_leaderboards", RecomputeLeaderboards),
+  __reducerSchema("refill_npc_spawners", RefillNpcSpawners),
   __reducerSchema("register_player", RegisterPlayer),
+  __reducerSchema("remove_bot", RemoveBot),
+  __reducerSchema("remove_control_point", RemoveControlPoint),
+  __reducerSchema("remove_crafting_recipe", RemoveCraftingRecipe),
+  __reducerSchema("remove_destructible", RemoveDestructible),
+  __reducerSchema("remove_friend", RemoveFriend),
+  __reducerSchema("remove_interactable", RemoveInteractable),
+  __reducerSchema("remove_moving_platform", RemoveMovingPlatform),
+  __reducerSchema("remove_npc_spawner", RemoveNpcSpawner),
+  __reducerSchema("remove_portal", RemovePortal),
+  __reducerSchema("remove_resource_node", RemoveResourceNode),
+  __reducerSchema("remove_spawn_point", RemoveSpawnPoint),
+  __reducerSchema("remove_static_collider", RemoveStaticCollider),
+  __reducerSchema("remove_vehicle", RemoveVehicle),
+  __reducerSchema("remove_water_volume", RemoveWaterVolume),
+  __reducerSchema("remove_zone", RemoveZone),
+  __reducerSchema("run_boss_ability", RunBossAbility),
+  __reducerSchema("run_matchmaker", RunMatchmaker),
+  __reducerSchema("sell_item", SellItem),
+  __reducerSchema("send_chat", SendChat),
+  __reducerSchema("send_chat_message", SendChatMessage),
+  __reducerSchema("send_friend_request", SendFriendRequest),
+  __reducerSchema("set_afk_thresholds", SetAfkThresholds),
+  __reducerSchema("set_bot_difficulty", SetBotDifficulty),
+  __reducerSchema("set_crafting_recipe", SetCraftingRecipe),
+  __reducerSchema("set_flag_home", SetFlagHome),
+  __reducerSchema("set_friendly_fire", SetFriendlyFire),
+  __reducerSchema("set_game_mode", SetGameMode),
+  __reducerSchema("set_gravity", SetGravity),
+  __reducerSchema("set_logged_out_retention", SetLoggedOutRetention),
+  __reducerSchema("set_loot_table", SetLootTable),
+  __reducerSchema("set_max_npcs", SetMaxNpcs),
+  __reducerSchema("set_max_party_size", SetMaxPartySize),
+  __reducerSchema("set_max_players", SetMaxPlayers),
+  __reducerSchema("set_player_health", SetPlayerHealth),
+  __reducerSchema("set_regen_config", SetRegenConfig),
+  __reducerSchema("set_spawn_strategy", SetSpawnStrategy),
+  __reducerSchema("set_team_swap_rules", SetTeamSwapRules),
+  __reducerSchema("set_tick_rate", SetTickRate),
+  __reducerSchema("set_trade_gold", SetTradeGold),
+  __reducerSchema("set_win_conditions", SetWinConditions),
+  __reducerSchema("spawn_bot", SpawnBot),
   __reducerSchema("spawn_projectile", SpawnProjectile),
+  __reducerSchema("start_match", StartMatch),
+  __reducerSchema("start_ranked_season", StartRankedSeason),
+  __reducerSchema("summon_pet", SummonPet),
+  __reducerSchema("taunt", Taunt),
+  __reducerSchema("teleport_player", TeleportPlayer),
+  __reducerSchema("toggle_stealth", ToggleStealth),
+  __reducerSchema("unban_player", UnbanPlayer),
+  __reducerSchema("unblock_player", UnblockPlayer),
+  __reducerSchema("unequip_item", UnequipItem),
+  __reducerSchema("unmute_player", UnmutePlayer),
   __reducerSchema("update_player_input", UpdatePlayerInput),
+  __reducerSchema("use_ability", UseAbility),
 );
 
 /** The schema information for all procedures in this module. This is defined the same way as the procedures would have been defined in the server. */
@@ -19,6 +19,7 @@ export default __t.object("InputState", {
   jump: __t.bool(),
   attack: __t.bool(),
   castSpell: __t.bool(),
+  crouch: __t.bool(),
   sequence: __t.i32(),
 });
 
@@ -9,22 +9,13 @@ import {
   type AlgebraicTypeType as __AlgebraicTypeType,
   type Infer as __Infer,
 } from "spacetimedb";
-import Vector3 from "./vector_3_type";
+import PersistentPlayerState from "./persistent_player_state_type";
 
 
 export default __t.row({
   identity: __t.identity().primaryKey(),
-  username: __t.string(),
-  characterClass: __t.string(),
-  get position() {
-    return Vector3;
+  get state() {
+    return PersistentPlayerState;
   },
-  get rotation() {
-    return Vector3;
-  },
-  health: __t.i32(),
-  maxHealth: __t.i32(),
-  mana: __t.i32(),
-  maxMana: __t.i32(),
   lastSeen: __t.timestamp(),
 });
@@ -11,6 +11,8 @@ import {
 } from "spacetimedb";
 import Vector3 from "./vector_3_type";
 import InputState from "./input_state_type";
+import BufferedInput from "./buffered_input_type";
+import Appearance from "./appearance_type";
 
 
 export default __t.object("PlayerData", {
@@ -43,7 +45,61 @@ export default __t.object("PlayerData", {
   },
   alive: __t.bool(),
   hitRadius: __t.f32(),
-  respawnTicksRemaining: __t.i32(),
+  respawnAt: __t.option(__t.timestamp()),
+  healthRegenProgress: __t.f32(),
+  manaRegenProgress: __t.f32(),
+  lastAttacker: __t.option(__t.identity()),
+  killedBy: __t.option(__t.identity()),
+  fallDistance: __t.f32(),
+  grounded: __t.bool(),
+  ultCharge: __t.f32(),
+  damagedBy: __t.array(__t.identity()),
+  isStealthed: __t.bool(),
+  revealedUntil: __t.option(__t.timestamp()),
+  get inputQueue() {
+    return __t.array(BufferedInput);
+  },
+  axeAmmo: __t.u32(),
+  team: __t.u8(),
+  lastTeamChange: __t.option(__t.timestamp()),
+  spreadHeat: __t.f32(),
+  isDamaged: __t.bool(),
+  lastCastTime: __t.option(__t.timestamp()),
+  lastDamageTime: __t.option(__t.timestamp()),
+  lastMeleeTime: __t.option(__t.timestamp()),
+  lastGatherTime: __t.option(__t.timestamp()),
+  lastHitscanTime: __t.option(__t.timestamp()),
+  lastHealTime: __t.option(__t.timestamp()),
+  lastTeleportTime: __t.option(__t.timestamp()),
+  get knockbackVelocity() {
+    return Vector3;
+  },
+  equipmentDamageMultiplier: __t.f32(),
+  equipmentDamageReduction: __t.f32(),
+  lastInputTime: __t.option(__t.timestamp()),
+  inputAllowance: __t.f32(),
+  shieldAmount: __t.i32(),
+  shieldExpiresAt: __t.option(__t.timestamp()),
+  isCrouching: __t.bool(),
+  isSwimming: __t.bool(),
+  isMounted: __t.bool(),
+  mountSpeedMultiplier: __t.f32(),
+  vehicleId: __t.option(__t.u64()),
+  breathRemaining: __t.f32(),
+  currentZone: __t.option(__t.string()),
+  duelId: __t.option(__t.u64()),
+  lastActivityTime: __t.timestamp(),
+  isAfk: __t.bool(),
+  get appearance() {
+    return Appearance;
+  },
+  xp: __t.u64(),
+  gold: __t.u64(),
+  mmr: __t.i32(),
+  placementMatches: __t.u32(),
+  mmrSeason: __t.u32(),
+  guildTag: __t.option(__t.string()),
+  instanceId: __t.u64(),
 });
 
 
@@ -10,6 +10,9 @@ import {
   type Infer as __Infer,
 } from "spacetimedb";
 import Vector3 from "./vector_3_type";
+import ProjectileKind from "./projectile_kind_type";
+import ImpactBehavior from "./impact_behavior_type";
+import StatusOnHit from "./status_on_hit_type";
 
 
 export default __t.row({
@@ -27,4 +30,26 @@ export default __t.row({
   get startPosition() {
     return Vector3;
   },
+  get kind() {
+    return ProjectileKind;
+  },
+  hitsPickups: __t.bool(),
+  bouncesRemaining: __t.u32(),
+  pierceRemaining: __t.u32(),
+  get impactBehavior() {
+    return ImpactBehavior;
+  },
+  lastHitIdentity: __t.option(__t.identity()),
+  gravityScale: __t.f32(),
+  gravityDelaySecs: __t.f32(),
+  ageSecs: __t.f32(),
+  minArmDistance: __t.f32(),
+  revealRadius: __t.f32(),
+  get statusOnHit() {
+    return __t.option(StatusOnHit);
+  },
+  explosionRadius: __t.f32(),
+  homingTarget: __t.option(__t.identity()),
+  homingTurnRate: __t.f32(),
+  instanceId: __t.u64(),
 });
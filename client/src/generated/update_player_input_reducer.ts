@@ -10,7 +10,6 @@ import {
   type Infer as __Infer,
 } from "spacetimedb";
 
-import Vector3 from "./vector_3_type";
 import InputState from "./input_state_type";
 
 export default {
@@ -18,8 +17,6 @@ export default {
     return InputState;
   },
   clientYaw: __t.f32(),
-  get forwardVector() {
-    return Vector3;
-  },
+  clientPitch: __t.f32(),
   clientAnimation: __t.string(),
 };
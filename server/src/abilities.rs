@@ -0,0 +1,263 @@
+/*
+ * Vibe Coding Starter Pack: 3D Multiplayer - abilities.rs
+ *
+ * Ability hotbar: numbered slots that map to existing actions, each with its own cooldown
+ * enforced on the server, plus the dash and blink movement abilities and taunt.
+ *
+ * Key components:
+ *    - HOTBAR: Slot layout; each AbilitySpec names an ability_id, what it does and its cooldown
+ *    - AbilityCooldown: Public table, one row per (identity, ability_id) with the time the
+ *      ability is ready again, so clients can draw cooldown sweeps
+ *    - use_ability: Reducer that rejects a slot still on cooldown, otherwise dispatches to
+ *      spells::cast_spell, combat::melee_attack, status_effects::cast_rejuvenate or
+ *      combat::cast_shield and starts the cooldown only if that action succeeded
+ *    - dash: Burst of horizontal speed along the player's yaw through the knockback impulse
+ *      (combat::apply_knockback), so it decays with friction and is capped like any push
+ *    - blink: Instant BLINK_DISTANCE hop along yaw. The path is swept against static colliders
+ *      at feet, waist and head height and the player stops short of the first wall
+ *    - taunt: Pulls every NPC within TAUNT_RADIUS onto the player (threat::taunt_nearby); fails
+ *      if there is nothing to taunt
+ *    - All three are reducers (cooldowns "dash"/"blink"/"taunt" in ability_cooldown) and
+ *      hotbar slots
+ *
+ * The underlying actions keep their own rules (class cast cooldown, mana, melee cooldown...);
+ * the hotbar cooldown is checked on top of them.
+ */
+
+use spacetimedb::{Identity, ReducerContext, Table, TimeDuration, Timestamp};
+
+use crate::combat::{apply_knockback, cast_shield, melee_attack};
+use crate::common::{Vector3, BLINK_COOLDOWN_SECS, BLINK_DISTANCE, DASH_COOLDOWN_SECS, DASH_SPEED, MELEE_COOLDOWN_SECS, TAUNT_COOLDOWN_SECS};
+use crate::environment::{first_collider_hit, load_static_colliders, resolve_player_colliders, StaticCollider};
+use crate::matches::require_in_progress;
+use crate::player;
+use crate::spells::cast_spell;
+use crate::status_effects::{cast_rejuvenate, is_stunned};
+use crate::threat::taunt_nearby;
+
+pub enum AbilityAction {
+    Spell(&'static str), // spell_id in spell_definition
+    Melee,
+    Heal,
+    Shield,
+    Dash,
+    Blink,
+    Taunt,
+}
+
+pub struct AbilitySpec {
+    pub ability_id: &'static str,
+    pub action: AbilityAction,
+    pub cooldown_secs: f32,
+}
+
+pub const HOTBAR: [AbilitySpec; 8] = [
+    AbilitySpec { ability_id: "bolt", action: AbilityAction::Spell("bolt"), cooldown_secs: 0.25 },
+    AbilitySpec { ability_id: "fireball", action: AbilityAction::Spell("fireball"), cooldown_secs: 2.0 },
+    AbilitySpec { ability_id: "melee", action: AbilityAction::Melee, cooldown_secs: MELEE_COOLDOWN_SECS },
+    AbilitySpec { ability_id: "rejuvenate", action: AbilityAction::Heal, cooldown_secs: 12.0 },
+    AbilitySpec { ability_id: "shield", action: AbilityAction::Shield, cooldown_secs: 15.0 },
+    AbilitySpec { ability_id: "dash", action: AbilityAction::Dash, cooldown_secs: DASH_COOLDOWN_SECS },
+    AbilitySpec { ability_id: "blink", action: AbilityAction::Blink, cooldown_secs: BLINK_COOLDOWN_SECS },
+    AbilitySpec { ability_id: "taunt", action: AbilityAction::Taunt, cooldown_secs: TAUNT_COOLDOWN_SECS },
+];
+
+#[spacetimedb::table(name = ability_cooldown, public)]
+#[derive(Clone)]
+pub struct AbilityCooldown {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    #[index(btree)]
+    pub identity: Identity,
+    pub ability_id: String,
+    pub ready_at: Timestamp,
+}
+
+fn find_cooldown(ctx: &ReducerContext, identity: Identity, ability_id: &str) -> Option<AbilityCooldown> {
+    ctx.db.ability_cooldown().identity().filter(identity).find(|c| c.ability_id == ability_id)
+}
+
+fn is_ready(cooldown: Option<&AbilityCooldown>, now: Timestamp) -> bool {
+    cooldown.is_none_or(|c| c.ready_at <= now)
+}
+
+fn ready_after(now: Timestamp, cooldown_secs: f32) -> Timestamp {
+    now + TimeDuration::from_micros((cooldown_secs * 1_000_000.0) as i64)
+}
+
+fn check_cooldown(ctx: &ReducerContext, identity: Identity, ability_id: &str) -> Result<(), String> {
+    if !is_ready(find_cooldown(ctx, identity, ability_id).as_ref(), ctx.timestamp) {
+        return Err(format!("{} is on cooldown", ability_id));
+    }
+    Ok(())
+}
+
+fn start_cooldown(ctx: &ReducerContext, identity: Identity, ability_id: &str, cooldown_secs: f32) {
+    let ready_at = ready_after(ctx.timestamp, cooldown_secs);
+    match find_cooldown(ctx, identity, ability_id) {
+        Some(mut existing) => {
+            existing.ready_at = ready_at;
+            ctx.db.ability_cooldown().id().update(existing);
+        }
+        None => {
+            ctx.db.ability_cooldown().insert(AbilityCooldown { id: 0, identity, ability_id: ability_id.to_string(), ready_at });
+        }
+    }
+}
+
+fn yaw_forward(yaw: f32) -> Vector3 {
+    Vector3 { x: -yaw.sin(), y: 0.0, z: -yaw.cos() }
+}
+
+fn perform_dash(ctx: &ReducerContext) -> Result<(), String> {
+    let Some(player) = ctx.db.player().identity().find(ctx.sender) else {
+        return Err("Player is not active".to_string());
+    };
+    if !player.alive {
+        return Err("Cannot dash while dead".to_string());
+    }
+    require_in_progress(ctx, player.instance_id)?;
+    if is_stunned(ctx, player.identity) {
+        return Err("Cannot dash while stunned".to_string());
+    }
+    apply_knockback(ctx, player.identity, yaw_forward(player.rotation.y) * DASH_SPEED);
+    Ok(())
+}
+
+fn perform_blink(ctx: &ReducerContext) -> Result<(), String> {
+    let Some(mut player) = ctx.db.player().identity().find(ctx.sender) else {
+        return Err("Player is not active".to_string());
+    };
+    if !player.alive {
+        return Err("Cannot blink while dead".to_string());
+    }
+    require_in_progress(ctx, player.instance_id)?;
+    if is_stunned(ctx, player.identity) {
+        return Err("Cannot blink while stunned".to_string());
+    }
+    let colliders = load_static_colliders(ctx);
+    let forward = yaw_forward(player.rotation.y);
+    let height = player.collision_height();
+    let distance = blink_distance(&colliders, player.instance_id, player.position, forward, player.hit_radius, height);
+    let mut destination = player.position + forward * distance;
+    resolve_player_colliders(&colliders, player.instance_id, &mut destination, player.hit_radius, height);
+    player.position = destination;
+    player.is_stealthed = false;
+    ctx.db.player().identity().update(player);
+    Ok(())
+}
+
+// How far a blink along `forward` gets before the body would touch a wall
+fn blink_distance(colliders: &[StaticCollider], instance_id: u64, position: Vector3, forward: Vector3, hit_radius: f32, height: f32) -> f32 {
+    // Sweep the body at a few heights so low walls and overhangs both stop the blink
+    let reach = BLINK_DISTANCE + hit_radius;
+    let blocked_at = [0.1, height * 0.5, height - 0.1]
+        .iter()
+        .filter_map(|height| {
+            let from = position + Vector3 { x: 0.0, y: *height, z: 0.0 };
+            first_collider_hit(colliders, instance_id, from, from + forward * reach).map(|(_, entry)| entry * reach)
+        })
+        .fold(reach, f32::min);
+    (blocked_at - hit_radius).clamp(0.0, BLINK_DISTANCE)
+}
+
+fn perform_taunt(ctx: &ReducerContext) -> Result<(), String> {
+    let Some(player) = ctx.db.player().identity().find(ctx.sender) else {
+        return Err("Player is not active".to_string());
+    };
+    if !player.alive {
+        return Err("Cannot taunt while dead".to_string());
+    }
+    require_in_progress(ctx, player.instance_id)?;
+    if is_stunned(ctx, player.identity) {
+        return Err("Cannot taunt while stunned".to_string());
+    }
+    if taunt_nearby(ctx, &player) == 0 {
+        return Err("Nothing nearby to taunt".to_string());
+    }
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn dash(ctx: &ReducerContext) -> Result<(), String> {
+    check_cooldown(ctx, ctx.sender, "dash")?;
+    perform_dash(ctx)?;
+    start_cooldown(ctx, ctx.sender, "dash", DASH_COOLDOWN_SECS);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn blink(ctx: &ReducerContext) -> Result<(), String> {
+    check_cooldown(ctx, ctx.sender, "blink")?;
+    perform_blink(ctx)?;
+    start_cooldown(ctx, ctx.sender, "blink", BLINK_COOLDOWN_SECS);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn taunt(ctx: &ReducerContext) -> Result<(), String> {
+    check_cooldown(ctx, ctx.sender, "taunt")?;
+    perform_taunt(ctx)?;
+    start_cooldown(ctx, ctx.sender, "taunt", TAUNT_COOLDOWN_SECS);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn use_ability(ctx: &ReducerContext, slot: u8, hand_position: Vector3) -> Result<(), String> {
+    let Some(ability) = HOTBAR.get(slot as usize) else {
+        return Err(format!("No ability in slot {}", slot));
+    };
+    check_cooldown(ctx, ctx.sender, ability.ability_id)?;
+
+    match ability.action {
+        AbilityAction::Spell(spell_id) => cast_spell(ctx, spell_id.to_string(), hand_position)?,
+        AbilityAction::Melee => melee_attack(ctx)?,
+        AbilityAction::Heal => cast_rejuvenate(ctx)?,
+        AbilityAction::Shield => cast_shield(ctx)?,
+        AbilityAction::Dash => perform_dash(ctx)?,
+        AbilityAction::Blink => perform_blink(ctx)?,
+        AbilityAction::Taunt => perform_taunt(ctx)?,
+    }
+    start_cooldown(ctx, ctx.sender, ability.ability_id, ability.cooldown_secs);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::{LOBBY_INSTANCE_ID, PLAYER_HEIGHT};
+
+    #[test]
+    fn each_slot_waits_out_its_own_cooldown() {
+        let used_at = Timestamp::from_micros_since_unix_epoch(1_000_000);
+        let fireball = &HOTBAR[1];
+        let cooldown = AbilityCooldown { id: 1, identity: Identity::from_byte_array([1; 32]), ability_id: fireball.ability_id.to_string(), ready_at: ready_after(used_at, fireball.cooldown_secs) };
+
+        assert!(is_ready(None, used_at), "never used");
+        assert!(!is_ready(Some(&cooldown), ready_after(used_at, fireball.cooldown_secs - 0.1)));
+        assert!(is_ready(Some(&cooldown), ready_after(used_at, fireball.cooldown_secs)));
+    }
+
+    #[test]
+    fn blinks_stop_short_of_walls() {
+        let forward = yaw_forward(0.0);
+        let origin = Vector3::default();
+        assert_eq!(blink_distance(&[], LOBBY_INSTANCE_ID, origin, forward, 0.5, PLAYER_HEIGHT), BLINK_DISTANCE);
+
+        let wall = StaticCollider { id: 1, center: Vector3 { x: 0.0, y: 1.0, z: -5.0 }, half_extents: Vector3 { x: 3.0, y: 3.0, z: 0.5 }, instance_id: LOBBY_INSTANCE_ID };
+        let distance = blink_distance(std::slice::from_ref(&wall), LOBBY_INSTANCE_ID, origin, forward, 0.5, PLAYER_HEIGHT);
+        assert!(distance > 3.5 && distance <= 4.0, "wall face at 4.5, the body stays clear of it: {distance}");
+        // A low wall still blocks: the sweep starts at the feet
+        let curb = StaticCollider { center: Vector3 { x: 0.0, y: 0.1, z: -5.0 }, half_extents: Vector3 { x: 3.0, y: 0.2, z: 0.5 }, ..wall };
+        assert!(blink_distance(std::slice::from_ref(&curb), LOBBY_INSTANCE_ID, origin, forward, 0.5, PLAYER_HEIGHT) < BLINK_DISTANCE);
+        assert_eq!(blink_distance(&[curb], LOBBY_INSTANCE_ID + 1, origin, forward, 0.5, PLAYER_HEIGHT), BLINK_DISTANCE, "another instance's wall");
+    }
+
+    #[test]
+    fn hotbar_slots_have_distinct_ids() {
+        for (i, a) in HOTBAR.iter().enumerate() {
+            assert!(HOTBAR[i + 1..].iter().all(|b| b.ability_id != a.ability_id), "{} is in two slots", a.ability_id);
+        }
+    }
+}
@@ -0,0 +1,170 @@
+/*
+ * Vibe Coding Starter Pack: 3D Multiplayer - achievements.rs
+ *
+ * One-off milestones unlocked by playing.
+ *
+ * Key components:
+ *    - AchievementDefinition: Public table keyed by achievement_id; seeded in init. Unlocks
+ *      once the player's value of `stat` reaches `threshold`
+ *    - AchievementStat / AchievementCounter: Public table of lifetime counters per identity
+ *      (kept across sessions and matches, unlike player_stats). Friends is the current size
+ *      of the friend list rather than a counter
+ *    - PlayerAchievement: Public table of unlocked achievements (one row per player and
+ *      achievement)
+ *    - Each unlock also emits an AchievementUnlockedEvent (events.rs) for toast notifications
+ *
+ * Hooks: on_player_kill (combat::apply_damage), on_npc_kill (npcs::damage_npc), on_jump
+ * (game_tick, when a jump starts) and on_friend_added (friends.rs).
+ */
+
+use spacetimedb::{Identity, ReducerContext, SpacetimeType, Table, Timestamp};
+
+use crate::events::emit_achievement_unlocked;
+use crate::friends::friend_count;
+
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq)]
+pub enum AchievementStat {
+    PlayerKills,
+    NpcKills,
+    Jumps,
+    Friends,
+}
+
+#[spacetimedb::table(name = achievement_definition, public)]
+#[derive(Clone)]
+pub struct AchievementDefinition {
+    #[primary_key]
+    pub achievement_id: String,
+    pub title: String,
+    pub description: String,
+    pub stat: AchievementStat,
+    pub threshold: u64,
+}
+
+#[spacetimedb::table(name = achievement_counter, public)]
+#[derive(Clone)]
+pub struct AchievementCounter {
+    #[primary_key]
+    pub identity: Identity,
+    pub player_kills: u64,
+    pub npc_kills: u64,
+    pub jumps: u64,
+}
+
+#[spacetimedb::table(name = player_achievement, public)]
+#[derive(Clone)]
+pub struct PlayerAchievement {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    #[index(btree)]
+    pub owner: Identity,
+    pub achievement_id: String,
+    pub unlocked_at: Timestamp,
+}
+
+fn achievement(achievement_id: &str, title: &str, description: &str, stat: AchievementStat, threshold: u64) -> AchievementDefinition {
+    AchievementDefinition {
+        achievement_id: achievement_id.to_string(),
+        title: title.to_string(),
+        description: description.to_string(),
+        stat,
+        threshold,
+    }
+}
+
+pub fn seed_achievement_definitions(ctx: &ReducerContext) {
+    if ctx.db.achievement_definition().count() > 0 {
+        return;
+    }
+    let defaults = [
+        achievement("first_blood", "First Blood", "Defeat another player", AchievementStat::PlayerKills, 1),
+        achievement("duelist", "Duelist", "Defeat 50 players", AchievementStat::PlayerKills, 50),
+        achievement("monster_hunter", "Monster Hunter", "Slay 100 monsters", AchievementStat::NpcKills, 100),
+        achievement("hopper", "Hopper", "Jump 100 times", AchievementStat::Jumps, 100),
+        achievement("popular", "Popular", "Have 10 friends", AchievementStat::Friends, 10),
+    ];
+    let count = defaults.len();
+    for definition in defaults {
+        ctx.db.achievement_definition().insert(definition);
+    }
+    spacetimedb::log::info!("[INIT] Seeded {} achievement definitions.", count);
+}
+
+fn bump_counter(ctx: &ReducerContext, identity: Identity, bump: impl FnOnce(&mut AchievementCounter) -> u64) -> u64 {
+    match ctx.db.achievement_counter().identity().find(identity) {
+        Some(mut counter) => {
+            let value = bump(&mut counter);
+            ctx.db.achievement_counter().identity().update(counter);
+            value
+        }
+        None => {
+            let mut counter = AchievementCounter { identity, player_kills: 0, npc_kills: 0, jumps: 0 };
+            let value = bump(&mut counter);
+            ctx.db.achievement_counter().insert(counter);
+            value
+        }
+    }
+}
+
+// Achievements on `stat` that `value` reaches and that aren't unlocked yet
+fn newly_reached(definitions: impl Iterator<Item = AchievementDefinition>, stat: AchievementStat, value: u64, unlocked: &[String]) -> Vec<AchievementDefinition> {
+    definitions.filter(|d| d.stat == stat && value >= d.threshold && !unlocked.contains(&d.achievement_id)).collect()
+}
+
+fn check_unlocks(ctx: &ReducerContext, identity: Identity, stat: AchievementStat, value: u64) {
+    let unlocked: Vec<String> = ctx.db.player_achievement().owner().filter(identity).map(|a| a.achievement_id).collect();
+    for definition in newly_reached(ctx.db.achievement_definition().iter(), stat, value, &unlocked) {
+        ctx.db.player_achievement().insert(PlayerAchievement {
+            id: 0, // auto_inc
+            owner: identity,
+            achievement_id: definition.achievement_id.clone(),
+            unlocked_at: ctx.timestamp,
+        });
+        emit_achievement_unlocked(ctx, identity, &definition.achievement_id);
+        spacetimedb::log::info!("Player {} unlocked achievement {}", identity, definition.achievement_id);
+    }
+}
+
+pub fn on_player_kill(ctx: &ReducerContext, killer: Identity) {
+    let value = bump_counter(ctx, killer, |c| { c.player_kills += 1; c.player_kills });
+    check_unlocks(ctx, killer, AchievementStat::PlayerKills, value);
+}
+
+pub fn on_npc_kill(ctx: &ReducerContext, killer: Identity) {
+    let value = bump_counter(ctx, killer, |c| { c.npc_kills += 1; c.npc_kills });
+    check_unlocks(ctx, killer, AchievementStat::NpcKills, value);
+}
+
+pub fn on_jump(ctx: &ReducerContext, identity: Identity) {
+    let value = bump_counter(ctx, identity, |c| { c.jumps += 1; c.jumps });
+    check_unlocks(ctx, identity, AchievementStat::Jumps, value);
+}
+
+pub fn on_friend_added(ctx: &ReducerContext, identity: Identity) {
+    check_unlocks(ctx, identity, AchievementStat::Friends, friend_count(ctx, identity) as u64);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn achievements_unlock_once_when_their_stat_reaches_the_threshold() {
+        let definitions = || {
+            vec![
+                achievement("first_blood", "First Blood", "", AchievementStat::PlayerKills, 1),
+                achievement("duelist", "Duelist", "", AchievementStat::PlayerKills, 50),
+                achievement("hopper", "Hopper", "", AchievementStat::Jumps, 1),
+            ]
+            .into_iter()
+        };
+        let ids = |reached: Vec<AchievementDefinition>| reached.into_iter().map(|d| d.achievement_id).collect::<Vec<_>>();
+
+        assert_eq!(ids(newly_reached(definitions(), AchievementStat::PlayerKills, 1, &[])), vec!["first_blood"]);
+        assert!(newly_reached(definitions(), AchievementStat::PlayerKills, 2, &["first_blood".to_string()]).is_empty());
+        assert_eq!(ids(newly_reached(definitions(), AchievementStat::PlayerKills, 50, &["first_blood".to_string()])), vec!["duelist"]);
+        assert_eq!(ids(newly_reached(definitions(), AchievementStat::PlayerKills, 60, &[])), vec!["first_blood", "duelist"], "catching up unlocks both");
+        assert!(newly_reached(definitions(), AchievementStat::NpcKills, 100, &[]).is_empty());
+    }
+}
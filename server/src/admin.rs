@@ -0,0 +1,132 @@
+/*
+ * Vibe Coding Starter Pack: 3D Multiplayer - admin.rs
+ *
+ * Identities allowed to call privileged reducers (server tuning, bot management, moderation).
+ *
+ * Key components:
+ *    - Admin: Private table of admin identities, seeded with the module owner in init
+ *    - is_admin / require_admin: Permission checks for reducers. A denied call returns an
+ *      error, which rolls back the transaction, so denials are audited in the module log
+ *      ([AUDIT] lines) rather than in a table
+ *    - record_admin_action: Writes an Admin entry to the audit log (audit.rs)
+ *    - kick_player: Moves a player to logged_out_player (they can register again)
+ *    - teleport_player / set_player_health: Direct edits of an active player
+ *    - broadcast_message: Server announcement to every player (chat::post_announcement)
+ */
+
+use spacetimedb::{Identity, ReducerContext, Table};
+
+use crate::audit::{record_audit, AuditCategory};
+use crate::common::Vector3;
+use crate::chat::post_announcement;
+use crate::{player, PlayerData};
+
+#[spacetimedb::table(name = admin)]
+#[derive(Clone)]
+pub struct Admin {
+    #[primary_key]
+    pub identity: Identity,
+}
+
+// Called from init, where ctx.sender is the identity that published the module
+pub fn seed_admin(ctx: &ReducerContext) {
+    if ctx.db.admin().identity().find(ctx.sender).is_none() {
+        ctx.db.admin().insert(Admin { identity: ctx.sender });
+        spacetimedb::log::info!("[INIT] Registered module owner {} as admin.", ctx.sender);
+    }
+}
+
+pub fn is_admin(ctx: &ReducerContext, identity: Identity) -> bool {
+    ctx.db.admin().identity().find(identity).is_some()
+}
+
+pub fn require_admin(ctx: &ReducerContext) -> Result<(), String> {
+    if is_admin(ctx, ctx.sender) {
+        Ok(())
+    } else {
+        spacetimedb::log::warn!("[AUDIT] Denied privileged call from non-admin {}", ctx.sender);
+        Err("Admin permission required".to_string())
+    }
+}
+
+pub fn record_admin_action(ctx: &ReducerContext, action: &str, target: Option<Identity>, details: String) {
+    record_audit(ctx, Some(ctx.sender), AuditCategory::Admin, action, target, details);
+}
+
+fn active_player(ctx: &ReducerContext, identity: Identity) -> Result<PlayerData, String> {
+    ctx.db.player().identity().find(identity).ok_or_else(|| "Player is not active".to_string())
+}
+
+#[spacetimedb::reducer]
+pub fn kick_player(ctx: &ReducerContext, target: Identity) -> Result<(), String> {
+    require_admin(ctx)?;
+    let player = active_player(ctx, target)?;
+    crate::log_out_player(ctx, target);
+    record_admin_action(ctx, "kick_player", Some(target), player.username);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn teleport_player(ctx: &ReducerContext, target: Identity, position: Vector3) -> Result<(), String> {
+    require_admin(ctx)?;
+    if !(position.x.is_finite() && position.y.is_finite() && position.z.is_finite()) {
+        return Err("Position must be finite".to_string());
+    }
+    let mut player = active_player(ctx, target)?;
+    let from = player.position;
+    player.position = position;
+    player.vertical_velocity = 0.0;
+    player.knockback_velocity = Vector3::default();
+    player.fall_distance = 0.0;
+    ctx.db.player().identity().update(player);
+    record_admin_action(ctx, "teleport_player", Some(target), format!("{:?} -> {:?}", from, position));
+    Ok(())
+}
+
+// Only living players, and never to zero (that would be a kill without a killer)
+fn check_health_edit(player: &PlayerData, health: i32) -> Result<(), String> {
+    if !player.alive {
+        return Err("Player is dead".to_string());
+    }
+    if !(1..=player.max_health).contains(&health) {
+        return Err(format!("Health must be between 1 and {}", player.max_health));
+    }
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn set_player_health(ctx: &ReducerContext, target: Identity, health: i32) -> Result<(), String> {
+    require_admin(ctx)?;
+    let mut player = active_player(ctx, target)?;
+    check_health_edit(&player, health)?;
+    let from = player.health;
+    player.health = health;
+    ctx.db.player().identity().update(player);
+    record_admin_action(ctx, "set_player_health", Some(target), format!("{} -> {}", from, health));
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn broadcast_message(ctx: &ReducerContext, text: String) -> Result<(), String> {
+    require_admin(ctx)?;
+    post_announcement(ctx, text.clone())?;
+    record_admin_action(ctx, "broadcast_message", None, text.trim().to_string());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_player;
+
+    #[test]
+    fn admins_can_set_health_within_the_living_range_only() {
+        let player = test_player(1);
+        assert_eq!(check_health_edit(&player, 1), Ok(()));
+        assert_eq!(check_health_edit(&player, player.max_health), Ok(()));
+        assert!(check_health_edit(&player, 0).is_err());
+        assert!(check_health_edit(&player, player.max_health + 1).is_err());
+        let dead = PlayerData { alive: false, ..player };
+        assert_eq!(check_health_edit(&dead, 50), Err("Player is dead".to_string()));
+    }
+}
@@ -0,0 +1,93 @@
+/*
+ * Vibe Coding Starter Pack: 3D Multiplayer - afk.rs
+ *
+ * AFK detection and idle disconnects.
+ *
+ * Key components:
+ *    - PlayerData::last_activity_time: Refreshed by update_player_input when the input does
+ *      something (a key held or the camera turned). Clients send input every frame even when
+ *      idle, so last_input_time (anticheat rate limiting) can't be used for this
+ *    - PlayerData::is_afk: Public flag set after GameConfig::afk_timeout_secs without activity
+ *      and cleared by the next activity; every change is broadcast as an AfkStatusEvent
+ *    - update_afk: Called from game_tick. Players idle for GameConfig::idle_kick_secs are
+ *      logged out (moved to logged_out_player) so the join queue can use their slot.
+ *      Either threshold can be 0 to disable it (config::set_afk_thresholds)
+ */
+
+use spacetimedb::{Identity, ReducerContext, Table};
+use std::time::Duration;
+
+use crate::common::BufferedInput;
+use crate::config::get_game_config;
+use crate::events::emit_afk_status;
+use crate::{player, PlayerData};
+
+fn is_active_input(player: &PlayerData, buffered: &BufferedInput) -> bool {
+    let input = &buffered.input;
+    let any_key = input.forward || input.backward || input.left || input.right || input.sprint
+        || input.jump || input.attack || input.cast_spell || input.crouch;
+    any_key || (buffered.client_yaw - player.rotation.y).abs() > 0.001
+}
+
+pub fn note_input(ctx: &ReducerContext, player: &mut PlayerData, buffered: &BufferedInput) {
+    if !is_active_input(player, buffered) {
+        return;
+    }
+    player.last_activity_time = ctx.timestamp;
+    if player.is_afk {
+        player.is_afk = false;
+        emit_afk_status(ctx, player.identity, false);
+    }
+}
+
+// 0 (or less) disables a threshold
+fn threshold(secs: f32) -> Option<Duration> {
+    (secs > 0.0).then(|| Duration::from_secs_f32(secs))
+}
+
+pub fn update_afk(ctx: &ReducerContext) {
+    let config = get_game_config(ctx);
+    let afk_after = threshold(config.afk_timeout_secs);
+    let kick_after = threshold(config.idle_kick_secs);
+
+    let mut idle_kicks: Vec<Identity> = Vec::new();
+    for mut player in ctx.db.player().iter() {
+        let idle = ctx.timestamp.duration_since(player.last_activity_time).unwrap_or_default();
+        if kick_after.is_some_and(|limit| idle >= limit) {
+            idle_kicks.push(player.identity);
+            continue;
+        }
+        if !player.is_afk && afk_after.is_some_and(|limit| idle >= limit) {
+            player.is_afk = true;
+            emit_afk_status(ctx, player.identity, true);
+            spacetimedb::log::info!("[AFK] {} is away", player.username);
+            ctx.db.player().identity().update(player);
+        }
+    }
+    for identity in idle_kicks {
+        spacetimedb::log::info!("[AFK] Logging out {} after {:.0}s idle", identity, config.idle_kick_secs);
+        crate::log_out_player(ctx, identity);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_player;
+
+    #[test]
+    fn idle_input_frames_do_not_count_as_activity() {
+        let player = test_player(1);
+        let idle = BufferedInput { input: player.input.clone(), client_yaw: player.rotation.y, client_pitch: 0.0, client_animation: "idle".to_string() };
+        assert!(!is_active_input(&player, &idle), "the client keeps sending input while idle");
+
+        let mut walking = idle.clone();
+        walking.input.forward = true;
+        assert!(is_active_input(&player, &walking));
+        let looking = BufferedInput { client_yaw: player.rotation.y + 0.5, ..idle };
+        assert!(is_active_input(&player, &looking), "turning the camera counts");
+
+        assert_eq!(threshold(0.0), None, "disabled");
+        assert_eq!(threshold(90.0), Some(Duration::from_secs(90)));
+    }
+}
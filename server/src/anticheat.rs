@@ -0,0 +1,162 @@
+/*
+ * Vibe Coding Starter Pack: 3D Multiplayer - anticheat.rs
+ *
+ * Server-side sanity checks on player input and movement. Violations are clamped or dropped
+ * and recorded so admins can review them.
+ *
+ * Key components:
+ *    - AnticheatFlag: Public table, one row per (identity, reason) with a violation count, the
+ *      time of the latest one and the worst observed ratio (observed / allowed)
+ *    - accept_input: Token bucket on update_player_input calls, refilled at
+ *      ANTICHEAT_MAX_INPUTS_PER_SEC from the real time since the player's last input. Inputs
+ *      beyond the burst allowance are dropped and flagged as InputFlood
+ *    - clamp_displacement: Caps a tick's horizontal displacement at max_speed * elapsed *
+ *      ANTICHEAT_SPEED_TOLERANCE (called from game_tick before collision resolution); anything
+ *      faster is pulled back and flagged as SpeedHack
+ *    - is_plausible_move: Final check on a tick's movement, comparing the new position with the
+ *      last known one. Beyond (max speed x elapsed x tolerance + ANTICHEAT_TELEPORT_SLACK) on
+ *      either axis, vertical speed including jumps and jump pads, the move is flagged as
+ *      Teleport and an AnticheatEvent is emitted; game_tick rubber-bands the player back
+ *    - New flags, and every ANTICHEAT_AUDIT_EVERY violations after that, go to the audit log
+ *    - clear_anticheat_flags: Admin reducer to reset a player's record
+ */
+
+use spacetimedb::{Identity, ReducerContext, SpacetimeType, Table, Timestamp};
+
+use crate::admin::{record_admin_action, require_admin};
+use crate::audit::{record_audit, AuditCategory};
+use crate::common::{Vector3, ANTICHEAT_AUDIT_EVERY, ANTICHEAT_INPUT_BURST, ANTICHEAT_MAX_INPUTS_PER_SEC, ANTICHEAT_SPEED_TOLERANCE, ANTICHEAT_TELEPORT_SLACK};
+use crate::events::emit_anticheat_event;
+use crate::PlayerData;
+
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq)]
+pub enum AnticheatReason {
+    SpeedHack,
+    InputFlood,
+    Teleport,
+}
+
+#[spacetimedb::table(name = anticheat_flag, public)]
+#[derive(Clone)]
+pub struct AnticheatFlag {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    #[index(btree)]
+    pub identity: Identity,
+    pub reason: AnticheatReason,
+    pub violations: u32,
+    pub last_violation: Timestamp,
+    pub worst_ratio: f32,
+}
+
+pub fn record_violation(ctx: &ReducerContext, identity: Identity, reason: AnticheatReason, ratio: f32) {
+    match ctx.db.anticheat_flag().identity().filter(identity).find(|f| f.reason == reason) {
+        Some(mut flag) => {
+            flag.violations += 1;
+            flag.last_violation = ctx.timestamp;
+            flag.worst_ratio = flag.worst_ratio.max(ratio);
+            if flag.violations % ANTICHEAT_AUDIT_EVERY == 0 {
+                let details = format!("{:?} x{} (worst x{:.2})", reason, flag.violations, flag.worst_ratio);
+                record_audit(ctx, None, AuditCategory::Anticheat, "anticheat_flag", Some(identity), details);
+            }
+            ctx.db.anticheat_flag().id().update(flag);
+        }
+        None => {
+            ctx.db.anticheat_flag().insert(AnticheatFlag { id: 0, identity, reason, violations: 1, last_violation: ctx.timestamp, worst_ratio: ratio });
+            spacetimedb::log::warn!("Anticheat: {} flagged for {:?} (x{:.2})", identity, reason, ratio);
+            record_audit(ctx, None, AuditCategory::Anticheat, "anticheat_flag", Some(identity), format!("{:?} (x{:.2})", reason, ratio));
+        }
+    }
+}
+
+// Returns false if the input should be dropped
+pub fn accept_input(ctx: &ReducerContext, player: &mut PlayerData) -> bool {
+    let elapsed = player.last_input_time
+        .and_then(|last| ctx.timestamp.duration_since(last))
+        .map_or(1.0, |d| d.as_secs_f32());
+    player.last_input_time = Some(ctx.timestamp);
+    player.input_allowance = (player.input_allowance + elapsed * ANTICHEAT_MAX_INPUTS_PER_SEC).min(ANTICHEAT_INPUT_BURST);
+    if player.input_allowance < 1.0 {
+        let ratio = 1.0 / (elapsed * ANTICHEAT_MAX_INPUTS_PER_SEC).max(0.01);
+        record_violation(ctx, player.identity, AnticheatReason::InputFlood, ratio);
+        return false;
+    }
+    player.input_allowance -= 1.0;
+    true
+}
+
+pub fn clamp_displacement(ctx: &ReducerContext, identity: Identity, from: Vector3, to: &mut Vector3, max_speed: f32, elapsed: f32) {
+    if let Some(ratio) = pull_back(from, to, max_speed * elapsed * ANTICHEAT_SPEED_TOLERANCE) {
+        record_violation(ctx, identity, AnticheatReason::SpeedHack, ratio);
+    }
+}
+
+// Shortens the horizontal move to `allowed`; returns observed / allowed if it had to
+fn pull_back(from: Vector3, to: &mut Vector3, allowed: f32) -> Option<f32> {
+    let delta = Vector3 { x: to.x - from.x, y: 0.0, z: to.z - from.z };
+    let distance = delta.length();
+    if distance <= allowed || distance <= 0.001 {
+        return None;
+    }
+    let pulled_back = from + delta * (allowed / distance);
+    to.x = pulled_back.x;
+    to.z = pulled_back.z;
+    Some(distance / allowed.max(0.001))
+}
+
+// False if the move is physically impossible (flagged and reported; the caller rejects it)
+pub fn is_plausible_move(ctx: &ReducerContext, identity: Identity, from: Vector3, to: Vector3, max_horizontal_speed: f32, max_vertical_speed: f32, elapsed: f32) -> bool {
+    let allowed_horizontal = max_horizontal_speed * elapsed * ANTICHEAT_SPEED_TOLERANCE + ANTICHEAT_TELEPORT_SLACK;
+    let allowed_vertical = max_vertical_speed * elapsed * ANTICHEAT_SPEED_TOLERANCE + ANTICHEAT_TELEPORT_SLACK;
+    let horizontal = Vector3 { x: to.x - from.x, y: 0.0, z: to.z - from.z }.length();
+    let vertical = (to.y - from.y).abs();
+    let Some(ratio) = excess_ratio(horizontal, allowed_horizontal, vertical, allowed_vertical) else {
+        return true;
+    };
+    record_violation(ctx, identity, AnticheatReason::Teleport, ratio);
+    emit_anticheat_event(ctx, identity, from, to, (horizontal - allowed_horizontal).max(0.0), (vertical - allowed_vertical).max(0.0));
+    false
+}
+
+// None while both axes are within their allowance, otherwise the worse observed / allowed
+fn excess_ratio(horizontal: f32, allowed_horizontal: f32, vertical: f32, allowed_vertical: f32) -> Option<f32> {
+    if horizontal <= allowed_horizontal && vertical <= allowed_vertical {
+        return None;
+    }
+    Some(f32::max(horizontal / allowed_horizontal, vertical / allowed_vertical))
+}
+
+#[spacetimedb::reducer]
+pub fn clear_anticheat_flags(ctx: &ReducerContext, identity: Identity) -> Result<(), String> {
+    require_admin(ctx)?;
+    ctx.db.anticheat_flag().identity().delete(identity);
+    record_admin_action(ctx, "clear_anticheat_flags", Some(identity), String::new());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn moves_beyond_the_allowance_are_pulled_back_but_falls_are_not() {
+        let from = Vector3 { x: 0.0, y: 5.0, z: 0.0 };
+        let mut legit = Vector3 { x: 3.0, y: 1.0, z: 4.0 };
+        assert_eq!(pull_back(from, &mut legit, 5.0), None);
+        assert_eq!(legit, Vector3 { x: 3.0, y: 1.0, z: 4.0 });
+
+        let mut teleport = Vector3 { x: 30.0, y: 1.0, z: 40.0 };
+        let ratio = pull_back(from, &mut teleport, 5.0).expect("a 50 unit jump must be flagged");
+        assert!((ratio - 10.0).abs() < 1e-4);
+        assert!((teleport.x - 3.0).abs() < 1e-4 && (teleport.z - 4.0).abs() < 1e-4);
+        assert_eq!(teleport.y, 1.0);
+    }
+
+    #[test]
+    fn either_axis_alone_can_make_a_move_implausible() {
+        assert_eq!(excess_ratio(4.0, 5.0, 1.0, 2.0), None);
+        assert_eq!(excess_ratio(10.0, 5.0, 1.0, 2.0), Some(2.0));
+        assert_eq!(excess_ratio(1.0, 5.0, 8.0, 2.0), Some(4.0));
+    }
+}
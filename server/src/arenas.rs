@@ -0,0 +1,225 @@
+/*
+ * Vibe Coding Starter Pack: 3D Multiplayer - arenas.rs
+ *
+ * One-on-one duels fought in arenas away from the main map.
+ *
+ * Key components:
+ *    - Arena: Public table of duel arenas, each with two spawn points (seeded in init); an
+ *      arena hosts one duel at a time
+ *    - PendingDuel: Public table of open challenges, expiring after DUEL_REQUEST_SECS
+ *    - Duel: Public table of running duels with where each duelist came from
+ *    - duel_request / accept_duel: Reducers to challenge a player and to accept a challenge.
+ *      Accepting moves both players to a free arena with full health and mana
+ *    - Isolation: Duelists can only hurt each other. zones::pvp_blocked rejects harm between a
+ *      duelist and anyone not in the same duel, and combat::apply_damage and
+ *      status_effects::apply_status_effect ignore environmental and NPC harm to duelists
+ *      (shielded_from_world)
+ *    - end_duel: Called when a duelist drops to zero health (combat::apply_damage, no real
+ *      death), disconnects (forfeit_duel), the round resets, or DUEL_MAX_SECS passes (draw).
+ *      Both players are sent back where they were with their health and mana restored
+ *    - update_duels: Expires challenges and overlong duels (called from game_tick)
+ */
+
+use spacetimedb::{Identity, ReducerContext, Table, Timestamp};
+use std::time::Duration;
+
+use crate::common::{Vector3, DUEL_MAX_SECS, DUEL_REQUEST_SECS};
+use crate::status_effects::clear_status_effects;
+use crate::{player, PlayerData};
+
+#[spacetimedb::table(name = arena, public)]
+#[derive(Clone)]
+pub struct Arena {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub spawn_a: Vector3, // challenger
+    pub spawn_b: Vector3,
+}
+
+#[spacetimedb::table(name = pending_duel, public)]
+#[derive(Clone)]
+pub struct PendingDuel {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub challenger: Identity,
+    #[index(btree)]
+    pub target: Identity,
+    pub created_at: Timestamp,
+}
+
+#[spacetimedb::table(name = duel, public)]
+#[derive(Clone)]
+pub struct Duel {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    #[unique]
+    pub arena_id: u64,
+    pub challenger: Identity,
+    pub opponent: Identity,
+    pub challenger_return: Vector3,
+    pub opponent_return: Vector3,
+    pub started_at: Timestamp,
+}
+
+pub fn seed_arenas(ctx: &ReducerContext) {
+    if ctx.db.arena().count() > 0 {
+        return;
+    }
+    for z in [0.0, 60.0] {
+        ctx.db.arena().insert(Arena {
+            id: 0, // auto_inc
+            spawn_a: Vector3 { x: 200.0, y: 1.0, z: z - 8.0 },
+            spawn_b: Vector3 { x: 200.0, y: 1.0, z: z + 8.0 },
+        });
+    }
+    spacetimedb::log::info!("[INIT] Seeded duel arenas.");
+}
+
+// Environmental and NPC damage (no attacking player) never reaches duelists
+pub fn shielded_from_world(player: &PlayerData, attacker: Option<Identity>) -> bool {
+    player.duel_id.is_some() && attacker.filter(|a| *a != player.identity).is_none()
+}
+
+fn prepare_for_arena(ctx: &ReducerContext, player: &mut PlayerData, duel_id: Option<u64>, position: Vector3) {
+    player.duel_id = duel_id;
+    player.position = position;
+    player.health = player.max_health;
+    player.mana = player.max_mana;
+    player.vertical_velocity = 0.0;
+    player.knockback_velocity = Vector3::default();
+    player.fall_distance = 0.0;
+    player.shield_amount = 0;
+    player.shield_expires_at = None;
+    player.input_queue.clear();
+    clear_status_effects(ctx, player.identity);
+}
+
+// Sends both duelists back; `loser` is None for a draw
+pub fn end_duel(ctx: &ReducerContext, duel_id: u64, loser: Option<Identity>) {
+    let Some(duel) = ctx.db.duel().id().find(duel_id) else {
+        return;
+    };
+    ctx.db.duel().id().delete(duel.id);
+    for (identity, back_to) in [(duel.challenger, duel.challenger_return), (duel.opponent, duel.opponent_return)] {
+        if let Some(mut player) = ctx.db.player().identity().find(identity) {
+            prepare_for_arena(ctx, &mut player, None, back_to);
+            ctx.db.player().identity().update(player);
+        }
+    }
+    match loser {
+        Some(loser) => {
+            let winner = if loser == duel.challenger { duel.opponent } else { duel.challenger };
+            spacetimedb::log::info!("[DUEL] {} beat {} in arena {}", winner, loser, duel.arena_id);
+        }
+        None => spacetimedb::log::info!("[DUEL] Duel in arena {} ended in a draw", duel.arena_id),
+    }
+}
+
+pub fn forfeit_duel(ctx: &ReducerContext, identity: Identity) {
+    ctx.db.pending_duel().target().delete(identity);
+    for pending in ctx.db.pending_duel().iter().filter(|p| p.challenger == identity) {
+        ctx.db.pending_duel().id().delete(pending.id);
+    }
+    if let Some(duel_id) = ctx.db.player().identity().find(identity).and_then(|p| p.duel_id) {
+        end_duel(ctx, duel_id, Some(identity));
+    }
+}
+
+pub fn end_all_duels(ctx: &ReducerContext) {
+    for duel in ctx.db.duel().iter() {
+        end_duel(ctx, duel.id, None);
+    }
+}
+
+pub fn update_duels(ctx: &ReducerContext) {
+    let request_ttl = Duration::from_secs_f32(DUEL_REQUEST_SECS);
+    for pending in ctx.db.pending_duel().iter() {
+        if ctx.timestamp.duration_since(pending.created_at).is_some_and(|age| age >= request_ttl) {
+            ctx.db.pending_duel().id().delete(pending.id);
+        }
+    }
+    let max_duration = Duration::from_secs_f32(DUEL_MAX_SECS);
+    for duel in ctx.db.duel().iter() {
+        if ctx.timestamp.duration_since(duel.started_at).is_some_and(|age| age >= max_duration) {
+            end_duel(ctx, duel.id, None);
+        }
+    }
+}
+
+fn available_duelist(ctx: &ReducerContext, identity: Identity) -> Result<PlayerData, String> {
+    match ctx.db.player().identity().find(identity) {
+        Some(p) if !p.alive => Err(format!("{} is dead", p.username)),
+        Some(p) if p.duel_id.is_some() => Err(format!("{} is already in a duel", p.username)),
+        Some(p) => Ok(p),
+        None => Err("Player is not active".to_string()),
+    }
+}
+
+#[spacetimedb::reducer]
+pub fn duel_request(ctx: &ReducerContext, target: Identity) -> Result<(), String> {
+    if target == ctx.sender {
+        return Err("You can't duel yourself".to_string());
+    }
+    available_duelist(ctx, ctx.sender)?;
+    available_duelist(ctx, target)?;
+    if ctx.db.pending_duel().target().filter(target).any(|p| p.challenger == ctx.sender) {
+        return Err("You already challenged that player".to_string());
+    }
+    ctx.db.pending_duel().insert(PendingDuel { id: 0, challenger: ctx.sender, target, created_at: ctx.timestamp });
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn accept_duel(ctx: &ReducerContext, challenger: Identity) -> Result<(), String> {
+    let Some(pending) = ctx.db.pending_duel().target().filter(ctx.sender).find(|p| p.challenger == challenger) else {
+        return Err("No duel request from that player".to_string());
+    };
+    let mut challenger_player = available_duelist(ctx, challenger)?;
+    let mut opponent_player = available_duelist(ctx, ctx.sender)?;
+    if challenger_player.instance_id != opponent_player.instance_id {
+        return Err("You are not in the same instance".to_string());
+    }
+    let Some(arena) = ctx.db.arena().iter().find(|a| ctx.db.duel().arena_id().find(a.id).is_none()) else {
+        return Err("All arenas are busy, try again shortly".to_string());
+    };
+
+    // Neither duelist keeps any other open challenges
+    ctx.db.pending_duel().id().delete(pending.id);
+    forfeit_duel(ctx, challenger);
+    forfeit_duel(ctx, ctx.sender);
+
+    let duel = ctx.db.duel().insert(Duel {
+        id: 0, // auto_inc
+        arena_id: arena.id,
+        challenger,
+        opponent: ctx.sender,
+        challenger_return: challenger_player.position,
+        opponent_return: opponent_player.position,
+        started_at: ctx.timestamp,
+    });
+    prepare_for_arena(ctx, &mut challenger_player, Some(duel.id), arena.spawn_a);
+    prepare_for_arena(ctx, &mut opponent_player, Some(duel.id), arena.spawn_b);
+    ctx.db.player().identity().update(challenger_player);
+    ctx.db.player().identity().update(opponent_player);
+    spacetimedb::log::info!("[DUEL] {} vs {} in arena {}", challenger, ctx.sender, arena.id);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_player;
+
+    #[test]
+    fn duelists_only_take_harm_from_players() {
+        let duelist = PlayerData { duel_id: Some(1), ..test_player(1) };
+        let opponent = test_player(2).identity;
+        assert!(shielded_from_world(&duelist, None), "environment and NPCs");
+        assert!(shielded_from_world(&duelist, Some(duelist.identity)), "own splash counts as the world");
+        assert!(!shielded_from_world(&duelist, Some(opponent)));
+        assert!(!shielded_from_world(&test_player(3), None), "outside a duel the world still hurts");
+    }
+}
@@ -0,0 +1,123 @@
+/*
+ * Vibe Coding Starter Pack: 3D Multiplayer - audit.rs
+ *
+ * Audit trail of sensitive actions for admins to review.
+ *
+ * Key components:
+ *    - AuditLog: Private table (actor, category, action, target, details, created_at). The
+ *      actor is None for entries the server raises on its own (anticheat)
+ *    - AuditCategory: Admin (admin::record_admin_action), Moderation (moderation.rs bans and
+ *      mutes) and Anticheat (a new flag and every ANTICHEAT_AUDIT_EVERY violations after it)
+ *    - record_audit: Writes an entry and mirrors it to the module log
+ *    - prune_audit_log: Scheduled cleanup (every AUDIT_CLEANUP_INTERVAL_SECS) that drops
+ *      entries older than AUDIT_LOG_RETENTION_SECS and keeps at most AUDIT_LOG_LIMIT rows
+ *
+ * Rejected calls roll back their transaction, so denied admin calls only reach the module
+ * log (admin::require_admin).
+ */
+
+use spacetimedb::{Identity, ReducerContext, ScheduleAt, SpacetimeType, Table, Timestamp};
+use std::time::Duration;
+
+use crate::common::{AUDIT_CLEANUP_INTERVAL_SECS, AUDIT_LOG_LIMIT, AUDIT_LOG_RETENTION_SECS};
+
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq)]
+pub enum AuditCategory {
+    Admin,
+    Moderation,
+    Anticheat,
+}
+
+#[spacetimedb::table(name = audit_log)]
+#[derive(Clone)]
+pub struct AuditLog {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub actor: Option<Identity>,
+    pub category: AuditCategory,
+    pub action: String,
+    pub target: Option<Identity>,
+    pub details: String,
+    pub created_at: Timestamp,
+}
+
+#[spacetimedb::table(name = audit_cleanup_schedule, scheduled(prune_audit_log))]
+pub struct AuditCleanupSchedule {
+    #[primary_key]
+    #[auto_inc]
+    scheduled_id: u64,
+    scheduled_at: ScheduleAt,
+}
+
+// Called from init
+pub fn schedule_audit_cleanup(ctx: &ReducerContext) {
+    if ctx.db.audit_cleanup_schedule().count() == 0 {
+        ctx.db.audit_cleanup_schedule().insert(AuditCleanupSchedule {
+            scheduled_id: 0,
+            scheduled_at: ScheduleAt::Interval(Duration::from_secs(AUDIT_CLEANUP_INTERVAL_SECS).into()),
+        });
+    }
+}
+
+pub fn record_audit(ctx: &ReducerContext, actor: Option<Identity>, category: AuditCategory, action: &str, target: Option<Identity>, details: String) {
+    spacetimedb::log::info!("[AUDIT] {:?} {:?} {} {:?} {}", category, actor, action, target, details);
+    ctx.db.audit_log().insert(AuditLog {
+        id: 0, // auto_inc
+        actor,
+        category,
+        action: action.to_string(),
+        target,
+        details,
+        created_at: ctx.timestamp,
+    });
+}
+
+#[spacetimedb::reducer]
+pub fn prune_audit_log(ctx: &ReducerContext, _schedule: AuditCleanupSchedule) -> Result<(), String> {
+    if ctx.sender != ctx.identity() {
+        return Err("prune_audit_log may only be run by the scheduler".to_string());
+    }
+    let entries: Vec<(u64, Timestamp)> = ctx.db.audit_log().iter().map(|e| (e.id, e.created_at)).collect();
+    let stale = entries_to_prune(entries, ctx.timestamp, Duration::from_secs(AUDIT_LOG_RETENTION_SECS), AUDIT_LOG_LIMIT);
+    let pruned = stale.len();
+    for id in stale {
+        ctx.db.audit_log().id().delete(id);
+    }
+    if pruned > 0 {
+        spacetimedb::log::info!("Pruned {} audit log entries", pruned);
+    }
+    Ok(())
+}
+
+// Entries past the retention window, then the oldest of the rest beyond the row limit
+fn entries_to_prune(entries: Vec<(u64, Timestamp)>, now: Timestamp, retention: Duration, limit: usize) -> Vec<u64> {
+    let (mut stale, mut kept): (Vec<u64>, Vec<u64>) = (Vec::new(), Vec::new());
+    for (id, created_at) in entries {
+        if now.duration_since(created_at).is_some_and(|age| age > retention) {
+            stale.push(id);
+        } else {
+            kept.push(id);
+        }
+    }
+    kept.sort();
+    let excess = kept.len().saturating_sub(limit);
+    stale.extend_from_slice(&kept[..excess]);
+    stale
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pruning_drops_old_entries_then_the_oldest_over_the_limit() {
+        let hour = Duration::from_secs(3600);
+        let at = |hours: u64| Timestamp::from_micros_since_unix_epoch((hours * 3_600_000_000) as i64);
+        let now = at(100);
+        let entries = vec![(4, at(99)), (1, at(10)), (3, at(98)), (2, at(97)), (5, at(100))];
+        assert_eq!(entries_to_prune(entries.clone(), now, hour * 24, 10), vec![1]);
+        assert_eq!(entries_to_prune(entries.clone(), now, hour * 24, 2), vec![1, 2, 3]);
+        assert!(entries_to_prune(entries, now, hour * 200, 5).is_empty());
+    }
+}
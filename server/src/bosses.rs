@@ -0,0 +1,298 @@
+/*
+ * Vibe Coding Starter Pack: 3D Multiplayer - bosses.rs
+ *
+ * Scripted boss encounters for NpcType::Boss (npcs.rs). The boss chases and swings like any
+ * other NPC; this module adds the script on top.
+ *
+ * Key components:
+ *    - BossEncounter: Public table, one row per living boss, with its phase, when it was
+ *      pulled and whether it is enraged. spawn_boss / the add_boss admin reducer create both
+ *      the NPC and its encounter (dungeons.rs places one in every boss room)
+ *    - Phases: The boss starts in phase 1 and moves to the next phase when its health drops
+ *      below each of BOSS_PHASE_THRESHOLDS, summoning adds every time
+ *    - BossAbilitySchedule: One-shot scheduled reducers driving the script (run_boss_ability):
+ *        - SlamWindup / Slam: Telegraphed AOE hitting every player (and pet) of the instance within
+ *          BOSS_SLAM_RADIUS; each slam schedules the next one BOSS_SLAM_INTERVAL_SECS / phase later
+ *        - SummonAdds: BOSS_ADD_COUNT goblins around the boss (no spawner, so they never respawn)
+ *        - Enrage: BOSS_ENRAGE_SECS after the pull the boss's hits deal
+ *          BOSS_ENRAGE_DAMAGE_MULTIPLIER times the damage
+ *    - BossContribution: Public table of damage dealt to each boss per player. When it dies,
+ *      everyone still online who dealt at least BOSS_LOOT_MIN_SHARE of its health gets gold
+ *      and a piece of equipment (inventory.rs; what doesn't fit is dropped at their feet)
+ *    - Reset: An ability firing while the boss has no target (the party wiped or ran) puts it
+ *      back at its spawn with full health and clears contributions and pending abilities
+ *    - BossEvent (events.rs): Feed of pulls, phase changes, telegraphs, slams, summons, enrage,
+ *      defeat and resets for client cinematics
+ */
+
+use spacetimedb::rand::Rng;
+use spacetimedb::{Identity, ReducerContext, ScheduleAt, SpacetimeType, Table, TimeDuration, Timestamp};
+
+use crate::admin::{record_admin_action, require_admin};
+use crate::combat::{alive_players_in_radius, apply_damage};
+use crate::common::{
+    Vector3, BOSS_ADD_COUNT, BOSS_ENRAGE_DAMAGE_MULTIPLIER, BOSS_ENRAGE_SECS, BOSS_LOOT_MIN_SHARE, BOSS_PHASE_THRESHOLDS, BOSS_SLAM_DAMAGE,
+    BOSS_SLAM_INTERVAL_SECS, BOSS_SLAM_RADIUS, BOSS_SLAM_WINDUP_SECS,
+};
+use crate::events::{emit_boss_event, BossEventKind};
+use crate::instances::instance;
+use crate::inventory::{add_item_to_inventory, item_definition};
+use crate::loot::spawn_dropped_item;
+use crate::npcs::{insert_npc, npc, Npc, NpcSpec, NpcState, NpcType};
+use crate::pets::damage_pets_in_radius;
+use crate::player;
+
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq)]
+pub enum BossAbility {
+    SlamWindup,
+    Slam,
+    SummonAdds,
+    Enrage,
+}
+
+#[spacetimedb::table(name = boss_encounter, public)]
+#[derive(Clone)]
+pub struct BossEncounter {
+    #[primary_key]
+    pub npc_id: u64,
+    #[index(btree)]
+    pub instance_id: u64,
+    pub home: Vector3, // where the boss resets to
+    pub phase: u8,     // 1-based
+    pub engaged_at: Option<Timestamp>,
+    pub enraged: bool,
+}
+
+#[spacetimedb::table(name = boss_contribution, public)]
+#[derive(Clone)]
+pub struct BossContribution {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    #[index(btree)]
+    pub npc_id: u64,
+    pub identity: Identity,
+    pub damage: u64,
+}
+
+#[spacetimedb::table(name = boss_ability_schedule, scheduled(run_boss_ability))]
+pub struct BossAbilitySchedule {
+    #[primary_key]
+    #[auto_inc]
+    scheduled_id: u64,
+    scheduled_at: ScheduleAt,
+    #[index(btree)]
+    npc_id: u64,
+    ability: BossAbility,
+}
+
+pub fn spawn_boss(ctx: &ReducerContext, position: Vector3, instance_id: u64) -> Npc {
+    let boss = insert_npc(ctx, NpcType::Boss, position, NpcSpec::for_type(NpcType::Boss).max_health, 0, instance_id);
+    ctx.db.boss_encounter().insert(BossEncounter { npc_id: boss.id, instance_id, home: position, phase: 1, engaged_at: None, enraged: false });
+    boss
+}
+
+fn schedule_ability(ctx: &ReducerContext, npc_id: u64, ability: BossAbility, delay_secs: f32) {
+    ctx.db.boss_ability_schedule().insert(BossAbilitySchedule {
+        scheduled_id: 0,
+        scheduled_at: ScheduleAt::Time(ctx.timestamp + TimeDuration::from_micros((delay_secs * 1_000_000.0) as i64)),
+        npc_id,
+        ability,
+    });
+}
+
+fn clear_encounter_state(ctx: &ReducerContext, npc_id: u64) {
+    ctx.db.boss_contribution().npc_id().delete(npc_id);
+    ctx.db.boss_ability_schedule().npc_id().delete(npc_id);
+}
+
+// Multiplier on the boss's melee and slam damage; 1 for every other NPC
+pub fn boss_damage_multiplier(ctx: &ReducerContext, npc_id: u64) -> i32 {
+    match ctx.db.boss_encounter().npc_id().find(npc_id) {
+        Some(encounter) if encounter.enraged => BOSS_ENRAGE_DAMAGE_MULTIPLIER,
+        _ => 1,
+    }
+}
+
+// Phase for the boss's remaining health: 1, plus one per threshold passed
+fn boss_phase(health: i32, max_health: i32) -> u8 {
+    let fraction = health as f32 / max_health as f32;
+    1 + BOSS_PHASE_THRESHOLDS.iter().filter(|t| fraction <= **t).count() as u8
+}
+
+// Damage that counts towards loot: overkill past the boss's last health point doesn't
+fn counted_damage(amount: i32, health_after: i32) -> u64 {
+    amount.min(health_after + amount).max(0) as u64
+}
+
+fn earns_loot(damage: u64, max_health: i32) -> bool {
+    damage >= (max_health as f32 * BOSS_LOOT_MIN_SHARE) as u64
+}
+
+// Called from npcs::damage_npc with the health already reduced
+pub fn on_boss_damaged(ctx: &ReducerContext, boss: &Npc, amount: i32, attacker: Identity) {
+    let Some(mut encounter) = ctx.db.boss_encounter().npc_id().find(boss.id) else {
+        return;
+    };
+    let dealt = counted_damage(amount, boss.health);
+    let existing = ctx.db.boss_contribution().npc_id().filter(boss.id).find(|c| c.identity == attacker);
+    match existing {
+        Some(mut contribution) => {
+            contribution.damage += dealt;
+            ctx.db.boss_contribution().id().update(contribution);
+        }
+        None => {
+            ctx.db.boss_contribution().insert(BossContribution { id: 0, npc_id: boss.id, identity: attacker, damage: dealt });
+        }
+    }
+
+    if encounter.engaged_at.is_none() {
+        encounter.engaged_at = Some(ctx.timestamp);
+        emit_boss_event(ctx, BossEventKind::Engaged, boss.id, boss.instance_id, encounter.phase, boss.position);
+        schedule_ability(ctx, boss.id, BossAbility::SlamWindup, BOSS_SLAM_INTERVAL_SECS);
+        schedule_ability(ctx, boss.id, BossAbility::Enrage, BOSS_ENRAGE_SECS);
+    }
+    let phase = boss_phase(boss.health, boss.max_health);
+    if boss.health > 0 && phase > encounter.phase {
+        encounter.phase = phase;
+        spacetimedb::log::info!("[BOSS] Boss {} entered phase {}", boss.id, phase);
+        emit_boss_event(ctx, BossEventKind::PhaseChanged, boss.id, boss.instance_id, phase, boss.position);
+        schedule_ability(ctx, boss.id, BossAbility::SummonAdds, 0.0);
+    }
+    ctx.db.boss_encounter().npc_id().update(encounter);
+}
+
+// Called from npcs::damage_npc once the boss's row is gone
+pub fn on_boss_killed(ctx: &ReducerContext, boss: &Npc) {
+    let Some(encounter) = ctx.db.boss_encounter().npc_id().find(boss.id) else {
+        return;
+    };
+    let mut equipment: Vec<String> = ctx.db.item_definition().iter().filter(|d| d.slot.is_some()).map(|d| d.item_id).collect();
+    equipment.sort();
+    let mut rng = ctx.rng();
+    for contribution in ctx.db.boss_contribution().npc_id().filter(boss.id) {
+        if !earns_loot(contribution.damage, boss.max_health) {
+            continue;
+        }
+        let Some(mut player) = ctx.db.player().identity().find(contribution.identity) else {
+            continue;
+        };
+        player.gold = player.gold.saturating_add(rng.gen_range(50..=100));
+        let instance_id = player.instance_id;
+        let position = player.position;
+        ctx.db.player().identity().update(player);
+        if !equipment.is_empty() {
+            let item_id = &equipment[rng.gen_range(0..equipment.len())];
+            let leftover = add_item_to_inventory(ctx, contribution.identity, item_id, 1).unwrap_or(1);
+            spawn_dropped_item(ctx, instance_id, item_id, leftover, position);
+        }
+    }
+    spacetimedb::log::info!("[BOSS] Boss {} defeated", boss.id);
+    emit_boss_event(ctx, BossEventKind::Defeated, boss.id, boss.instance_id, encounter.phase, boss.position);
+    clear_encounter_state(ctx, boss.id);
+    ctx.db.boss_encounter().npc_id().delete(boss.id);
+}
+
+// Called from instances::remove_instance, after the instance's NPCs are deleted
+pub fn remove_boss_encounters(ctx: &ReducerContext, instance_id: u64) {
+    for encounter in ctx.db.boss_encounter().instance_id().filter(instance_id) {
+        clear_encounter_state(ctx, encounter.npc_id);
+        ctx.db.boss_encounter().npc_id().delete(encounter.npc_id);
+    }
+}
+
+fn reset_encounter(ctx: &ReducerContext, mut boss: Npc, mut encounter: BossEncounter) {
+    clear_encounter_state(ctx, boss.id);
+    boss.health = boss.max_health;
+    boss.position = encounter.home;
+    boss.path.clear();
+    encounter.phase = 1;
+    encounter.engaged_at = None;
+    encounter.enraged = false;
+    spacetimedb::log::info!("[BOSS] Boss {} reset", boss.id);
+    emit_boss_event(ctx, BossEventKind::Reset, boss.id, boss.instance_id, 1, boss.position);
+    ctx.db.npc().id().update(boss);
+    ctx.db.boss_encounter().npc_id().update(encounter);
+}
+
+#[spacetimedb::reducer]
+pub fn run_boss_ability(ctx: &ReducerContext, schedule: BossAbilitySchedule) -> Result<(), String> {
+    if ctx.sender != ctx.identity() {
+        return Err("run_boss_ability may only be run by the scheduler".to_string());
+    }
+    let (Some(boss), Some(mut encounter)) = (ctx.db.npc().id().find(schedule.npc_id), ctx.db.boss_encounter().npc_id().find(schedule.npc_id)) else {
+        return Ok(());
+    };
+    if boss.state == NpcState::Idle {
+        reset_encounter(ctx, boss, encounter);
+        return Ok(());
+    }
+
+    match schedule.ability {
+        BossAbility::SlamWindup => {
+            emit_boss_event(ctx, BossEventKind::SlamWindup, boss.id, boss.instance_id, encounter.phase, boss.position);
+            schedule_ability(ctx, boss.id, BossAbility::Slam, BOSS_SLAM_WINDUP_SECS);
+        }
+        BossAbility::Slam => {
+            let damage = BOSS_SLAM_DAMAGE * boss_damage_multiplier(ctx, boss.id);
+            for victim in alive_players_in_radius(ctx, boss.instance_id, boss.position, BOSS_SLAM_RADIUS) {
+                apply_damage(ctx, victim.identity, damage, None);
+            }
+            damage_pets_in_radius(ctx, boss.instance_id, boss.position, BOSS_SLAM_RADIUS, damage, None);
+            emit_boss_event(ctx, BossEventKind::Slam, boss.id, boss.instance_id, encounter.phase, boss.position);
+            schedule_ability(ctx, boss.id, BossAbility::SlamWindup, BOSS_SLAM_INTERVAL_SECS / encounter.phase as f32);
+        }
+        BossAbility::SummonAdds => {
+            let spec = NpcSpec::for_type(NpcType::Goblin);
+            for i in 0..BOSS_ADD_COUNT {
+                let angle = i as f32 * std::f32::consts::TAU / BOSS_ADD_COUNT as f32;
+                let offset = Vector3 { x: angle.cos() * 3.0, y: 0.0, z: angle.sin() * 3.0 };
+                insert_npc(ctx, NpcType::Goblin, boss.position + offset, spec.max_health, 0, boss.instance_id);
+            }
+            emit_boss_event(ctx, BossEventKind::SummonAdds, boss.id, boss.instance_id, encounter.phase, boss.position);
+        }
+        BossAbility::Enrage => {
+            encounter.enraged = true;
+            spacetimedb::log::info!("[BOSS] Boss {} enraged", boss.id);
+            emit_boss_event(ctx, BossEventKind::Enraged, boss.id, boss.instance_id, encounter.phase, boss.position);
+            ctx.db.boss_encounter().npc_id().update(encounter);
+        }
+    }
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn add_boss(ctx: &ReducerContext, position: Vector3, instance_id: u64) -> Result<(), String> {
+    require_admin(ctx)?;
+    if ctx.db.instance().id().find(instance_id).is_none() {
+        return Err("Instance not found".to_string());
+    }
+    let boss = spawn_boss(ctx, position, instance_id);
+    record_admin_action(ctx, "add_boss", None, format!("npc {} in instance {}", boss.id, instance_id));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bosses_change_phase_as_their_health_drops() {
+        assert_eq!(boss_phase(1000, 1000), 1);
+        assert_eq!(boss_phase(701, 1000), 1);
+        assert_eq!(boss_phase(700, 1000), 2);
+        assert_eq!(boss_phase(350, 1000), 3);
+        assert_eq!(boss_phase(1, 1000), 3, "no phase past the last threshold");
+    }
+
+    #[test]
+    fn only_real_damage_counts_towards_boss_loot() {
+        assert_eq!(counted_damage(40, 60), 40);
+        assert_eq!(counted_damage(40, -25), 15, "the killing blow only counts the health it took");
+        assert_eq!(counted_damage(40, -50), 0);
+
+        let share = (1000.0 * BOSS_LOOT_MIN_SHARE) as u64;
+        assert!(earns_loot(share, 1000));
+        assert!(!earns_loot(share - 1, 1000), "below the minimum share");
+    }
+}
@@ -0,0 +1,128 @@
+/*
+ * Vibe Coding Starter Pack: 3D Multiplayer - bots.rs
+ *
+ * PvE bots: stationary turrets that shoot Bolts at the nearest living player.
+ *
+ * Key components:
+ *    - Bot: Public table of bots; shots are owned by the module identity
+ *    - spawn_bot / remove_bot: Admin reducers
+ *    - update_bots: Per-tick targeting and firing (called from game_tick)
+ *
+ * Difficulty (GameConfig::bot_difficulty, 0.0..=1.0):
+ *    - aim_error_radians: Max random yaw/pitch error, shrinking to zero at full difficulty
+ *    - reaction_secs: Delay between shots, shorter at higher difficulty
+ *    - Jitter comes from ctx.rng(), which is seeded from the tick timestamp, so a given
+ *      tick and difficulty always produce the same shot
+ */
+
+use spacetimedb::{ReducerContext, Table};
+
+use crate::admin::require_admin;
+use crate::common::{
+    ProjectileKind, Vector3, LOBBY_INSTANCE_ID, BOT_FAST_REACTION_SECS, BOT_MAX_AIM_ERROR_RADIANS, BOT_SIGHT_RANGE, BOT_SLOW_REACTION_SECS,
+};
+use crate::config::get_game_config;
+use crate::player;
+use crate::projectile_logic::{consume_global_spawn_budget, jitter_direction, spawn_projectile_of_kind};
+
+const BOT_MUZZLE_HEIGHT: f32 = 1.5;
+
+#[spacetimedb::table(name = bot, public)]
+#[derive(Clone)]
+pub struct Bot {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub name: String,
+    pub position: Vector3,
+    pub reaction_timer: f32, // seconds until the bot may fire again
+}
+
+pub fn aim_error_radians(difficulty: f32) -> f32 {
+    BOT_MAX_AIM_ERROR_RADIANS * (1.0 - difficulty.clamp(0.0, 1.0))
+}
+
+pub fn reaction_secs(difficulty: f32) -> f32 {
+    let d = difficulty.clamp(0.0, 1.0);
+    BOT_SLOW_REACTION_SECS + (BOT_FAST_REACTION_SECS - BOT_SLOW_REACTION_SECS) * d
+}
+
+#[spacetimedb::reducer]
+pub fn spawn_bot(ctx: &ReducerContext, name: String, position: Vector3) -> Result<(), String> {
+    require_admin(ctx)?;
+    let bot = ctx.db.bot().insert(Bot { id: 0, name, position, reaction_timer: 0.0 });
+    spacetimedb::log::info!("Spawned bot {} ({}) at {:?}", bot.id, bot.name, bot.position);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn remove_bot(ctx: &ReducerContext, bot_id: u64) -> Result<(), String> {
+    require_admin(ctx)?;
+    if !ctx.db.bot().id().delete(bot_id) {
+        return Err("Bot not found".to_string());
+    }
+    Ok(())
+}
+
+pub fn update_bots(ctx: &ReducerContext, delta_time: f32) {
+    if ctx.db.bot().count() == 0 {
+        return;
+    }
+    let difficulty = get_game_config(ctx).bot_difficulty;
+    let mut rng = ctx.rng();
+
+    for mut bot in ctx.db.bot().iter() {
+        bot.reaction_timer = (bot.reaction_timer - delta_time).max(0.0);
+        if bot.reaction_timer > 0.0 {
+            ctx.db.bot().id().update(bot);
+            continue;
+        }
+
+        let muzzle = bot.position + Vector3 { x: 0.0, y: BOT_MUZZLE_HEIGHT, z: 0.0 };
+        let target = ctx.db.player()
+            .iter()
+            .filter(|p| p.alive && p.instance_id == LOBBY_INSTANCE_ID && (p.position - muzzle).length() <= BOT_SIGHT_RANGE)
+            .min_by(|a, b| (a.position - muzzle).length().total_cmp(&(b.position - muzzle).length()));
+        let Some(target) = target else {
+            continue;
+        };
+        if consume_global_spawn_budget(ctx).is_err() {
+            continue;
+        }
+
+        let aim_point = target.position + Vector3 { x: 0.0, y: target.hit_radius, z: 0.0 };
+        let direction = jitter_direction((aim_point - muzzle).normalize(), aim_error_radians(difficulty), &mut rng);
+        spawn_projectile_of_kind(ctx, ctx.identity(), muzzle, direction, ProjectileKind::Bolt);
+        bot.reaction_timer = reaction_secs(difficulty);
+        ctx.db.bot().id().update(bot);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use spacetimedb::rand::rngs::StdRng;
+    use spacetimedb::rand::SeedableRng;
+
+    // Mean angle between the true direction and the shots a bot fires at this difficulty
+    fn mean_aim_error(difficulty: f32) -> f32 {
+        let mut rng = StdRng::seed_from_u64(7);
+        let direction = Vector3 { x: 0.0, y: 0.0, z: 1.0 };
+        let shots = 500;
+        let total: f32 = (0..shots)
+            .map(|_| {
+                let shot = jitter_direction(direction, aim_error_radians(difficulty), &mut rng);
+                (shot.x * direction.x + shot.y * direction.y + shot.z * direction.z).clamp(-1.0, 1.0).acos()
+            })
+            .sum();
+        total / shots as f32
+    }
+
+    #[test]
+    fn higher_difficulty_aims_tighter() {
+        let errors: Vec<f32> = [0.0, 0.25, 0.5, 0.75].into_iter().map(mean_aim_error).collect();
+        assert!(errors.windows(2).all(|w| w[1] < w[0]), "{errors:?}");
+        assert!(mean_aim_error(1.0) < 1e-3, "full difficulty never misses its aim");
+        assert!(reaction_secs(1.0) < reaction_secs(0.0));
+    }
+}
@@ -0,0 +1,328 @@
+/*
+ * Vibe Coding Starter Pack: 3D Multiplayer - chat.rs
+ *
+ * Player chat with global, team and whisper channels and per-player block lists.
+ *
+ * Messages are stored once in the private `chat_message` table and fanned out to
+ * one row per recipient in the private `chat_delivery` table. Clients subscribe to the
+ * `my_chat` view, which only returns the caller's own deliveries, so team and whisper
+ * messages never reach anyone else and the server can skip recipients who have blocked the
+ * sender without any client-side filtering. Block lists are private as well; `my_blocks`
+ * shows a player the blocks they made.
+ *
+ * Views:
+ *    - my_chat: The caller's chat feed (their chat_delivery rows)
+ *    - my_blocks: The players the caller has blocked
+ *
+ * Reducers:
+ *    - send_chat_message: Post to the global channel (kept for existing clients)
+ *    - send_chat: Post to a specific ChatChannel (Global, Team, Whisper(target), Party, Guild)
+ *    - post_announcement: Server-wide Announcement from admin::broadcast_message, delivered to
+ *      everyone regardless of block lists and not rate limited
+ *    - block_player / unblock_player: Manage your block list
+ *    - prune_chat_messages: Scheduled cleanup (every CHAT_CLEANUP_INTERVAL_SECS) that deletes
+ *      messages and deliveries older than CHAT_RETENTION_SECS
+ *
+ * Limits: messages are trimmed, must be 1..=MAX_CHAT_MESSAGE_LEN characters, and each sender
+ * may post at most CHAT_RATE_LIMIT_MESSAGES per CHAT_RATE_LIMIT_WINDOW_SECS. Muted players
+ * (moderation.rs) can't post at all.
+ */
+
+use spacetimedb::{Identity, ReducerContext, ScheduleAt, SpacetimeType, Table, Timestamp, ViewContext};
+use std::time::Duration;
+
+use crate::common::{
+    CHAT_CLEANUP_INTERVAL_SECS, CHAT_RATE_LIMIT_MESSAGES, CHAT_RATE_LIMIT_WINDOW_SECS, CHAT_RETENTION_SECS, MAX_CHAT_MESSAGE_LEN,
+};
+use crate::guilds::guild_members;
+use crate::moderation::require_not_muted;
+use crate::party::party_members;
+use crate::player;
+
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq)]
+pub enum ChatChannel {
+    Global,
+    Team,              // sender's current team only
+    Whisper(Identity), // a single recipient (the sender also gets a copy)
+    Party,             // sender's party (party.rs)
+    Guild,             // online members of the sender's guild (guilds.rs)
+    Announcement,      // admin broadcast (post_announcement), can't be sent with send_chat
+}
+
+#[spacetimedb::table(name = chat_message)]
+#[derive(Clone)]
+pub struct ChatMessage {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    #[index(btree)]
+    pub sender: Identity,
+    pub sender_name: String,
+    pub channel: ChatChannel,
+    pub text: String,
+    pub sent_at: Timestamp,
+}
+
+#[spacetimedb::table(name = chat_delivery)]
+#[derive(Clone)]
+pub struct ChatDelivery {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub message_id: u64,
+    #[index(btree)]
+    pub recipient: Identity,
+    pub sender: Identity,
+    pub sender_name: String,
+    pub channel: ChatChannel,
+    pub text: String,
+    pub sent_at: Timestamp,
+}
+
+#[spacetimedb::table(name = player_block)]
+#[derive(Clone)]
+pub struct PlayerBlock {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    #[index(btree)]
+    pub blocker: Identity,
+    pub blocked: Identity,
+}
+
+#[spacetimedb::view(name = my_chat, public)]
+pub fn my_chat(ctx: &ViewContext) -> Vec<ChatDelivery> {
+    ctx.db.chat_delivery().recipient().filter(ctx.sender).collect()
+}
+
+#[spacetimedb::view(name = my_blocks, public)]
+pub fn my_blocks(ctx: &ViewContext) -> Vec<PlayerBlock> {
+    ctx.db.player_block().blocker().filter(ctx.sender).collect()
+}
+
+#[spacetimedb::table(name = chat_cleanup_schedule, scheduled(prune_chat_messages))]
+pub struct ChatCleanupSchedule {
+    #[primary_key]
+    #[auto_inc]
+    scheduled_id: u64,
+    scheduled_at: ScheduleAt,
+}
+
+// Called from init
+pub fn schedule_chat_cleanup(ctx: &ReducerContext) {
+    if ctx.db.chat_cleanup_schedule().count() == 0 {
+        ctx.db.chat_cleanup_schedule().insert(ChatCleanupSchedule {
+            scheduled_id: 0,
+            scheduled_at: ScheduleAt::Interval(Duration::from_secs(CHAT_CLEANUP_INTERVAL_SECS).into()),
+        });
+    }
+}
+
+pub fn is_blocked(ctx: &ReducerContext, blocker: Identity, sender: Identity) -> bool {
+    ctx.db.player_block().blocker().filter(blocker).any(|b| b.blocked == sender)
+}
+
+#[spacetimedb::reducer]
+pub fn send_chat_message(ctx: &ReducerContext, text: String) -> Result<(), String> {
+    send_chat(ctx, ChatChannel::Global, text)
+}
+
+#[spacetimedb::reducer]
+pub fn send_chat(ctx: &ReducerContext, channel: ChatChannel, text: String) -> Result<(), String> {
+    let Some(sender) = ctx.db.player().identity().find(ctx.sender) else {
+        return Err("Player is not active".to_string());
+    };
+    require_not_muted(ctx, sender.identity)?;
+    let text = clean_message(&text)?;
+
+    let window = Duration::from_secs(CHAT_RATE_LIMIT_WINDOW_SECS);
+    let recent = ctx.db.chat_message()
+        .sender()
+        .filter(sender.identity)
+        .filter(|m| ctx.timestamp.duration_since(m.sent_at).is_none_or(|age| age < window))
+        .count();
+    if recent >= CHAT_RATE_LIMIT_MESSAGES {
+        return Err("You are sending messages too quickly".to_string());
+    }
+
+    let recipients: Vec<Identity> = match channel {
+        ChatChannel::Global => ctx.db.player().iter().filter(|p| p.instance_id == sender.instance_id).map(|p| p.identity).collect(),
+        ChatChannel::Team => ctx.db.player().iter().filter(|p| p.instance_id == sender.instance_id && p.team == sender.team).map(|p| p.identity).collect(),
+        ChatChannel::Whisper(target) => {
+            if target == sender.identity {
+                return Err("You cannot whisper to yourself".to_string());
+            }
+            if ctx.db.player().identity().find(target).is_none() {
+                return Err("That player is not online".to_string());
+            }
+            vec![target, sender.identity]
+        }
+        ChatChannel::Party => {
+            let members = party_members(ctx, sender.identity);
+            if members.is_empty() {
+                return Err("You are not in a party".to_string());
+            }
+            members
+        }
+        ChatChannel::Guild => {
+            let members = guild_members(ctx, sender.identity);
+            if members.is_empty() {
+                return Err("You are not in a guild".to_string());
+            }
+            members
+        }
+        ChatChannel::Announcement => return Err("Announcements are sent with broadcast_message".to_string()),
+    };
+
+    let message = ctx.db.chat_message().insert(ChatMessage {
+        id: 0, // auto_inc
+        sender: sender.identity,
+        sender_name: sender.username.clone(),
+        channel,
+        text,
+        sent_at: ctx.timestamp,
+    });
+
+    let blocks: Vec<PlayerBlock> = recipients.iter().flat_map(|r| ctx.db.player_block().blocker().filter(*r)).collect();
+    for recipient in unblocked_recipients(recipients, sender.identity, &blocks) {
+        ctx.db.chat_delivery().insert(ChatDelivery {
+            id: 0, // auto_inc
+            message_id: message.id,
+            recipient,
+            sender: message.sender,
+            sender_name: message.sender_name.clone(),
+            channel,
+            text: message.text.clone(),
+            sent_at: message.sent_at,
+        });
+    }
+    Ok(())
+}
+
+// Trimmed text, rejected when empty or longer than MAX_CHAT_MESSAGE_LEN characters
+fn clean_message(text: &str) -> Result<String, String> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err("Message is empty".to_string());
+    }
+    if text.chars().count() > MAX_CHAT_MESSAGE_LEN {
+        return Err(format!("Message is longer than {} characters", MAX_CHAT_MESSAGE_LEN));
+    }
+    Ok(text.to_string())
+}
+
+// Drop every recipient who has blocked the sender
+fn unblocked_recipients(recipients: Vec<Identity>, sender: Identity, blocks: &[PlayerBlock]) -> Vec<Identity> {
+    recipients.into_iter().filter(|r| !blocks.iter().any(|b| b.blocker == *r && b.blocked == sender)).collect()
+}
+
+#[spacetimedb::reducer]
+pub fn prune_chat_messages(ctx: &ReducerContext, _schedule: ChatCleanupSchedule) -> Result<(), String> {
+    if ctx.sender != ctx.identity() {
+        return Err("prune_chat_messages may only be run by the scheduler".to_string());
+    }
+    let retention = Duration::from_secs(CHAT_RETENTION_SECS);
+    let is_old = |sent_at: Timestamp| ctx.timestamp.duration_since(sent_at).is_some_and(|age| age > retention);
+
+    let mut pruned = 0;
+    for message in ctx.db.chat_message().iter() {
+        if is_old(message.sent_at) {
+            ctx.db.chat_message().id().delete(message.id);
+            pruned += 1;
+        }
+    }
+    for delivery in ctx.db.chat_delivery().iter() {
+        if is_old(delivery.sent_at) {
+            ctx.db.chat_delivery().id().delete(delivery.id);
+        }
+    }
+    if pruned > 0 {
+        spacetimedb::log::info!("Pruned {} chat messages older than {}s", pruned, CHAT_RETENTION_SECS);
+    }
+    Ok(())
+}
+
+pub fn post_announcement(ctx: &ReducerContext, text: String) -> Result<(), String> {
+    let text = clean_message(&text)?;
+    let message = ctx.db.chat_message().insert(ChatMessage {
+        id: 0, // auto_inc
+        sender: ctx.sender,
+        sender_name: "Server".to_string(),
+        channel: ChatChannel::Announcement,
+        text,
+        sent_at: ctx.timestamp,
+    });
+    for player in ctx.db.player().iter() {
+        ctx.db.chat_delivery().insert(ChatDelivery {
+            id: 0, // auto_inc
+            message_id: message.id,
+            recipient: player.identity,
+            sender: message.sender,
+            sender_name: message.sender_name.clone(),
+            channel: ChatChannel::Announcement,
+            text: message.text.clone(),
+            sent_at: message.sent_at,
+        });
+    }
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn block_player(ctx: &ReducerContext, target: Identity) -> Result<(), String> {
+    if target == ctx.sender {
+        return Err("You cannot block yourself".to_string());
+    }
+    if is_blocked(ctx, ctx.sender, target) {
+        return Ok(()); // already blocked
+    }
+    ctx.db.player_block().insert(PlayerBlock {
+        id: 0, // auto_inc
+        blocker: ctx.sender,
+        blocked: target,
+    });
+    spacetimedb::log::info!("Player {} blocked {}", ctx.sender, target);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn unblock_player(ctx: &ReducerContext, target: Identity) -> Result<(), String> {
+    let Some(block) = ctx.db.player_block().blocker().filter(ctx.sender).find(|b| b.blocked == target) else {
+        return Err("Player is not blocked".to_string());
+    };
+    ctx.db.player_block().id().delete(block.id);
+    spacetimedb::log::info!("Player {} unblocked {}", ctx.sender, target);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn identity(n: u8) -> Identity {
+        Identity::from_byte_array([n; 32])
+    }
+
+    #[test]
+    fn a_blocked_sender_is_hidden_from_the_blocker_only() {
+        let (sender, blocker, bystander) = (identity(1), identity(2), identity(3));
+        let blocks = [PlayerBlock { id: 1, blocker, blocked: sender }];
+        assert_eq!(unblocked_recipients(vec![sender, blocker, bystander], sender, &blocks), vec![sender, bystander]);
+    }
+
+    #[test]
+    fn messages_are_trimmed_and_length_checked() {
+        assert_eq!(clean_message("  gg  "), Ok("gg".to_string()));
+        assert!(clean_message("   ").is_err());
+        // The limit counts characters, not bytes
+        assert!(clean_message(&"é".repeat(MAX_CHAT_MESSAGE_LEN)).is_ok());
+        assert!(clean_message(&"a".repeat(MAX_CHAT_MESSAGE_LEN + 1)).is_err());
+    }
+
+    #[test]
+    fn blocks_only_apply_one_way() {
+        let (sender, blocker) = (identity(1), identity(2));
+        let blocks = [PlayerBlock { id: 1, blocker, blocked: sender }];
+        // The blocker still reaches the player they blocked
+        assert_eq!(unblocked_recipients(vec![sender, blocker], blocker, &blocks), vec![sender, blocker]);
+    }
+}
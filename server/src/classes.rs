@@ -0,0 +1,173 @@
+/*
+ * Vibe Coding Starter Pack: 3D Multiplayer - classes.rs
+ *
+ * Character class stats, stored as data so new classes don't need code changes.
+ *
+ * Key components:
+ *    - ClassDefinition: Public table keyed by class name (matches the client's character
+ *      select, e.g. "Wizard", "Paladin"); seeded in init
+ *    - class_definition_for: Lookup with a generic fallback for unknown class names
+ *    - load_class_definitions: Snapshot for per-tick loops (one table scan per tick)
+ *    - set_class_definition: Admin reducer to add or retune a class at runtime
+ *
+ * Consumers: register_player (base health/mana), game_tick movement (move_speed),
+ * spells::cast (base mana cost, global cooldown, damage multiplier), player_logic (regen rates).
+ */
+
+use spacetimedb::{ReducerContext, Table};
+
+use crate::admin::require_admin;
+use crate::common::PLAYER_SPEED;
+
+#[spacetimedb::table(name = class_definition, public)]
+#[derive(Clone)]
+pub struct ClassDefinition {
+    #[primary_key]
+    pub name: String,
+    pub base_health: i32,
+    pub base_mana: i32,
+    pub move_speed: f32,
+    pub projectile_damage_multiplier: f32,
+    pub mana_cost_per_cast: i32,  // charged on every cast, plus the spell's own cost (thrown axes use ammo instead)
+    pub cast_cooldown_secs: f32,  // minimum time between casts, enforced via last_cast_time
+    pub health_regen_per_sec: f32,
+    pub mana_regen_per_sec: f32,
+}
+
+impl ClassDefinition {
+    // Stats for a class name with no row (free-form names from older clients)
+    fn fallback(name: &str) -> Self {
+        ClassDefinition {
+            name: name.to_string(),
+            base_health: 100,
+            base_mana: 100,
+            move_speed: PLAYER_SPEED,
+            projectile_damage_multiplier: 1.0,
+            mana_cost_per_cast: 10,
+            cast_cooldown_secs: 0.4,
+            health_regen_per_sec: 2.0,
+            mana_regen_per_sec: 2.0,
+        }
+    }
+}
+
+pub fn default_class_definitions() -> [ClassDefinition; 2] {
+    [
+        // Caster: fast mana, slow health, cheap rapid casts
+        ClassDefinition {
+            name: "Wizard".to_string(),
+            base_health: 90,
+            base_mana: 120,
+            move_speed: PLAYER_SPEED,
+            projectile_damage_multiplier: 1.0,
+            mana_cost_per_cast: 8,
+            cast_cooldown_secs: 0.25,
+            health_regen_per_sec: 1.0,
+            mana_regen_per_sec: 5.0,
+        },
+        // Melee: fast health, slow mana, expensive slow casts
+        ClassDefinition {
+            name: "Paladin".to_string(),
+            base_health: 130,
+            base_mana: 80,
+            move_speed: PLAYER_SPEED * 0.9,
+            projectile_damage_multiplier: 1.2,
+            mana_cost_per_cast: 15,
+            cast_cooldown_secs: 0.6,
+            health_regen_per_sec: 4.0,
+            mana_regen_per_sec: 1.5,
+        },
+    ]
+}
+
+pub fn seed_class_definitions(ctx: &ReducerContext) {
+    if ctx.db.class_definition().count() > 0 {
+        return;
+    }
+    let defaults = default_class_definitions();
+    let count = defaults.len();
+    for definition in defaults {
+        ctx.db.class_definition().insert(definition);
+    }
+    spacetimedb::log::info!("[INIT] Seeded {} class definitions.", count);
+}
+
+pub fn class_definition_for(ctx: &ReducerContext, name: &str) -> ClassDefinition {
+    ctx.db.class_definition().name().find(name.to_string()).unwrap_or_else(|| ClassDefinition::fallback(name))
+}
+
+pub fn load_class_definitions(ctx: &ReducerContext) -> Vec<ClassDefinition> {
+    ctx.db.class_definition().iter().collect()
+}
+
+// Lookup in a per-tick snapshot from load_class_definitions
+pub fn find_class(definitions: &[ClassDefinition], name: &str) -> ClassDefinition {
+    definitions.iter().find(|d| d.name == name).cloned().unwrap_or_else(|| ClassDefinition::fallback(name))
+}
+
+#[spacetimedb::reducer]
+#[allow(clippy::too_many_arguments)]
+pub fn set_class_definition(
+    ctx: &ReducerContext,
+    name: String,
+    base_health: i32,
+    base_mana: i32,
+    move_speed: f32,
+    projectile_damage_multiplier: f32,
+    mana_cost_per_cast: i32,
+    cast_cooldown_secs: f32,
+    health_regen_per_sec: f32,
+    mana_regen_per_sec: f32,
+) -> Result<(), String> {
+    require_admin(ctx)?;
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("Class name is empty".to_string());
+    }
+    if base_health <= 0 || base_mana < 0 || mana_cost_per_cast < 0 {
+        return Err("Health must be positive and mana values non-negative".to_string());
+    }
+    let rates = [move_speed, projectile_damage_multiplier, cast_cooldown_secs, health_regen_per_sec, mana_regen_per_sec];
+    if !rates.iter().all(|v| v.is_finite() && *v >= 0.0) {
+        return Err("Speeds, multipliers, cooldowns and regen rates must be non-negative".to_string());
+    }
+
+    let definition = ClassDefinition {
+        name: name.clone(),
+        base_health,
+        base_mana,
+        move_speed,
+        projectile_damage_multiplier,
+        mana_cost_per_cast,
+        cast_cooldown_secs,
+        health_regen_per_sec,
+        mana_regen_per_sec,
+    };
+    if ctx.db.class_definition().name().find(name.clone()).is_some() {
+        ctx.db.class_definition().name().update(definition);
+    } else {
+        ctx.db.class_definition().insert(definition);
+    }
+    spacetimedb::log::info!("Class definition '{}' saved", name);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_classes_use_their_row_and_unknown_names_fall_back() {
+        let mut definitions = default_class_definitions().to_vec();
+        definitions[1].move_speed = 3.0; // retuned at runtime via set_class_definition
+
+        let paladin = find_class(&definitions, "Paladin");
+        assert_eq!(paladin.move_speed, 3.0);
+        assert_eq!(paladin.base_health, 130);
+
+        let unknown = find_class(&definitions, "Bard");
+        assert_eq!(unknown.name, "Bard");
+        assert_eq!(unknown.move_speed, PLAYER_SPEED);
+        assert_eq!(unknown.mana_cost_per_cast, ClassDefinition::fallback("Bard").mana_cost_per_cast);
+    }
+}
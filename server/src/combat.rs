@@ -0,0 +1,561 @@
+/*
+ * Vibe Coding Starter Pack: 3D Multiplayer - combat.rs
+ *
+ * Shared damage and healing pipeline. Every system that hurts or heals a player
+ * (projectiles, explosions, hazards) goes through these helpers so death handling
+ * stays in one place.
+ *
+ * Key components:
+ *    - apply_damage: Subtracts health, clamps at zero, tracks last_attacker, handles death
+ *      (killed_by for the death camera, loot drop) and the respawn time (respawn_at). Sets the
+ *      one-tick `is_damaged` flag and emits a DamageEvent for client hit feedback. Scaled by the
+ *      attacker's weapon and reduced by the target's armor (inventory.rs), never below 1, then
+ *      absorbed by any shield before health; a hit the shield soaks entirely counts for nothing
+ *      else. Returns the damage that reached health, or None when the hit was blocked, so
+ *      callers report what actually landed. Player-vs-player damage is cancelled in no_pvp zones
+ *      (zones.rs), and all damage outside a running match (matches.rs). A duelist reaching zero
+ *      health loses the duel instead of dying (arenas::end_duel)
+ *    - cast_shield: Reducer granting SHIELD_AMOUNT absorb for SHIELD_SECS (refreshes, doesn't
+ *      stack); expire_shields (game_tick) removes timed-out shields. A ShieldBreakEvent is
+ *      emitted whenever a shield is depleted or expires
+ *    - heal_player: Restores health capped at max_health
+ *    - cast_heal: Reducer healing a living ally (same team and duel, or yourself) within HEAL_RANGE
+ *      for HEAL_MANA_COST, cooldown in last_heal_time; emits a HealEvent for healing numbers
+ *    - apply_knockback: Pushes a living player; horizontal impulse goes into knockback_velocity
+ *      (capped at MAX_KNOCKBACK_SPEED, integrated with friction by player_logic::calculate_new_position), upward impulse
+ *      into vertical_velocity. Direct projectile hits push along the projectile's flight,
+ *      explosions push away from the blast
+ *    - alive_players_in_radius: Sphere query used by area effects
+ *    - melee_attack: Reducer for a cone swing in front of the attacker (physics::in_melee_arc),
+ *      with a cooldown tracked in last_melee_time; emits CombatEvent rows carrying the damage
+ *      actually dealt (blocked hits aren't reported, a swing with no landed hit is a miss). Also hits
+ *      destructible props in the arc (destructibles.rs)
+ *    - fire_hitscan: Reducer for an instant shot from the client's muzzle along the aim
+ *      direction (physics::raycast against players and static colliders). Damages the first
+ *      player hit, cooldown in last_hitscan_time; emits a HitscanTraceEvent for tracers carrying
+ *      the damage actually dealt (0 when the hit was blocked)
+ *
+ * Ultimate charge:
+ *    - Attackers gain ult_charge per point of damage dealt; killers and assisters
+ *      (anyone else in the victim's damaged_by list) get a bonus on each kill
+ *    - Killers also earn PLAYER_KILL_XP (progression::award_kill_xp)
+ *    - Passive gain over time happens in player_logic::update_players_logic
+ *    - cast_ultimate: Arcane nova around the caster, only usable at full charge
+ */
+
+use spacetimedb::{Identity, ReducerContext, Table, TimeDuration, Timestamp};
+
+use crate::common::{
+    Vector3, HITSCAN_COOLDOWN_SECS, HITSCAN_DAMAGE, HITSCAN_MAX_MUZZLE_OFFSET, HITSCAN_RANGE, MAX_KNOCKBACK_SPEED, SHIELD_AMOUNT, SHIELD_MANA_COST, SHIELD_SECS, MELEE_ARC_DEGREES, MELEE_COOLDOWN_SECS, MELEE_DAMAGE, MELEE_RANGE, RESPAWN_SECS, ULT_CHARGE_MAX, ULT_CHARGE_PER_ASSIST, ULT_CHARGE_PER_DAMAGE, HEAL_AMOUNT, HEAL_COOLDOWN_SECS, HEAL_MANA_COST, HEAL_RANGE,
+    ULT_CHARGE_PER_KILL, ULT_NOVA_DAMAGE, ULT_NOVA_RADIUS, PLAYER_KILL_XP,
+};
+use crate::achievements::on_player_kill;
+use crate::arenas::{end_duel, shielded_from_world};
+use crate::classes::class_definition_for;
+use crate::environment::{load_static_colliders, StaticCollider};
+use crate::mounts::force_dismount;
+use crate::pets::{set_pet_target, PetTarget};
+use crate::events::{emit_combat_event, emit_damage, emit_heal, emit_hitscan_trace, emit_shield_break, CombatAction, ImpactTarget};
+use crate::loot::drop_player_loot;
+use crate::matches::{gameplay_enabled, require_in_progress};
+use crate::destructibles::damage_destructibles_in_arc;
+use crate::physics::{in_melee_arc, raycast};
+use crate::progression::award_kill_xp;
+use crate::scoreboard::record_kill;
+use crate::status_effects::{clear_status_effects, require_armed, is_stunned};
+use crate::zones::{pvp_blocked, require_can_cast};
+use crate::{player, PlayerData};
+use std::time::Duration;
+
+pub fn apply_knockback(ctx: &ReducerContext, target: Identity, impulse: Vector3) {
+    let Some(mut player) = ctx.db.player().identity().find(target) else {
+        return;
+    };
+    if !player.alive {
+        return;
+    }
+    let mut push = player.knockback_velocity + Vector3 { x: impulse.x, y: 0.0, z: impulse.z };
+    let speed = push.length();
+    if speed > MAX_KNOCKBACK_SPEED {
+        push = push * (MAX_KNOCKBACK_SPEED / speed);
+    }
+    player.knockback_velocity = push;
+    if impulse.y > 0.0 {
+        player.vertical_velocity = player.vertical_velocity.max(0.0) + impulse.y;
+        player.grounded = false;
+    }
+    ctx.db.player().identity().update(player);
+}
+
+// Apply damage to a living player. Returns the damage that reached their health, or None
+// if the hit was blocked entirely (dead or missing target).
+pub fn apply_damage(ctx: &ReducerContext, target: Identity, amount: i32, attacker: Option<Identity>) -> Option<i32> {
+    let mut player = ctx.db.player().identity().find(target)?;
+    if !player.alive || amount <= 0 || !gameplay_enabled(ctx, player.instance_id) || pvp_blocked(ctx, attacker, target) || shielded_from_world(&player, attacker) {
+        return None;
+    }
+
+    // Equipment: the attacker's weapon scales it, the target's armor soaks a fraction
+    let weapon_multiplier = attacker
+        .filter(|a| *a != target)
+        .and_then(|a| ctx.db.player().identity().find(a))
+        .map_or(1.0, |a| a.equipment_damage_multiplier);
+    let mut amount = equipped_damage(amount, weapon_multiplier, player.equipment_damage_reduction);
+
+    if player.shield_amount > 0 {
+        amount -= absorb_with_shield(&mut player, amount);
+        if player.shield_amount == 0 {
+            player.shield_expires_at = None;
+            emit_shield_break(ctx, target, false, player.position);
+        }
+    }
+    // Fully absorbed: the shield took it, so no hit reaction, dismount or kill credit
+    if amount == 0 {
+        ctx.db.player().identity().update(player);
+        return Some(0);
+    }
+
+    player.last_damage_time = Some(ctx.timestamp);
+    let attacker = attacker.filter(|a| *a != target); // self-damage is never credited
+    let dealt = take_hit(&mut player, amount, attacker);
+
+    // Losing a duel isn't a death: both duelists go back where they came from
+    if player.health <= 0 {
+        if let Some(duel_id) = player.duel_id {
+            player.health = 0;
+            emit_damage(ctx, target, attacker, dealt, 0, player.position, false);
+            ctx.db.player().identity().update(player);
+            end_duel(ctx, duel_id, Some(target));
+            return Some(dealt);
+        }
+    }
+
+    // Handle death - delayed respawn after RESPAWN_SECS
+    let killed = player.health <= 0;
+    if killed {
+        mark_dead(&mut player, ctx.timestamp);
+        clear_status_effects(ctx, player.identity);
+        drop_player_loot(ctx, &mut player);
+        spacetimedb::log::info!("Player {} died! Respawning in {} seconds...", player.username, RESPAWN_SECS);
+    }
+
+    emit_damage(ctx, target, attacker, dealt, player.health, player.position, killed);
+    let killed_by = player.killed_by;
+    let assisters: Vec<Identity> = player.damaged_by.iter().copied().filter(|a| Some(*a) != killed_by).collect();
+    ctx.db.player().identity().update(player);
+
+    if let Some(attacker) = attacker {
+        add_ult_charge(ctx, attacker, dealt as f32 * ULT_CHARGE_PER_DAMAGE);
+        set_pet_target(ctx, attacker, PetTarget::Player(target));
+    }
+    if killed {
+        if let Some(killer) = killed_by {
+            add_ult_charge(ctx, killer, ULT_CHARGE_PER_KILL);
+        }
+        for assister in &assisters {
+            add_ult_charge(ctx, *assister, ULT_CHARGE_PER_ASSIST);
+        }
+        record_kill(ctx, killed_by, &assisters, target);
+        if let Some(killer) = killed_by.filter(|k| *k != target) {
+            award_kill_xp(ctx, killer, PLAYER_KILL_XP);
+            on_player_kill(ctx, killer);
+        }
+    }
+    Some(dealt)
+}
+
+fn equipped_damage(amount: i32, weapon_multiplier: f32, armor_reduction: f32) -> i32 {
+    ((amount as f32 * weapon_multiplier * (1.0 - armor_reduction)).round() as i32).max(1)
+}
+
+// Soak up to `amount` with the player's shield, returns how much it absorbed
+fn absorb_with_shield(player: &mut PlayerData, amount: i32) -> i32 {
+    let absorbed = amount.min(player.shield_amount);
+    player.shield_amount -= absorbed;
+    absorbed
+}
+
+// Take health off and credit the attacker. Returns the damage actually dealt
+fn take_hit(player: &mut PlayerData, amount: i32, attacker: Option<Identity>) -> i32 {
+    let dealt = amount.min(player.health);
+    player.health -= dealt;
+    player.is_damaged = true; // hit-reaction flag, cleared by the next game_tick movement pass
+    force_dismount(player);
+    if let Some(attacker) = attacker {
+        player.last_attacker = Some(attacker);
+        if !player.damaged_by.contains(&attacker) {
+            player.damaged_by.push(attacker);
+        }
+    }
+    dealt
+}
+
+// The last attacker gets the kill
+fn mark_dead(player: &mut PlayerData, now: Timestamp) {
+    player.health = 0;
+    player.alive = false;
+    player.respawn_at = Some(now + TimeDuration::from_micros((RESPAWN_SECS * 1_000_000.0) as i64));
+    player.killed_by = player.last_attacker;
+    player.shield_amount = 0;
+    player.shield_expires_at = None;
+}
+
+pub fn add_ult_charge(ctx: &ReducerContext, identity: Identity, amount: f32) {
+    let Some(mut player) = ctx.db.player().identity().find(identity) else {
+        return;
+    };
+    if charge_ult(&mut player, amount) {
+        ctx.db.player().identity().update(player);
+    }
+}
+
+// Returns false if there was nothing to add
+fn charge_ult(player: &mut PlayerData, amount: f32) -> bool {
+    if amount <= 0.0 || player.ult_charge >= ULT_CHARGE_MAX {
+        return false;
+    }
+    player.ult_charge = (player.ult_charge + amount).min(ULT_CHARGE_MAX);
+    true
+}
+
+fn require_ult_ready(player: &PlayerData) -> Result<(), String> {
+    if player.ult_charge < ULT_CHARGE_MAX {
+        return Err(format!("Ultimate not ready ({:.0}/{:.0})", player.ult_charge, ULT_CHARGE_MAX));
+    }
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn cast_ultimate(ctx: &ReducerContext) -> Result<(), String> {
+    let Some(mut caster) = ctx.db.player().identity().find(ctx.sender) else {
+        return Err("Player is not active".to_string());
+    };
+    if !caster.alive {
+        return Err("Cannot cast while dead".to_string());
+    }
+    require_in_progress(ctx, caster.instance_id)?;
+    require_can_cast(ctx, &caster)?;
+    require_ult_ready(&caster)?;
+    require_armed(ctx, caster.identity, "cast")?;
+    if is_stunned(ctx, caster.identity) {
+        return Err("Cannot cast while stunned".to_string());
+    }
+
+    caster.ult_charge = 0.0;
+    let center = caster.position;
+    let caster_identity = caster.identity;
+    let instance_id = caster.instance_id;
+    ctx.db.player().identity().update(caster);
+
+    for victim in alive_players_in_radius(ctx, instance_id, center, ULT_NOVA_RADIUS) {
+        if victim.identity != caster_identity {
+            apply_damage(ctx, victim.identity, ULT_NOVA_DAMAGE, Some(caster_identity));
+        }
+    }
+    spacetimedb::log::info!("Player {} cast their ultimate", caster_identity);
+    Ok(())
+}
+
+// Server-authoritative melee swing in the direction the attacker is facing
+#[spacetimedb::reducer]
+pub fn melee_attack(ctx: &ReducerContext) -> Result<(), String> {
+    let Some(mut attacker) = ctx.db.player().identity().find(ctx.sender) else {
+        return Err("Player is not active".to_string());
+    };
+    if !attacker.alive {
+        return Err("Cannot attack while dead".to_string());
+    }
+    require_in_progress(ctx, attacker.instance_id)?;
+    require_armed(ctx, attacker.identity, "attack")?;
+    if is_stunned(ctx, attacker.identity) {
+        return Err("Cannot attack while stunned".to_string());
+    }
+    let on_cooldown = attacker.last_melee_time
+        .and_then(|last| ctx.timestamp.duration_since(last))
+        .is_some_and(|elapsed| elapsed < Duration::from_secs_f32(MELEE_COOLDOWN_SECS));
+    if on_cooldown {
+        return Err("Attack is on cooldown".to_string());
+    }
+
+    attacker.last_melee_time = Some(ctx.timestamp);
+    attacker.is_attacking = true;
+    attacker.is_stealthed = false; // swinging gives away your position
+    let attacker_identity = attacker.identity;
+    let origin = attacker.position;
+    let yaw = attacker.rotation.y;
+    let instance_id = attacker.instance_id;
+    let damage = (MELEE_DAMAGE as f32 * class_definition_for(ctx, &attacker.character_class).projectile_damage_multiplier).round() as i32;
+    ctx.db.player().identity().update(attacker);
+
+    let half_angle = MELEE_ARC_DEGREES.to_radians() / 2.0;
+    let victims: Vec<PlayerData> = alive_players_in_radius(ctx, instance_id, origin, MELEE_RANGE)
+        .into_iter()
+        .filter(|p| p.identity != attacker_identity && in_melee_arc(origin, yaw, p.position, MELEE_RANGE + p.hit_radius, half_angle))
+        .collect();
+    let mut landed = false;
+    for victim in victims {
+        if let Some(dealt) = apply_damage(ctx, victim.identity, damage, Some(attacker_identity)) {
+            emit_combat_event(ctx, CombatAction::MeleeHit, attacker_identity, Some(victim.identity), dealt, origin);
+            landed = true;
+        }
+    }
+    if !landed {
+        emit_combat_event(ctx, CombatAction::MeleeMiss, attacker_identity, None, 0, origin);
+    }
+    damage_destructibles_in_arc(ctx, instance_id, origin, yaw, MELEE_RANGE, half_angle, damage);
+    Ok(())
+}
+
+// Server-authoritative instant shot along the shooter's aim, starting at the client's muzzle
+#[spacetimedb::reducer]
+pub fn fire_hitscan(ctx: &ReducerContext, muzzle_position: Vector3) -> Result<(), String> {
+    let Some(mut shooter) = ctx.db.player().identity().find(ctx.sender) else {
+        return Err("Player is not active".to_string());
+    };
+    if !shooter.alive {
+        return Err("Cannot fire while dead".to_string());
+    }
+    require_in_progress(ctx, shooter.instance_id)?;
+    require_can_cast(ctx, &shooter)?;
+    require_armed(ctx, shooter.identity, "fire")?;
+    if is_stunned(ctx, shooter.identity) {
+        return Err("Cannot fire while stunned".to_string());
+    }
+    let on_cooldown = shooter.last_hitscan_time
+        .and_then(|last| ctx.timestamp.duration_since(last))
+        .is_some_and(|elapsed| elapsed < Duration::from_secs_f32(HITSCAN_COOLDOWN_SECS));
+    if on_cooldown {
+        return Err("Shot is on cooldown".to_string());
+    }
+    if (muzzle_position - shooter.position).length() > HITSCAN_MAX_MUZZLE_OFFSET {
+        return Err("Muzzle is too far from the shooter".to_string());
+    }
+
+    shooter.last_hitscan_time = Some(ctx.timestamp);
+    shooter.is_stealthed = false; // the shot gives away your position
+    let shooter_identity = shooter.identity;
+    let instance_id = shooter.instance_id;
+    let direction = shooter.forward_vector.normalize();
+    let damage = (HITSCAN_DAMAGE as f32 * class_definition_for(ctx, &shooter.character_class).projectile_damage_multiplier).round() as i32;
+    ctx.db.player().identity().update(shooter);
+
+    let targets: Vec<PlayerData> = ctx.db.player().iter().filter(|p| p.alive && p.identity != shooter_identity && p.instance_id == instance_id).collect();
+    let colliders: Vec<StaticCollider> = load_static_colliders(ctx).into_iter().filter(|c| c.instance_id == instance_id).collect();
+    let hit = raycast(muzzle_position, direction, HITSCAN_RANGE, &targets, &colliders);
+    let end = muzzle_position + direction * hit.map_or(HITSCAN_RANGE, |(_, distance)| distance);
+    let dealt = match hit {
+        Some((ImpactTarget::Player(victim), _)) => apply_damage(ctx, victim, damage, Some(shooter_identity)).unwrap_or(0),
+        _ => 0,
+    };
+    emit_hitscan_trace(ctx, shooter_identity, muzzle_position, end, hit.map(|(target, _)| target), dealt);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn cast_shield(ctx: &ReducerContext) -> Result<(), String> {
+    let Some(mut caster) = ctx.db.player().identity().find(ctx.sender) else {
+        return Err("Player is not active".to_string());
+    };
+    if !caster.alive {
+        return Err("Cannot cast while dead".to_string());
+    }
+    if is_stunned(ctx, caster.identity) {
+        return Err("Cannot cast while stunned".to_string());
+    }
+    require_in_progress(ctx, caster.instance_id)?;
+    require_can_cast(ctx, &caster)?;
+    if caster.mana < SHIELD_MANA_COST {
+        return Err("Not enough mana".to_string());
+    }
+    caster.mana -= SHIELD_MANA_COST;
+    caster.shield_amount = caster.shield_amount.max(SHIELD_AMOUNT);
+    caster.shield_expires_at = Some(ctx.timestamp + TimeDuration::from_micros((SHIELD_SECS * 1_000_000.0) as i64));
+    ctx.db.player().identity().update(caster);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn cast_heal(ctx: &ReducerContext, target: Identity) -> Result<(), String> {
+    let Some(mut caster) = ctx.db.player().identity().find(ctx.sender) else {
+        return Err("Player is not active".to_string());
+    };
+    if !caster.alive {
+        return Err("Cannot cast while dead".to_string());
+    }
+    if is_stunned(ctx, caster.identity) {
+        return Err("Cannot cast while stunned".to_string());
+    }
+    require_in_progress(ctx, caster.instance_id)?;
+    require_can_cast(ctx, &caster)?;
+    let Some(patient) = ctx.db.player().identity().find(target) else {
+        return Err("Target is not active".to_string());
+    };
+    check_heal_target(&caster, &patient)?;
+    let on_cooldown = caster.last_heal_time
+        .and_then(|last| ctx.timestamp.duration_since(last))
+        .is_some_and(|elapsed| elapsed < Duration::from_secs_f32(HEAL_COOLDOWN_SECS));
+    if on_cooldown {
+        return Err("Heal is on cooldown".to_string());
+    }
+    if caster.mana < HEAL_MANA_COST {
+        return Err("Not enough mana".to_string());
+    }
+
+    caster.mana -= HEAL_MANA_COST;
+    caster.last_heal_time = Some(ctx.timestamp);
+    let caster_identity = caster.identity;
+    ctx.db.player().identity().update(caster);
+
+    let healed = heal_player(ctx, target, HEAL_AMOUNT);
+    let remaining_health = ctx.db.player().identity().find(target).map_or(0, |p| p.health);
+    emit_heal(ctx, caster_identity, target, healed, remaining_health, patient.position);
+    Ok(())
+}
+
+// Living ally (or yourself) within HEAL_RANGE
+fn check_heal_target(caster: &PlayerData, patient: &PlayerData) -> Result<(), String> {
+    if !patient.alive {
+        return Err("Target is dead".to_string());
+    }
+    if patient.identity != caster.identity && patient.team != caster.team {
+        return Err("Can only heal allies".to_string());
+    }
+    // Duelists are isolated from everyone outside their duel, heals included
+    if patient.duel_id != caster.duel_id {
+        return Err("Cannot heal across a duel".to_string());
+    }
+    if patient.instance_id != caster.instance_id || (patient.position - caster.position).length() > HEAL_RANGE {
+        return Err("Target is out of range".to_string());
+    }
+    Ok(())
+}
+
+pub fn expire_shields(ctx: &ReducerContext) {
+    for mut player in ctx.db.player().iter().filter(|p| p.shield_amount > 0) {
+        if player.shield_expires_at.is_some_and(|at| at <= ctx.timestamp) {
+            player.shield_amount = 0;
+            player.shield_expires_at = None;
+            emit_shield_break(ctx, player.identity, true, player.position);
+            ctx.db.player().identity().update(player);
+        }
+    }
+}
+
+// Heal a living player, returns the amount actually restored
+pub fn heal_player(ctx: &ReducerContext, target: Identity, amount: i32) -> i32 {
+    let Some(mut player) = ctx.db.player().identity().find(target) else {
+        return 0;
+    };
+    if !player.alive || amount <= 0 {
+        return 0;
+    }
+    let healed = amount.min(player.max_health - player.health).max(0);
+    if healed > 0 {
+        player.health += healed;
+        ctx.db.player().identity().update(player);
+    }
+    healed
+}
+
+pub fn alive_players_in_radius(ctx: &ReducerContext, instance_id: u64, center: Vector3, radius: f32) -> Vec<PlayerData> {
+    ctx.db.player()
+        .iter()
+        .filter(|p| p.alive && p.instance_id == instance_id && (p.position - center).length() <= radius + p.hit_radius)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::PROJECTILE_DAMAGE;
+    use crate::test_player;
+
+    #[test]
+    fn a_projectile_kill_is_credited_to_its_owner() {
+        let mut victim = test_player(1);
+        let bystander = test_player(2).identity;
+        let shooter = test_player(3).identity;
+        victim.health = PROJECTILE_DAMAGE + 5;
+
+        // Someone else chips in first, the shooter's projectile lands the killing blow
+        assert_eq!(take_hit(&mut victim, 10, Some(bystander)), 10);
+        assert!(victim.health > 0);
+        assert_eq!(take_hit(&mut victim, PROJECTILE_DAMAGE, Some(shooter)), PROJECTILE_DAMAGE - 5);
+        assert_eq!(victim.health, 0);
+        mark_dead(&mut victim, Timestamp::UNIX_EPOCH);
+
+        assert!(!victim.alive);
+        assert_eq!(victim.killed_by, Some(shooter));
+        assert_eq!(victim.damaged_by, vec![bystander, shooter]);
+        assert_eq!(victim.respawn_at, Some(Timestamp::UNIX_EPOCH + TimeDuration::from_micros((RESPAWN_SECS * 1_000_000.0) as i64)));
+    }
+
+    #[test]
+    fn dealing_damage_charges_the_ultimate_until_it_is_ready() {
+        let mut attacker = test_player(1);
+        let hit = PROJECTILE_DAMAGE as f32 * ULT_CHARGE_PER_DAMAGE;
+        let hits_to_fill = (ULT_CHARGE_MAX / hit).ceil() as u32;
+        for _ in 1..hits_to_fill {
+            assert!(charge_ult(&mut attacker, hit));
+            assert!(require_ult_ready(&attacker).is_err());
+        }
+        assert!(charge_ult(&mut attacker, hit));
+        assert_eq!(attacker.ult_charge, ULT_CHARGE_MAX);
+        assert!(require_ult_ready(&attacker).is_ok());
+        assert!(!charge_ult(&mut attacker, hit), "charge is capped");
+    }
+
+    #[test]
+    fn a_hit_clamps_health_at_zero_and_flags_the_hit_reaction() {
+        let mut victim = test_player(1);
+        victim.health = 30;
+        assert!(!victim.is_damaged);
+
+        assert_eq!(take_hit(&mut victim, PROJECTILE_DAMAGE, None), 30, "only the remaining health is dealt");
+        assert_eq!(victim.health, 0);
+        assert!(victim.is_damaged);
+    }
+
+    #[test]
+    fn heals_reach_living_allies_in_range_only() {
+        let caster = test_player(1);
+        let ally = PlayerData { position: Vector3 { x: HEAL_RANGE - 1.0, y: 0.0, z: 0.0 }, ..test_player(2) };
+        assert_eq!(check_heal_target(&caster, &caster), Ok(()), "self-heal");
+        assert_eq!(check_heal_target(&caster, &ally), Ok(()));
+        let enemy = PlayerData { team: caster.team + 1, ..ally.clone() };
+        assert_eq!(check_heal_target(&caster, &enemy), Err("Can only heal allies".to_string()));
+        let far = PlayerData { position: Vector3 { x: HEAL_RANGE + 1.0, y: 0.0, z: 0.0 }, ..ally.clone() };
+        assert_eq!(check_heal_target(&caster, &far), Err("Target is out of range".to_string()));
+        let dueling = PlayerData { duel_id: Some(1), ..ally.clone() };
+        assert_eq!(check_heal_target(&caster, &dueling), Err("Cannot heal across a duel".to_string()));
+        let dead = PlayerData { alive: false, ..ally };
+        assert_eq!(check_heal_target(&caster, &dead), Err("Target is dead".to_string()));
+    }
+
+    #[test]
+    fn shields_soak_damage_before_health() {
+        let mut player = test_player(1);
+        player.shield_amount = 30;
+        assert_eq!(absorb_with_shield(&mut player, 20), 20);
+        assert_eq!(player.shield_amount, 10);
+        assert_eq!(absorb_with_shield(&mut player, 25), 10, "the rest goes through");
+        assert_eq!(player.shield_amount, 0);
+        assert_eq!(absorb_with_shield(&mut player, 25), 0);
+    }
+
+    #[test]
+    fn weapons_scale_damage_and_armor_soaks_it_but_every_hit_does_something() {
+        assert_eq!(equipped_damage(20, 1.0, 0.0), 20);
+        assert_eq!(equipped_damage(20, 1.5, 0.0), 30);
+        assert_eq!(equipped_damage(20, 1.5, 0.5), 15);
+        assert_eq!(equipped_damage(1, 1.0, 0.9), 1);
+    }
+
+    #[test]
+    fn uncredited_deaths_have_no_killer() {
+        let mut victim = test_player(1);
+        take_hit(&mut victim, 100, None);
+        mark_dead(&mut victim, Timestamp::UNIX_EPOCH);
+        assert_eq!(victim.killed_by, None);
+    }
+}
@@ -1,4 +1,4 @@
-/**
+/*!
  * Vibe Coding Starter Pack: 3D Multiplayer - common.rs
  * 
  * This file contains shared data structures and constants used throughout the application.
@@ -100,23 +100,90 @@ pub struct InputState {
     pub jump: bool,
     pub attack: bool,
     pub cast_spell: bool, // server field; TS will map to castSpell
+    pub crouch: bool,     // held: crouch (slower, shorter); crouch + jump drops through one-way platforms
     pub sequence: i32,
 }
 
+// One update_player_input call waiting in a player's input queue (applied by game_tick)
+#[derive(SpacetimeType, Clone, Debug)]
+pub struct BufferedInput {
+    pub input: InputState,
+    pub client_yaw: f32,
+    pub client_pitch: f32,
+    pub client_animation: String,
+}
+
+// How a player's character looks (customization.rs), kept between sessions
+#[derive(SpacetimeType, Clone, Debug, PartialEq)]
+pub struct Appearance {
+    pub skin_color: String, // "#RRGGBB"
+    pub model_variant: u8,  // below APPEARANCE_MODEL_VARIANTS
+    pub accessory_ids: Vec<String>,
+}
+
+impl Default for Appearance {
+    fn default() -> Self {
+        Self { skin_color: "#e0ac69".to_string(), model_variant: 0, accessory_ids: Vec::new() }
+    }
+}
+
 // --- Game Constants ---
 
+pub const DEFAULT_TICK_INTERVAL_MS: u32 = 50; // initial game_tick rate (GameConfig::tick_interval_ms)
+pub const MIN_TICK_INTERVAL_MS: u32 = 10;
+pub const MAX_TICK_INTERVAL_MS: u32 = 500;
+pub const MAX_TICK_DELTA_SECS: f32 = 0.25; // longest simulated step when ticks run late
+pub const DEFAULT_MAX_PLAYERS: u32 = 16;    // GameConfig::max_players, later joiners are queued
+pub const AFK_TIMEOUT_SECS: f32 = 180.0;    // default GameConfig::afk_timeout_secs
+pub const IDLE_KICK_SECS: f32 = 900.0;      // default GameConfig::idle_kick_secs
+
 pub const PLAYER_SPEED: f32 = 15.0;        // authoritative
 pub const SPRINT_MULTIPLIER: f32 = 1.8;
+pub const CROUCH_SPEED_MULTIPLIER: f32 = 0.5; // crouching also prevents sprinting
+
+// --- Swimming Constants ---
+pub const SWIM_SPEED_MULTIPLIER: f32 = 0.6; // no sprinting in water
+pub const SWIM_VERTICAL_SPEED: f32 = 3.0;   // jump / crouch swim up / down
+pub const SWIM_BUOYANCY: f32 = 1.5;         // max speed buoyancy moves a floating player
+pub const SWIM_FLOAT_DEPTH: f32 = 1.4;      // feet this far under the surface when floating (head above)
+pub const MAX_BREATH_SECS: f32 = 10.0;
+pub const BREATH_RECOVERY_PER_SEC: f32 = 2.0;
+pub const DROWN_DAMAGE: i32 = 10; // every DROWN_INTERVAL_SECS with no breath left
+pub const DROWN_INTERVAL_SECS: f32 = 1.0;
 pub const GRAVITY: f32 = -6.0;
 pub const JUMP_FORCE: f32 = 9.0;
+pub const RESPAWN_SECS: f32 = 3.0;
+pub const MAX_AIM_PITCH: f32 = 1.4; // radians (~80 degrees) up or down from level
+
+// Inputs queued per player and drained one per tick, so bursts of client updates
+// spread over consecutive ticks instead of collapsing into the latest one.
+// Overflow drops the oldest input; 0 applies inputs immediately (no buffering).
+pub const INPUT_BUFFER_TICKS: usize = 3;
+
+pub const FALL_DAMAGE_MIN_HEIGHT: f32 = 8.0;  // a normal jump peaks at ~6.75m, so it never hurts
+pub const FALL_DAMAGE_PER_METER: f32 = 5.0;
+pub const JUMP_PAD_TRIGGER_HEIGHT: f32 = 0.3;
+pub const PORTAL_HEIGHT: f32 = 2.5;        // entry volume is an upright cylinder this tall
+pub const PORTAL_EXIT_OFFSET: f32 = 1.0;   // arrive this far in front of the exit's entry volume
+pub const PORTAL_COOLDOWN_SECS: f32 = 1.5; // per player, so an exit can't bounce them straight back
+pub const Y_KILL_PLANE: f32 = -50.0;   // anything below this has fallen out of the world
+pub const KILL_PLANE_PENALTY: i32 = 10;
 
 // --- Projectile Constants ---
 pub const PROJECTILE_SPEED: f32 = 15.0;
 pub const PROJECTILE_DAMAGE: i32 = 50;
 pub const PROJECTILE_LIFETIME: f32 = 5.0; // seconds
 pub const PROJECTILE_RADIUS: f32 = 0.2;
-pub const PLAYER_RADIUS: f32 = 0.5;
 pub const PLAYER_HEIGHT: f32 = 2.0;
+pub const CROUCH_HEIGHT: f32 = 1.2;
+pub const MAX_PLAYER_HIT_RADIUS: f32 = 1.0; // broadphase padding, keep >= every PlayerData::hit_radius
+pub const SPATIAL_CELL_SIZE: f32 = 4.0;
+pub const BOUNCE_SPEED_RETAINED: f32 = 0.7; // fraction of speed kept on each ricochet
+
+// Bloom: each shot adds heat (0..1), which scales a random spread up to SPREAD_MAX_RADIANS
+pub const SPREAD_HEAT_PER_SHOT: f32 = 0.15;
+pub const SPREAD_HEAT_RECOVERY_PER_SEC: f32 = 0.6;
+pub const SPREAD_MAX_RADIANS: f32 = 0.12;
 
 // Helper struct for Projectile state
 #[derive(SpacetimeType, Clone, Debug)]
@@ -127,3 +194,340 @@ pub struct Projectile {
     pub damage: i32,
     pub lifetime: f32,
 }
+
+// Homing: targets are picked once at cast time, nearest enemy inside the cone
+pub const HOMING_ACQUIRE_RANGE: f32 = 40.0;
+pub const HOMING_ACQUIRE_HALF_ANGLE_DEGREES: f32 = 20.0;
+
+pub const PROJECTILE_SOFT_LIMIT: usize = 400;   // warn once live projectiles exceed this
+pub const PROJECTILE_CULL_TARGET: usize = 300;  // proactive culling trims back down to this
+pub const PROJECTILE_PROACTIVE_CULL: bool = true;
+pub const DEFAULT_REGEN_DELAY_SECS: f32 = 5.0; // regen pauses this long after taking damage (GameConfig)
+
+pub const GLOBAL_SPAWN_RATE: u32 = 200; // max player-fired projectiles per second across the whole server
+pub const MAX_HAND_OFFSET: f32 = 3.0;     // how far from the caster's feet the client's casting hand may be
+
+// --- Melee Constants ---
+pub const MELEE_DAMAGE: i32 = 30;       // scaled by the class projectile_damage_multiplier
+pub const MELEE_RANGE: f32 = 2.5;
+pub const MELEE_ARC_DEGREES: f32 = 90.0; // full width of the swing in front of the attacker
+pub const MELEE_COOLDOWN_SECS: f32 = 0.8;
+
+// --- Hitscan Constants ---
+pub const HITSCAN_DAMAGE: i32 = 20;        // scaled by the class projectile_damage_multiplier
+pub const HITSCAN_RANGE: f32 = 100.0;
+pub const HITSCAN_COOLDOWN_SECS: f32 = 0.5;
+pub const HITSCAN_MAX_MUZZLE_OFFSET: f32 = 3.0; // how far from the shooter's feet the client's muzzle may be
+
+// --- Impact Behavior Constants ---
+pub const HEAL_SPLASH_RADIUS: f32 = 3.0;
+pub const SHARD_SPAWN_OFFSET: f32 = 1.5; // start shards outside the player they burst from
+pub const FIRE_PATCH_RADIUS: f32 = 2.5;
+pub const FIRE_PATCH_DAMAGE: i32 = 5; // per pulse
+pub const FIRE_PULSE_SECS: f32 = 0.5;
+
+// --- Knockback Constants ---
+pub const KNOCKBACK_PER_DAMAGE: f32 = 0.2;      // m/s of push per point of direct projectile damage
+pub const EXPLOSION_KNOCKBACK: f32 = 12.0;      // m/s at the blast centre, scaled by falloff
+pub const EXPLOSION_KNOCKBACK_LIFT: f32 = 0.4;  // fraction of the blast push applied upwards
+pub const KNOCKBACK_FRICTION: f32 = 6.0;        // exponential decay rate of knockback_velocity per second
+pub const MAX_KNOCKBACK_SPEED: f32 = 20.0;
+
+// --- Status Effect Constants ---
+pub const HEX_DISARM_SECS: f32 = 2.5;
+pub const FROST_SLOW_AMOUNT: f32 = 0.4;
+pub const FROST_SLOW_SECS: f32 = 3.0;
+pub const MAX_SLOW: f32 = 0.8; // players always keep at least 20% of their speed
+pub const MAX_STATUS_EFFECT_SECS: f32 = 30.0; // cap for inflict_status_effect
+pub const REJUVENATE_MANA_COST: i32 = 25;
+pub const REJUVENATE_HEAL_PER_SEC: f32 = 8.0;
+pub const REJUVENATE_SECS: f32 = 5.0;
+pub const SHIELD_MANA_COST: i32 = 30;
+pub const SHIELD_AMOUNT: i32 = 40; // damage absorbed before health
+pub const SHIELD_SECS: f32 = 6.0;
+pub const HEAL_MANA_COST: i32 = 20;
+pub const HEAL_AMOUNT: i32 = 30;
+pub const HEAL_RANGE: f32 = 20.0;
+pub const HEAL_COOLDOWN_SECS: f32 = 4.0;
+pub const DASH_SPEED: f32 = 18.0; // horizontal impulse, decays with KNOCKBACK_FRICTION
+pub const DASH_COOLDOWN_SECS: f32 = 3.0;
+pub const BLINK_DISTANCE: f32 = 8.0;
+pub const BLINK_COOLDOWN_SECS: f32 = 6.0;
+
+// --- Mount Constants ---
+pub const MOUNT_COMBAT_LOCKOUT_SECS: f32 = 5.0; // can't mount this soon after taking damage
+
+// --- Pet Constants ---
+pub const PET_FOLLOW_DISTANCE: f32 = 2.0;    // how closely an idle pet trails its owner
+pub const PET_LEASH_RANGE: f32 = 20.0;       // pets ignore targets farther than this from the owner
+pub const PET_TELEPORT_DISTANCE: f32 = 30.0; // pets left this far behind reappear next to the owner
+
+// --- Interactable Constants ---
+pub const INTERACT_RANGE: f32 = 2.5; // from the player's edge
+
+// --- Gathering Constants ---
+pub const GATHER_RANGE: f32 = 2.5; // from the player's edge
+pub const GATHER_COOLDOWN_SECS: f32 = 1.0;
+
+// --- Vehicle Constants ---
+pub const VEHICLE_ENTER_RANGE: f32 = 2.0;           // reach from the vehicle's edge
+pub const VEHICLE_HEIGHT: f32 = 1.5;
+pub const VEHICLE_DRAG: f32 = 0.5;                  // fraction of speed lost per second
+pub const VEHICLE_BRAKE_DECELERATION: f32 = 20.0;   // m/s per second while braking (or driverless)
+pub const VEHICLE_REVERSE_SPEED_FRACTION: f32 = 0.4; // of max_speed
+pub const VEHICLE_WALL_SPEED_RETAINED: f32 = 0.2;   // fraction of speed kept after hitting a wall
+pub const VEHICLE_MIN_IMPACT_SPEED: f32 = 4.0;      // slower vehicles just nudge players
+pub const VEHICLE_IMPACT_DAMAGE_PER_SPEED: f32 = 2.0;
+pub const VEHICLE_IMPACT_COOLDOWN_SECS: f32 = 0.5;
+pub const VEHICLE_IMPACT_SPEED_RETAINED: f32 = 0.5;
+
+// --- Visibility Constants ---
+pub const SCANNER_REVEAL_RADIUS: f32 = 4.0;
+pub const REVEAL_DURATION_SECS: f32 = 5.0;
+pub const NEAR_MISS_RADIUS: f32 = 2.0;     // a shot passing this close pings its shooter on your minimap
+pub const NEAR_MISS_PING_SECS: f32 = 2.0;
+
+// --- Ultimate Constants ---
+pub const ULT_CHARGE_MAX: f32 = 100.0;
+pub const ULT_CHARGE_PER_DAMAGE: f32 = 0.25;
+pub const ULT_CHARGE_PER_KILL: f32 = 15.0;
+pub const ULT_CHARGE_PER_ASSIST: f32 = 7.5;
+pub const ULT_CHARGE_PER_SEC: f32 = 0.5;
+pub const ULT_NOVA_RADIUS: f32 = 8.0;
+pub const ULT_NOVA_DAMAGE: i32 = 80;
+
+// --- Team Constants ---
+pub const TEAM_COUNT: u8 = 2;
+pub const DEFAULT_TEAM_SWAP_COOLDOWN_SECS: f32 = 30.0;
+pub const DEFAULT_TEAM_IMBALANCE_THRESHOLD: u32 = 1; // max allowed size difference after a swap
+
+// --- Bot Constants ---
+pub const DEFAULT_BOT_DIFFICULTY: f32 = 0.5;
+pub const BOT_MAX_AIM_ERROR_RADIANS: f32 = 0.25; // aim error at difficulty 0.0 (none at 1.0)
+pub const BOT_SLOW_REACTION_SECS: f32 = 2.0;     // delay between shots at difficulty 0.0
+pub const BOT_FAST_REACTION_SECS: f32 = 0.6;     // ...and at difficulty 1.0
+pub const BOT_SIGHT_RANGE: f32 = 30.0;
+
+// --- NPC Constants ---
+pub const NPC_AGGRO_RANGE: f32 = 15.0; // idle NPCs notice players this close
+pub const NPC_LEASH_RANGE: f32 = 25.0; // chasing NPCs give up past this distance
+pub const NPC_REPATH_SECS: f32 = 0.5;   // minimum time between A* searches per NPC
+pub const NPC_WAYPOINT_RADIUS: f32 = 0.4;
+pub const NPC_SPAWNER_INTERVAL_SECS: u64 = 2; // how often spawners top up their populations
+pub const DEFAULT_MAX_NPCS: u32 = 40;         // GameConfig::max_npcs
+pub const THREAT_PER_DAMAGE: f32 = 1.0;
+pub const THREAT_PROXIMITY_MAX: f32 = 10.0;   // bonus for standing on top of an NPC, fading out at NPC_AGGRO_RANGE
+pub const THREAT_SWITCH_RATIO: f32 = 1.1;     // a new target needs 10% more threat than the current one
+pub const THREAT_TAUNT_BONUS: f32 = 50.0;     // taunting sets your threat this far above the top
+pub const TAUNT_RADIUS: f32 = 8.0;
+pub const TAUNT_COOLDOWN_SECS: f32 = 10.0;
+
+// --- Loot Constants ---
+pub const LOOT_DESPAWN_SECS: f32 = 60.0;
+pub const LOOT_PICKUP_RANGE: f32 = 2.5;
+pub const LOOT_POTION_CHANCE: f64 = 0.25; // chance an NPC also drops a potion
+pub const LOOT_OGRE_EQUIPMENT_CHANCE: f64 = 0.5; // chance an ogre also drops a weapon or armor
+
+// --- Inventory Constants ---
+pub const INVENTORY_CAPACITY: usize = 20; // stacks per player
+pub const MAX_DAMAGE_REDUCTION: f32 = 0.75; // armor can never make a player immune
+
+// --- Instance Constants ---
+pub const LOBBY_INSTANCE_ID: u64 = 0; // default world every player joins
+
+// --- Dungeon Constants ---
+pub const DUNGEON_MIN_ROOMS: u32 = 3;
+pub const DUNGEON_MAX_ROOMS: u32 = 10;
+pub const DUNGEON_CELL_SPACING: f32 = 20.0;    // distance between neighbouring room centers
+pub const DUNGEON_ROOM_HALF_SIZE: f32 = 6.0;
+pub const DUNGEON_DOOR_HALF_WIDTH: f32 = 2.0;  // also half the corridor width
+pub const DUNGEON_WALL_HALF_HEIGHT: f32 = 2.0;
+pub const DUNGEON_WALL_HALF_THICKNESS: f32 = 0.25;
+pub const DUNGEON_SPAWNER_RESPAWN_SECS: f32 = 45.0;
+pub const DUNGEON_CHEST_RANGE: f32 = 2.5;
+pub const DUNGEON_CHECK_INTERVAL_SECS: u64 = 5; // how often empty dungeons are torn down
+
+// --- Boss Constants ---
+pub const BOSS_PHASE_THRESHOLDS: [f32; 2] = [0.7, 0.35]; // health fractions starting phases 2 and 3
+pub const BOSS_SLAM_INTERVAL_SECS: f32 = 9.0;  // divided by the current phase
+pub const BOSS_SLAM_WINDUP_SECS: f32 = 1.5;    // telegraph before the slam lands
+pub const BOSS_SLAM_RADIUS: f32 = 6.0;
+pub const BOSS_SLAM_DAMAGE: i32 = 25;
+pub const BOSS_ADD_COUNT: u32 = 3;             // goblins summoned on each phase change
+pub const BOSS_ENRAGE_SECS: f32 = 180.0;       // after the pull
+pub const BOSS_ENRAGE_DAMAGE_MULTIPLIER: i32 = 2;
+pub const BOSS_LOOT_MIN_SHARE: f32 = 0.05;     // fraction of the boss's health a player must deal for loot
+
+// --- Economy Constants ---
+pub const CURRENCY_ITEM_ID: &str = "gold"; // dropped gold, credited to PlayerData::gold on pickup
+pub const TRADE_RANGE: f32 = 5.0;
+pub const VENDOR_RANGE: f32 = 4.0;
+pub const SHOP_SELL_RATIO: f32 = 0.5; // vendors pay this fraction of the buy price
+pub const DAILY_REWARD_GOLD: u64 = 20; // per day of the login streak
+pub const DAILY_REWARD_MAX_STREAK: u32 = 7; // streak days counted towards the gold reward
+
+// --- Anticheat Constants ---
+pub const ANTICHEAT_SPEED_TOLERANCE: f32 = 1.25; // slack on top of the fastest legal speed
+pub const ANTICHEAT_MAX_INPUTS_PER_SEC: f32 = 90.0; // sustained update_player_input rate (clients send ~60/s)
+pub const ANTICHEAT_INPUT_BURST: f32 = 30.0; // inputs that may arrive at once after a network stall
+pub const ANTICHEAT_TELEPORT_SLACK: f32 = 2.0; // metres of collision push-out / floor snapping allowed per tick
+pub const ANTICHEAT_EVENT_RETENTION_SECS: u64 = 3600;
+pub const ANTICHEAT_AUDIT_EVERY: u32 = 50; // repeat violations written to the audit log
+
+// --- Navigation Constants ---
+pub const NAV_GRID_HALF_SIZE: f32 = 60.0;
+pub const NAV_CELL_SIZE: f32 = 1.0;
+pub const NAV_AGENT_RADIUS: f32 = 0.6; // clearance kept from colliders (goblin-sized)
+pub const NAV_MAX_EXPANSIONS: u32 = 5000;
+
+// --- Match Constants ---
+pub const MATCH_KILL_TARGET: u32 = 20;         // default team kills to win (GameConfig)
+pub const MATCH_TIME_LIMIT_SECS: f32 = 600.0;   // default round length (GameConfig)
+pub const MATCH_MIN_PLAYERS: u64 = 2; // the lobby starts a countdown once this many are online
+pub const MATCH_COUNTDOWN_SECS: f32 = 5.0;
+pub const POST_MATCH_SECS: f32 = 10.0;
+
+// --- Capture the Flag Constants ---
+pub const CTF_CAPTURE_TARGET: u32 = 3; // default captures to win (GameConfig)
+pub const FLAG_PICKUP_RADIUS: f32 = 1.5;
+pub const FLAG_CAPTURE_RADIUS: f32 = 2.5;
+pub const FLAG_RETURN_SECS: f32 = 20.0; // a dropped flag goes home on its own after this
+pub const FLAG_CAPTURE_POINTS: u32 = 1;
+
+// --- Duel Constants ---
+pub const DUEL_REQUEST_SECS: f32 = 30.0; // unanswered challenges expire
+pub const DUEL_MAX_SECS: f32 = 180.0;    // duels still running after this end in a draw
+
+// --- King of the Hill Constants ---
+pub const KOTH_SCORE_TARGET: u32 = 100;       // default points to win (GameConfig)
+pub const CONTROL_CAPTURE_RATE: f32 = 0.1;    // progress per second per capturing player (10s solo)
+pub const CONTROL_MAX_CAPTURERS: u32 = 3;     // extra players beyond this don't capture faster
+pub const CONTROL_POINT_HEIGHT: f32 = 3.0;    // vertical reach of the capture circle
+pub const CONTROL_POINT_SCORE_PER_SEC: f32 = 1.0;
+pub const MATCH_HISTORY_LIMIT: usize = 50;
+pub const KILL_FEED_LIMIT: usize = 50;
+
+// --- Username Constants ---
+pub const USERNAME_MIN_LEN: usize = 3;
+pub const USERNAME_MAX_LEN: usize = 16;
+pub const RESERVED_USERNAMES: &[&str] = &["admin", "administrator", "moderator", "server", "system"];
+
+// --- Customization Constants ---
+pub const RENAME_COOLDOWN_SECS: u64 = 24 * 3600;
+pub const CUSTOMIZE_COOLDOWN_SECS: u64 = 30;
+pub const APPEARANCE_MODEL_VARIANTS: u8 = 4;
+pub const MAX_ACCESSORIES: usize = 3;
+pub const MAX_ACCESSORY_ID_LEN: usize = 32;
+
+// --- Social Constants ---
+pub const MAX_FRIENDS: usize = 100;
+pub const DEFAULT_MAX_PARTY_SIZE: u32 = 4; // GameConfig::max_party_size
+pub const PARTY_INVITE_SECS: f32 = 60.0;
+
+pub const MAX_GUILD_MEMBERS: usize = 50;
+pub const GUILD_NAME_MIN_LEN: usize = 3;
+pub const GUILD_NAME_MAX_LEN: usize = 24;
+pub const GUILD_TAG_MIN_LEN: usize = 2;
+pub const GUILD_TAG_MAX_LEN: usize = 4;
+
+// --- Progression Constants ---
+pub const PLAYER_KILL_XP: u64 = 100;
+pub const NPC_KILL_XP: u64 = 25;
+pub const MAX_ACTIVE_QUESTS: usize = 10;
+pub const LEADERBOARD_SIZE: usize = 50; // ranked rows kept per category
+pub const LEADERBOARD_INTERVAL_SECS: u64 = 60;
+pub const LEADERBOARD_MIN_KD_KILLS: u64 = 10;
+pub const DEFAULT_MMR: i32 = 1000;
+pub const MMR_K_FACTOR: f32 = 32.0;
+pub const MMR_PLACEMENT_K_FACTOR: f32 = 64.0;
+pub const MMR_PLACEMENT_MATCHES: u32 = 5; // rounds per season rated with the placement K factor
+pub const RANKED_RESULT_LIMIT: usize = 1000;
+pub const MATCHMAKING_TEAM_SIZE: usize = 2;
+pub const MATCHMAKING_INTERVAL_SECS: u64 = 2;
+pub const MATCHMAKING_MMR_BAND: f32 = 150.0;      // max MMR gap between groups at first
+pub const MATCHMAKING_BAND_GROWTH: f32 = 5.0;     // band widening per second in the queue
+pub const MATCHMAKING_START_DELAY_SECS: f32 = 5.0; // between forming a match and starting it
+pub const PARTY_XP_SHARE: f32 = 0.5;          // fraction of the killer's XP each nearby party member gets
+pub const PARTY_XP_SHARE_RADIUS: f32 = 40.0;
+pub const XP_PER_LEVEL: u64 = 100; // level 1 at 0 XP, level 2 at 100, ...
+
+// --- Chat Constants ---
+pub const MAX_CHAT_MESSAGE_LEN: usize = 200; // characters, after trimming
+pub const CHAT_RATE_LIMIT_MESSAGES: usize = 5;
+pub const CHAT_RATE_LIMIT_WINDOW_SECS: u64 = 10;
+pub const CHAT_RETENTION_SECS: u64 = 600;     // messages older than 10 minutes are pruned
+pub const CHAT_CLEANUP_INTERVAL_SECS: u64 = 60;
+
+// --- Retention Constants ---
+pub const LOGGED_OUT_RETENTION_SECS: u64 = 30 * 24 * 3600; // default GameConfig::logged_out_retention_secs
+pub const LOGGED_OUT_CLEANUP_INTERVAL_SECS: u64 = 3600;
+pub const OPS_LOG_LIMIT: usize = 1000;
+pub const AUDIT_LOG_RETENTION_SECS: u64 = 14 * 24 * 3600;
+pub const AUDIT_LOG_LIMIT: usize = 10_000;
+pub const AUDIT_CLEANUP_INTERVAL_SECS: u64 = 3600;
+
+// --- Pickup Constants ---
+pub const PICKUP_RADIUS: f32 = 0.5;
+pub const AXE_MAX_AMMO: u32 = 2;
+
+// --- Event Constants ---
+pub const EVENT_TTL_SECS: u64 = 2; // how long one-shot event rows live before game_tick prunes them
+pub const PROJECTILE_SPAWN_LOG_SECS: u64 = 30;
+pub const PROJECTILE_SPAWN_LOG_LIMIT: usize = 2000;
+
+// Projectile archetypes a player can fire (parameters live in projectile_logic::ProjectileSpec)
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq)]
+pub enum ProjectileKind {
+    Bolt,    // standard shot, passes through every pickup
+    Breaker, // collides with destructible pickups (e.g. denying a health pack)
+    Bouncer, // ricochets off the ground and walls a few times before it is spent
+    Hex,     // weak hit that disarms the target
+    Fireball, // explodes on impact
+    Cluster,  // splits into shards on impact
+    Napalm,   // leaves a burning patch where it lands
+    Mender,   // heals everyone near the impact point
+    Lance,    // pierces through a couple of players
+    Shard,    // fragment spawned by Cluster, not directly castable
+    Grenade,  // flies straight, then drops after a delay and explodes
+    FrostBolt, // damages and slows the target
+    Boulder,   // heavy, slow shot that only arms after traveling a minimum distance
+    Scanner,   // harmless pulse that reveals stealthed enemies it passes near
+    Axe,       // thrown melee weapon: arcs under gravity, uses axe ammo, retrievable where it lands
+}
+
+// What happens when a projectile touches something (dispatched by projectile_logic::handle_impact)
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq)]
+pub enum ImpactBehavior {
+    Stop,            // deleted on first contact
+    Split(u32),      // spawns this many shards radiating from the impact
+    LingerFire(f32), // leaves a fire patch burning for this many seconds
+    Heal(i32),       // heals living players near the impact
+    Afflict(StatusOnHit), // applies a status effect to the player hit
+    Retrievable,     // thrown weapon: drops as a pickup on a miss, returns to the thrower on a hit
+}
+
+// Status effect an Afflict impact inflicts on the player it hits
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq)]
+pub struct StatusOnHit {
+    pub kind: StatusEffectKind,
+    pub magnitude: f32,
+    pub duration_secs: f32,
+}
+
+// Timed effects tracked in the status_effect table
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq)]
+pub enum StatusEffectKind {
+    Disarm, // target can move but cannot fire
+    Slow,   // movement speed reduced by `magnitude` (0.3 = 30% slower)
+    Stun,   // target can neither move, jump nor attack
+    Burn,   // `magnitude` damage per second, credited to the source
+    HealOverTime, // `magnitude` health per second
+}
+
+// What a world pickup grants when collected
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq)]
+pub enum PickupKind {
+    Health,
+    Mana,
+    Axe, // a thrown axe lying where it landed; `amount` ammo is returned to whoever picks it up
+}
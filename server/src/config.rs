@@ -0,0 +1,291 @@
+/*
+ * Vibe Coding Starter Pack: 3D Multiplayer - config.rs
+ *
+ * Runtime-tunable server settings, stored so they survive module restarts.
+ *
+ * Key components:
+ *    - GameConfig: Public singleton row (id = 0), created with defaults on first access
+ *    - get_game_config: Read helper used by gameplay systems
+ *    - set_tick_rate: Admin reducer that changes tick_interval_ms and reschedules game_tick
+ *    - set_bot_difficulty: Admin reducer for the PvE bot difficulty (0.0 = sloppy, 1.0 = sharp)
+ *    - set_team_swap_rules: Admin reducer for the change_team cooldown and balance threshold
+ *    - set_gravity: Admin reducer for per-class gravity (players, projectiles, thrown weapons)
+ *    - set_regen_config: Admin reducer for the post-damage regen delay and global regen multiplier
+ *    - set_friendly_fire: Admin reducer for whether explosions hurt the shooter's teammates
+ *    - set_spawn_strategy: Admin reducer for how spawn points are picked (spawns.rs)
+ *    - set_win_conditions: Admin reducer for the team kill target and round time limit (matches.rs)
+ *    - set_game_mode: Admin reducer switching between game modes (not while a match is running)
+ *    - set_max_players: Admin reducer for server capacity; extra players wait in join_queue.rs
+ *    - set_afk_thresholds: Admin reducer for when players are marked AFK and logged out (afk.rs)
+ *    - set_logged_out_retention: Admin reducer for how long saved players are kept (retention.rs)
+ *    - set_max_party_size: Admin reducer for how many players a party can hold (party.rs)
+ *    - set_max_npcs: Admin reducer for the global NPC cap spawners respect (npcs.rs)
+ *    - ranked_season: Current ranked season, advanced by ranking::start_ranked_season
+ */
+
+use spacetimedb::{ReducerContext, ScheduleAt, Table};
+use std::time::Duration;
+
+use crate::admin::require_admin;
+use crate::matches::{get_match_state, GameMode, MatchPhase};
+use crate::spawns::SpawnStrategy;
+use crate::{game_tick_schedule, GameTickSchedule};
+use crate::common::{GRAVITY, DEFAULT_MAX_NPCS, DEFAULT_MAX_PARTY_SIZE, LOGGED_OUT_RETENTION_SECS, AFK_TIMEOUT_SECS, IDLE_KICK_SECS, DEFAULT_MAX_PLAYERS, CTF_CAPTURE_TARGET, KOTH_SCORE_TARGET, MATCH_KILL_TARGET, MATCH_TIME_LIMIT_SECS, DEFAULT_TICK_INTERVAL_MS, MAX_TICK_INTERVAL_MS, MIN_TICK_INTERVAL_MS, DEFAULT_BOT_DIFFICULTY, DEFAULT_REGEN_DELAY_SECS, DEFAULT_TEAM_IMBALANCE_THRESHOLD, DEFAULT_TEAM_SWAP_COOLDOWN_SECS};
+
+#[spacetimedb::table(name = game_config, public)]
+#[derive(Clone)]
+pub struct GameConfig {
+    #[primary_key]
+    pub id: u32,
+    pub tick_interval_ms: u32, // game_tick schedule interval, changed with set_tick_rate
+    pub bot_difficulty: f32, // 0.0..=1.0, scales bot aim error and reaction time
+    pub team_swap_cooldown_secs: f32,
+    pub team_imbalance_threshold: u32,
+    pub player_gravity: f32,     // vertical acceleration per entity class; 0.0 disables gravity
+    pub projectile_gravity: f32, // scaled by each projectile's gravity_scale
+    pub thrown_gravity: f32,     // thrown weapons (axes, grenades)
+    pub regen_delay_secs: f32,      // no health/mana regen for this long after taking damage
+    pub regen_rate_multiplier: f32, // scales every class's regen rates (classes.rs)
+    pub friendly_fire: bool,        // explosions damage the shooter's teammates
+    pub spawn_strategy: SpawnStrategy,
+    pub match_kill_target: u32,     // team kills that win the round, 0 = no kill limit
+    pub match_time_limit_secs: f32, // round length, 0 = no time limit
+    pub game_mode: GameMode,
+    pub ctf_capture_target: u32,    // captures that win a CTF round, 0 = no capture limit
+    pub koth_score_target: u32,     // control point score that wins a KotH round, 0 = no limit
+    pub max_players: u32,           // active players before register_player queues newcomers
+    pub afk_timeout_secs: f32,      // idle time before a player is marked AFK, 0 = never
+    pub idle_kick_secs: f32,        // idle time before a player is logged out, 0 = never
+    pub logged_out_retention_secs: u64, // logged_out_player rows unseen this long are purged, 0 = keep
+    pub max_party_size: u32,
+    pub ranked_season: u32, // bumped by ranking::start_ranked_season
+    pub max_npcs: u32,      // spawners stop topping up once this many NPCs are alive
+}
+
+pub fn get_game_config(ctx: &ReducerContext) -> GameConfig {
+    ctx.db.game_config().id().find(0).unwrap_or_else(|| {
+        ctx.db.game_config().insert(GameConfig {
+            id: 0,
+            tick_interval_ms: DEFAULT_TICK_INTERVAL_MS,
+            bot_difficulty: DEFAULT_BOT_DIFFICULTY,
+            team_swap_cooldown_secs: DEFAULT_TEAM_SWAP_COOLDOWN_SECS,
+            team_imbalance_threshold: DEFAULT_TEAM_IMBALANCE_THRESHOLD,
+            player_gravity: GRAVITY,
+            projectile_gravity: GRAVITY,
+            thrown_gravity: GRAVITY,
+            regen_delay_secs: DEFAULT_REGEN_DELAY_SECS,
+            regen_rate_multiplier: 1.0,
+            friendly_fire: false,
+            spawn_strategy: SpawnStrategy::TeamBased,
+            match_kill_target: MATCH_KILL_TARGET,
+            match_time_limit_secs: MATCH_TIME_LIMIT_SECS,
+            game_mode: GameMode::TeamDeathmatch,
+            ctf_capture_target: CTF_CAPTURE_TARGET,
+            koth_score_target: KOTH_SCORE_TARGET,
+            max_players: DEFAULT_MAX_PLAYERS,
+            afk_timeout_secs: AFK_TIMEOUT_SECS,
+            idle_kick_secs: IDLE_KICK_SECS,
+            logged_out_retention_secs: LOGGED_OUT_RETENTION_SECS,
+            max_party_size: DEFAULT_MAX_PARTY_SIZE,
+            ranked_season: 1,
+            max_npcs: DEFAULT_MAX_NPCS,
+        })
+    })
+}
+
+#[spacetimedb::reducer]
+pub fn set_tick_rate(ctx: &ReducerContext, tick_interval_ms: u32) -> Result<(), String> {
+    require_admin(ctx)?;
+    if !(MIN_TICK_INTERVAL_MS..=MAX_TICK_INTERVAL_MS).contains(&tick_interval_ms) {
+        return Err(format!("Tick interval must be between {}ms and {}ms", MIN_TICK_INTERVAL_MS, MAX_TICK_INTERVAL_MS));
+    }
+    let mut config = get_game_config(ctx);
+    config.tick_interval_ms = tick_interval_ms;
+    ctx.db.game_config().id().update(config);
+
+    // Replace the running schedule; game_tick measures its own delta so the change is seamless
+    for schedule in ctx.db.game_tick_schedule().iter() {
+        ctx.db.game_tick_schedule().scheduled_id().delete(schedule.scheduled_id);
+    }
+    ctx.db.game_tick_schedule().insert(GameTickSchedule {
+        scheduled_id: 0,
+        scheduled_at: ScheduleAt::Interval(Duration::from_millis(tick_interval_ms as u64).into()),
+    });
+    spacetimedb::log::info!("Game tick rescheduled to every {}ms", tick_interval_ms);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn set_bot_difficulty(ctx: &ReducerContext, difficulty: f32) -> Result<(), String> {
+    require_admin(ctx)?;
+    if !(0.0..=1.0).contains(&difficulty) {
+        return Err("Bot difficulty must be between 0.0 and 1.0".to_string());
+    }
+    let mut config = get_game_config(ctx);
+    config.bot_difficulty = difficulty;
+    ctx.db.game_config().id().update(config);
+    spacetimedb::log::info!("Bot difficulty set to {:.2}", difficulty);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn set_team_swap_rules(ctx: &ReducerContext, cooldown_secs: f32, imbalance_threshold: u32) -> Result<(), String> {
+    require_admin(ctx)?;
+    if !cooldown_secs.is_finite() || cooldown_secs < 0.0 {
+        return Err("Cooldown must be a non-negative number of seconds".to_string());
+    }
+    let mut config = get_game_config(ctx);
+    config.team_swap_cooldown_secs = cooldown_secs;
+    config.team_imbalance_threshold = imbalance_threshold;
+    ctx.db.game_config().id().update(config);
+    spacetimedb::log::info!("Team swap rules: {:.0}s cooldown, imbalance threshold {}", cooldown_secs, imbalance_threshold);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn set_gravity(ctx: &ReducerContext, player_gravity: f32, projectile_gravity: f32, thrown_gravity: f32) -> Result<(), String> {
+    require_admin(ctx)?;
+    if ![player_gravity, projectile_gravity, thrown_gravity].iter().all(|g| g.is_finite()) {
+        return Err("Gravity values must be finite".to_string());
+    }
+    let mut config = get_game_config(ctx);
+    config.player_gravity = player_gravity;
+    config.projectile_gravity = projectile_gravity;
+    config.thrown_gravity = thrown_gravity;
+    ctx.db.game_config().id().update(config);
+    spacetimedb::log::info!("Gravity set: players {}, projectiles {}, thrown {}", player_gravity, projectile_gravity, thrown_gravity);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn set_regen_config(ctx: &ReducerContext, regen_delay_secs: f32, regen_rate_multiplier: f32) -> Result<(), String> {
+    require_admin(ctx)?;
+    if !regen_delay_secs.is_finite() || regen_delay_secs < 0.0 || !regen_rate_multiplier.is_finite() || regen_rate_multiplier < 0.0 {
+        return Err("Regen delay and multiplier must be non-negative".to_string());
+    }
+    let mut config = get_game_config(ctx);
+    config.regen_delay_secs = regen_delay_secs;
+    config.regen_rate_multiplier = regen_rate_multiplier;
+    ctx.db.game_config().id().update(config);
+    spacetimedb::log::info!("Regen config: {:.1}s delay after damage, x{:.2} rate", regen_delay_secs, regen_rate_multiplier);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn set_friendly_fire(ctx: &ReducerContext, enabled: bool) -> Result<(), String> {
+    require_admin(ctx)?;
+    let mut config = get_game_config(ctx);
+    config.friendly_fire = enabled;
+    ctx.db.game_config().id().update(config);
+    spacetimedb::log::info!("Friendly fire {}", if enabled { "enabled" } else { "disabled" });
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn set_spawn_strategy(ctx: &ReducerContext, strategy: SpawnStrategy) -> Result<(), String> {
+    require_admin(ctx)?;
+    let mut config = get_game_config(ctx);
+    config.spawn_strategy = strategy;
+    ctx.db.game_config().id().update(config);
+    spacetimedb::log::info!("Spawn strategy set to {:?}", strategy);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn set_win_conditions(ctx: &ReducerContext, kill_target: u32, time_limit_secs: f32) -> Result<(), String> {
+    require_admin(ctx)?;
+    if !time_limit_secs.is_finite() || time_limit_secs < 0.0 {
+        return Err("Time limit must be a non-negative number of seconds".to_string());
+    }
+    if kill_target == 0 && time_limit_secs == 0.0 {
+        return Err("At least one win condition is required".to_string());
+    }
+    let mut config = get_game_config(ctx);
+    config.match_kill_target = kill_target;
+    config.match_time_limit_secs = time_limit_secs;
+    ctx.db.game_config().id().update(config);
+    spacetimedb::log::info!("Win conditions: {} team kills, {:.0}s time limit", kill_target, time_limit_secs);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn set_game_mode(ctx: &ReducerContext, game_mode: GameMode, score_target: u32) -> Result<(), String> {
+    require_admin(ctx)?;
+    if get_match_state(ctx).phase == MatchPhase::InProgress {
+        return Err("Can't change the game mode during a match".to_string());
+    }
+    let mut config = get_game_config(ctx);
+    config.game_mode = game_mode;
+    match game_mode {
+        GameMode::TeamDeathmatch => config.match_kill_target = score_target,
+        GameMode::CaptureTheFlag => config.ctf_capture_target = score_target,
+        GameMode::KingOfTheHill => config.koth_score_target = score_target,
+    }
+    ctx.db.game_config().id().update(config);
+    spacetimedb::log::info!("Game mode set to {:?} (score target {})", game_mode, score_target);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn set_max_players(ctx: &ReducerContext, max_players: u32) -> Result<(), String> {
+    require_admin(ctx)?;
+    if max_players == 0 {
+        return Err("Max players must be at least 1".to_string());
+    }
+    let mut config = get_game_config(ctx);
+    config.max_players = max_players;
+    ctx.db.game_config().id().update(config);
+    // Lowering the cap doesn't kick anyone; raising it lets the queue in on the next tick
+    spacetimedb::log::info!("Max players set to {}", max_players);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn set_afk_thresholds(ctx: &ReducerContext, afk_timeout_secs: f32, idle_kick_secs: f32) -> Result<(), String> {
+    require_admin(ctx)?;
+    if ![afk_timeout_secs, idle_kick_secs].iter().all(|s| s.is_finite() && *s >= 0.0) {
+        return Err("Thresholds must be non-negative numbers of seconds".to_string());
+    }
+    if afk_timeout_secs > 0.0 && idle_kick_secs > 0.0 && idle_kick_secs < afk_timeout_secs {
+        return Err("Idle logout can't come before the AFK timeout".to_string());
+    }
+    let mut config = get_game_config(ctx);
+    config.afk_timeout_secs = afk_timeout_secs;
+    config.idle_kick_secs = idle_kick_secs;
+    ctx.db.game_config().id().update(config);
+    spacetimedb::log::info!("AFK after {:.0}s, idle logout after {:.0}s", afk_timeout_secs, idle_kick_secs);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn set_logged_out_retention(ctx: &ReducerContext, retention_secs: u64) -> Result<(), String> {
+    require_admin(ctx)?;
+    let mut config = get_game_config(ctx);
+    config.logged_out_retention_secs = retention_secs;
+    ctx.db.game_config().id().update(config);
+    spacetimedb::log::info!("Logged out players kept for {}s (0 = forever)", retention_secs);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn set_max_party_size(ctx: &ReducerContext, max_party_size: u32) -> Result<(), String> {
+    require_admin(ctx)?;
+    if max_party_size < 2 {
+        return Err("Parties need room for at least 2 players".to_string());
+    }
+    let mut config = get_game_config(ctx);
+    config.max_party_size = max_party_size;
+    ctx.db.game_config().id().update(config);
+    spacetimedb::log::info!("Max party size set to {}", max_party_size);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn set_max_npcs(ctx: &ReducerContext, max_npcs: u32) -> Result<(), String> {
+    require_admin(ctx)?;
+    let mut config = get_game_config(ctx);
+    config.max_npcs = max_npcs;
+    ctx.db.game_config().id().update(config);
+    spacetimedb::log::info!("Max NPCs set to {}", max_npcs);
+    Ok(())
+}
@@ -0,0 +1,205 @@
+/*
+ * Vibe Coding Starter Pack: 3D Multiplayer - control_points.rs
+ *
+ * King of the Hill (GameMode::KingOfTheHill, see matches.rs).
+ *
+ * Key components:
+ *    - ControlPoint: Public table of capturable circles. `progress` (0..1) belongs to
+ *      `capturing_team`; the point is owned by `owner_team` once a team fills it
+ *    - update_control_points: Run every tick while a KotH match is in progress
+ *        - Living players within `radius` (and CONTROL_POINT_HEIGHT above or below) are
+ *          counted per team. Two or more teams inside contest the point and freeze it
+ *        - A single team pushes progress at CONTROL_CAPTURE_RATE per second per player (up to
+ *          CONTROL_MAX_CAPTURERS); another team's progress is drained to zero first, which
+ *          also neutralizes the point if that team owned it
+ *        - An empty point drifts back to its owner (or to neutral if unowned)
+ *        - The owner scores CONTROL_POINT_SCORE_PER_SEC into its team score (matches.rs)
+ *    - reset_control_points: Back to neutral (called when a round counts down)
+ *    - add_control_point / remove_control_point: Admin reducers
+ */
+
+use spacetimedb::{ReducerContext, Table};
+
+use crate::admin::require_admin;
+use crate::common::{Vector3, LOBBY_INSTANCE_ID, CONTROL_CAPTURE_RATE, CONTROL_MAX_CAPTURERS, CONTROL_POINT_HEIGHT, CONTROL_POINT_SCORE_PER_SEC, TEAM_COUNT};
+use crate::matches::add_team_score;
+use crate::{player, PlayerData};
+
+#[spacetimedb::table(name = control_point, public)]
+#[derive(Clone)]
+pub struct ControlPoint {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub position: Vector3,
+    pub radius: f32,
+    pub owner_team: Option<u8>,
+    pub capturing_team: Option<u8>, // whose progress this is
+    pub progress: f32,              // 0..1, the capturing team owns the point at 1
+    pub contested: bool,            // more than one team inside this tick
+    pub score_progress: f32,        // fractional score carried between ticks
+}
+
+pub fn seed_control_points(ctx: &ReducerContext) {
+    if ctx.db.control_point().count() > 0 {
+        return;
+    }
+    ctx.db.control_point().insert(ControlPoint {
+        id: 0, // auto_inc
+        position: Vector3 { x: 0.0, y: 0.0, z: -15.0 },
+        radius: 5.0,
+        owner_team: None,
+        capturing_team: None,
+        progress: 0.0,
+        contested: false,
+        score_progress: 0.0,
+    });
+    spacetimedb::log::info!("[INIT] Seeded default control point.");
+}
+
+pub fn reset_control_points(ctx: &ReducerContext) {
+    for mut point in ctx.db.control_point().iter() {
+        point.owner_team = None;
+        point.capturing_team = None;
+        point.progress = 0.0;
+        point.contested = false;
+        point.score_progress = 0.0;
+        ctx.db.control_point().id().update(point);
+    }
+}
+
+fn players_inside(point: &ControlPoint, players: &[PlayerData]) -> Vec<u32> {
+    let mut counts = vec![0; TEAM_COUNT as usize];
+    for p in players {
+        let dx = p.position.x - point.position.x;
+        let dz = p.position.z - point.position.z;
+        if dx * dx + dz * dz <= point.radius * point.radius && (p.position.y - point.position.y).abs() <= CONTROL_POINT_HEIGHT {
+            if let Some(count) = counts.get_mut(p.team as usize) {
+                *count += 1;
+            }
+        }
+    }
+    counts
+}
+
+// Moves progress towards `team` by `amount`: draining someone else's first, then filling
+fn push_progress(point: &mut ControlPoint, team: u8, amount: f32) {
+    if point.capturing_team != Some(team) && point.progress > 0.0 {
+        point.progress -= amount;
+        if point.progress <= 0.0 {
+            if point.owner_team == point.capturing_team {
+                point.owner_team = None; // neutralized
+            }
+            point.progress = 0.0;
+            point.capturing_team = Some(team);
+        }
+        return;
+    }
+    point.capturing_team = Some(team);
+    point.progress = (point.progress + amount).min(1.0);
+    if point.progress >= 1.0 && point.owner_team != Some(team) {
+        point.owner_team = Some(team);
+        spacetimedb::log::info!("[KOTH] Team {} captured control point {}", team, point.id);
+    }
+}
+
+pub fn update_control_points(ctx: &ReducerContext, delta_time: f32) {
+    let players: Vec<PlayerData> = ctx.db.player().iter().filter(|p| p.alive && p.instance_id == LOBBY_INSTANCE_ID).collect();
+    for mut point in ctx.db.control_point().iter() {
+        let counts = players_inside(&point, &players);
+        let present: Vec<u8> = (0..TEAM_COUNT).filter(|t| counts[*t as usize] > 0).collect();
+        point.contested = present.len() > 1;
+
+        match present.as_slice() {
+            [team] => {
+                let capturers = counts[*team as usize].min(CONTROL_MAX_CAPTURERS) as f32;
+                push_progress(&mut point, *team, CONTROL_CAPTURE_RATE * capturers * delta_time);
+            }
+            [] => match point.owner_team {
+                Some(owner) => push_progress(&mut point, owner, CONTROL_CAPTURE_RATE * delta_time),
+                None => point.progress = (point.progress - CONTROL_CAPTURE_RATE * delta_time).max(0.0),
+            },
+            _ => {} // contested: frozen
+        }
+
+        if let Some(owner) = point.owner_team {
+            point.score_progress += CONTROL_POINT_SCORE_PER_SEC * delta_time;
+            let whole = point.score_progress.floor();
+            if whole >= 1.0 {
+                point.score_progress -= whole;
+                add_team_score(ctx, owner, whole as u32);
+            }
+        }
+        ctx.db.control_point().id().update(point);
+    }
+}
+
+#[spacetimedb::reducer]
+pub fn add_control_point(ctx: &ReducerContext, position: Vector3, radius: f32) -> Result<(), String> {
+    require_admin(ctx)?;
+    if !radius.is_finite() || radius <= 0.0 {
+        return Err("Radius must be positive".to_string());
+    }
+    let point = ctx.db.control_point().insert(ControlPoint {
+        id: 0,
+        position,
+        radius,
+        owner_team: None,
+        capturing_team: None,
+        progress: 0.0,
+        contested: false,
+        score_progress: 0.0,
+    });
+    spacetimedb::log::info!("Added control point {} at {:?}", point.id, position);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn remove_control_point(ctx: &ReducerContext, id: u64) -> Result<(), String> {
+    require_admin(ctx)?;
+    if !ctx.db.control_point().id().delete(id) {
+        return Err("Control point not found".to_string());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_player;
+
+    fn neutral_point() -> ControlPoint {
+        ControlPoint {
+            id: 1,
+            position: Vector3 { x: 0.0, y: 0.0, z: 0.0 },
+            radius: 5.0,
+            owner_team: None,
+            capturing_team: None,
+            progress: 0.0,
+            contested: false,
+            score_progress: 0.0,
+        }
+    }
+
+    #[test]
+    fn only_players_on_the_point_count_towards_capturing_it() {
+        let on = |id: u8, team: u8, x: f32, y: f32| PlayerData { team, position: Vector3 { x, y, z: 0.0 }, ..test_player(id) };
+        let players = [on(1, 0, 1.0, 0.0), on(2, 0, 4.9, 0.0), on(3, 1, 6.0, 0.0), on(4, 1, 0.0, CONTROL_POINT_HEIGHT + 1.0)];
+        assert_eq!(players_inside(&neutral_point(), &players), vec![2, 0], "outside the circle or above it");
+    }
+
+    #[test]
+    fn enemy_progress_is_drained_before_a_point_changes_hands() {
+        let mut point = neutral_point();
+        push_progress(&mut point, 0, 0.6);
+        push_progress(&mut point, 0, 0.6);
+        assert_eq!((point.owner_team, point.progress), (Some(0), 1.0), "filled up, owned");
+
+        push_progress(&mut point, 1, 0.5);
+        assert_eq!((point.owner_team, point.capturing_team), (Some(0), Some(0)), "still owned while draining");
+        push_progress(&mut point, 1, 0.5);
+        assert_eq!((point.owner_team, point.capturing_team, point.progress), (None, Some(1), 0.0), "neutralized");
+        push_progress(&mut point, 1, 1.0);
+        assert_eq!(point.owner_team, Some(1));
+    }
+}
@@ -0,0 +1,147 @@
+/*
+ * Vibe Coding Starter Pack: 3D Multiplayer - crafting.rs
+ *
+ * Turning gathered materials (gathering.rs) into items.
+ *
+ * Key components:
+ *    - CraftingRecipe: Public table keyed by recipe_id; consumes `ingredients` and produces
+ *      output_quantity of output_item_id. Seeded in init (ore into ingots, ingots and wood into
+ *      equipment)
+ *    - craft_item: Player reducer. Checks that the unequipped stacks hold every ingredient, takes
+ *      them (inventory::remove_items_from_inventory) and adds the output. Fails as a whole if
+ *      the output doesn't fit, so no materials are lost
+ *    - set_crafting_recipe / remove_crafting_recipe: Admin reducers
+ */
+
+use spacetimedb::{ReducerContext, SpacetimeType, Table};
+
+use crate::admin::{record_admin_action, require_admin};
+use crate::inventory::{add_item_to_inventory, count_items, item_definition_for, remove_items_from_inventory};
+use crate::player;
+
+#[derive(SpacetimeType, Clone, Debug, PartialEq)]
+pub struct RecipeIngredient {
+    pub item_id: String,
+    pub quantity: u32,
+}
+
+#[spacetimedb::table(name = crafting_recipe, public)]
+#[derive(Clone)]
+pub struct CraftingRecipe {
+    #[primary_key]
+    pub recipe_id: String,
+    pub output_item_id: String,
+    pub output_quantity: u32,
+    pub ingredients: Vec<RecipeIngredient>,
+}
+
+fn recipe(recipe_id: &str, output_item_id: &str, output_quantity: u32, ingredients: &[(&str, u32)]) -> CraftingRecipe {
+    CraftingRecipe {
+        recipe_id: recipe_id.to_string(),
+        output_item_id: output_item_id.to_string(),
+        output_quantity,
+        ingredients: ingredients.iter().map(|(item_id, quantity)| RecipeIngredient { item_id: item_id.to_string(), quantity: *quantity }).collect(),
+    }
+}
+
+pub fn seed_crafting_recipes(ctx: &ReducerContext) {
+    if ctx.db.crafting_recipe().count() > 0 {
+        return;
+    }
+    let defaults = [
+        recipe("smelt_iron", "iron_ingot", 1, &[("iron_ore", 2)]),
+        recipe("iron_sword", "iron_sword", 1, &[("iron_ingot", 3), ("wood", 1)]),
+        recipe("apprentice_staff", "apprentice_staff", 1, &[("wood", 4)]),
+        recipe("plate_armor", "plate_armor", 1, &[("iron_ingot", 8)]),
+    ];
+    let count = defaults.len();
+    for definition in defaults {
+        ctx.db.crafting_recipe().insert(definition);
+    }
+    spacetimedb::log::info!("[INIT] Seeded {} crafting recipes.", count);
+}
+
+fn display_name(ctx: &ReducerContext, item_id: &str) -> String {
+    item_definition_for(ctx, item_id).map_or_else(|| item_id.to_string(), |d| d.display_name)
+}
+
+// The first ingredient the crafter is short of, with how many they have
+fn missing_ingredient(recipe: &CraftingRecipe, have: impl Fn(&str) -> u32) -> Option<(&RecipeIngredient, u32)> {
+    recipe.ingredients.iter().map(|i| (i, have(&i.item_id))).find(|(i, have)| *have < i.quantity)
+}
+
+#[spacetimedb::reducer]
+pub fn craft_item(ctx: &ReducerContext, recipe_id: String) -> Result<(), String> {
+    let Some(player) = ctx.db.player().identity().find(ctx.sender) else {
+        return Err("Player is not active".to_string());
+    };
+    if !player.alive {
+        return Err("Cannot craft while dead".to_string());
+    }
+    let Some(recipe) = ctx.db.crafting_recipe().recipe_id().find(recipe_id.clone()) else {
+        return Err(format!("Unknown recipe '{}'", recipe_id));
+    };
+    if let Some((ingredient, have)) = missing_ingredient(&recipe, |item_id| count_items(ctx, player.identity, item_id)) {
+        return Err(format!("Need {} {} (have {})", ingredient.quantity, display_name(ctx, &ingredient.item_id), have));
+    }
+    for ingredient in &recipe.ingredients {
+        remove_items_from_inventory(ctx, player.identity, &ingredient.item_id, ingredient.quantity)?;
+    }
+    if add_item_to_inventory(ctx, player.identity, &recipe.output_item_id, recipe.output_quantity)? > 0 {
+        return Err("Inventory is full".to_string());
+    }
+    spacetimedb::log::info!("Player {} crafted {} x{}", player.username, recipe.output_item_id, recipe.output_quantity);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn set_crafting_recipe(ctx: &ReducerContext, recipe_id: String, output_item_id: String, output_quantity: u32, ingredients: Vec<RecipeIngredient>) -> Result<(), String> {
+    require_admin(ctx)?;
+    if recipe_id.trim().is_empty() {
+        return Err("Recipe id must not be empty".to_string());
+    }
+    if output_quantity == 0 || ingredients.is_empty() || ingredients.iter().any(|i| i.quantity == 0) {
+        return Err("Recipes need an output and at least one ingredient".to_string());
+    }
+    for item_id in std::iter::once(&output_item_id).chain(ingredients.iter().map(|i| &i.item_id)) {
+        if item_definition_for(ctx, item_id).is_none() {
+            return Err(format!("Unknown item '{}'", item_id));
+        }
+    }
+    let definition = CraftingRecipe { recipe_id: recipe_id.clone(), output_item_id, output_quantity, ingredients };
+    if ctx.db.crafting_recipe().recipe_id().find(recipe_id.clone()).is_some() {
+        ctx.db.crafting_recipe().recipe_id().update(definition);
+    } else {
+        ctx.db.crafting_recipe().insert(definition);
+    }
+    record_admin_action(ctx, "set_crafting_recipe", None, recipe_id);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn remove_crafting_recipe(ctx: &ReducerContext, recipe_id: String) -> Result<(), String> {
+    require_admin(ctx)?;
+    if !ctx.db.crafting_recipe().recipe_id().delete(recipe_id.clone()) {
+        return Err(format!("Unknown recipe '{}'", recipe_id));
+    }
+    record_admin_action(ctx, "remove_crafting_recipe", None, recipe_id);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crafting_needs_every_ingredient_in_full() {
+        let ingot = recipe("iron_ingot", "iron_ingot", 1, &[("iron_ore", 3), ("wood", 1)]);
+        let bag = |ore: u32, wood: u32| move |item_id: &str| if item_id == "iron_ore" { ore } else if item_id == "wood" { wood } else { 0 };
+
+        assert!(missing_ingredient(&ingot, bag(3, 1)).is_none());
+        assert!(missing_ingredient(&ingot, bag(10, 5)).is_none());
+        let (short, have) = missing_ingredient(&ingot, bag(2, 1)).unwrap();
+        assert_eq!((short.item_id.as_str(), have), ("iron_ore", 2));
+        let (short, have) = missing_ingredient(&ingot, bag(3, 0)).unwrap();
+        assert_eq!((short.item_id.as_str(), have), ("wood", 0));
+    }
+}
@@ -0,0 +1,228 @@
+/*
+ * Vibe Coding Starter Pack: 3D Multiplayer - ctf.rs
+ *
+ * Capture the Flag (GameMode::CaptureTheFlag, see matches.rs).
+ *
+ * Key components:
+ *    - Flag: Public table with one flag per team: its home, where it is now, who carries it
+ *      and when it was dropped. A flag is at home, carried, or dropped on the ground
+ *    - update_flags: Proximity rules, run every tick while a CTF match is in progress
+ *        - An enemy within FLAG_PICKUP_RADIUS of a flag at home or on the ground takes it
+ *          (FlagTakenEvent); a teammate touching a dropped flag returns it home
+ *        - Carried flags follow their carrier; dying or leaving drops the flag where it was
+ *        - Dropped flags return home on their own after FLAG_RETURN_SECS
+ *        - Bringing the enemy flag within FLAG_CAPTURE_RADIUS of your own flag's home while your
+ *          flag is at home scores FLAG_CAPTURE_POINTS for your team (FlagCapturedEvent)
+ *    - reset_flags: Every flag back home (called when a round counts down)
+ *    - set_flag_home: Admin reducer to move a team's flag base
+ */
+
+use spacetimedb::{Identity, ReducerContext, Table, Timestamp};
+use std::time::Duration;
+
+use crate::admin::require_admin;
+use crate::common::{Vector3, LOBBY_INSTANCE_ID, FLAG_CAPTURE_POINTS, FLAG_CAPTURE_RADIUS, FLAG_PICKUP_RADIUS, FLAG_RETURN_SECS, TEAM_COUNT};
+use crate::events::{emit_flag_captured, emit_flag_taken};
+use crate::matches::add_team_score;
+use crate::{player, PlayerData};
+
+#[spacetimedb::table(name = flag, public)]
+#[derive(Clone)]
+pub struct Flag {
+    #[primary_key]
+    pub team: u8, // team that defends this flag
+    pub home_position: Vector3,
+    pub position: Vector3,
+    pub carrier: Option<Identity>,
+    pub dropped_at: Option<Timestamp>, // Some while lying away from home
+}
+
+impl Flag {
+    fn at_home(&self) -> bool {
+        self.carrier.is_none() && self.dropped_at.is_none()
+    }
+
+    fn send_home(&mut self) {
+        self.position = self.home_position;
+        self.carrier = None;
+        self.dropped_at = None;
+    }
+}
+
+fn default_home(team: u8) -> Vector3 {
+    let x = if team == 0 { -20.0 } else { 20.0 };
+    Vector3 { x, y: 0.0, z: -10.0 }
+}
+
+pub fn seed_flags(ctx: &ReducerContext) {
+    for team in 0..TEAM_COUNT {
+        if ctx.db.flag().team().find(team).is_none() {
+            let home = default_home(team);
+            ctx.db.flag().insert(Flag { team, home_position: home, position: home, carrier: None, dropped_at: None });
+        }
+    }
+}
+
+pub fn reset_flags(ctx: &ReducerContext) {
+    for mut flag in ctx.db.flag().iter() {
+        flag.send_home();
+        ctx.db.flag().team().update(flag);
+    }
+}
+
+fn horizontal_distance(a: Vector3, b: Vector3) -> f32 {
+    let dx = a.x - b.x;
+    let dz = a.z - b.z;
+    (dx * dx + dz * dz).sqrt()
+}
+
+fn any_within(players: &[PlayerData], point: Vector3, radius: f32, team: u8) -> bool {
+    players.iter().any(|p| p.team == team && horizontal_distance(p.position, point) <= radius)
+}
+
+// What happened to the flags this tick, for scoring and events
+#[derive(Default)]
+struct FlagChanges {
+    taken: Vec<(u8, Identity, Vector3)>, // flag team, taker, where
+    captured: Vec<(u8, Identity)>,       // flag team, carrier
+}
+
+// The proximity rules, applied to `flags` in place
+fn step_flags(flags: &mut [Flag], players: &[PlayerData], now: Timestamp) -> FlagChanges {
+    let return_after = Duration::from_secs_f32(FLAG_RETURN_SECS);
+    let mut carriers: Vec<Identity> = flags.iter().filter_map(|f| f.carrier).collect();
+    let mut changes = FlagChanges::default();
+
+    for flag in flags.iter_mut() {
+        if let Some(carrier) = flag.carrier {
+            match players.iter().find(|p| p.identity == carrier) {
+                Some(p) => flag.position = p.position,
+                None => {
+                    // Carrier died or left: drop it where they were last seen
+                    flag.carrier = None;
+                    flag.dropped_at = Some(now);
+                }
+            }
+            continue;
+        }
+        let flag_team = flag.team;
+        if let Some(dropped_at) = flag.dropped_at {
+            let expired = now.duration_since(dropped_at).is_some_and(|elapsed| elapsed >= return_after);
+            if expired || any_within(players, flag.position, FLAG_PICKUP_RADIUS, flag_team) {
+                flag.send_home();
+                continue;
+            }
+        }
+        let taker = players
+            .iter()
+            .filter(|p| p.team != flag_team && !carriers.contains(&p.identity) && horizontal_distance(p.position, flag.position) <= FLAG_PICKUP_RADIUS)
+            .min_by(|a, b| horizontal_distance(a.position, flag.position).total_cmp(&horizontal_distance(b.position, flag.position)));
+        if let Some(taker) = taker {
+            carriers.push(taker.identity);
+            flag.carrier = Some(taker.identity);
+            flag.dropped_at = None;
+            flag.position = taker.position;
+            changes.taken.push((flag.team, taker.identity, taker.position));
+        }
+    }
+
+    // Captures: a carrier at their own base while their own flag is safe at home
+    let homes: Vec<(u8, Vector3, bool)> = flags.iter().map(|f| (f.team, f.home_position, f.at_home())).collect();
+    for flag in flags.iter_mut() {
+        let Some(carrier) = flag.carrier.and_then(|id| players.iter().find(|p| p.identity == id)) else {
+            continue;
+        };
+        let at_own_base = homes
+            .iter()
+            .any(|(team, home, home_safe)| *team == carrier.team && *home_safe && horizontal_distance(carrier.position, *home) <= FLAG_CAPTURE_RADIUS);
+        if at_own_base {
+            changes.captured.push((flag.team, carrier.identity));
+            flag.send_home();
+        }
+    }
+    changes
+}
+
+pub fn update_flags(ctx: &ReducerContext) {
+    let players: Vec<PlayerData> = ctx.db.player().iter().filter(|p| p.alive && p.instance_id == LOBBY_INSTANCE_ID).collect();
+    let mut flags: Vec<Flag> = ctx.db.flag().iter().collect();
+    let changes = step_flags(&mut flags, &players, ctx.timestamp);
+
+    for (flag_team, taker, position) in changes.taken {
+        emit_flag_taken(ctx, flag_team, taker, position);
+    }
+    for (flag_team, carrier) in changes.captured {
+        let Some(carrier) = players.iter().find(|p| p.identity == carrier) else {
+            continue;
+        };
+        add_team_score(ctx, carrier.team, FLAG_CAPTURE_POINTS);
+        emit_flag_captured(ctx, carrier.team, flag_team, carrier.identity);
+        spacetimedb::log::info!("[CTF] {} captured team {}'s flag", carrier.username, flag_team);
+    }
+
+    for flag in flags {
+        ctx.db.flag().team().update(flag);
+    }
+}
+
+#[spacetimedb::reducer]
+pub fn set_flag_home(ctx: &ReducerContext, team: u8, home_position: Vector3) -> Result<(), String> {
+    require_admin(ctx)?;
+    let Some(mut flag) = ctx.db.flag().team().find(team) else {
+        return Err(format!("Team must be below {}", TEAM_COUNT));
+    };
+    flag.home_position = home_position;
+    flag.send_home();
+    ctx.db.flag().team().update(flag);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_player;
+
+    fn home_flag(team: u8) -> Flag {
+        let home = default_home(team);
+        Flag { team, home_position: home, position: home, carrier: None, dropped_at: None }
+    }
+
+    fn player_at(id: u8, team: u8, position: Vector3) -> PlayerData {
+        PlayerData { team, position, ..test_player(id) }
+    }
+
+    #[test]
+    fn flags_are_taken_carried_and_captured_at_the_carriers_base() {
+        let now = Timestamp::from_micros_since_unix_epoch(1_000_000);
+        let mut flags = [home_flag(0), home_flag(1)];
+        let mut raider = player_at(1, 0, default_home(1));
+        let changes = step_flags(&mut flags, &[raider.clone()], now);
+        assert_eq!(changes.taken.len(), 1);
+        assert_eq!(flags[1].carrier, Some(raider.identity), "enemy flag taken");
+        assert!(flags[0].at_home(), "can't take your own");
+
+        raider.position = default_home(0);
+        let changes = step_flags(&mut flags, &[raider.clone()], now);
+        assert_eq!(changes.captured, vec![(1, raider.identity)]);
+        assert!(flags[1].at_home(), "captured flags go home");
+    }
+
+    #[test]
+    fn dropped_flags_return_home_when_touched_by_a_defender_or_left_too_long() {
+        let now = Timestamp::from_micros_since_unix_epoch(1_000_000);
+        let later = Timestamp::from_micros_since_unix_epoch(1_000_000 + (FLAG_RETURN_SECS as i64 + 1) * 1_000_000);
+        let raider = player_at(1, 0, default_home(1));
+        let mut flags = [home_flag(0), home_flag(1)];
+        step_flags(&mut flags, &[raider], now);
+        step_flags(&mut flags, &[], now);
+        assert!(flags[1].carrier.is_none() && flags[1].dropped_at == Some(now), "carrier died, flag dropped");
+
+        let mut touched = flags.clone();
+        let defender = player_at(2, 1, flags[1].position);
+        step_flags(&mut touched, &[defender], now);
+        assert!(touched[1].at_home());
+
+        step_flags(&mut flags, &[], later);
+        assert!(flags[1].at_home(), "returned on its own");
+    }
+}
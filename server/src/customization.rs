@@ -0,0 +1,139 @@
+/*
+ * Vibe Coding Starter Pack: 3D Multiplayer - customization.rs
+ *
+ * Renaming and character appearance.
+ *
+ * Key components:
+ *    - Appearance (common.rs): Skin color, model variant and accessory ids, stored on
+ *      PlayerData and carried through LoggedOutPlayerData so it survives logging out
+ *    - CustomizationCooldown: Private table of when each identity last renamed or changed
+ *      its look. Kept apart from the player rows so logging out doesn't reset the cooldowns
+ *    - change_username: Same rules as register_player (names::validate_username), at most
+ *      once per RENAME_COOLDOWN_SECS; the scoreboard name follows
+ *    - customize_character: Validates and applies an Appearance, at most once per
+ *      CUSTOMIZE_COOLDOWN_SECS
+ */
+
+use spacetimedb::{Identity, ReducerContext, Table, Timestamp};
+use std::time::Duration;
+
+use crate::common::{Appearance, APPEARANCE_MODEL_VARIANTS, CUSTOMIZE_COOLDOWN_SECS, MAX_ACCESSORIES, MAX_ACCESSORY_ID_LEN, RENAME_COOLDOWN_SECS};
+use crate::names::validate_username;
+use crate::{friends, player, scoreboard, PlayerData};
+
+#[spacetimedb::table(name = customization_cooldown)]
+#[derive(Clone)]
+pub struct CustomizationCooldown {
+    #[primary_key]
+    pub identity: Identity,
+    pub last_rename_at: Option<Timestamp>,
+    pub last_customize_at: Option<Timestamp>,
+}
+
+fn cooldowns(ctx: &ReducerContext, identity: Identity) -> CustomizationCooldown {
+    ctx.db.customization_cooldown().identity().find(identity).unwrap_or(CustomizationCooldown {
+        identity,
+        last_rename_at: None,
+        last_customize_at: None,
+    })
+}
+
+fn save_cooldowns(ctx: &ReducerContext, row: CustomizationCooldown) {
+    if ctx.db.customization_cooldown().identity().find(row.identity).is_some() {
+        ctx.db.customization_cooldown().identity().update(row);
+    } else {
+        ctx.db.customization_cooldown().insert(row);
+    }
+}
+
+fn require_cooled_down(ctx: &ReducerContext, last: Option<Timestamp>, cooldown_secs: u64, what: &str) -> Result<(), String> {
+    let cooldown = Duration::from_secs(cooldown_secs);
+    match last.and_then(|l| ctx.timestamp.duration_since(l)) {
+        Some(elapsed) if elapsed < cooldown => Err(format!("You can {} again in {}s", what, (cooldown - elapsed).as_secs() + 1)),
+        _ => Ok(()),
+    }
+}
+
+fn active_player(ctx: &ReducerContext) -> Result<PlayerData, String> {
+    ctx.db.player().identity().find(ctx.sender).ok_or_else(|| "Player is not active".to_string())
+}
+
+fn validate_appearance(appearance: &Appearance) -> Result<(), String> {
+    let color = appearance.skin_color.strip_prefix('#').unwrap_or("");
+    if color.len() != 6 || !color.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err("Skin color must look like #RRGGBB".to_string());
+    }
+    if appearance.model_variant >= APPEARANCE_MODEL_VARIANTS {
+        return Err(format!("Model variant must be below {}", APPEARANCE_MODEL_VARIANTS));
+    }
+    if appearance.accessory_ids.len() > MAX_ACCESSORIES {
+        return Err(format!("At most {} accessories", MAX_ACCESSORIES));
+    }
+    for (i, id) in appearance.accessory_ids.iter().enumerate() {
+        if id.is_empty() || id.len() > MAX_ACCESSORY_ID_LEN || !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return Err(format!("Invalid accessory id \"{}\"", id));
+        }
+        if appearance.accessory_ids[..i].contains(id) {
+            return Err(format!("Accessory \"{}\" is listed twice", id));
+        }
+    }
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn change_username(ctx: &ReducerContext, username: String) -> Result<(), String> {
+    let mut player = active_player(ctx)?;
+    let mut row = cooldowns(ctx, ctx.sender);
+    require_cooled_down(ctx, row.last_rename_at, RENAME_COOLDOWN_SECS, "rename")?;
+    let username = validate_username(ctx, ctx.sender, &username)?;
+    if username == player.username {
+        return Err("That is already your name".to_string());
+    }
+    spacetimedb::log::info!("{} renamed {} -> {}", ctx.sender, player.username, username);
+    scoreboard::ensure_player_stats(ctx, ctx.sender, &username);
+    player.username = username;
+    ctx.db.player().identity().update(player);
+    friends::refresh_friend_status(ctx, ctx.sender);
+    row.last_rename_at = Some(ctx.timestamp);
+    save_cooldowns(ctx, row);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn customize_character(ctx: &ReducerContext, appearance: Appearance) -> Result<(), String> {
+    let mut player = active_player(ctx)?;
+    let mut row = cooldowns(ctx, ctx.sender);
+    require_cooled_down(ctx, row.last_customize_at, CUSTOMIZE_COOLDOWN_SECS, "change your look")?;
+    validate_appearance(&appearance)?;
+    player.appearance = appearance;
+    ctx.db.player().identity().update(player);
+    row.last_customize_at = Some(ctx.timestamp);
+    save_cooldowns(ctx, row);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn look(skin_color: &str, model_variant: u8, accessories: &[&str]) -> Appearance {
+        Appearance {
+            skin_color: skin_color.to_string(),
+            model_variant,
+            accessory_ids: accessories.iter().map(|a| a.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn appearances_are_validated() {
+        assert!(validate_appearance(&look("#a1B2c3", 0, &["hat", "cape_2"])).is_ok());
+        assert!(validate_appearance(&look("a1b2c3", 0, &[])).is_err(), "missing #");
+        assert!(validate_appearance(&look("#a1b2cg", 0, &[])).is_err());
+        assert!(validate_appearance(&look("#a1b2c3", APPEARANCE_MODEL_VARIANTS, &[])).is_err());
+        assert!(validate_appearance(&look("#a1b2c3", 0, &["hat", "hat"])).is_err());
+        assert!(validate_appearance(&look("#a1b2c3", 0, &["bad id"])).is_err());
+        let mut crowded = look("#a1b2c3", 0, &[]);
+        crowded.accessory_ids = (0..=MAX_ACCESSORIES).map(|i| format!("item{}", i)).collect();
+        assert!(validate_appearance(&crowded).is_err());
+    }
+}
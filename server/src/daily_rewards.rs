@@ -0,0 +1,118 @@
+/*
+ * Vibe Coding Starter Pack: 3D Multiplayer - daily_rewards.rs
+ *
+ * Consecutive-day login streaks and the daily reward.
+ *
+ * Key components:
+ *    - LoginStreak: Public table keyed by identity, kept across sessions. Days are UTC calendar
+ *      days counted on the server clock (ctx.timestamp), never the client's
+ *    - note_login: Called from identity_connected (and again on claiming, for players who stay
+ *      connected past midnight). A login the day after the previous one extends the streak,
+ *      a later one restarts it at 1; more logins on the same day change nothing
+ *    - claim_daily_reward: Reducer, once per UTC day (last_claim_day). Grants
+ *      DAILY_REWARD_GOLD per streak day (capped at DAILY_REWARD_MAX_STREAK days) plus the
+ *      bonus items for that day of the week-long cycle (reward_items_for). Fails without
+ *      changes if the items don't fit in the inventory
+ */
+
+use spacetimedb::{Identity, ReducerContext, Table, Timestamp};
+
+use crate::common::{DAILY_REWARD_GOLD, DAILY_REWARD_MAX_STREAK};
+use crate::inventory::add_item_to_inventory;
+use crate::player;
+
+const MICROS_PER_DAY: i64 = 86_400 * 1_000_000;
+
+#[spacetimedb::table(name = login_streak, public)]
+#[derive(Clone)]
+pub struct LoginStreak {
+    #[primary_key]
+    pub identity: Identity,
+    pub streak: u32,
+    pub best_streak: u32,
+    pub last_login_day: i64, // UTC days since the Unix epoch
+    pub last_claim_day: Option<i64>,
+}
+
+fn day_of(timestamp: Timestamp) -> i64 {
+    timestamp.to_micros_since_unix_epoch().div_euclid(MICROS_PER_DAY)
+}
+
+// Streak after logging in on `today`, given the last login day (a different day)
+fn next_streak(streak: u32, last_login_day: i64, today: i64) -> u32 {
+    if last_login_day == today - 1 { streak + 1 } else { 1 }
+}
+
+pub fn note_login(ctx: &ReducerContext, identity: Identity) -> LoginStreak {
+    let today = day_of(ctx.timestamp);
+    let Some(mut record) = ctx.db.login_streak().identity().find(identity) else {
+        return ctx.db.login_streak().insert(LoginStreak { identity, streak: 1, best_streak: 1, last_login_day: today, last_claim_day: None });
+    };
+    if record.last_login_day == today {
+        return record;
+    }
+    record.streak = next_streak(record.streak, record.last_login_day, today);
+    record.best_streak = record.best_streak.max(record.streak);
+    record.last_login_day = today;
+    ctx.db.login_streak().identity().update(record.clone());
+    record
+}
+
+// Bonus items by day of the weekly cycle (day 1 = first day of a streak)
+fn reward_items_for(streak: u32) -> &'static [(&'static str, u32)] {
+    match (streak - 1) % 7 + 1 {
+        3 => &[("health_potion", 1)],
+        5 => &[("mana_potion", 2)],
+        7 => &[("health_potion", 3), ("mana_potion", 3)],
+        _ => &[],
+    }
+}
+
+#[spacetimedb::reducer]
+pub fn claim_daily_reward(ctx: &ReducerContext) -> Result<(), String> {
+    let Some(mut player) = ctx.db.player().identity().find(ctx.sender) else {
+        return Err("Player is not active".to_string());
+    };
+    let mut record = note_login(ctx, ctx.sender);
+    let today = day_of(ctx.timestamp);
+    if record.last_claim_day == Some(today) {
+        return Err("You already claimed today's reward".to_string());
+    }
+
+    let gold = DAILY_REWARD_GOLD * record.streak.min(DAILY_REWARD_MAX_STREAK) as u64;
+    for (item_id, quantity) in reward_items_for(record.streak) {
+        if add_item_to_inventory(ctx, ctx.sender, item_id, *quantity)? > 0 {
+            return Err("Make room in your inventory to claim today's reward".to_string());
+        }
+    }
+    player.gold = player.gold.saturating_add(gold);
+    ctx.db.player().identity().update(player);
+    record.last_claim_day = Some(today);
+    let streak = record.streak;
+    ctx.db.login_streak().identity().update(record);
+    spacetimedb::log::info!("Player {} claimed daily reward (day {} streak, {} gold)", ctx.sender, streak, gold);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn streaks_count_consecutive_utc_days() {
+        let at = |micros: i64| Timestamp::from_micros_since_unix_epoch(micros);
+        assert_eq!(day_of(at(MICROS_PER_DAY - 1)), 0);
+        assert_eq!(day_of(at(MICROS_PER_DAY)), 1, "midnight UTC starts a new day");
+
+        assert_eq!(next_streak(4, 99, 100), 5);
+        assert_eq!(next_streak(4, 97, 100), 1, "a missed day restarts the streak");
+    }
+
+    #[test]
+    fn bonus_items_repeat_every_week() {
+        assert!(reward_items_for(1).is_empty());
+        assert_eq!(reward_items_for(3), reward_items_for(10));
+        assert_eq!(reward_items_for(7).len(), 2);
+        assert_eq!(reward_items_for(14), reward_items_for(7));
+    }
+}
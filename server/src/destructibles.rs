@@ -0,0 +1,257 @@
+/*
+ * Vibe Coding Starter Pack: 3D Multiplayer - destructibles.rs
+ *
+ * Breakable props (crates, barrels) that block the way until they are smashed.
+ *
+ * Key components:
+ *    - Destructible: Public table. While standing (health > 0) a prop is a static_collider row
+ *      (collider_id) sized half_extents, so it blocks players, NPC paths and projectiles
+ *    - damage_destructible: Projectiles that hit the prop's collider (projectile_logic.rs),
+ *      explosions around it and melee swings (combat::melee_attack) take off health. At 0 the
+ *      collider goes away and the prop's loot_table_id is rolled onto the ground (loot.rs)
+ *    - Respawning: Props with respawn_secs come back at full health after that long (checked by
+ *      update_destructibles from game_tick, waiting while a player stands in the way); the rest
+ *      are deleted when destroyed
+ *    - add_destructible / remove_destructible: Admin reducers
+ */
+
+use spacetimedb::{ReducerContext, Table, TimeDuration, Timestamp};
+
+use crate::admin::{record_admin_action, require_admin};
+use crate::common::{Vector3, LOBBY_INSTANCE_ID};
+use crate::environment::{static_collider, StaticCollider};
+use crate::instances::instance;
+use crate::loot::{drop_loot_table, loot_table};
+use crate::pathfinding::rebuild_nav_grid;
+use crate::physics::in_melee_arc;
+use crate::player;
+
+#[spacetimedb::table(name = destructible, public)]
+#[derive(Clone)]
+pub struct Destructible {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub position: Vector3,
+    pub half_extents: Vector3,
+    pub health: i32,
+    pub max_health: i32,
+    pub loot_table_id: Option<String>,
+    pub respawn_secs: Option<f32>, // None: gone for good once destroyed
+    pub respawn_at: Option<Timestamp>, // set while destroyed
+    pub collider_id: Option<u64>,      // static_collider row while standing
+    pub instance_id: u64,
+}
+
+impl Destructible {
+    fn standing(&self) -> bool {
+        self.health > 0
+    }
+
+    // Horizontal distance from `point` to the prop's box (0 inside it)
+    fn distance_to(&self, point: Vector3) -> f32 {
+        let dx = ((point.x - self.position.x).abs() - self.half_extents.x).max(0.0);
+        let dz = ((point.z - self.position.z).abs() - self.half_extents.z).max(0.0);
+        (dx * dx + dz * dz).sqrt()
+    }
+}
+
+fn insert_collider(ctx: &ReducerContext, prop: &Destructible) -> u64 {
+    ctx.db.static_collider().insert(StaticCollider { id: 0, center: prop.position, half_extents: prop.half_extents, instance_id: prop.instance_id }).id
+}
+
+fn insert_destructible(ctx: &ReducerContext, position: Vector3, half_extents: Vector3, max_health: i32, loot_table_id: Option<String>, respawn_secs: Option<f32>, instance_id: u64) -> Destructible {
+    let mut prop = Destructible {
+        id: 0, // auto_inc
+        position,
+        half_extents,
+        health: max_health,
+        max_health,
+        loot_table_id,
+        respawn_secs,
+        respawn_at: None,
+        collider_id: None,
+        instance_id,
+    };
+    prop.collider_id = Some(insert_collider(ctx, &prop));
+    ctx.db.destructible().insert(prop)
+}
+
+pub fn seed_destructibles(ctx: &ReducerContext) {
+    if ctx.db.destructible().count() > 0 {
+        return;
+    }
+    let crate_size = Vector3 { x: 0.5, y: 0.5, z: 0.5 };
+    let barrel_size = Vector3 { x: 0.4, y: 0.6, z: 0.4 };
+    let defaults = [
+        (Vector3 { x: 6.0, y: 0.5, z: 12.0 }, crate_size, 30, "crate"),
+        (Vector3 { x: 7.2, y: 0.5, z: 12.0 }, crate_size, 30, "crate"),
+        (Vector3 { x: -6.0, y: 0.6, z: 12.0 }, barrel_size, 20, "barrel"),
+    ];
+    for (position, half_extents, max_health, loot_table_id) in defaults {
+        insert_destructible(ctx, position, half_extents, max_health, Some(loot_table_id.to_string()), Some(60.0), LOBBY_INSTANCE_ID);
+    }
+    rebuild_nav_grid(ctx, LOBBY_INSTANCE_ID);
+    spacetimedb::log::info!("[INIT] Seeded destructibles.");
+}
+
+pub fn damage_destructible(ctx: &ReducerContext, destructible_id: u64, amount: i32) {
+    let Some(mut prop) = ctx.db.destructible().id().find(destructible_id) else {
+        return;
+    };
+    if !prop.standing() || amount <= 0 {
+        return;
+    }
+    prop.health = (prop.health - amount).max(0);
+    if prop.standing() {
+        ctx.db.destructible().id().update(prop);
+        return;
+    }
+
+    if let Some(collider_id) = prop.collider_id.take() {
+        ctx.db.static_collider().id().delete(collider_id);
+    }
+    if let Some(loot_table_id) = &prop.loot_table_id {
+        drop_loot_table(ctx, loot_table_id, prop.instance_id, prop.position);
+    }
+    spacetimedb::log::info!("Destructible {} destroyed", prop.id);
+    let instance_id = prop.instance_id;
+    match prop.respawn_secs {
+        Some(secs) => {
+            prop.respawn_at = Some(ctx.timestamp + TimeDuration::from_micros((secs * 1_000_000.0) as i64));
+            ctx.db.destructible().id().update(prop);
+        }
+        None => {
+            ctx.db.destructible().id().delete(prop.id);
+        }
+    }
+    rebuild_nav_grid(ctx, instance_id);
+}
+
+// Projectile contact with a static collider (ignored unless it belongs to a prop in that instance)
+pub fn damage_destructible_collider(ctx: &ReducerContext, collider_id: u64, instance_id: u64, amount: i32) {
+    if let Some(prop) = ctx.db.destructible().iter().find(|d| d.collider_id == Some(collider_id) && d.instance_id == instance_id) {
+        damage_destructible(ctx, prop.id, amount);
+    }
+}
+
+pub fn damage_destructibles_in_radius(ctx: &ReducerContext, instance_id: u64, center: Vector3, radius: f32, amount: i32) {
+    let hit: Vec<u64> = ctx
+        .db
+        .destructible()
+        .iter()
+        .filter(|d| d.standing() && d.instance_id == instance_id && d.distance_to(center) <= radius)
+        .map(|d| d.id)
+        .collect();
+    for id in hit {
+        damage_destructible(ctx, id, amount);
+    }
+}
+
+pub fn damage_destructibles_in_arc(ctx: &ReducerContext, instance_id: u64, origin: Vector3, yaw: f32, range: f32, half_angle: f32, amount: i32) {
+    let hit: Vec<u64> = ctx
+        .db
+        .destructible()
+        .iter()
+        .filter(|d| d.standing() && d.instance_id == instance_id)
+        .filter(|d| in_melee_arc(origin, yaw, d.position, range + d.half_extents.x.max(d.half_extents.z), half_angle))
+        .map(|d| d.id)
+        .collect();
+    for id in hit {
+        damage_destructible(ctx, id, amount);
+    }
+}
+
+pub fn update_destructibles(ctx: &ReducerContext) {
+    let due: Vec<Destructible> = ctx.db.destructible().iter().filter(|d| d.respawn_at.is_some_and(|t| t <= ctx.timestamp)).collect();
+    let mut respawned: Vec<u64> = Vec::new();
+    for mut prop in due {
+        let blocked = ctx.db.player().iter().any(|p| p.alive && p.instance_id == prop.instance_id && prop.distance_to(p.position) < p.hit_radius);
+        if blocked {
+            continue;
+        }
+        prop.health = prop.max_health;
+        prop.respawn_at = None;
+        prop.collider_id = Some(insert_collider(ctx, &prop));
+        if !respawned.contains(&prop.instance_id) {
+            respawned.push(prop.instance_id);
+        }
+        ctx.db.destructible().id().update(prop);
+    }
+    for instance_id in respawned {
+        rebuild_nav_grid(ctx, instance_id);
+    }
+}
+
+// Deletes one prop along with its collider
+pub fn delete_destructible(ctx: &ReducerContext, prop: Destructible) {
+    if let Some(collider_id) = prop.collider_id {
+        ctx.db.static_collider().id().delete(collider_id);
+    }
+    ctx.db.destructible().id().delete(prop.id);
+}
+
+#[spacetimedb::reducer]
+pub fn add_destructible(ctx: &ReducerContext, position: Vector3, half_extents: Vector3, max_health: i32, loot_table_id: Option<String>, respawn_secs: Option<f32>, instance_id: u64) -> Result<(), String> {
+    require_admin(ctx)?;
+    if ctx.db.instance().id().find(instance_id).is_none() {
+        return Err("Instance not found".to_string());
+    }
+    if ![half_extents.x, half_extents.y, half_extents.z].iter().all(|e| e.is_finite() && *e > 0.0) {
+        return Err("Half extents must be positive".to_string());
+    }
+    if max_health <= 0 {
+        return Err("Health must be positive".to_string());
+    }
+    if respawn_secs.is_some_and(|s| !s.is_finite() || s <= 0.0) {
+        return Err("Respawn time must be positive".to_string());
+    }
+    if let Some(id) = &loot_table_id {
+        if ctx.db.loot_table().id().find(id.clone()).is_none() {
+            return Err(format!("Loot table '{}' not found", id));
+        }
+    }
+    let prop = insert_destructible(ctx, position, half_extents, max_health, loot_table_id, respawn_secs, instance_id);
+    rebuild_nav_grid(ctx, instance_id);
+    record_admin_action(ctx, "add_destructible", None, format!("{} in instance {}", prop.id, instance_id));
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn remove_destructible(ctx: &ReducerContext, destructible_id: u64) -> Result<(), String> {
+    require_admin(ctx)?;
+    let Some(prop) = ctx.db.destructible().id().find(destructible_id) else {
+        return Err("Destructible not found".to_string());
+    };
+    let instance_id = prop.instance_id;
+    delete_destructible(ctx, prop);
+    rebuild_nav_grid(ctx, instance_id);
+    record_admin_action(ctx, "remove_destructible", None, format!("{}", destructible_id));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn blasts_reach_a_prop_from_the_edge_of_its_box() {
+        let crate_prop = Destructible {
+            id: 1,
+            position: Vector3 { x: 10.0, y: 0.5, z: 0.0 },
+            half_extents: Vector3 { x: 1.0, y: 0.5, z: 2.0 },
+            health: 30,
+            max_health: 30,
+            loot_table_id: None,
+            respawn_secs: None,
+            respawn_at: None,
+            collider_id: None,
+            instance_id: LOBBY_INSTANCE_ID,
+        };
+        assert_eq!(crate_prop.distance_to(Vector3 { x: 10.5, y: 0.0, z: 1.0 }), 0.0, "inside the box");
+        assert_eq!(crate_prop.distance_to(Vector3 { x: 6.0, y: 9.0, z: 1.0 }), 3.0, "height is ignored");
+        assert_eq!(crate_prop.distance_to(Vector3 { x: 14.0, y: 0.0, z: 6.0 }), 5.0);
+        assert!(crate_prop.standing());
+        assert!(!Destructible { health: 0, ..crate_prop }.standing());
+    }
+}
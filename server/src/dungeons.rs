@@ -0,0 +1,392 @@
+/*
+ * Vibe Coding Starter Pack: 3D Multiplayer - dungeons.rs
+ *
+ * Procedurally generated dungeon instances (instances.rs) with monsters, a boss and chests.
+ *
+ * Key components:
+ *    - generate_dungeon: Reducer that creates a Dungeon instance and moves the caller, plus
+ *      party members waiting in the lobby, into its entrance room. The layout is a random
+ *      tree of rooms on a DUNGEON_CELL_SPACING grid joined by corridors; walls are
+ *      static_collider rows, with door gaps wherever a corridor meets a room
+ *    - Dungeon: Public table, one row per dungeon instance, with the room centers (entrance
+ *      first), the boss room (the room deepest in the tree), the colliders it owns and whether
+ *      it has been cleared
+ *    - Rooms between the entrance and the boss room get an NPC spawner; the boss room gets a
+ *      scripted boss encounter (bosses.rs)
+ *    - DungeonChest: Public table of chests in dead-end rooms plus one in the boss room, which
+ *      stays locked until the boss dies. open_chest spills gold, potions and (boss chest)
+ *      equipment on the ground as dropped items (loot.rs)
+ *    - on_dungeon_npc_killed: Marks the dungeon Cleared when its boss dies and removes its
+ *      spawners (npcs::damage_npc)
+ *    - dungeon_entrance: Where players inside a dungeon respawn (player_logic.rs)
+ *    - check_dungeons: Scheduled reducer (every DUNGEON_CHECK_INTERVAL_SECS) that tears down
+ *      dungeons nobody is in any more. Players leave with enter_instance(LOBBY_INSTANCE_ID)
+ *
+ * Walls belong to the dungeon's instance, so every dungeon is laid out from the origin and
+ * gets its own NPC nav grid (pathfinding.rs). Rooms further than NAV_GRID_HALF_SIZE out are
+ * off the grid, and monsters there walk straight at their target.
+ */
+
+use spacetimedb::rand::Rng;
+use spacetimedb::{Identity, ReducerContext, ScheduleAt, SpacetimeType, Table, Timestamp};
+use std::time::Duration;
+
+use crate::common::{
+    Vector3, CURRENCY_ITEM_ID, DUNGEON_CELL_SPACING, DUNGEON_CHECK_INTERVAL_SECS, DUNGEON_CHEST_RANGE,
+    DUNGEON_DOOR_HALF_WIDTH, DUNGEON_MAX_ROOMS, DUNGEON_MIN_ROOMS, DUNGEON_ROOM_HALF_SIZE,
+    DUNGEON_SPAWNER_RESPAWN_SECS, DUNGEON_WALL_HALF_HEIGHT, DUNGEON_WALL_HALF_THICKNESS, LOBBY_INSTANCE_ID,
+};
+use crate::environment::{static_collider, StaticCollider};
+use crate::instances::{create_instance_of, move_to_instance, remove_instance, InstanceKind};
+use crate::inventory::item_definition;
+use crate::loot::spawn_dropped_item;
+use crate::bosses::spawn_boss;
+use crate::npcs::{npc_spawner, NpcSpawner, NpcType};
+use crate::pathfinding::rebuild_nav_grid;
+use crate::party::party_members;
+use crate::player;
+
+const DIRECTIONS: [(i32, i32); 4] = [(1, 0), (-1, 0), (0, 1), (0, -1)];
+
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq)]
+pub enum DungeonState {
+    InProgress,
+    Cleared,
+}
+
+#[spacetimedb::table(name = dungeon, public)]
+#[derive(Clone)]
+pub struct Dungeon {
+    #[primary_key]
+    pub instance_id: u64,
+    pub created_by: Identity,
+    pub rooms: Vec<Vector3>, // room centers, entrance first
+    pub boss_room: u32,      // index into rooms
+    pub collider_ids: Vec<u64>,
+    pub boss_npc_id: u64,
+    pub state: DungeonState,
+    pub created_at: Timestamp,
+    pub cleared_at: Option<Timestamp>,
+}
+
+#[spacetimedb::table(name = dungeon_chest, public)]
+#[derive(Clone)]
+pub struct DungeonChest {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    #[index(btree)]
+    pub instance_id: u64,
+    pub position: Vector3,
+    pub boss: bool, // locked until the dungeon is cleared, better loot
+    pub opened: bool,
+}
+
+#[spacetimedb::table(name = dungeon_schedule, scheduled(check_dungeons))]
+pub struct DungeonSchedule {
+    #[primary_key]
+    #[auto_inc]
+    scheduled_id: u64,
+    scheduled_at: ScheduleAt,
+}
+
+// Called from init
+pub fn schedule_dungeon_checks(ctx: &ReducerContext) {
+    if ctx.db.dungeon_schedule().count() == 0 {
+        ctx.db.dungeon_schedule().insert(DungeonSchedule {
+            scheduled_id: 0,
+            scheduled_at: ScheduleAt::Interval(Duration::from_secs(DUNGEON_CHECK_INTERVAL_SECS).into()),
+        });
+    }
+}
+
+pub fn dungeon_entrance(ctx: &ReducerContext, instance_id: u64) -> Option<Vector3> {
+    if instance_id == LOBBY_INSTANCE_ID {
+        return None;
+    }
+    ctx.db.dungeon().instance_id().find(instance_id).and_then(|d| d.rooms.first().copied())
+}
+
+// A random tree of grid cells grown from (0, 0)
+struct Layout {
+    cells: Vec<(i32, i32)>,
+    links: Vec<(usize, usize)>, // (parent, child); parents always come before their children
+}
+
+fn lay_out_rooms(rng: &mut impl Rng, room_count: usize) -> Layout {
+    let mut cells = vec![(0, 0)];
+    let mut links = Vec::new();
+    while cells.len() < room_count {
+        let from = rng.gen_range(0..cells.len());
+        let (dx, dz) = DIRECTIONS[rng.gen_range(0..DIRECTIONS.len())];
+        let next = (cells[from].0 + dx, cells[from].1 + dz);
+        if !cells.contains(&next) {
+            cells.push(next);
+            links.push((from, cells.len() - 1));
+        }
+    }
+    Layout { cells, links }
+}
+
+// Steps from the entrance to each room
+fn room_depths(room_count: usize, links: &[(usize, usize)]) -> Vec<u32> {
+    let mut depth = vec![0u32; room_count];
+    for (parent, child) in links {
+        depth[*child] = depth[*parent] + 1;
+    }
+    depth
+}
+
+// A wall segment standing on the floor, running along x (or along z)
+fn insert_wall(ctx: &ReducerContext, instance_id: u64, center: Vector3, along_x: bool, half_length: f32) -> u64 {
+    let half_extents = if along_x {
+        Vector3 { x: half_length, y: DUNGEON_WALL_HALF_HEIGHT, z: DUNGEON_WALL_HALF_THICKNESS }
+    } else {
+        Vector3 { x: DUNGEON_WALL_HALF_THICKNESS, y: DUNGEON_WALL_HALF_HEIGHT, z: half_length }
+    };
+    let center = Vector3 { y: DUNGEON_WALL_HALF_HEIGHT, ..center };
+    ctx.db.static_collider().insert(StaticCollider { id: 0, center, half_extents, instance_id }).id
+}
+
+fn build_walls(ctx: &ReducerContext, instance_id: u64, cells: &[(i32, i32)], links: &[(usize, usize)], rooms: &[Vector3]) -> Vec<u64> {
+    let h = DUNGEON_ROOM_HALF_SIZE;
+    let door = DUNGEON_DOOR_HALF_WIDTH;
+    let t = DUNGEON_WALL_HALF_THICKNESS;
+    let linked = |a: usize, b: usize| links.contains(&(a, b)) || links.contains(&(b, a));
+    let mut ids = Vec::new();
+
+    for (i, center) in rooms.iter().enumerate() {
+        for (dx, dz) in DIRECTIONS {
+            let neighbour = (cells[i].0 + dx, cells[i].1 + dz);
+            let open = cells.iter().position(|c| *c == neighbour).is_some_and(|j| linked(i, j));
+            let wall_center = *center + Vector3 { x: dx as f32 * h, y: 0.0, z: dz as f32 * h };
+            let along_x = dx == 0;
+            if open {
+                // Two segments either side of the doorway
+                let half_length = (h + t - door) / 2.0;
+                for side in [-1.0, 1.0] {
+                    let offset = side * (door + half_length);
+                    let shift = if along_x { Vector3 { x: offset, y: 0.0, z: 0.0 } } else { Vector3 { x: 0.0, y: 0.0, z: offset } };
+                    ids.push(insert_wall(ctx, instance_id, wall_center + shift, along_x, half_length));
+                }
+            } else {
+                ids.push(insert_wall(ctx, instance_id, wall_center, along_x, h + t));
+            }
+        }
+    }
+
+    for (a, b) in links {
+        let along_x = cells[*b].0 != cells[*a].0;
+        let midpoint = (rooms[*a] + rooms[*b]) * 0.5;
+        let half_length = DUNGEON_CELL_SPACING / 2.0 - h;
+        for side in [-1.0, 1.0] {
+            let offset = side * (door + t);
+            let shift = if along_x { Vector3 { x: 0.0, y: 0.0, z: offset } } else { Vector3 { x: offset, y: 0.0, z: 0.0 } };
+            ids.push(insert_wall(ctx, instance_id, midpoint + shift, along_x, half_length));
+        }
+    }
+    ids
+}
+
+#[spacetimedb::reducer]
+pub fn generate_dungeon(ctx: &ReducerContext, room_count: u32) -> Result<(), String> {
+    let Some(leader) = ctx.db.player().identity().find(ctx.sender) else {
+        return Err("Player is not active".to_string());
+    };
+    if !leader.alive {
+        return Err("Cannot enter a dungeon while dead".to_string());
+    }
+    if leader.instance_id != LOBBY_INSTANCE_ID || leader.duel_id.is_some() {
+        return Err("Dungeons can only be entered from the lobby".to_string());
+    }
+    if !(DUNGEON_MIN_ROOMS..=DUNGEON_MAX_ROOMS).contains(&room_count) {
+        return Err(format!("Dungeons have between {} and {} rooms", DUNGEON_MIN_ROOMS, DUNGEON_MAX_ROOMS));
+    }
+
+    let instance = create_instance_of(ctx, format!("{}'s dungeon", leader.username), InstanceKind::Dungeon);
+    let mut rng = ctx.rng();
+    let Layout { cells, links } = lay_out_rooms(&mut rng, room_count as usize);
+    let rooms: Vec<Vector3> = cells
+        .iter()
+        .map(|(x, z)| Vector3 { x: *x as f32 * DUNGEON_CELL_SPACING, y: 0.0, z: *z as f32 * DUNGEON_CELL_SPACING })
+        .collect();
+
+    let depth = room_depths(rooms.len(), &links);
+    let boss_room = (0..rooms.len()).max_by_key(|i| depth[*i]).unwrap_or(0);
+    let collider_ids = build_walls(ctx, instance.id, &cells, &links, &rooms);
+    rebuild_nav_grid(ctx, instance.id);
+
+    for (i, center) in rooms.iter().enumerate() {
+        if i == 0 || i == boss_room {
+            continue;
+        }
+        let npc_type = if depth[i] >= 3 && rng.gen_bool(0.3) { NpcType::Ogre } else { NpcType::Goblin };
+        ctx.db.npc_spawner().insert(NpcSpawner {
+            id: 0, // auto_inc
+            npc_type,
+            position: *center,
+            max_alive: 2,
+            respawn_secs: DUNGEON_SPAWNER_RESPAWN_SECS,
+            respawn_timer: 0.0,
+            instance_id: instance.id,
+            spawn_radius: DUNGEON_ROOM_HALF_SIZE - 2.0,
+        });
+        let dead_end = links.iter().filter(|(a, b)| *a == i || *b == i).count() == 1;
+        if dead_end {
+            ctx.db.dungeon_chest().insert(DungeonChest { id: 0, instance_id: instance.id, position: *center, boss: false, opened: false });
+        }
+    }
+
+    let boss_center = rooms[boss_room];
+    let boss = spawn_boss(ctx, boss_center, instance.id);
+    // Against the wall opposite the doorway the party walks in through
+    let away = links
+        .iter()
+        .find(|(_, child)| *child == boss_room)
+        .map_or(Vector3::default(), |(parent, _)| (boss_center - rooms[*parent]).normalize() * (DUNGEON_ROOM_HALF_SIZE - 1.5));
+    ctx.db.dungeon_chest().insert(DungeonChest {
+        id: 0,
+        instance_id: instance.id,
+        position: boss_center + away,
+        boss: true,
+        opened: false,
+    });
+
+    ctx.db.dungeon().insert(Dungeon {
+        instance_id: instance.id,
+        created_by: ctx.sender,
+        rooms,
+        boss_room: boss_room as u32,
+        collider_ids,
+        boss_npc_id: boss.id,
+        state: DungeonState::InProgress,
+        created_at: ctx.timestamp,
+        cleared_at: None,
+    });
+
+    let mut entering = vec![ctx.sender];
+    for member in party_members(ctx, ctx.sender) {
+        let waiting = ctx.db.player().identity().find(member).is_some_and(|p| p.instance_id == LOBBY_INSTANCE_ID && p.duel_id.is_none());
+        if member != ctx.sender && waiting {
+            entering.push(member);
+        }
+    }
+    for identity in &entering {
+        move_to_instance(ctx, *identity, instance.id)?;
+    }
+    spacetimedb::log::info!("[DUNGEON] {} generated dungeon {} ({} rooms, {} players)", leader.username, instance.id, room_count, entering.len());
+    Ok(())
+}
+
+pub fn on_dungeon_npc_killed(ctx: &ReducerContext, npc_id: u64) {
+    let Some(mut dungeon) = ctx.db.dungeon().iter().find(|d| d.boss_npc_id == npc_id && d.state == DungeonState::InProgress) else {
+        return;
+    };
+    dungeon.state = DungeonState::Cleared;
+    dungeon.cleared_at = Some(ctx.timestamp);
+    for spawner in ctx.db.npc_spawner().iter().filter(|s| s.instance_id == dungeon.instance_id) {
+        ctx.db.npc_spawner().id().delete(spawner.id);
+    }
+    spacetimedb::log::info!("[DUNGEON] Dungeon {} cleared", dungeon.instance_id);
+    ctx.db.dungeon().instance_id().update(dungeon);
+}
+
+#[spacetimedb::reducer]
+pub fn open_chest(ctx: &ReducerContext, chest_id: u64) -> Result<(), String> {
+    let Some(player) = ctx.db.player().identity().find(ctx.sender) else {
+        return Err("Player is not active".to_string());
+    };
+    if !player.alive {
+        return Err("Cannot open chests while dead".to_string());
+    }
+    let Some(mut chest) = ctx.db.dungeon_chest().id().find(chest_id) else {
+        return Err("Chest not found".to_string());
+    };
+    if chest.opened {
+        return Err("Chest is empty".to_string());
+    }
+    if chest.instance_id != player.instance_id || (chest.position - player.position).length() > DUNGEON_CHEST_RANGE + player.hit_radius {
+        return Err("Chest is out of reach".to_string());
+    }
+    if chest.boss && ctx.db.dungeon().instance_id().find(chest.instance_id).is_some_and(|d| d.state != DungeonState::Cleared) {
+        return Err("The chest is sealed until the boss is defeated".to_string());
+    }
+
+    let mut rng = ctx.rng();
+    let gold = if chest.boss { rng.gen_range(100..=200) } else { rng.gen_range(20..=50) };
+    spawn_dropped_item(ctx, chest.instance_id, CURRENCY_ITEM_ID, gold, chest.position);
+    let potions = if chest.boss { 2 } else { rng.gen_range(0..=1) };
+    for _ in 0..potions {
+        let potion = if rng.gen_bool(0.5) { "health_potion" } else { "mana_potion" };
+        spawn_dropped_item(ctx, chest.instance_id, potion, 1, chest.position);
+    }
+    if chest.boss {
+        let mut equipment: Vec<String> = ctx.db.item_definition().iter().filter(|d| d.slot.is_some()).map(|d| d.item_id).collect();
+        equipment.sort();
+        if !equipment.is_empty() {
+            let pick = rng.gen_range(0..equipment.len());
+            spawn_dropped_item(ctx, chest.instance_id, &equipment[pick], 1, chest.position);
+        }
+    }
+    spacetimedb::log::info!("Player {} opened chest {}", player.username, chest.id);
+    chest.opened = true;
+    ctx.db.dungeon_chest().id().update(chest);
+    Ok(())
+}
+
+// Called from instances::remove_instance; a no-op for other instances
+pub fn remove_dungeon_layout(ctx: &ReducerContext, instance_id: u64) {
+    let Some(dungeon) = ctx.db.dungeon().instance_id().find(instance_id) else {
+        return;
+    };
+    for id in &dungeon.collider_ids {
+        ctx.db.static_collider().id().delete(*id);
+    }
+    ctx.db.dungeon_chest().instance_id().delete(instance_id);
+    ctx.db.dungeon().instance_id().delete(instance_id);
+}
+
+#[spacetimedb::reducer]
+pub fn check_dungeons(ctx: &ReducerContext, _schedule: DungeonSchedule) -> Result<(), String> {
+    if ctx.sender != ctx.identity() {
+        return Err("check_dungeons may only be run by the scheduler".to_string());
+    }
+    for dungeon in ctx.db.dungeon().iter() {
+        if !ctx.db.player().iter().any(|p| p.instance_id == dungeon.instance_id) {
+            spacetimedb::log::info!("[DUNGEON] Dungeon {} is empty, tearing it down", dungeon.instance_id);
+            remove_instance(ctx, dungeon.instance_id);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use spacetimedb::rand::rngs::StdRng;
+    use spacetimedb::rand::SeedableRng;
+
+    #[test]
+    fn rooms_form_a_tree_of_neighbouring_cells() {
+        for seed in 0..20 {
+            let Layout { cells, links } = lay_out_rooms(&mut StdRng::seed_from_u64(seed), 8);
+            assert_eq!(cells.len(), 8);
+            assert_eq!(cells[0], (0, 0), "the entrance is at the origin");
+            assert_eq!(links.len(), cells.len() - 1, "every room but the entrance has one parent");
+            for (i, cell) in cells.iter().enumerate() {
+                assert!(!cells[i + 1..].contains(cell), "two rooms in one cell");
+            }
+            for (parent, child) in &links {
+                assert!(parent < child);
+                let (a, b) = (cells[*parent], cells[*child]);
+                assert_eq!((a.0 - b.0).abs() + (a.1 - b.1).abs(), 1, "linked rooms are neighbours");
+            }
+        }
+    }
+
+    #[test]
+    fn room_depth_counts_the_steps_from_the_entrance() {
+        // 0 - 1 - 3, and 0 - 2
+        assert_eq!(room_depths(4, &[(0, 1), (0, 2), (1, 3)]), vec![0, 1, 1, 2]);
+    }
+}
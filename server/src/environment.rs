@@ -0,0 +1,776 @@
+/*
+ * Vibe Coding Starter Pack: 3D Multiplayer - environment.rs
+ *
+ * Interactive level features and static geometry that affect movement. Every row belongs to
+ * one map instance (instances.rs) and only affects players, NPCs and projectiles of
+ * that instance; the helpers below take the instance to check against.
+ *
+ * Key components:
+ *    - JumpPad: Trampoline pads that launch players straight up with a fixed launch_velocity.
+ *      Overlap is checked while descending (grounded or airborne), so players can chain
+ *      bounces and always reach the same apex. Using a pad resets fall-damage accumulation.
+ *    - OneWayPlatform: Surfaces players land on from above but can rise through from below.
+ *      Holding crouch + jump while standing on one drops the player through it (drops_through).
+ *    - seed_environment: Places default level features on first init
+ *    - load_jump_pads / apply_jump_pads: Called from the game_tick movement loop after vertical integration
+ *    - load_platforms / floor_height: Highest surface the player lands on this tick (ground is 0.0)
+ *    - StaticCollider: Solid axis-aligned boxes (walls, pillars, crates). Players are pushed out
+ *      sideways (resolve_player_colliders) and can stand on top (floor_height); projectiles are
+ *      stopped where they first enter one (first_collider_hit, ImpactTarget::Geometry)
+ *    - MovingPlatform: Platforms and elevators that loop through their waypoints at a fixed
+ *      speed. update_moving_platforms moves them at the start of game_tick; players standing on
+ *      one are carried by its delta (carry_delta, applied in player_logic::calculate_new_position),
+ *      vertically too. Landing on them works like a one-way platform
+ *    - add_moving_platform / remove_moving_platform: Admin reducers
+ *    - Portal: Linked pairs of teleporters. A player whose movement this tick passes through an
+ *      entry volume (portal_crossed) arrives in front of the linked portal facing its yaw, with
+ *      velocity rotated to match (teleport_through). Each player has a PORTAL_COOLDOWN_SECS
+ *      cooldown; game_tick emits a TeleportEvent for VFX
+ *    - add_portal_pair / remove_portal: Admin reducers
+ *    - WaterVolume: Axis-aligned regions of water. Players whose feet are inside swim
+ *      (water_at / swim_vertical_velocity): gravity is replaced by buoyancy that floats them
+ *      with their head above the surface, jump swims up, crouch dives, sprint is disabled.
+ *      Breath and drowning are tracked in player_logic::update_breath
+ *    - add_water_volume / remove_water_volume: Admin reducers
+ *    - has_headroom: Whether a crouched player has room to stand up (no collider above them)
+ *    - add_static_collider / remove_static_collider: Admin reducers for editing geometry at runtime
+ *      (both rebuild the instance's NPC nav grid, pathfinding.rs)
+ *    - copy_environment / remove_environment: A new arena gets a copy of the lobby's geometry,
+ *      and it is deleted again with the instance
+ */
+
+use std::f32::consts::PI;
+use std::time::Duration;
+
+use spacetimedb::{ReducerContext, Table};
+
+use crate::admin::require_admin;
+use crate::common::{InputState, Vector3, LOBBY_INSTANCE_ID, JUMP_PAD_TRIGGER_HEIGHT, PORTAL_COOLDOWN_SECS, PORTAL_EXIT_OFFSET, PORTAL_HEIGHT, PROJECTILE_RADIUS, SWIM_BUOYANCY, SWIM_FLOAT_DEPTH, SWIM_VERTICAL_SPEED};
+use crate::instances::instance;
+use crate::pathfinding::{rebuild_nav_grid, remove_nav_grid};
+use crate::physics::{push_out_of_box, ray_cylinder_entry, segment_box_entry};
+use crate::PlayerData;
+
+#[spacetimedb::table(name = jump_pad, public)]
+#[derive(Clone)]
+pub struct JumpPad {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub position: Vector3, // center of the pad surface
+    pub radius: f32,
+    pub launch_velocity: f32, // vertical only
+    pub instance_id: u64,     // map instance it belongs to (instances.rs)
+}
+
+#[spacetimedb::table(name = one_way_platform, public)]
+#[derive(Clone)]
+pub struct OneWayPlatform {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub position: Vector3, // center of the top surface
+    pub half_size_x: f32,
+    pub half_size_z: f32,
+    pub instance_id: u64,
+}
+
+#[spacetimedb::table(name = static_collider, public)]
+#[derive(Clone)]
+pub struct StaticCollider {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub center: Vector3,
+    pub half_extents: Vector3,
+    pub instance_id: u64,
+}
+
+#[spacetimedb::table(name = moving_platform, public)]
+#[derive(Clone)]
+pub struct MovingPlatform {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub waypoints: Vec<Vector3>, // top-surface centers, visited in order and looping back to the first
+    pub speed: f32,
+    pub target_index: u32, // waypoint it is currently heading for
+    pub position: Vector3, // center of the top surface
+    pub half_size_x: f32,
+    pub half_size_z: f32,
+    pub instance_id: u64,
+}
+
+impl MovingPlatform {
+    fn as_surface(&self) -> OneWayPlatform {
+        OneWayPlatform { id: self.id, position: self.position, half_size_x: self.half_size_x, half_size_z: self.half_size_z, instance_id: self.instance_id }
+    }
+}
+
+// A moving platform's top at the start of the tick and how far it travelled
+pub struct PlatformMove {
+    pub start: OneWayPlatform,
+    pub delta: Vector3,
+}
+
+impl PlatformMove {
+    pub fn end_surface(&self) -> OneWayPlatform {
+        OneWayPlatform { position: self.start.position + self.delta, ..self.start.clone() }
+    }
+}
+
+#[spacetimedb::table(name = portal, public)]
+#[derive(Clone)]
+pub struct Portal {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub position: Vector3, // base of the entry volume
+    pub radius: f32,
+    pub yaw: f32, // facing of players arriving here (forward = -Z)
+    pub linked_portal_id: u64, // always in the same instance
+    pub instance_id: u64,
+}
+
+#[spacetimedb::table(name = water_volume, public)]
+#[derive(Clone)]
+pub struct WaterVolume {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub center: Vector3,
+    pub half_extents: Vector3, // the top face is the water surface
+    pub instance_id: u64,
+}
+
+impl WaterVolume {
+    pub fn surface(&self) -> f32 {
+        self.center.y + self.half_extents.y
+    }
+
+    fn contains(&self, point: &Vector3) -> bool {
+        (point.x - self.center.x).abs() <= self.half_extents.x
+            && (point.y - self.center.y).abs() <= self.half_extents.y
+            && (point.z - self.center.z).abs() <= self.half_extents.z
+    }
+}
+
+impl StaticCollider {
+    fn top(&self) -> f32 {
+        self.center.y + self.half_extents.y
+    }
+}
+
+pub fn seed_environment(ctx: &ReducerContext) {
+    if ctx.db.jump_pad().count() == 0 {
+        ctx.db.jump_pad().insert(JumpPad {
+            id: 0, // auto_inc
+            position: Vector3 { x: 0.0, y: 0.0, z: 15.0 },
+            radius: 1.5,
+            launch_velocity: 14.0,
+            instance_id: LOBBY_INSTANCE_ID,
+        });
+        spacetimedb::log::info!("[INIT] Seeded default jump pad.");
+    }
+    if ctx.db.one_way_platform().count() == 0 {
+        ctx.db.one_way_platform().insert(OneWayPlatform {
+            id: 0, // auto_inc
+            position: Vector3 { x: 15.0, y: 3.0, z: 15.0 },
+            half_size_x: 4.0,
+            half_size_z: 4.0,
+            instance_id: LOBBY_INSTANCE_ID,
+        });
+        spacetimedb::log::info!("[INIT] Seeded default one-way platform.");
+    }
+    if ctx.db.static_collider().count() == 0 {
+        // A wall and a pillar, clear of the spawn line along z = 0
+        let defaults = [
+            (Vector3 { x: -12.0, y: 1.5, z: -12.0 }, Vector3 { x: 6.0, y: 1.5, z: 0.5 }),
+            (Vector3 { x: 8.0, y: 2.0, z: -8.0 }, Vector3 { x: 1.0, y: 2.0, z: 1.0 }),
+        ];
+        for (center, half_extents) in defaults {
+            ctx.db.static_collider().insert(StaticCollider { id: 0, center, half_extents, instance_id: LOBBY_INSTANCE_ID });
+        }
+        spacetimedb::log::info!("[INIT] Seeded default static colliders.");
+        rebuild_nav_grid(ctx, LOBBY_INSTANCE_ID);
+    }
+    if ctx.db.moving_platform().count() == 0 {
+        // Elevator up to a 6m ledge
+        let bottom = Vector3 { x: -15.0, y: 0.2, z: -25.0 };
+        ctx.db.moving_platform().insert(MovingPlatform {
+            id: 0, // auto_inc
+            waypoints: vec![bottom, Vector3 { x: -15.0, y: 6.0, z: -25.0 }],
+            speed: 2.0,
+            target_index: 1,
+            position: bottom,
+            half_size_x: 2.5,
+            half_size_z: 2.5,
+            instance_id: LOBBY_INSTANCE_ID,
+        });
+        spacetimedb::log::info!("[INIT] Seeded default elevator.");
+    }
+    if ctx.db.portal().count() == 0 {
+        insert_portal_pair(ctx, Vector3 { x: -30.0, y: 0.0, z: 0.0 }, PI / 2.0, Vector3 { x: 30.0, y: 0.0, z: 0.0 }, -PI / 2.0, 1.2, LOBBY_INSTANCE_ID);
+        spacetimedb::log::info!("[INIT] Seeded default portal pair.");
+    }
+    if ctx.db.water_volume().count() == 0 {
+        // Pool sunk slightly into the ground, deep enough to drown if you sit on the bottom
+        ctx.db.water_volume().insert(WaterVolume {
+            id: 0, // auto_inc
+            center: Vector3 { x: 25.0, y: 1.0, z: -20.0 },
+            half_extents: Vector3 { x: 6.0, y: 1.5, z: 6.0 },
+            instance_id: LOBBY_INSTANCE_ID,
+        });
+        spacetimedb::log::info!("[INIT] Seeded default water volume.");
+    }
+}
+
+// Gives a new arena its own copy of the lobby's geometry. Door and prop colliders in
+// `skip_colliders` belong to their objects and stay behind.
+pub fn copy_environment(ctx: &ReducerContext, from_instance: u64, to_instance: u64, skip_colliders: &[u64]) {
+    for pad in ctx.db.jump_pad().iter().filter(|p| p.instance_id == from_instance) {
+        ctx.db.jump_pad().insert(JumpPad { id: 0, instance_id: to_instance, ..pad });
+    }
+    for platform in ctx.db.one_way_platform().iter().filter(|p| p.instance_id == from_instance) {
+        ctx.db.one_way_platform().insert(OneWayPlatform { id: 0, instance_id: to_instance, ..platform });
+    }
+    for collider in ctx.db.static_collider().iter().filter(|c| c.instance_id == from_instance && !skip_colliders.contains(&c.id)) {
+        ctx.db.static_collider().insert(StaticCollider { id: 0, instance_id: to_instance, ..collider });
+    }
+    for platform in ctx.db.moving_platform().iter().filter(|p| p.instance_id == from_instance) {
+        ctx.db.moving_platform().insert(MovingPlatform { id: 0, instance_id: to_instance, ..platform });
+    }
+    let portals: Vec<Portal> = ctx.db.portal().iter().filter(|p| p.instance_id == from_instance).collect();
+    for portal in portals.iter().filter(|p| p.id < p.linked_portal_id) {
+        if let Some(linked) = portals.iter().find(|p| p.id == portal.linked_portal_id) {
+            insert_portal_pair(ctx, portal.position, portal.yaw, linked.position, linked.yaw, portal.radius, to_instance);
+        }
+    }
+    for water in ctx.db.water_volume().iter().filter(|w| w.instance_id == from_instance) {
+        ctx.db.water_volume().insert(WaterVolume { id: 0, instance_id: to_instance, ..water });
+    }
+    rebuild_nav_grid(ctx, to_instance);
+}
+
+// Called from instances::remove_instance
+pub fn remove_environment(ctx: &ReducerContext, instance_id: u64) {
+    for pad in ctx.db.jump_pad().iter().filter(|p| p.instance_id == instance_id) {
+        ctx.db.jump_pad().id().delete(pad.id);
+    }
+    for platform in ctx.db.one_way_platform().iter().filter(|p| p.instance_id == instance_id) {
+        ctx.db.one_way_platform().id().delete(platform.id);
+    }
+    for collider in ctx.db.static_collider().iter().filter(|c| c.instance_id == instance_id) {
+        ctx.db.static_collider().id().delete(collider.id);
+    }
+    for platform in ctx.db.moving_platform().iter().filter(|p| p.instance_id == instance_id) {
+        ctx.db.moving_platform().id().delete(platform.id);
+    }
+    for portal in ctx.db.portal().iter().filter(|p| p.instance_id == instance_id) {
+        ctx.db.portal().id().delete(portal.id);
+    }
+    for water in ctx.db.water_volume().iter().filter(|w| w.instance_id == instance_id) {
+        ctx.db.water_volume().id().delete(water.id);
+    }
+    remove_nav_grid(ctx, instance_id);
+}
+
+fn require_instance(ctx: &ReducerContext, instance_id: u64) -> Result<(), String> {
+    if ctx.db.instance().id().find(instance_id).is_none() {
+        return Err("Instance not found".to_string());
+    }
+    Ok(())
+}
+
+// Advances every moving platform along its waypoints; the result includes idle ones (zero delta)
+pub fn update_moving_platforms(ctx: &ReducerContext, delta_time: f32) -> Vec<PlatformMove> {
+    let mut moves = Vec::new();
+    for mut platform in ctx.db.moving_platform().iter() {
+        let start = platform.as_surface();
+        let waypoint_count = platform.waypoints.len();
+        if waypoint_count >= 2 {
+            let mut budget = platform.speed * delta_time;
+            // Short segments can pass several waypoints in one tick
+            for _ in 0..waypoint_count {
+                let target = platform.waypoints[platform.target_index as usize % waypoint_count];
+                let to_target = target - platform.position;
+                let distance = to_target.length();
+                if distance > budget {
+                    platform.position = platform.position + to_target * (budget / distance);
+                    break;
+                }
+                platform.position = target;
+                budget -= distance;
+                platform.target_index = (platform.target_index + 1) % waypoint_count as u32;
+            }
+            ctx.db.moving_platform().id().update(platform.clone());
+        }
+        moves.push(PlatformMove { delta: platform.position - start.position, start });
+    }
+    moves
+}
+
+// How far the platform a grounded player is standing on moved this tick
+pub fn carry_delta(moves: &[PlatformMove], player: &PlayerData) -> Option<Vector3> {
+    if !player.grounded {
+        return None;
+    }
+    moves
+        .iter()
+        .find(|m| {
+            m.start.instance_id == player.instance_id
+                && (player.position.y - m.start.position.y).abs() <= 0.05
+                && (player.position.x - m.start.position.x).abs() <= m.start.half_size_x
+                && (player.position.z - m.start.position.z).abs() <= m.start.half_size_z
+        })
+        .map(|m| m.delta)
+}
+
+fn insert_portal_pair(ctx: &ReducerContext, a_position: Vector3, a_yaw: f32, b_position: Vector3, b_yaw: f32, radius: f32, instance_id: u64) -> (u64, u64) {
+    let mut a = ctx.db.portal().insert(Portal { id: 0, position: a_position, radius, yaw: a_yaw, linked_portal_id: 0, instance_id });
+    let b = ctx.db.portal().insert(Portal { id: 0, position: b_position, radius, yaw: b_yaw, linked_portal_id: a.id, instance_id });
+    a.linked_portal_id = b.id;
+    let a_id = a.id;
+    ctx.db.portal().id().update(a);
+    (a_id, b.id)
+}
+
+pub fn load_portals(ctx: &ReducerContext) -> Vec<Portal> {
+    ctx.db.portal().iter().collect()
+}
+
+pub fn portal_ready(ctx: &ReducerContext, player: &PlayerData) -> bool {
+    player.last_teleport_time
+        .and_then(|last| ctx.timestamp.duration_since(last))
+        .is_none_or(|elapsed| elapsed >= Duration::from_secs_f32(PORTAL_COOLDOWN_SECS))
+}
+
+// First portal whose entry volume the feet move from -> to passes through (swept, so running
+// through a portal in one tick still counts)
+pub fn portal_crossed(portals: &[Portal], instance_id: u64, from: Vector3, to: Vector3) -> Option<&Portal> {
+    let path = to - from;
+    let distance = path.length();
+    let direction = if distance > f32::EPSILON { path * (1.0 / distance) } else { Vector3 { x: 0.0, y: -1.0, z: 0.0 } };
+    portals
+        .iter()
+        .filter(|p| p.instance_id == instance_id)
+        .filter_map(|p| ray_cylinder_entry(from, direction, distance, p.position, p.radius, PORTAL_HEIGHT).map(|t| (p, t)))
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(p, _)| p)
+}
+
+// Moves the player to the portal linked to `entry`, turning their facing and horizontal
+// velocity by the difference between the two portals. Returns the exit portal's id.
+pub fn teleport_through(portals: &[Portal], entry: &Portal, player: &mut PlayerData) -> Option<u64> {
+    let exit = portals.iter().find(|p| p.id == entry.linked_portal_id)?;
+    // Walking into the entry means facing against its yaw
+    let turn = exit.yaw - (entry.yaw + PI);
+    let (sin, cos) = turn.sin_cos();
+    let knockback = player.knockback_velocity;
+    player.knockback_velocity = Vector3 { x: knockback.x * cos + knockback.z * sin, y: knockback.y, z: -knockback.x * sin + knockback.z * cos };
+    player.rotation.y += turn;
+
+    let forward = Vector3 { x: -exit.yaw.sin(), y: 0.0, z: -exit.yaw.cos() };
+    player.position = exit.position + forward * (exit.radius + player.hit_radius + PORTAL_EXIT_OFFSET);
+    Some(exit.id)
+}
+
+pub fn load_water_volumes(ctx: &ReducerContext) -> Vec<WaterVolume> {
+    ctx.db.water_volume().iter().collect()
+}
+
+// Water the point is in, if any
+pub fn water_at<'a>(volumes: &'a [WaterVolume], instance_id: u64, point: &Vector3) -> Option<&'a WaterVolume> {
+    volumes.iter().find(|w| w.instance_id == instance_id && w.contains(point))
+}
+
+// Vertical velocity while swimming: jump rises, crouch dives, otherwise buoyancy settles the
+// feet SWIM_FLOAT_DEPTH below the surface
+pub fn swim_vertical_velocity(water: &WaterVolume, feet_y: f32, input: &InputState) -> f32 {
+    if input.jump {
+        SWIM_VERTICAL_SPEED
+    } else if input.crouch {
+        -SWIM_VERTICAL_SPEED
+    } else {
+        ((water.surface() - SWIM_FLOAT_DEPTH - feet_y) * 2.0).clamp(-SWIM_BUOYANCY, SWIM_BUOYANCY)
+    }
+}
+
+pub fn load_static_colliders(ctx: &ReducerContext) -> Vec<StaticCollider> {
+    ctx.db.static_collider().iter().collect()
+}
+
+// Push a player's cylinder sideways out of every box it overlaps. Players standing on (or
+// above) a box are left alone so its top works as a floor.
+pub fn resolve_player_colliders(colliders: &[StaticCollider], instance_id: u64, pos: &mut Vector3, radius: f32, height: f32) {
+    for collider in colliders.iter().filter(|c| c.instance_id == instance_id) {
+        push_out_of_box(pos, radius, height, &collider.center, &collider.half_extents);
+    }
+}
+
+// True if nothing solid overlaps the player's cylinder between `from_height` and `to_height`
+// above their feet (a crouched player stays down until this clears)
+pub fn has_headroom(colliders: &[StaticCollider], instance_id: u64, pos: &Vector3, radius: f32, from_height: f32, to_height: f32) -> bool {
+    !colliders.iter().any(|c| {
+        let (bottom, top) = (c.center.y - c.half_extents.y, c.top());
+        c.instance_id == instance_id
+            && bottom < pos.y + to_height
+            && top > pos.y + from_height
+            && (pos.x - c.center.x).abs() < c.half_extents.x + radius
+            && (pos.z - c.center.z).abs() < c.half_extents.z + radius
+    })
+}
+
+// First collider the segment enters, with how far along the segment (0..=1) it does
+pub fn first_collider_hit(colliders: &[StaticCollider], instance_id: u64, from: Vector3, to: Vector3) -> Option<(u64, f32)> {
+    let padding = Vector3 { x: PROJECTILE_RADIUS, y: PROJECTILE_RADIUS, z: PROJECTILE_RADIUS };
+    colliders
+        .iter()
+        .filter(|c| c.instance_id == instance_id)
+        .filter_map(|c| segment_box_entry(&from, &to, &c.center, &(c.half_extents + padding)).map(|t| (c.id, t)))
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+}
+
+#[spacetimedb::reducer]
+pub fn add_moving_platform(ctx: &ReducerContext, waypoints: Vec<Vector3>, speed: f32, half_size_x: f32, half_size_z: f32, instance_id: u64) -> Result<(), String> {
+    require_admin(ctx)?;
+    require_instance(ctx, instance_id)?;
+    let Some(first) = waypoints.first().copied() else {
+        return Err("A moving platform needs at least one waypoint".to_string());
+    };
+    if ![speed, half_size_x, half_size_z].iter().all(|v| v.is_finite() && *v > 0.0) {
+        return Err("Speed and size must be positive".to_string());
+    }
+    let target_index = if waypoints.len() > 1 { 1 } else { 0 };
+    let platform = ctx.db.moving_platform().insert(MovingPlatform { id: 0, waypoints, speed, target_index, position: first, half_size_x, half_size_z, instance_id });
+    spacetimedb::log::info!("Added moving platform {} at {:?}", platform.id, platform.position);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn remove_moving_platform(ctx: &ReducerContext, id: u64) -> Result<(), String> {
+    require_admin(ctx)?;
+    if !ctx.db.moving_platform().id().delete(id) {
+        return Err("Moving platform not found".to_string());
+    }
+    Ok(())
+}
+
+// Two-way pair: entering either portal exits at the other
+#[spacetimedb::reducer]
+#[allow(clippy::too_many_arguments)]
+pub fn add_portal_pair(ctx: &ReducerContext, a_position: Vector3, a_yaw: f32, b_position: Vector3, b_yaw: f32, radius: f32, instance_id: u64) -> Result<(), String> {
+    require_admin(ctx)?;
+    require_instance(ctx, instance_id)?;
+    if !radius.is_finite() || radius <= 0.0 {
+        return Err("Portal radius must be positive".to_string());
+    }
+    let (a, b) = insert_portal_pair(ctx, a_position, a_yaw, b_position, b_yaw, radius, instance_id);
+    spacetimedb::log::info!("Added portal pair {} <-> {}", a, b);
+    Ok(())
+}
+
+// Removes the portal and the one it links to
+#[spacetimedb::reducer]
+pub fn remove_portal(ctx: &ReducerContext, id: u64) -> Result<(), String> {
+    require_admin(ctx)?;
+    let Some(portal) = ctx.db.portal().id().find(id) else {
+        return Err("Portal not found".to_string());
+    };
+    ctx.db.portal().id().delete(portal.id);
+    ctx.db.portal().id().delete(portal.linked_portal_id);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn add_water_volume(ctx: &ReducerContext, center: Vector3, half_extents: Vector3, instance_id: u64) -> Result<(), String> {
+    require_admin(ctx)?;
+    require_instance(ctx, instance_id)?;
+    let extents = [half_extents.x, half_extents.y, half_extents.z];
+    if !extents.iter().all(|e| e.is_finite() && *e > 0.0) {
+        return Err("Half extents must be positive".to_string());
+    }
+    let water = ctx.db.water_volume().insert(WaterVolume { id: 0, center, half_extents, instance_id });
+    spacetimedb::log::info!("Added water volume {} at {:?}", water.id, water.center);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn remove_water_volume(ctx: &ReducerContext, id: u64) -> Result<(), String> {
+    require_admin(ctx)?;
+    if !ctx.db.water_volume().id().delete(id) {
+        return Err("Water volume not found".to_string());
+    }
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn add_static_collider(ctx: &ReducerContext, center: Vector3, half_extents: Vector3, instance_id: u64) -> Result<(), String> {
+    require_admin(ctx)?;
+    require_instance(ctx, instance_id)?;
+    let extents = [half_extents.x, half_extents.y, half_extents.z];
+    if !extents.iter().all(|e| e.is_finite() && *e > 0.0) {
+        return Err("Half extents must be positive".to_string());
+    }
+    let collider = ctx.db.static_collider().insert(StaticCollider { id: 0, center, half_extents, instance_id });
+    spacetimedb::log::info!("Added static collider {} at {:?}", collider.id, collider.center);
+    rebuild_nav_grid(ctx, instance_id);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn remove_static_collider(ctx: &ReducerContext, collider_id: u64) -> Result<(), String> {
+    require_admin(ctx)?;
+    let Some(collider) = ctx.db.static_collider().id().find(collider_id) else {
+        return Err("Collider not found".to_string());
+    };
+    ctx.db.static_collider().id().delete(collider_id);
+    rebuild_nav_grid(ctx, collider.instance_id);
+    Ok(())
+}
+
+pub fn load_platforms(ctx: &ReducerContext) -> Vec<OneWayPlatform> {
+    ctx.db.one_way_platform().iter().collect()
+}
+
+// Height of the surface the player should stand on after moving from `prev_y` to `new_pos`.
+// A platform or collider top only catches a player who started the tick at or above it and
+// is within its footprint; `drop_through` (crouch + jump) ignores platforms, not colliders.
+pub fn floor_height(platforms: &[OneWayPlatform], colliders: &[StaticCollider], instance_id: u64, prev_y: f32, new_pos: &Vector3, drop_through: bool) -> f32 {
+    let platform_floor = platforms
+        .iter()
+        .filter(|p| {
+            !drop_through
+                && p.instance_id == instance_id
+                && prev_y >= p.position.y
+                && new_pos.y <= p.position.y
+                && (new_pos.x - p.position.x).abs() <= p.half_size_x
+                && (new_pos.z - p.position.z).abs() <= p.half_size_z
+        })
+        .map(|p| p.position.y);
+    let collider_floor = colliders
+        .iter()
+        .filter(|c| {
+            c.instance_id == instance_id
+                && prev_y >= c.top()
+                && new_pos.y <= c.top()
+                && (new_pos.x - c.center.x).abs() <= c.half_extents.x
+                && (new_pos.z - c.center.z).abs() <= c.half_extents.z
+        })
+        .map(|c| c.top());
+    platform_floor.chain(collider_floor).fold(0.0, f32::max)
+}
+
+pub fn drops_through(input: &InputState) -> bool {
+    input.crouch && input.jump
+}
+
+pub fn load_jump_pads(ctx: &ReducerContext) -> Vec<JumpPad> {
+    ctx.db.jump_pad().iter().collect()
+}
+
+// Launch the player if they are descending onto a pad. `pads` is loaded once per tick.
+pub fn apply_jump_pads(pads: &[JumpPad], player: &mut PlayerData, new_pos: &mut Vector3) {
+    if player.vertical_velocity > 0.0 {
+        return; // only trigger on the way down so one touch is one launch
+    }
+    let on_pad = pads.iter().filter(|pad| pad.instance_id == player.instance_id).find(|pad| {
+        let dx = new_pos.x - pad.position.x;
+        let dz = new_pos.z - pad.position.z;
+        (dx * dx + dz * dz).sqrt() <= pad.radius
+            && new_pos.y <= pad.position.y + JUMP_PAD_TRIGGER_HEIGHT
+    });
+    if let Some(pad) = on_pad {
+        new_pos.y = new_pos.y.max(pad.position.y);
+        player.vertical_velocity = pad.launch_velocity;
+        player.fall_distance = 0.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::{CROUCH_HEIGHT, FALL_DAMAGE_MIN_HEIGHT, GRAVITY, PLAYER_HEIGHT};
+    use crate::player_logic::{calculate_new_position, fall_damage};
+    use crate::test_player;
+
+    // game_tick's vertical pass over flat ground at y = 0: gravity, fall tracking, pads, landing
+    fn vertical_tick(pads: &[JumpPad], player: &mut PlayerData, delta_time: f32) -> i32 {
+        let mut new_pos = player.position;
+        player.vertical_velocity += GRAVITY * delta_time;
+        new_pos.y += player.vertical_velocity * delta_time;
+        if player.vertical_velocity < 0.0 {
+            player.fall_distance += -player.vertical_velocity * delta_time;
+        } else {
+            player.fall_distance = 0.0;
+        }
+        apply_jump_pads(pads, player, &mut new_pos);
+        let mut landing_damage = 0;
+        if new_pos.y <= 0.0 {
+            new_pos.y = 0.0;
+            if player.vertical_velocity <= 0.0 {
+                landing_damage = fall_damage(player.fall_distance);
+                player.vertical_velocity = 0.0;
+                player.fall_distance = 0.0;
+            }
+        }
+        player.position = new_pos;
+        landing_damage
+    }
+
+    fn input(crouch: bool, jump: bool) -> InputState {
+        InputState { forward: false, backward: false, left: false, right: false, sprint: false, jump, attack: false, cast_spell: false, crouch, sequence: 0 }
+    }
+
+    #[test]
+    fn one_way_platforms_catch_a_fall_unless_crouch_and_jump_are_held() {
+        let platform = OneWayPlatform { id: 1, position: Vector3 { x: 0.0, y: 3.0, z: 0.0 }, half_size_x: 2.0, half_size_z: 2.0, instance_id: LOBBY_INSTANCE_ID };
+        let platforms = std::slice::from_ref(&platform);
+        let landing = Vector3 { x: 1.0, y: 2.9, z: -1.0 };
+        let floor = |held: InputState| floor_height(platforms, &[], LOBBY_INSTANCE_ID, 3.0, &landing, drops_through(&held));
+
+        assert_eq!(floor(input(false, false)), 3.0);
+        assert_eq!(floor(input(true, false)), 3.0, "crouching alone stays on the platform");
+        assert_eq!(floor(input(false, true)), 3.0, "jumping alone stays on the platform");
+        assert_eq!(floor(input(true, true)), 0.0);
+        // Rising through from below never lands on it
+        assert_eq!(floor_height(platforms, &[], LOBBY_INSTANCE_ID, 2.0, &Vector3 { y: 2.5, ..landing }, false), 0.0);
+    }
+
+    #[test]
+    fn repeated_pad_landings_reach_the_same_apex_without_fall_damage() {
+        let pad = JumpPad { id: 1, position: Vector3::default(), radius: 1.5, launch_velocity: 14.0, instance_id: LOBBY_INSTANCE_ID };
+        let mut player = test_player(1);
+        player.position.y = 20.0; // dropped onto the pad from well above the fall damage height
+
+        let mut apexes = Vec::new();
+        let mut rising = false;
+        for _ in 0..2000 {
+            let previous_y = player.position.y;
+            assert_eq!(vertical_tick(std::slice::from_ref(&pad), &mut player, 0.05), 0);
+            if player.vertical_velocity > 0.0 {
+                rising = true;
+            } else if rising {
+                rising = false;
+                apexes.push(previous_y.max(player.position.y));
+            }
+        }
+
+        let expected = pad.launch_velocity * pad.launch_velocity / (-2.0 * GRAVITY);
+        assert!(apexes.len() > 5, "the pad keeps launching");
+        assert!(expected > FALL_DAMAGE_MIN_HEIGHT, "each fall is high enough to hurt without the pad");
+        // Tick timing moves each apex by a fraction of a step, but the bounces never build up
+        let (lowest, highest) = apexes.iter().fold((f32::MAX, f32::MIN), |(lo, hi), a| (lo.min(*a), hi.max(*a)));
+        assert!(highest - lowest < 0.5, "apex drifted: {apexes:?}");
+        assert!((apexes[apexes.len() - 1] - expected).abs() < 0.5);
+    }
+
+    fn pillar() -> StaticCollider {
+        StaticCollider { id: 7, center: Vector3 { x: 0.0, y: 2.0, z: 0.0 }, half_extents: Vector3 { x: 1.0, y: 2.0, z: 1.0 }, instance_id: LOBBY_INSTANCE_ID }
+    }
+
+    #[test]
+    fn players_are_pushed_out_of_walls_but_can_stand_on_top() {
+        let colliders = [pillar()];
+        let mut walking_in = Vector3 { x: 1.2, y: 0.0, z: 0.3 };
+        resolve_player_colliders(&colliders, LOBBY_INSTANCE_ID, &mut walking_in, 0.5, PLAYER_HEIGHT);
+        assert!((walking_in.x - 1.5).abs() < 1e-5, "pushed out along the shallow axis");
+        assert_eq!(walking_in.z, 0.3);
+
+        let mut on_top = Vector3 { x: 0.5, y: 4.0, z: 0.5 };
+        resolve_player_colliders(&colliders, LOBBY_INSTANCE_ID, &mut on_top, 0.5, PLAYER_HEIGHT);
+        assert_eq!(on_top, Vector3 { x: 0.5, y: 4.0, z: 0.5 });
+        assert_eq!(floor_height(&[], &colliders, LOBBY_INSTANCE_ID, 4.1, &Vector3 { y: 3.9, ..on_top }, false), 4.0);
+
+        let mut elsewhere = Vector3 { x: 1.2, y: 0.0, z: 0.3 };
+        resolve_player_colliders(&colliders, LOBBY_INSTANCE_ID + 1, &mut elsewhere, 0.5, PLAYER_HEIGHT);
+        assert_eq!(elsewhere.x, 1.2, "walls only block players of their own instance");
+    }
+
+    #[test]
+    fn crouched_players_only_stand_up_where_there_is_headroom() {
+        // A slab whose underside sits between crouching and standing height
+        let underside = (CROUCH_HEIGHT + PLAYER_HEIGHT) / 2.0;
+        let slab = StaticCollider { id: 9, center: Vector3 { x: 0.0, y: underside + 0.5, z: 0.0 }, half_extents: Vector3 { x: 2.0, y: 0.5, z: 2.0 }, instance_id: LOBBY_INSTANCE_ID };
+        let colliders = [slab];
+        assert!(!has_headroom(&colliders, LOBBY_INSTANCE_ID, &Vector3::default(), 0.5, CROUCH_HEIGHT, PLAYER_HEIGHT));
+        assert!(has_headroom(&colliders, LOBBY_INSTANCE_ID, &Vector3 { x: 4.0, y: 0.0, z: 0.0 }, 0.5, CROUCH_HEIGHT, PLAYER_HEIGHT), "clear of the slab");
+
+        // Crouched under it, the shorter body isn't pushed out
+        let mut crouched = Vector3::default();
+        resolve_player_colliders(&colliders, LOBBY_INSTANCE_ID, &mut crouched, 0.5, CROUCH_HEIGHT);
+        assert_eq!(crouched, Vector3::default());
+    }
+
+    #[test]
+    fn swimmers_float_near_the_surface_unless_diving_or_rising() {
+        let pool = WaterVolume { id: 1, center: Vector3 { x: 0.0, y: 1.0, z: 0.0 }, half_extents: Vector3 { x: 5.0, y: 2.0, z: 5.0 }, instance_id: LOBBY_INSTANCE_ID };
+        let pools = [pool.clone()];
+        assert!(water_at(&pools, LOBBY_INSTANCE_ID, &Vector3 { x: 1.0, y: 0.5, z: 1.0 }).is_some());
+        assert!(water_at(&pools, LOBBY_INSTANCE_ID, &Vector3 { x: 1.0, y: 3.5, z: 1.0 }).is_none(), "above the surface");
+        assert!(water_at(&pools, LOBBY_INSTANCE_ID + 1, &Vector3 { x: 1.0, y: 0.5, z: 1.0 }).is_none(), "another instance's pool");
+
+        let idle = InputState { forward: false, backward: false, left: false, right: false, sprint: false, jump: false, attack: false, cast_spell: false, crouch: false, sequence: 0 };
+        let floating = pool.surface() - SWIM_FLOAT_DEPTH;
+        assert!(swim_vertical_velocity(&pool, floating - 1.0, &idle) > 0.0, "sunk: buoyancy lifts");
+        assert!(swim_vertical_velocity(&pool, floating + 0.5, &idle) < 0.0, "too high: settles back");
+        assert!(swim_vertical_velocity(&pool, -10.0, &idle) <= SWIM_BUOYANCY);
+        assert_eq!(swim_vertical_velocity(&pool, floating, &InputState { jump: true, ..idle }), SWIM_VERTICAL_SPEED);
+        assert_eq!(swim_vertical_velocity(&pool, floating, &InputState { crouch: true, ..idle }), -SWIM_VERTICAL_SPEED);
+    }
+
+    #[test]
+    fn elevators_carry_the_players_standing_on_them() {
+        let floor = OneWayPlatform { id: 1, position: Vector3 { x: 0.0, y: 0.2, z: 0.0 }, half_size_x: 2.5, half_size_z: 2.5, instance_id: LOBBY_INSTANCE_ID };
+        let lift = PlatformMove { start: floor, delta: Vector3 { x: 0.0, y: 0.1, z: 0.0 } };
+        let moves = [lift];
+        let mut rider = test_player(1);
+        rider.position = Vector3 { x: 1.0, y: 0.2, z: -1.0 };
+        rider.grounded = true;
+        rider.input.forward = true;
+
+        let carry = carry_delta(&moves, &rider);
+        assert_eq!(carry, Some(Vector3 { x: 0.0, y: 0.1, z: 0.0 }));
+        let new_pos = calculate_new_position(&mut rider, carry, 10.0, 1.0, 0.1);
+        assert!((rider.position.y - 0.3).abs() < 1e-5, "lifted with the elevator");
+        assert!((new_pos.y - 0.3).abs() < 1e-5 && (new_pos.z - -2.0).abs() < 1e-5, "and still walks on it");
+        assert_eq!(floor_height(&[moves[0].end_surface()], &[], LOBBY_INSTANCE_ID, rider.position.y, &new_pos, false), new_pos.y, "stands on its new top");
+
+        let jumping = PlayerData { grounded: false, ..rider.clone() };
+        assert_eq!(carry_delta(&moves, &jumping), None, "airborne players aren't carried");
+        let beside = PlayerData { position: Vector3 { x: 4.0, y: 0.2, z: 0.0 }, ..rider };
+        assert_eq!(carry_delta(&moves, &beside), None);
+    }
+
+    #[test]
+    fn portals_send_players_out_of_the_linked_portal_keeping_their_momentum() {
+        let a = Portal { id: 1, position: Vector3 { x: -30.0, y: 0.0, z: 0.0 }, radius: 1.2, yaw: PI / 2.0, linked_portal_id: 2, instance_id: LOBBY_INSTANCE_ID };
+        let b = Portal { id: 2, position: Vector3 { x: 30.0, y: 0.0, z: 0.0 }, radius: 1.2, yaw: -PI / 2.0, linked_portal_id: 1, instance_id: LOBBY_INSTANCE_ID };
+        let portals = [a, b];
+        let from = Vector3 { x: -32.0, y: 0.0, z: 0.0 };
+        let to = Vector3 { x: -29.5, y: 0.0, z: 0.0 };
+        assert_eq!(portal_crossed(&portals, LOBBY_INSTANCE_ID, from, to).map(|p| p.id), Some(1), "swept through in one tick");
+        assert!(portal_crossed(&portals, LOBBY_INSTANCE_ID, from, Vector3 { x: -32.0, y: 0.0, z: 5.0 }).is_none());
+
+        let mut player = test_player(1);
+        player.position = to;
+        player.knockback_velocity = Vector3 { x: 5.0, y: 0.0, z: 0.0 };
+        assert_eq!(teleport_through(&portals, &portals[0], &mut player), Some(2));
+        let exit_x = 30.0 + 1.2 + player.hit_radius + PORTAL_EXIT_OFFSET;
+        assert!((player.position.x - exit_x).abs() < 1e-4 && player.position.z.abs() < 1e-4, "in front of the exit");
+        assert!((player.knockback_velocity.x - 5.0).abs() < 1e-4 && player.knockback_velocity.z.abs() < 1e-4, "still moving away from it");
+
+        let orphan = Portal { linked_portal_id: 9, ..portals[0].clone() };
+        assert_eq!(teleport_through(&portals, &orphan, &mut player), None);
+    }
+
+    #[test]
+    fn projectiles_stop_at_the_first_collider_in_their_path() {
+        let far = StaticCollider { id: 8, center: Vector3 { x: 6.0, y: 2.0, z: 0.0 }, ..pillar() };
+        let colliders = [far, pillar()];
+        let (id, t) = first_collider_hit(&colliders, LOBBY_INSTANCE_ID, Vector3 { x: -5.0, y: 1.0, z: 0.0 }, Vector3 { x: 10.0, y: 1.0, z: 0.0 }).unwrap();
+        assert_eq!(id, 7);
+        assert!(t > 0.0 && t < 0.5);
+        assert!(first_collider_hit(&colliders, LOBBY_INSTANCE_ID, Vector3 { x: -5.0, y: 6.0, z: 0.0 }, Vector3 { x: 10.0, y: 6.0, z: 0.0 }).is_none(), "over the top");
+    }
+}
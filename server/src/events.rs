@@ -0,0 +1,579 @@
+/*
+ * Vibe Coding Starter Pack: 3D Multiplayer - events.rs
+ *
+ * One-shot event tables that clients subscribe to for feedback (VFX, sounds, UI).
+ *
+ * Each event is a plain row insert with a `created_at` timestamp. SpacetimeDB has
+ * no transient tables, so game_tick calls prune_events every tick to delete rows
+ * older than EVENT_TTL_SECS; clients should react to inserts, not read history.
+ *
+ * When adding a new event table:
+ *    - Mark it `public` and give it an auto_inc `id` plus `created_at`
+ *    - Add its pruning loop to prune_events
+ *
+ * ProjectileSpawnLog is the exception: it records every projectile spawn with its full
+ * launch parameters and is kept for PROJECTILE_SPAWN_LOG_SECS (capped at
+ * PROJECTILE_SPAWN_LOG_LIMIT rows), so spectators and replays can reconstruct projectiles
+ * that were created and destroyed between two subscription updates.
+ *
+ * AnticheatEvent rows (rejected impossible moves) are likewise kept for
+ * ANTICHEAT_EVENT_RETENTION_SECS so admins can review them.
+ */
+
+use spacetimedb::{Identity, ReducerContext, Table, Timestamp};
+use std::time::Duration;
+
+use crate::common::{ProjectileKind, Vector3, ANTICHEAT_EVENT_RETENTION_SECS, EVENT_TTL_SECS, PROJECTILE_SPAWN_LOG_LIMIT, PROJECTILE_SPAWN_LOG_SECS};
+
+// What a projectile ran into
+#[derive(spacetimedb::SpacetimeType, Clone, Copy, Debug, PartialEq)]
+pub enum ImpactTarget {
+    Player(Identity),
+    Pickup(u64),
+    Ground,
+    Geometry(u64), // environment::StaticCollider id
+    Npc(u64),
+}
+
+#[spacetimedb::table(name = projectile_impact, public)]
+#[derive(Clone)]
+pub struct ProjectileImpactEvent {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub projectile_id: u64,
+    pub owner_identity: Identity,
+    pub position: Vector3,
+    pub target: ImpactTarget,
+    pub created_at: Timestamp,
+}
+
+pub fn emit_projectile_impact(
+    ctx: &ReducerContext,
+    projectile_id: u64,
+    owner_identity: Identity,
+    position: Vector3,
+    target: ImpactTarget,
+) {
+    ctx.db.projectile_impact().insert(ProjectileImpactEvent {
+        id: 0, // auto_inc
+        projectile_id,
+        owner_identity,
+        position,
+        target,
+        created_at: ctx.timestamp,
+    });
+}
+
+#[spacetimedb::table(name = explosion_event, public)]
+#[derive(Clone)]
+pub struct ExplosionEvent {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub projectile_id: u64,
+    pub owner_identity: Identity,
+    pub position: Vector3,
+    pub radius: f32,
+    pub victims: u32, // players damaged by the blast
+    pub created_at: Timestamp,
+}
+
+pub fn emit_explosion(ctx: &ReducerContext, projectile_id: u64, owner_identity: Identity, position: Vector3, radius: f32, victims: u32) {
+    ctx.db.explosion_event().insert(ExplosionEvent {
+        id: 0, // auto_inc
+        projectile_id,
+        owner_identity,
+        position,
+        radius,
+        victims,
+        created_at: ctx.timestamp,
+    });
+}
+
+#[spacetimedb::table(name = projectile_spawn_log, public)]
+#[derive(Clone)]
+pub struct ProjectileSpawnLog {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub projectile_id: u64,
+    pub owner_identity: Identity,
+    pub position: Vector3,
+    pub velocity: Vector3,
+    pub kind: ProjectileKind,
+    pub created_at: Timestamp,
+}
+
+pub fn log_projectile_spawn(
+    ctx: &ReducerContext,
+    projectile_id: u64,
+    owner_identity: Identity,
+    position: Vector3,
+    velocity: Vector3,
+    kind: ProjectileKind,
+) {
+    ctx.db.projectile_spawn_log().insert(ProjectileSpawnLog {
+        id: 0, // auto_inc
+        projectile_id,
+        owner_identity,
+        position,
+        velocity,
+        kind,
+        created_at: ctx.timestamp,
+    });
+}
+
+#[spacetimedb::table(name = hitscan_trace, public)]
+#[derive(Clone)]
+pub struct HitscanTraceEvent {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub shooter_identity: Identity,
+    pub start: Vector3,
+    pub end: Vector3, // first hit, or the end of HITSCAN_RANGE
+    pub target: Option<ImpactTarget>, // Player or Geometry
+    pub damage: i32,
+    pub created_at: Timestamp,
+}
+
+pub fn emit_hitscan_trace(ctx: &ReducerContext, shooter_identity: Identity, start: Vector3, end: Vector3, target: Option<ImpactTarget>, damage: i32) {
+    ctx.db.hitscan_trace().insert(HitscanTraceEvent {
+        id: 0, // auto_inc
+        shooter_identity,
+        start,
+        end,
+        target,
+        damage,
+        created_at: ctx.timestamp,
+    });
+}
+
+#[spacetimedb::table(name = shield_break_event, public)]
+#[derive(Clone)]
+pub struct ShieldBreakEvent {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub identity: Identity,
+    pub expired: bool, // true: ran out of time, false: depleted by damage
+    pub position: Vector3,
+    pub created_at: Timestamp,
+}
+
+pub fn emit_shield_break(ctx: &ReducerContext, identity: Identity, expired: bool, position: Vector3) {
+    ctx.db.shield_break_event().insert(ShieldBreakEvent {
+        id: 0, // auto_inc
+        identity,
+        expired,
+        position,
+        created_at: ctx.timestamp,
+    });
+}
+
+#[spacetimedb::table(name = teleport_event, public)]
+#[derive(Clone)]
+pub struct TeleportEvent {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub identity: Identity,
+    pub entry_portal_id: u64,
+    pub exit_portal_id: u64,
+    pub from: Vector3,
+    pub to: Vector3,
+    pub created_at: Timestamp,
+}
+
+pub fn emit_teleport(ctx: &ReducerContext, identity: Identity, entry_portal_id: u64, exit_portal_id: u64, from: Vector3, to: Vector3) {
+    ctx.db.teleport_event().insert(TeleportEvent {
+        id: 0, // auto_inc
+        identity,
+        entry_portal_id,
+        exit_portal_id,
+        from,
+        to,
+        created_at: ctx.timestamp,
+    });
+}
+
+#[spacetimedb::table(name = flag_taken_event, public)]
+#[derive(Clone)]
+pub struct FlagTakenEvent {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub flag_team: u8,
+    pub carrier: Identity,
+    pub position: Vector3,
+    pub created_at: Timestamp,
+}
+
+pub fn emit_flag_taken(ctx: &ReducerContext, flag_team: u8, carrier: Identity, position: Vector3) {
+    ctx.db.flag_taken_event().insert(FlagTakenEvent {
+        id: 0, // auto_inc
+        flag_team,
+        carrier,
+        position,
+        created_at: ctx.timestamp,
+    });
+}
+
+#[spacetimedb::table(name = flag_captured_event, public)]
+#[derive(Clone)]
+pub struct FlagCapturedEvent {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub scoring_team: u8,
+    pub flag_team: u8,
+    pub carrier: Identity,
+    pub created_at: Timestamp,
+}
+
+pub fn emit_flag_captured(ctx: &ReducerContext, scoring_team: u8, flag_team: u8, carrier: Identity) {
+    ctx.db.flag_captured_event().insert(FlagCapturedEvent {
+        id: 0, // auto_inc
+        scoring_team,
+        flag_team,
+        carrier,
+        created_at: ctx.timestamp,
+    });
+}
+
+// A player went AFK (`afk`) or came back
+#[spacetimedb::table(name = afk_status_event, public)]
+#[derive(Clone)]
+pub struct AfkStatusEvent {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub identity: Identity,
+    pub afk: bool,
+    pub created_at: Timestamp,
+}
+
+pub fn emit_afk_status(ctx: &ReducerContext, identity: Identity, afk: bool) {
+    ctx.db.afk_status_event().insert(AfkStatusEvent {
+        id: 0, // auto_inc
+        identity,
+        afk,
+        created_at: ctx.timestamp,
+    });
+}
+
+// Toast notification for a newly unlocked achievement (achievements.rs)
+#[spacetimedb::table(name = achievement_unlocked_event, public)]
+#[derive(Clone)]
+pub struct AchievementUnlockedEvent {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub identity: Identity,
+    pub achievement_id: String,
+    pub created_at: Timestamp,
+}
+
+pub fn emit_achievement_unlocked(ctx: &ReducerContext, identity: Identity, achievement_id: &str) {
+    ctx.db.achievement_unlocked_event().insert(AchievementUnlockedEvent {
+        id: 0, // auto_inc
+        identity,
+        achievement_id: achievement_id.to_string(),
+        created_at: ctx.timestamp,
+    });
+}
+
+#[derive(spacetimedb::SpacetimeType, Clone, Copy, Debug, PartialEq)]
+pub enum BossEventKind {
+    Engaged,
+    PhaseChanged,
+    SlamWindup, // telegraph: the slam lands BOSS_SLAM_WINDUP_SECS later
+    Slam,
+    SummonAdds,
+    Enraged,
+    Defeated,
+    Reset, // the party wiped or fled; the boss is back at full health
+}
+
+#[spacetimedb::table(name = boss_event, public)]
+#[derive(Clone)]
+pub struct BossEvent {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub npc_id: u64,
+    pub instance_id: u64,
+    pub kind: BossEventKind,
+    pub phase: u8,
+    pub position: Vector3,
+    pub created_at: Timestamp,
+}
+
+pub fn emit_boss_event(ctx: &ReducerContext, kind: BossEventKind, npc_id: u64, instance_id: u64, phase: u8, position: Vector3) {
+    ctx.db.boss_event().insert(BossEvent {
+        id: 0, // auto_inc
+        npc_id,
+        instance_id,
+        kind,
+        phase,
+        position,
+        created_at: ctx.timestamp,
+    });
+}
+
+#[spacetimedb::table(name = anticheat_event, public)]
+#[derive(Clone)]
+pub struct AnticheatEvent {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub identity: Identity,
+    pub from: Vector3, // last accepted position, where the player was rubber-banded to
+    pub to: Vector3,   // rejected position
+    pub horizontal_excess: f32, // metres beyond what was allowed
+    pub vertical_excess: f32,
+    pub created_at: Timestamp,
+}
+
+pub fn emit_anticheat_event(ctx: &ReducerContext, identity: Identity, from: Vector3, to: Vector3, horizontal_excess: f32, vertical_excess: f32) {
+    ctx.db.anticheat_event().insert(AnticheatEvent {
+        id: 0, // auto_inc
+        identity,
+        from,
+        to,
+        horizontal_excess,
+        vertical_excess,
+        created_at: ctx.timestamp,
+    });
+}
+
+// Kind of combat action a CombatEvent describes
+#[derive(spacetimedb::SpacetimeType, Clone, Copy, Debug, PartialEq)]
+pub enum CombatAction {
+    MeleeHit,  // one row per player struck, with the damage that reached their health
+    MeleeMiss, // swing that landed on nobody
+}
+
+#[spacetimedb::table(name = combat_event, public)]
+#[derive(Clone)]
+pub struct CombatEvent {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub action: CombatAction,
+    pub attacker_identity: Identity,
+    pub target_identity: Option<Identity>,
+    pub damage: i32,
+    pub position: Vector3, // attacker position at the time of the swing
+    pub created_at: Timestamp,
+}
+
+pub fn emit_combat_event(
+    ctx: &ReducerContext,
+    action: CombatAction,
+    attacker_identity: Identity,
+    target_identity: Option<Identity>,
+    damage: i32,
+    position: Vector3,
+) {
+    ctx.db.combat_event().insert(CombatEvent {
+        id: 0, // auto_inc
+        action,
+        attacker_identity,
+        target_identity,
+        damage,
+        position,
+        created_at: ctx.timestamp,
+    });
+}
+
+#[spacetimedb::table(name = damage_event, public)]
+#[derive(Clone)]
+pub struct DamageEvent {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub target_identity: Identity,
+    pub attacker_identity: Option<Identity>, // None for environmental damage (falls, hazards without an owner)
+    pub amount: i32,
+    pub remaining_health: i32,
+    pub position: Vector3,
+    pub killed: bool,
+    pub created_at: Timestamp,
+}
+
+pub fn emit_damage(
+    ctx: &ReducerContext,
+    target_identity: Identity,
+    attacker_identity: Option<Identity>,
+    amount: i32,
+    remaining_health: i32,
+    position: Vector3,
+    killed: bool,
+) {
+    ctx.db.damage_event().insert(DamageEvent {
+        id: 0, // auto_inc
+        target_identity,
+        attacker_identity,
+        amount,
+        remaining_health,
+        position,
+        killed,
+        created_at: ctx.timestamp,
+    });
+}
+
+#[spacetimedb::table(name = heal_event, public)]
+#[derive(Clone)]
+pub struct HealEvent {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub healer_identity: Identity,
+    pub target_identity: Identity,
+    pub amount: i32, // health actually restored (0 at full health)
+    pub remaining_health: i32,
+    pub position: Vector3,
+    pub created_at: Timestamp,
+}
+
+pub fn emit_heal(ctx: &ReducerContext, healer_identity: Identity, target_identity: Identity, amount: i32, remaining_health: i32, position: Vector3) {
+    ctx.db.heal_event().insert(HealEvent {
+        id: 0, // auto_inc
+        healer_identity,
+        target_identity,
+        amount,
+        remaining_health,
+        position,
+        created_at: ctx.timestamp,
+    });
+}
+
+fn is_older_than(ctx: &ReducerContext, created_at: Timestamp, ttl_secs: u64) -> bool {
+    older_than(ctx.timestamp, created_at, ttl_secs)
+}
+
+fn older_than(now: Timestamp, created_at: Timestamp, ttl_secs: u64) -> bool {
+    now.duration_since(created_at).is_some_and(|age| age > Duration::from_secs(ttl_secs))
+}
+
+// (id, created_at) of every spawn log row -> ids to delete. Entries only age out; whether
+// their projectile still exists doesn't matter
+fn stale_spawn_log_ids(entries: Vec<(u64, Timestamp)>, now: Timestamp) -> Vec<u64> {
+    let (expired, mut kept): (Vec<_>, Vec<_>) = entries.into_iter().partition(|(_, created_at)| older_than(now, *created_at, PROJECTILE_SPAWN_LOG_SECS));
+    let mut stale: Vec<u64> = expired.into_iter().map(|(id, _)| id).collect();
+    if kept.len() > PROJECTILE_SPAWN_LOG_LIMIT {
+        kept.sort_by_key(|(id, _)| *id);
+        stale.extend(kept[..kept.len() - PROJECTILE_SPAWN_LOG_LIMIT].iter().map(|(id, _)| *id));
+    }
+    stale
+}
+
+fn is_expired(ctx: &ReducerContext, created_at: Timestamp) -> bool {
+    is_older_than(ctx, created_at, EVENT_TTL_SECS)
+}
+
+// Delete event rows older than EVENT_TTL_SECS and trim the spawn log (called from game_tick)
+pub fn prune_events(ctx: &ReducerContext) {
+    for event in ctx.db.projectile_impact().iter() {
+        if is_expired(ctx, event.created_at) {
+            ctx.db.projectile_impact().id().delete(event.id);
+        }
+    }
+    for event in ctx.db.explosion_event().iter() {
+        if is_expired(ctx, event.created_at) {
+            ctx.db.explosion_event().id().delete(event.id);
+        }
+    }
+    for event in ctx.db.hitscan_trace().iter() {
+        if is_expired(ctx, event.created_at) {
+            ctx.db.hitscan_trace().id().delete(event.id);
+        }
+    }
+    for event in ctx.db.shield_break_event().iter() {
+        if is_expired(ctx, event.created_at) {
+            ctx.db.shield_break_event().id().delete(event.id);
+        }
+    }
+
+    for event in ctx.db.combat_event().iter() {
+        if is_expired(ctx, event.created_at) {
+            ctx.db.combat_event().id().delete(event.id);
+        }
+    }
+    for event in ctx.db.damage_event().iter() {
+        if is_expired(ctx, event.created_at) {
+            ctx.db.damage_event().id().delete(event.id);
+        }
+    }
+    for event in ctx.db.heal_event().iter() {
+        if is_expired(ctx, event.created_at) {
+            ctx.db.heal_event().id().delete(event.id);
+        }
+    }
+    for event in ctx.db.teleport_event().iter() {
+        if is_expired(ctx, event.created_at) {
+            ctx.db.teleport_event().id().delete(event.id);
+        }
+    }
+    for event in ctx.db.flag_taken_event().iter() {
+        if is_expired(ctx, event.created_at) {
+            ctx.db.flag_taken_event().id().delete(event.id);
+        }
+    }
+    for event in ctx.db.flag_captured_event().iter() {
+        if is_expired(ctx, event.created_at) {
+            ctx.db.flag_captured_event().id().delete(event.id);
+        }
+    }
+    for event in ctx.db.afk_status_event().iter() {
+        if is_expired(ctx, event.created_at) {
+            ctx.db.afk_status_event().id().delete(event.id);
+        }
+    }
+    for event in ctx.db.achievement_unlocked_event().iter() {
+        if is_expired(ctx, event.created_at) {
+            ctx.db.achievement_unlocked_event().id().delete(event.id);
+        }
+    }
+    for event in ctx.db.boss_event().iter() {
+        if is_expired(ctx, event.created_at) {
+            ctx.db.boss_event().id().delete(event.id);
+        }
+    }
+
+    let log_entries = ctx.db.projectile_spawn_log().iter().map(|e| (e.id, e.created_at)).collect();
+    for id in stale_spawn_log_ids(log_entries, ctx.timestamp) {
+        ctx.db.projectile_spawn_log().id().delete(id);
+    }
+
+    for event in ctx.db.anticheat_event().iter() {
+        if is_older_than(ctx, event.created_at, ANTICHEAT_EVENT_RETENTION_SECS) {
+            ctx.db.anticheat_event().id().delete(event.id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_projectile_destroyed_in_its_spawn_tick_stays_in_the_log() {
+        // Spawned and removed in the same tick: only its log row is left
+        let spawned_at = Timestamp::UNIX_EPOCH;
+        let next_tick = spawned_at + Duration::from_millis(50);
+        assert!(stale_spawn_log_ids(vec![(1, spawned_at)], next_tick).is_empty());
+
+        let after_retention = spawned_at + Duration::from_secs(PROJECTILE_SPAWN_LOG_SECS + 1);
+        assert_eq!(stale_spawn_log_ids(vec![(1, spawned_at)], after_retention), vec![1]);
+    }
+
+    #[test]
+    fn the_spawn_log_keeps_only_the_newest_rows() {
+        let now = Timestamp::UNIX_EPOCH;
+        let entries = (1..=PROJECTILE_SPAWN_LOG_LIMIT as u64 + 2).map(|id| (id, now)).collect();
+        assert_eq!(stale_spawn_log_ids(entries, now), vec![1, 2]);
+    }
+}
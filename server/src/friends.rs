@@ -0,0 +1,225 @@
+/*
+ * Vibe Coding Starter Pack: 3D Multiplayer - friends.rs
+ *
+ * Friend requests and the friend list.
+ *
+ * Key components:
+ *    - FriendRequest: Public table of pending requests (sender -> recipient)
+ *    - Friendship: Public table with one row per pair, stored with the smaller identity as
+ *      `a` so each friendship exists once
+ *    - FriendStatus: Public table with one row per friend per player (owner -> friend), for
+ *      a `SELECT * FROM friend_status WHERE owner = '<identity>'` subscription. Holds the
+ *      friend's name, whether they are online and, for offline friends,
+ *      logged_out_player.last_seen. refresh_friend_status rebuilds a player's friends' rows
+ *      when they register (spawn_player), log out or rename
+ *    - send_friend_request / accept_friend_request / decline_friend_request / remove_friend:
+ *      Reducers. Sending a request to someone who already asked you accepts theirs
+ */
+
+use spacetimedb::{Identity, ReducerContext, Table, Timestamp};
+
+use crate::achievements::on_friend_added;
+use crate::common::MAX_FRIENDS;
+use crate::{logged_out_player, player};
+
+#[spacetimedb::table(name = friend_request, public)]
+#[derive(Clone)]
+pub struct FriendRequest {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    #[index(btree)]
+    pub sender: Identity,
+    #[index(btree)]
+    pub recipient: Identity,
+    pub created_at: Timestamp,
+}
+
+#[spacetimedb::table(name = friendship, public)]
+#[derive(Clone)]
+pub struct Friendship {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    #[index(btree)]
+    pub a: Identity,
+    #[index(btree)]
+    pub b: Identity,
+    pub since: Timestamp,
+}
+
+#[spacetimedb::table(name = friend_status, public)]
+#[derive(Clone)]
+pub struct FriendStatus {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    #[index(btree)]
+    pub owner: Identity,
+    #[index(btree)]
+    pub friend: Identity,
+    pub username: String,
+    pub online: bool,
+    pub last_seen: Option<Timestamp>, // None while online
+}
+
+fn ordered(x: Identity, y: Identity) -> (Identity, Identity) {
+    if x < y { (x, y) } else { (y, x) }
+}
+
+fn find_friendship(ctx: &ReducerContext, x: Identity, y: Identity) -> Option<Friendship> {
+    let (a, b) = ordered(x, y);
+    ctx.db.friendship().a().filter(a).find(|f| f.b == b)
+}
+
+fn friends_of(ctx: &ReducerContext, identity: Identity) -> Vec<Identity> {
+    let mut friends: Vec<Identity> = ctx.db.friendship().a().filter(identity).map(|f| f.b).collect();
+    friends.extend(ctx.db.friendship().b().filter(identity).map(|f| f.a));
+    friends
+}
+
+pub fn friend_count(ctx: &ReducerContext, identity: Identity) -> usize {
+    friends_of(ctx, identity).len()
+}
+
+// Name, online flag and last_seen for `friend` as their friends should see it
+fn status_of(ctx: &ReducerContext, friend: Identity) -> (String, bool, Option<Timestamp>) {
+    if let Some(p) = ctx.db.player().identity().find(friend) {
+        return (p.username, true, None);
+    }
+    match ctx.db.logged_out_player().identity().find(friend) {
+        Some(saved) => (saved.state.username, false, Some(saved.last_seen)),
+        None => ("Unknown".to_string(), false, None),
+    }
+}
+
+fn upsert_status(ctx: &ReducerContext, owner: Identity, friend: Identity) {
+    let (username, online, last_seen) = status_of(ctx, friend);
+    match ctx.db.friend_status().owner().filter(owner).find(|s| s.friend == friend) {
+        Some(mut row) => {
+            row.username = username;
+            row.online = online;
+            row.last_seen = last_seen;
+            ctx.db.friend_status().id().update(row);
+        }
+        None => {
+            ctx.db.friend_status().insert(FriendStatus { id: 0, owner, friend, username, online, last_seen });
+        }
+    }
+}
+
+fn delete_status(ctx: &ReducerContext, owner: Identity, friend: Identity) {
+    if let Some(row) = ctx.db.friend_status().owner().filter(owner).find(|s| s.friend == friend) {
+        ctx.db.friend_status().id().delete(row.id);
+    }
+}
+
+// Drops every friendship, status row and pending request involving `identity`; returns the row count
+pub fn purge_friend_data(ctx: &ReducerContext, identity: Identity) -> u32 {
+    let mut removed = 0;
+    for friendship in ctx.db.friendship().a().filter(identity).chain(ctx.db.friendship().b().filter(identity)) {
+        ctx.db.friendship().id().delete(friendship.id);
+        removed += 1;
+    }
+    for status in ctx.db.friend_status().owner().filter(identity).chain(ctx.db.friend_status().friend().filter(identity)) {
+        ctx.db.friend_status().id().delete(status.id);
+        removed += 1;
+    }
+    for request in ctx.db.friend_request().sender().filter(identity).chain(ctx.db.friend_request().recipient().filter(identity)) {
+        ctx.db.friend_request().id().delete(request.id);
+        removed += 1;
+    }
+    removed
+}
+
+// Call whenever `identity` comes online, goes offline or changes name
+pub fn refresh_friend_status(ctx: &ReducerContext, identity: Identity) {
+    for friend in friends_of(ctx, identity) {
+        upsert_status(ctx, friend, identity);
+    }
+}
+
+fn make_friends(ctx: &ReducerContext, x: Identity, y: Identity) -> Result<(), String> {
+    for who in [x, y] {
+        if friends_of(ctx, who).len() >= MAX_FRIENDS {
+            return Err(format!("Friend lists are limited to {} friends", MAX_FRIENDS));
+        }
+    }
+    let (a, b) = ordered(x, y);
+    ctx.db.friendship().insert(Friendship { id: 0, a, b, since: ctx.timestamp });
+    upsert_status(ctx, x, y);
+    upsert_status(ctx, y, x);
+    on_friend_added(ctx, x);
+    on_friend_added(ctx, y);
+    Ok(())
+}
+
+fn take_request(ctx: &ReducerContext, sender: Identity, recipient: Identity) -> Option<FriendRequest> {
+    let request = ctx.db.friend_request().recipient().filter(recipient).find(|r| r.sender == sender)?;
+    ctx.db.friend_request().id().delete(request.id);
+    Some(request)
+}
+
+#[spacetimedb::reducer]
+pub fn send_friend_request(ctx: &ReducerContext, target: Identity) -> Result<(), String> {
+    if target == ctx.sender {
+        return Err("You can't befriend yourself".to_string());
+    }
+    if ctx.db.player().identity().find(ctx.sender).is_none() {
+        return Err("Player is not active".to_string());
+    }
+    if ctx.db.player().identity().find(target).is_none() && ctx.db.logged_out_player().identity().find(target).is_none() {
+        return Err("No such player".to_string());
+    }
+    if find_friendship(ctx, ctx.sender, target).is_some() {
+        return Err("You are already friends".to_string());
+    }
+    if take_request(ctx, target, ctx.sender).is_some() {
+        return make_friends(ctx, ctx.sender, target);
+    }
+    if ctx.db.friend_request().sender().filter(ctx.sender).any(|r| r.recipient == target) {
+        return Err("Friend request already sent".to_string());
+    }
+    ctx.db.friend_request().insert(FriendRequest { id: 0, sender: ctx.sender, recipient: target, created_at: ctx.timestamp });
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn accept_friend_request(ctx: &ReducerContext, sender: Identity) -> Result<(), String> {
+    if take_request(ctx, sender, ctx.sender).is_none() {
+        return Err("No friend request from that player".to_string());
+    }
+    make_friends(ctx, sender, ctx.sender)
+}
+
+#[spacetimedb::reducer]
+pub fn decline_friend_request(ctx: &ReducerContext, sender: Identity) -> Result<(), String> {
+    if take_request(ctx, sender, ctx.sender).is_none() {
+        return Err("No friend request from that player".to_string());
+    }
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn remove_friend(ctx: &ReducerContext, friend: Identity) -> Result<(), String> {
+    let Some(friendship) = find_friendship(ctx, ctx.sender, friend) else {
+        return Err("You are not friends".to_string());
+    };
+    ctx.db.friendship().id().delete(friendship.id);
+    delete_status(ctx, ctx.sender, friend);
+    delete_status(ctx, friend, ctx.sender);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_pair_is_stored_the_same_way_whoever_sends_the_request() {
+        let x = Identity::from_byte_array([1; 32]);
+        let y = Identity::from_byte_array([2; 32]);
+        assert_eq!(ordered(x, y), ordered(y, x));
+        assert_eq!(ordered(y, x), (x, y), "smaller identity first");
+    }
+}
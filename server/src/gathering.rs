@@ -0,0 +1,167 @@
+/*
+ * Vibe Coding Starter Pack: 3D Multiplayer - gathering.rs
+ *
+ * Ore veins and trees players harvest for crafting materials (crafting.rs).
+ *
+ * Key components:
+ *    - ResourceKind: Ore (yields "iron_ore") or Wood (yields "wood")
+ *    - ResourceNode: Public table. Each gather takes one of `remaining` charges; an exhausted
+ *      node is depleted until respawn_at, then update_resource_nodes (called from game_tick)
+ *      refills it to max_gathers
+ *    - gather_resource: Player reducer. Checks instance, GATHER_RANGE and a per-player
+ *      GATHER_COOLDOWN_SECS (PlayerData::last_gather_time), then adds 1 to 2 of the node's item
+ *      to the inventory. Fails without using a charge if nothing fits; counts toward
+ *      CollectItem quest objectives
+ *    - add_resource_node / remove_resource_node: Admin reducers
+ */
+
+use spacetimedb::rand::Rng;
+use spacetimedb::{ReducerContext, SpacetimeType, Table, TimeDuration, Timestamp};
+
+use crate::admin::{record_admin_action, require_admin};
+use crate::common::{Vector3, GATHER_COOLDOWN_SECS, GATHER_RANGE, LOBBY_INSTANCE_ID};
+use crate::instances::instance;
+use crate::inventory::add_item_to_inventory;
+use crate::loot::spawn_dropped_item;
+use crate::player;
+use crate::quests::on_item_collected;
+
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq)]
+pub enum ResourceKind {
+    Ore,
+    Wood,
+}
+
+impl ResourceKind {
+    pub fn item_id(self) -> &'static str {
+        match self {
+            ResourceKind::Ore => "iron_ore",
+            ResourceKind::Wood => "wood",
+        }
+    }
+}
+
+#[spacetimedb::table(name = resource_node, public)]
+#[derive(Clone)]
+pub struct ResourceNode {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub kind: ResourceKind,
+    pub position: Vector3,
+    pub remaining: u32,
+    pub max_gathers: u32,
+    pub respawn_secs: f32,
+    pub respawn_at: Option<Timestamp>, // set while depleted
+    pub instance_id: u64,
+}
+
+fn insert_resource_node(ctx: &ReducerContext, kind: ResourceKind, position: Vector3, max_gathers: u32, respawn_secs: f32, instance_id: u64) -> ResourceNode {
+    ctx.db.resource_node().insert(ResourceNode {
+        id: 0, // auto_inc
+        kind,
+        position,
+        remaining: max_gathers,
+        max_gathers,
+        respawn_secs,
+        respawn_at: None,
+        instance_id,
+    })
+}
+
+pub fn seed_resource_nodes(ctx: &ReducerContext) {
+    if ctx.db.resource_node().count() > 0 {
+        return;
+    }
+    let defaults = [
+        (ResourceKind::Ore, Vector3 { x: 20.0, y: 0.0, z: -20.0 }),
+        (ResourceKind::Ore, Vector3 { x: 23.0, y: 0.0, z: -18.0 }),
+        (ResourceKind::Wood, Vector3 { x: -20.0, y: 0.0, z: 20.0 }),
+        (ResourceKind::Wood, Vector3 { x: -23.0, y: 0.0, z: 17.0 }),
+        (ResourceKind::Wood, Vector3 { x: -18.0, y: 0.0, z: 24.0 }),
+    ];
+    for (kind, position) in defaults {
+        insert_resource_node(ctx, kind, position, 5, 45.0, LOBBY_INSTANCE_ID);
+    }
+    spacetimedb::log::info!("[INIT] Seeded resource nodes.");
+}
+
+pub fn update_resource_nodes(ctx: &ReducerContext) {
+    for mut node in ctx.db.resource_node().iter().filter(|n| n.respawn_at.is_some_and(|t| t <= ctx.timestamp)) {
+        node.remaining = node.max_gathers;
+        node.respawn_at = None;
+        ctx.db.resource_node().id().update(node);
+    }
+}
+
+#[spacetimedb::reducer]
+pub fn gather_resource(ctx: &ReducerContext, node_id: u64) -> Result<(), String> {
+    let Some(mut player) = ctx.db.player().identity().find(ctx.sender) else {
+        return Err("Player is not active".to_string());
+    };
+    if !player.alive {
+        return Err("Cannot gather while dead".to_string());
+    }
+    let Some(mut node) = ctx.db.resource_node().id().find(node_id).filter(|n| n.instance_id == player.instance_id) else {
+        return Err("Resource not found".to_string());
+    };
+    let dx = node.position.x - player.position.x;
+    let dz = node.position.z - player.position.z;
+    if (dx * dx + dz * dz).sqrt() > GATHER_RANGE + player.hit_radius {
+        return Err("Resource is out of reach".to_string());
+    }
+    if node.remaining == 0 {
+        return Err("This resource is depleted".to_string());
+    }
+    let on_cooldown = player
+        .last_gather_time
+        .is_some_and(|t| ctx.timestamp.duration_since(t).is_some_and(|d| d.as_secs_f32() < GATHER_COOLDOWN_SECS));
+    if on_cooldown {
+        return Err("Gathering is on cooldown".to_string());
+    }
+
+    let item_id = node.kind.item_id();
+    let quantity = ctx.rng().gen_range(1..=2);
+    let leftover = add_item_to_inventory(ctx, player.identity, item_id, quantity)?;
+    if leftover == quantity {
+        return Err("Inventory is full".to_string());
+    }
+    spawn_dropped_item(ctx, node.instance_id, item_id, leftover, player.position);
+    on_item_collected(ctx, player.identity, item_id, quantity - leftover);
+
+    node.remaining -= 1;
+    if node.remaining == 0 {
+        node.respawn_at = Some(ctx.timestamp + TimeDuration::from_micros((node.respawn_secs * 1_000_000.0) as i64));
+    }
+    ctx.db.resource_node().id().update(node);
+    player.last_gather_time = Some(ctx.timestamp);
+    ctx.db.player().identity().update(player);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn add_resource_node(ctx: &ReducerContext, kind: ResourceKind, position: Vector3, max_gathers: u32, respawn_secs: f32, instance_id: u64) -> Result<(), String> {
+    require_admin(ctx)?;
+    if ctx.db.instance().id().find(instance_id).is_none() {
+        return Err("Instance not found".to_string());
+    }
+    if max_gathers == 0 {
+        return Err("A node needs at least one gather".to_string());
+    }
+    if !respawn_secs.is_finite() || respawn_secs <= 0.0 {
+        return Err("Respawn time must be positive".to_string());
+    }
+    let node = insert_resource_node(ctx, kind, position, max_gathers, respawn_secs, instance_id);
+    record_admin_action(ctx, "add_resource_node", None, format!("{} {:?} in instance {}", node.id, kind, instance_id));
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn remove_resource_node(ctx: &ReducerContext, node_id: u64) -> Result<(), String> {
+    require_admin(ctx)?;
+    if !ctx.db.resource_node().id().delete(node_id) {
+        return Err("Resource not found".to_string());
+    }
+    record_admin_action(ctx, "remove_resource_node", None, format!("{}", node_id));
+    Ok(())
+}
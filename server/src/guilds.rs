@@ -0,0 +1,314 @@
+/*
+ * Vibe Coding Starter Pack: 3D Multiplayer - guilds.rs
+ *
+ * Player guilds with ranked members.
+ *
+ * Key components:
+ *    - Guild: Public table of guilds with a unique name and short tag (both compared
+ *      case-insensitively)
+ *    - GuildMember: Public table mapping each member to their guild and GuildRole. Membership
+ *      lives here rather than on PlayerData, so it survives logging out
+ *    - GuildInvite: Public table of open invites
+ *    - PlayerData::guild_tag: Tag shown on nameplates, filled in by spawn_player and kept in
+ *      sync by every membership change (sync_guild_tag)
+ *    - Reducers:
+ *        - create_guild: Founds a guild with you as Leader
+ *        - invite_to_guild / accept_guild_invite: Officers and the Leader invite
+ *        - leave_guild: The Leader has to promote someone or disband first
+ *        - kick_from_guild: Officers kick Members; the Leader kicks anyone
+ *        - promote_guild_member: Leader only. Member -> Officer; promoting an Officer hands
+ *          them leadership and makes you an Officer
+ *        - disband_guild: Leader only
+ *    - guild_members: Members of a player's guild (ChatChannel::Guild)
+ *    - purge_guild_data: Drops a purged player's membership (retention.rs); a Leader's guild
+ *      passes to the longest-serving Officer, then Member, and is disbanded once empty
+ */
+
+use spacetimedb::{Identity, ReducerContext, SpacetimeType, Table, Timestamp};
+
+use crate::common::{GUILD_NAME_MAX_LEN, GUILD_NAME_MIN_LEN, GUILD_TAG_MAX_LEN, GUILD_TAG_MIN_LEN, MAX_GUILD_MEMBERS};
+use crate::player;
+
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq)]
+pub enum GuildRole {
+    Leader,
+    Officer,
+    Member,
+}
+
+#[spacetimedb::table(name = guild, public)]
+#[derive(Clone)]
+pub struct Guild {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub name: String,
+    pub tag: String,
+    pub created_at: Timestamp,
+}
+
+#[spacetimedb::table(name = guild_member, public)]
+#[derive(Clone)]
+pub struct GuildMember {
+    #[primary_key]
+    pub identity: Identity,
+    #[index(btree)]
+    pub guild_id: u64,
+    pub role: GuildRole,
+    pub joined_at: Timestamp,
+}
+
+#[spacetimedb::table(name = guild_invite, public)]
+#[derive(Clone)]
+pub struct GuildInvite {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub guild_id: u64,
+    pub inviter: Identity,
+    #[index(btree)]
+    pub invitee: Identity,
+    pub created_at: Timestamp,
+}
+
+pub fn guild_tag_of(ctx: &ReducerContext, identity: Identity) -> Option<String> {
+    let member = ctx.db.guild_member().identity().find(identity)?;
+    ctx.db.guild().id().find(member.guild_id).map(|g| g.tag)
+}
+
+// Online members of `identity`'s guild, including them; empty when not in a guild
+pub fn guild_members(ctx: &ReducerContext, identity: Identity) -> Vec<Identity> {
+    let Some(member) = ctx.db.guild_member().identity().find(identity) else {
+        return Vec::new();
+    };
+    ctx.db.guild_member()
+        .guild_id()
+        .filter(member.guild_id)
+        .map(|m| m.identity)
+        .filter(|id| ctx.db.player().identity().find(*id).is_some())
+        .collect()
+}
+
+fn sync_guild_tag(ctx: &ReducerContext, identity: Identity) {
+    if let Some(mut player) = ctx.db.player().identity().find(identity) {
+        player.guild_tag = guild_tag_of(ctx, identity);
+        ctx.db.player().identity().update(player);
+    }
+}
+
+fn membership(ctx: &ReducerContext, identity: Identity) -> Result<GuildMember, String> {
+    ctx.db.guild_member().identity().find(identity).ok_or_else(|| "You are not in a guild".to_string())
+}
+
+fn validate_label(value: &str, what: &str, min: usize, max: usize, allow_spaces: bool) -> Result<String, String> {
+    let value = value.trim();
+    if !(min..=max).contains(&value.chars().count()) {
+        return Err(format!("Guild {} must be {} to {} characters", what, min, max));
+    }
+    if !value.chars().all(|c| c.is_ascii_alphanumeric() || (allow_spaces && c == ' ')) {
+        return Err(format!("Guild {} may only contain letters and digits", what));
+    }
+    Ok(value.to_string())
+}
+
+fn remove_member(ctx: &ReducerContext, identity: Identity) {
+    ctx.db.guild_member().identity().delete(identity);
+    sync_guild_tag(ctx, identity);
+}
+
+// Who takes over from a departing Leader: the longest-serving Officer, else Member
+fn heir(members: impl Iterator<Item = GuildMember>) -> Option<GuildMember> {
+    members.min_by_key(|m| (m.role != GuildRole::Officer, m.joined_at))
+}
+
+// Returns the number of rows removed
+pub fn purge_guild_data(ctx: &ReducerContext, identity: Identity) -> u32 {
+    let mut removed = 0;
+    for invite in ctx.db.guild_invite().invitee().filter(identity) {
+        ctx.db.guild_invite().id().delete(invite.id);
+        removed += 1;
+    }
+    let Some(me) = ctx.db.guild_member().identity().find(identity) else {
+        return removed;
+    };
+    remove_member(ctx, identity);
+    removed += 1;
+    if me.role != GuildRole::Leader {
+        return removed;
+    }
+    if let Some(mut heir) = heir(ctx.db.guild_member().guild_id().filter(me.guild_id)) {
+        heir.role = GuildRole::Leader;
+        ctx.db.guild_member().identity().update(heir);
+        return removed;
+    }
+    for invite in ctx.db.guild_invite().iter().filter(|i| i.guild_id == me.guild_id) {
+        ctx.db.guild_invite().id().delete(invite.id);
+        removed += 1;
+    }
+    ctx.db.guild().id().delete(me.guild_id);
+    spacetimedb::log::info!("[GUILD] Guild {} disbanded, its last member was purged", me.guild_id);
+    removed + 1
+}
+
+#[spacetimedb::reducer]
+pub fn create_guild(ctx: &ReducerContext, name: String, tag: String) -> Result<(), String> {
+    if ctx.db.player().identity().find(ctx.sender).is_none() {
+        return Err("Player is not active".to_string());
+    }
+    if ctx.db.guild_member().identity().find(ctx.sender).is_some() {
+        return Err("Leave your current guild first".to_string());
+    }
+    let name = validate_label(&name, "name", GUILD_NAME_MIN_LEN, GUILD_NAME_MAX_LEN, true)?;
+    let tag = validate_label(&tag, "tag", GUILD_TAG_MIN_LEN, GUILD_TAG_MAX_LEN, false)?.to_ascii_uppercase();
+    for guild in ctx.db.guild().iter() {
+        if guild.name.eq_ignore_ascii_case(&name) {
+            return Err(format!("A guild named \"{}\" already exists", name));
+        }
+        if guild.tag == tag {
+            return Err(format!("The tag [{}] is taken", tag));
+        }
+    }
+    let guild = ctx.db.guild().insert(Guild { id: 0, name, tag, created_at: ctx.timestamp });
+    ctx.db.guild_member().insert(GuildMember { identity: ctx.sender, guild_id: guild.id, role: GuildRole::Leader, joined_at: ctx.timestamp });
+    sync_guild_tag(ctx, ctx.sender);
+    spacetimedb::log::info!("[GUILD] {} founded {} [{}]", ctx.sender, guild.name, guild.tag);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn invite_to_guild(ctx: &ReducerContext, target: Identity) -> Result<(), String> {
+    let me = membership(ctx, ctx.sender)?;
+    if me.role == GuildRole::Member {
+        return Err("Only officers and the leader can invite".to_string());
+    }
+    if ctx.db.player().identity().find(target).is_none() {
+        return Err("That player is not online".to_string());
+    }
+    if ctx.db.guild_member().identity().find(target).is_some() {
+        return Err("That player is already in a guild".to_string());
+    }
+    if ctx.db.guild_member().guild_id().filter(me.guild_id).count() >= MAX_GUILD_MEMBERS {
+        return Err(format!("Guilds are limited to {} members", MAX_GUILD_MEMBERS));
+    }
+    if ctx.db.guild_invite().invitee().filter(target).any(|i| i.guild_id == me.guild_id) {
+        return Err("That player is already invited".to_string());
+    }
+    ctx.db.guild_invite().insert(GuildInvite { id: 0, guild_id: me.guild_id, inviter: ctx.sender, invitee: target, created_at: ctx.timestamp });
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn accept_guild_invite(ctx: &ReducerContext, guild_id: u64) -> Result<(), String> {
+    if !ctx.db.guild_invite().invitee().filter(ctx.sender).any(|i| i.guild_id == guild_id) {
+        return Err("No invite to that guild".to_string());
+    }
+    if ctx.db.guild_member().identity().find(ctx.sender).is_some() {
+        return Err("Leave your current guild first".to_string());
+    }
+    if ctx.db.guild().id().find(guild_id).is_none() {
+        return Err("That guild no longer exists".to_string());
+    }
+    if ctx.db.guild_member().guild_id().filter(guild_id).count() >= MAX_GUILD_MEMBERS {
+        return Err("That guild is full".to_string());
+    }
+    ctx.db.guild_invite().invitee().delete(ctx.sender);
+    ctx.db.guild_member().insert(GuildMember { identity: ctx.sender, guild_id, role: GuildRole::Member, joined_at: ctx.timestamp });
+    sync_guild_tag(ctx, ctx.sender);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn leave_guild(ctx: &ReducerContext) -> Result<(), String> {
+    let me = membership(ctx, ctx.sender)?;
+    if me.role == GuildRole::Leader {
+        return Err("Promote a new leader or disband the guild first".to_string());
+    }
+    remove_member(ctx, ctx.sender);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn kick_from_guild(ctx: &ReducerContext, target: Identity) -> Result<(), String> {
+    let me = membership(ctx, ctx.sender)?;
+    let Some(them) = ctx.db.guild_member().identity().find(target).filter(|m| m.guild_id == me.guild_id) else {
+        return Err("That player is not in your guild".to_string());
+    };
+    let allowed = match me.role {
+        GuildRole::Leader => them.role != GuildRole::Leader,
+        GuildRole::Officer => them.role == GuildRole::Member,
+        GuildRole::Member => false,
+    };
+    if !allowed {
+        return Err("You can't kick that member".to_string());
+    }
+    remove_member(ctx, target);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn promote_guild_member(ctx: &ReducerContext, target: Identity) -> Result<(), String> {
+    let mut me = membership(ctx, ctx.sender)?;
+    if me.role != GuildRole::Leader {
+        return Err("Only the leader can promote".to_string());
+    }
+    let Some(mut them) = ctx.db.guild_member().identity().find(target).filter(|m| m.guild_id == me.guild_id && m.identity != ctx.sender) else {
+        return Err("That player is not in your guild".to_string());
+    };
+    if them.role == GuildRole::Officer {
+        me.role = GuildRole::Officer;
+        ctx.db.guild_member().identity().update(me);
+        them.role = GuildRole::Leader;
+    } else {
+        them.role = GuildRole::Officer;
+    }
+    ctx.db.guild_member().identity().update(them);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn disband_guild(ctx: &ReducerContext) -> Result<(), String> {
+    let me = membership(ctx, ctx.sender)?;
+    if me.role != GuildRole::Leader {
+        return Err("Only the leader can disband the guild".to_string());
+    }
+    for member in ctx.db.guild_member().guild_id().filter(me.guild_id) {
+        remove_member(ctx, member.identity);
+    }
+    for invite in ctx.db.guild_invite().iter().filter(|i| i.guild_id == me.guild_id) {
+        ctx.db.guild_invite().id().delete(invite.id);
+    }
+    ctx.db.guild().id().delete(me.guild_id);
+    spacetimedb::log::info!("[GUILD] Guild {} disbanded", me.guild_id);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn member(id: u8, role: GuildRole, joined_secs: i64) -> GuildMember {
+        GuildMember {
+            identity: Identity::from_byte_array([id; 32]),
+            guild_id: 1,
+            role,
+            joined_at: Timestamp::from_micros_since_unix_epoch(joined_secs * 1_000_000),
+        }
+    }
+
+    #[test]
+    fn the_longest_serving_officer_inherits_before_any_member() {
+        let members = vec![member(1, GuildRole::Member, 10), member(2, GuildRole::Officer, 50), member(3, GuildRole::Officer, 30)];
+        assert_eq!(heir(members.into_iter()).map(|m| m.identity), Some(Identity::from_byte_array([3; 32])));
+        let members = vec![member(1, GuildRole::Member, 40), member(2, GuildRole::Member, 20)];
+        assert_eq!(heir(members.into_iter()).map(|m| m.identity), Some(Identity::from_byte_array([2; 32])));
+        assert!(heir(std::iter::empty()).is_none());
+    }
+
+    #[test]
+    fn guild_names_and_tags_are_validated() {
+        assert_eq!(validate_label("  Night Owls ", "name", 3, 24, true), Ok("Night Owls".to_string()));
+        assert!(validate_label("Night Owls", "tag", 2, 5, false).is_err());
+        assert!(validate_label("OWL!", "tag", 2, 5, false).is_err());
+        assert!(validate_label("O", "tag", 2, 5, false).is_err());
+    }
+}
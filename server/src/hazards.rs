@@ -0,0 +1,61 @@
+/*
+ * Vibe Coding Starter Pack: 3D Multiplayer - hazards.rs
+ *
+ * Lingering ground hazards left behind by projectiles (e.g. the fire patch from a
+ * LingerFire impact). Hazards pulse damage on a fixed interval to every living
+ * player inside their radius other than the owner, then expire.
+ */
+
+use spacetimedb::{Identity, ReducerContext, Table};
+
+use crate::combat::{alive_players_in_radius, apply_damage};
+use crate::common::{Vector3, FIRE_PULSE_SECS};
+
+#[spacetimedb::table(name = fire_patch, public)]
+#[derive(Clone)]
+pub struct FirePatch {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub owner_identity: Identity,
+    pub position: Vector3,
+    pub radius: f32,
+    pub damage_per_pulse: i32,
+    pub pulse_timer: f32,
+    pub remaining_secs: f32,
+    pub instance_id: u64,
+}
+
+pub fn spawn_fire_patch(ctx: &ReducerContext, owner_identity: Identity, instance_id: u64, position: Vector3, radius: f32, damage_per_pulse: i32, duration_secs: f32) {
+    ctx.db.fire_patch().insert(FirePatch {
+        id: 0, // auto_inc
+        owner_identity,
+        position: Vector3 { x: position.x, y: 0.0, z: position.z }, // patches sit on the ground
+        radius,
+        damage_per_pulse,
+        pulse_timer: 0.0,
+        remaining_secs: duration_secs,
+        instance_id,
+    });
+}
+
+pub fn update_hazards(ctx: &ReducerContext, delta_time: f32) {
+    for mut patch in ctx.db.fire_patch().iter() {
+        patch.remaining_secs -= delta_time;
+        if patch.remaining_secs <= 0.0 {
+            ctx.db.fire_patch().id().delete(patch.id);
+            continue;
+        }
+
+        patch.pulse_timer -= delta_time;
+        if patch.pulse_timer <= 0.0 {
+            patch.pulse_timer += FIRE_PULSE_SECS;
+            for victim in alive_players_in_radius(ctx, patch.instance_id, patch.position, patch.radius) {
+                if victim.identity != patch.owner_identity {
+                    apply_damage(ctx, victim.identity, patch.damage_per_pulse, Some(patch.owner_identity));
+                }
+            }
+        }
+        ctx.db.fire_patch().id().update(patch);
+    }
+}
@@ -0,0 +1,268 @@
+/*
+ * Vibe Coding Starter Pack: 3D Multiplayer - instances.rs
+ *
+ * Separate copies of the map running side by side in one module.
+ *
+ * Key components:
+ *    - Instance: Public table of map instances. LOBBY_INSTANCE_ID is the default world, seeded
+ *      in init, and can't be destroyed; arenas are created by admins or by matchmaking.rs for
+ *      each match it starts, dungeons by dungeons.rs
+ *    - instance_id: Column on player, projectile, npc, npc_spawner, zone, fire_patch,
+ *      dropped_item, vehicle, interactable, destructible, resource_node and the environment
+ *      tables. Players only hit, see through the spatial grid, chat with (Global and
+ *      Team channels) and trade with players of their own instance. Projectiles take their
+ *      owner's instance and NPCs their spawner's
+ *    - The match (matches.rs) only covers the lobby: other instances ignore its phase and
+ *      aren't reset between rounds
+ *    - Static geometry (environment.rs) belongs to one instance: arenas get a copy of the
+ *      lobby's when they are created, dungeons only have their own walls. Pickups, CTF flags,
+ *      control points, bot turrets and vendors only exist in the lobby
+ *    - instance_of: Instance of an active player (the lobby for anyone else, e.g. bots)
+ *    - move_to_instance: Moves a player and respawns them at one of their team's spawn points;
+ *      ends their duel, cancels their trade and takes them out of their vehicle first
+ *    - create_instance / destroy_instance / move_player_to_instance: Admin reducers. Destroying
+ *      sends everyone inside to the lobby and deletes the instance's projectiles, NPCs,
+ *      spawners, zones, hazards, loot, vehicles, interactables, destructibles, resource
+ *      nodes and geometry (and a dungeon's layout)
+ *    - enter_instance: Player reducer to go back to the lobby, into the arena of the match they
+ *      were matched into (matchmaking.rs), or into a dungeon they or a party member generated
+ */
+
+use spacetimedb::{Identity, ReducerContext, SpacetimeType, Table, Timestamp};
+
+use crate::admin::{record_admin_action, require_admin};
+use crate::arenas::forfeit_duel;
+use crate::bosses::remove_boss_encounters;
+use crate::common::LOBBY_INSTANCE_ID;
+use crate::destructibles::{delete_destructible, destructible};
+use crate::dungeons::{dungeon, remove_dungeon_layout};
+use crate::environment::{copy_environment, remove_environment};
+use crate::gathering::resource_node;
+use crate::hazards::fire_patch;
+use crate::interactables::{delete_interactable, interactable};
+use crate::loot::dropped_item;
+use crate::matchmaking::match_arena_of;
+use crate::npcs::{npc, npc_spawner};
+use crate::party::party_members;
+use crate::player_logic::respawn_player;
+use crate::threat::clear_threat;
+use crate::trading::cancel_trades_for;
+use crate::vehicles::{leave_vehicle, vehicle};
+use crate::zones::zone;
+use crate::{player, projectile};
+
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq)]
+pub enum InstanceKind {
+    Lobby,
+    Arena,
+    Dungeon, // generated by dungeons.rs
+}
+
+#[spacetimedb::table(name = instance, public)]
+#[derive(Clone)]
+pub struct Instance {
+    #[primary_key]
+    pub id: u64,
+    pub name: String,
+    pub kind: InstanceKind,
+    pub created_at: Timestamp,
+}
+
+pub fn seed_instances(ctx: &ReducerContext) {
+    if ctx.db.instance().id().find(LOBBY_INSTANCE_ID).is_none() {
+        ctx.db.instance().insert(Instance { id: LOBBY_INSTANCE_ID, name: "Lobby".to_string(), kind: InstanceKind::Lobby, created_at: ctx.timestamp });
+        spacetimedb::log::info!("[INIT] Seeded lobby instance.");
+    }
+}
+
+pub fn instance_of(ctx: &ReducerContext, identity: Identity) -> u64 {
+    ctx.db.player().identity().find(identity).map_or(LOBBY_INSTANCE_ID, |p| p.instance_id)
+}
+
+// Ids are never reused while an instance exists; the lobby always has the lowest one
+fn next_instance_id(existing: impl Iterator<Item = u64>) -> u64 {
+    existing.max().unwrap_or(LOBBY_INSTANCE_ID) + 1
+}
+
+pub fn create_instance_of(ctx: &ReducerContext, name: String, kind: InstanceKind) -> Instance {
+    let id = next_instance_id(ctx.db.instance().iter().map(|i| i.id));
+    spacetimedb::log::info!("[INSTANCE] Created {:?} instance {} '{}'", kind, id, name);
+    ctx.db.instance().insert(Instance { id, name, kind, created_at: ctx.timestamp })
+}
+
+// Arenas start as a copy of the lobby's geometry (environment.rs)
+pub fn create_arena(ctx: &ReducerContext, name: String) -> Instance {
+    let arena = create_instance_of(ctx, name, InstanceKind::Arena);
+    let owned_colliders: Vec<u64> = ctx
+        .db
+        .interactable()
+        .iter()
+        .filter_map(|o| o.collider_id)
+        .chain(ctx.db.destructible().iter().filter_map(|d| d.collider_id))
+        .collect();
+    copy_environment(ctx, LOBBY_INSTANCE_ID, arena.id, &owned_colliders);
+    arena
+}
+
+pub fn move_to_instance(ctx: &ReducerContext, identity: Identity, instance_id: u64) -> Result<(), String> {
+    if ctx.db.instance().id().find(instance_id).is_none() {
+        return Err("Instance not found".to_string());
+    }
+    let Some(mut player) = ctx.db.player().identity().find(identity) else {
+        return Err("Player is not active".to_string());
+    };
+    if player.instance_id == instance_id {
+        return Ok(());
+    }
+    if leave_vehicle(ctx, identity) {
+        player = ctx.db.player().identity().find(identity).ok_or_else(|| "Player is not active".to_string())?;
+    }
+    if player.duel_id.is_some() {
+        // Ending the duel moves the player back, so reload them afterwards
+        forfeit_duel(ctx, identity);
+        player = ctx.db.player().identity().find(identity).ok_or_else(|| "Player is not active".to_string())?;
+    }
+    cancel_trades_for(ctx, identity);
+    player.instance_id = instance_id;
+    respawn_player(ctx, &mut player);
+    ctx.db.player().identity().update(player);
+    Ok(())
+}
+
+// Removes an arena or dungeon after sending its players back to the lobby
+pub fn remove_instance(ctx: &ReducerContext, instance_id: u64) {
+    let inside: Vec<Identity> = ctx.db.player().iter().filter(|p| p.instance_id == instance_id).map(|p| p.identity).collect();
+    for identity in inside {
+        let _ = move_to_instance(ctx, identity, LOBBY_INSTANCE_ID);
+    }
+    for projectile in ctx.db.projectile().iter().filter(|p| p.instance_id == instance_id) {
+        ctx.db.projectile().id().delete(projectile.id);
+    }
+    for spawner in ctx.db.npc_spawner().iter().filter(|s| s.instance_id == instance_id) {
+        ctx.db.npc_spawner().id().delete(spawner.id);
+    }
+    for npc in ctx.db.npc().iter().filter(|n| n.instance_id == instance_id) {
+        clear_threat(ctx, npc.id);
+        ctx.db.npc().id().delete(npc.id);
+    }
+    for zone in ctx.db.zone().iter().filter(|z| z.instance_id == instance_id) {
+        ctx.db.zone().name().delete(zone.name);
+    }
+    for patch in ctx.db.fire_patch().iter().filter(|p| p.instance_id == instance_id) {
+        ctx.db.fire_patch().id().delete(patch.id);
+    }
+    for item in ctx.db.dropped_item().iter().filter(|i| i.instance_id == instance_id) {
+        ctx.db.dropped_item().id().delete(item.id);
+    }
+    for vehicle in ctx.db.vehicle().iter().filter(|v| v.instance_id == instance_id) {
+        ctx.db.vehicle().id().delete(vehicle.id);
+    }
+    for node in ctx.db.resource_node().iter().filter(|n| n.instance_id == instance_id) {
+        ctx.db.resource_node().id().delete(node.id);
+    }
+    for object in ctx.db.interactable().iter().filter(|o| o.instance_id == instance_id) {
+        delete_interactable(ctx, object);
+    }
+    for prop in ctx.db.destructible().iter().filter(|d| d.instance_id == instance_id) {
+        delete_destructible(ctx, prop);
+    }
+    remove_boss_encounters(ctx, instance_id);
+    remove_dungeon_layout(ctx, instance_id);
+    remove_environment(ctx, instance_id);
+    ctx.db.instance().id().delete(instance_id);
+    spacetimedb::log::info!("[INSTANCE] Removed instance {}", instance_id);
+}
+
+#[spacetimedb::reducer]
+pub fn create_instance(ctx: &ReducerContext, name: String) -> Result<(), String> {
+    require_admin(ctx)?;
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("Instance name must not be empty".to_string());
+    }
+    let instance = create_arena(ctx, name);
+    record_admin_action(ctx, "create_instance", None, format!("{} '{}'", instance.id, instance.name));
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn destroy_instance(ctx: &ReducerContext, instance_id: u64) -> Result<(), String> {
+    require_admin(ctx)?;
+    if instance_id == LOBBY_INSTANCE_ID {
+        return Err("The lobby can't be destroyed".to_string());
+    }
+    if ctx.db.instance().id().find(instance_id).is_none() {
+        return Err("Instance not found".to_string());
+    }
+    remove_instance(ctx, instance_id);
+    record_admin_action(ctx, "destroy_instance", None, instance_id.to_string());
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn move_player_to_instance(ctx: &ReducerContext, target: Identity, instance_id: u64) -> Result<(), String> {
+    require_admin(ctx)?;
+    move_to_instance(ctx, target, instance_id)?;
+    record_admin_action(ctx, "move_player_to_instance", Some(target), instance_id.to_string());
+    Ok(())
+}
+
+// Instances a player may walk into on their own; admins can move anyone anywhere. `group` is
+// the player and their party
+fn may_enter(instance: &Instance, match_arena: Option<u64>, dungeon_creator: Option<Identity>, group: &[Identity]) -> bool {
+    match instance.kind {
+        InstanceKind::Lobby => true,
+        InstanceKind::Arena => match_arena == Some(instance.id),
+        InstanceKind::Dungeon => dungeon_creator.is_some_and(|creator| group.contains(&creator)),
+    }
+}
+
+#[spacetimedb::reducer]
+pub fn enter_instance(ctx: &ReducerContext, instance_id: u64) -> Result<(), String> {
+    let Some(player) = ctx.db.player().identity().find(ctx.sender) else {
+        return Err("Player is not active".to_string());
+    };
+    if player.duel_id.is_some() {
+        return Err("Finish your duel first".to_string());
+    }
+    let Some(instance) = ctx.db.instance().id().find(instance_id) else {
+        return Err("Instance not found".to_string());
+    };
+    let dungeon_creator = ctx.db.dungeon().instance_id().find(instance.id).map(|d| d.created_by);
+    let mut group = party_members(ctx, ctx.sender);
+    group.push(ctx.sender);
+    if !may_enter(&instance, match_arena_of(ctx, ctx.sender), dungeon_creator, &group) {
+        return Err("You can't enter that instance".to_string());
+    }
+    move_to_instance(ctx, ctx.sender, instance_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arenas_get_ids_after_the_highest_existing_instance() {
+        assert_eq!(next_instance_id(std::iter::empty()), LOBBY_INSTANCE_ID + 1);
+        assert_eq!(next_instance_id([LOBBY_INSTANCE_ID].into_iter()), LOBBY_INSTANCE_ID + 1);
+        assert_eq!(next_instance_id([LOBBY_INSTANCE_ID, 4, 2].into_iter()), 5, "gaps left by removed arenas aren't refilled");
+    }
+
+    #[test]
+    fn players_may_only_enter_the_lobby_their_match_arena_or_their_party_dungeon() {
+        let at = Timestamp::from_micros_since_unix_epoch(0);
+        let lobby = Instance { id: LOBBY_INSTANCE_ID, name: "Lobby".to_string(), kind: InstanceKind::Lobby, created_at: at };
+        let arena = Instance { id: 3, name: "Match 1".to_string(), kind: InstanceKind::Arena, created_at: at };
+        let (me, friend, stranger) = (Identity::from_byte_array([1; 32]), Identity::from_byte_array([2; 32]), Identity::from_byte_array([3; 32]));
+        assert!(may_enter(&lobby, None, None, &[me]) && may_enter(&lobby, Some(3), None, &[me]));
+        assert!(may_enter(&arena, Some(3), None, &[me]));
+        assert!(!may_enter(&arena, Some(4), None, &[me]), "someone else's match");
+        assert!(!may_enter(&arena, None, None, &[me]), "not in a running match");
+
+        let dungeon = Instance { id: 5, name: "Dungeon".to_string(), kind: InstanceKind::Dungeon, created_at: at };
+        assert!(may_enter(&dungeon, None, Some(me), &[me]));
+        assert!(may_enter(&dungeon, None, Some(friend), &[friend, me]), "generated by a party member");
+        assert!(!may_enter(&dungeon, None, Some(stranger), &[friend, me]));
+        assert!(!may_enter(&dungeon, Some(5), None, &[me]), "layout already gone");
+    }
+}
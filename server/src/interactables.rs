@@ -0,0 +1,308 @@
+/*
+ * Vibe Coding Starter Pack: 3D Multiplayer - interactables.rs
+ *
+ * World objects players use by calling interact: doors, levers and chests.
+ *
+ * Key components:
+ *    - InteractableKind: Door, Lever or Chest
+ *    - Interactable: Public table. `state` is open (doors), pulled (levers) or looted (chests).
+ *      required_item is a key that must be in the player's inventory (it is not used up) and
+ *      required_level a minimum progression::level_for_xp
+ *    - Doors: A closed door is a static_collider row (collider_id) sized half_extents; opening
+ *      removes it and closing puts it back, rebuilding the nav grid either way
+ *    - Levers: Flip their state and trigger every object in linked_ids. A linked door toggles
+ *      regardless of its own requirements, a linked lever triggers its own links (each object
+ *      at most once per pull); chests ignore levers
+ *    - Chests: Roll their loot entries (loot::roll_loot) once, straight into the opener's
+ *      inventory. Gold (CURRENCY_ITEM_ID) goes to PlayerData::gold and whatever doesn't fit is
+ *      dropped at the chest. A looted chest stays empty
+ *    - interact: Player reducer, checks instance, INTERACT_RANGE and requirements
+ *    - add_door / add_lever / add_chest / remove_interactable: Admin reducers
+ */
+
+use spacetimedb::{ReducerContext, SpacetimeType, Table};
+
+use crate::admin::{record_admin_action, require_admin};
+use crate::common::{Vector3, CURRENCY_ITEM_ID, INTERACT_RANGE, LOBBY_INSTANCE_ID};
+use crate::environment::{static_collider, StaticCollider};
+use crate::instances::instance;
+use crate::inventory::{add_item_to_inventory, item_definition_for, player_inventory};
+use crate::loot::{roll_loot, spawn_dropped_item, validate_loot_entries, LootTableEntry};
+use crate::pathfinding::rebuild_nav_grid;
+use crate::progression::level_for_xp;
+use crate::{player, PlayerData};
+
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq)]
+pub enum InteractableKind {
+    Door,
+    Lever,
+    Chest,
+}
+
+#[spacetimedb::table(name = interactable, public)]
+#[derive(Clone)]
+pub struct Interactable {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub kind: InteractableKind,
+    pub position: Vector3,
+    pub state: bool,
+    pub required_item: Option<String>,
+    pub required_level: u32,
+    pub half_extents: Vector3,     // doors: collider size while closed
+    pub collider_id: Option<u64>,  // doors: static_collider row while closed
+    pub linked_ids: Vec<u64>,      // levers
+    pub loot: Vec<LootTableEntry>, // chests
+    pub instance_id: u64,
+}
+
+fn interactable_of(kind: InteractableKind, position: Vector3, instance_id: u64) -> Interactable {
+    Interactable {
+        id: 0, // auto_inc
+        kind,
+        position,
+        state: false,
+        required_item: None,
+        required_level: 0,
+        half_extents: Vector3::default(),
+        collider_id: None,
+        linked_ids: Vec::new(),
+        loot: Vec::new(),
+        instance_id,
+    }
+}
+
+fn insert_door(ctx: &ReducerContext, door: Interactable) -> Interactable {
+    let collider = ctx.db.static_collider().insert(StaticCollider { id: 0, center: door.position, half_extents: door.half_extents, instance_id: door.instance_id });
+    ctx.db.interactable().insert(Interactable { collider_id: Some(collider.id), ..door })
+}
+
+pub fn seed_interactables(ctx: &ReducerContext) {
+    if ctx.db.interactable().count() > 0 {
+        return;
+    }
+    let door = insert_door(
+        ctx,
+        Interactable { half_extents: Vector3 { x: 1.5, y: 1.5, z: 0.25 }, ..interactable_of(InteractableKind::Door, Vector3 { x: 0.0, y: 1.5, z: 30.0 }, LOBBY_INSTANCE_ID) },
+    );
+    ctx.db.interactable().insert(Interactable { linked_ids: vec![door.id], ..interactable_of(InteractableKind::Lever, Vector3 { x: 4.0, y: 1.0, z: 28.0 }, LOBBY_INSTANCE_ID) });
+    let loot = vec![
+        LootTableEntry { item_id: CURRENCY_ITEM_ID.to_string(), min_quantity: 20, max_quantity: 50, chance: 1.0 },
+        LootTableEntry { item_id: "health_potion".to_string(), min_quantity: 1, max_quantity: 2, chance: 0.5 },
+        LootTableEntry { item_id: "iron_sword".to_string(), min_quantity: 1, max_quantity: 1, chance: 0.1 },
+    ];
+    ctx.db.interactable().insert(Interactable { loot, ..interactable_of(InteractableKind::Chest, Vector3 { x: 0.0, y: 0.5, z: 33.0 }, LOBBY_INSTANCE_ID) });
+    rebuild_nav_grid(ctx, LOBBY_INSTANCE_ID);
+    spacetimedb::log::info!("[INIT] Seeded interactables.");
+}
+
+// Writes `door` back
+fn set_door_open(ctx: &ReducerContext, mut door: Interactable, open: bool) {
+    if open {
+        if let Some(collider_id) = door.collider_id.take() {
+            ctx.db.static_collider().id().delete(collider_id);
+        }
+    } else if door.collider_id.is_none() {
+        door.collider_id = Some(ctx.db.static_collider().insert(StaticCollider { id: 0, center: door.position, half_extents: door.half_extents, instance_id: door.instance_id }).id);
+    }
+    door.state = open;
+    ctx.db.interactable().id().update(door);
+}
+
+// The lever and every lever and door downstream of it, each once even if the links loop
+fn lever_chain(objects: &[Interactable], lever_id: u64) -> Vec<u64> {
+    let mut chain = vec![lever_id];
+    let mut next = 0;
+    while next < chain.len() {
+        let id = chain[next];
+        next += 1;
+        if let Some(lever) = objects.iter().find(|o| o.id == id && o.kind == InteractableKind::Lever) {
+            for linked in &lever.linked_ids {
+                if !chain.contains(linked) && objects.iter().any(|o| o.id == *linked && o.kind != InteractableKind::Chest) {
+                    chain.push(*linked);
+                }
+            }
+        }
+    }
+    chain
+}
+
+// Flips a lever and everything downstream of it. Returns true if a door moved
+fn pull_lever(ctx: &ReducerContext, lever_id: u64) -> bool {
+    let objects: Vec<Interactable> = ctx.db.interactable().iter().collect();
+    let mut doors_moved = false;
+    for id in lever_chain(&objects, lever_id) {
+        let Some(mut object) = objects.iter().find(|o| o.id == id).cloned() else {
+            continue;
+        };
+        if object.kind == InteractableKind::Door {
+            let open = !object.state;
+            set_door_open(ctx, object, open);
+            doors_moved = true;
+        } else {
+            object.state = !object.state;
+            ctx.db.interactable().id().update(object);
+        }
+    }
+    doors_moved
+}
+
+fn loot_chest(ctx: &ReducerContext, player: &PlayerData, mut chest: Interactable) -> Result<(), String> {
+    if chest.state {
+        return Err("The chest is empty".to_string());
+    }
+    let mut gold = 0u64;
+    for (item_id, quantity) in roll_loot(ctx, &chest.loot) {
+        if item_id == CURRENCY_ITEM_ID {
+            gold += quantity as u64;
+            continue;
+        }
+        let leftover = add_item_to_inventory(ctx, player.identity, &item_id, quantity)?;
+        spawn_dropped_item(ctx, chest.instance_id, &item_id, leftover, chest.position);
+    }
+    if gold > 0 {
+        if let Some(mut opener) = ctx.db.player().identity().find(player.identity) {
+            opener.gold = opener.gold.saturating_add(gold);
+            ctx.db.player().identity().update(opener);
+        }
+    }
+    spacetimedb::log::info!("Player {} looted chest {} ({} gold)", player.username, chest.id, gold);
+    chest.state = true;
+    ctx.db.interactable().id().update(chest);
+    Ok(())
+}
+
+fn check_requirements(ctx: &ReducerContext, player: &PlayerData, object: &Interactable) -> Result<(), String> {
+    if level_for_xp(player.xp) < object.required_level {
+        return Err(format!("Requires level {}", object.required_level));
+    }
+    if let Some(key) = &object.required_item {
+        if !ctx.db.player_inventory().owner().filter(player.identity).any(|s| &s.item_id == key) {
+            let name = item_definition_for(ctx, key).map_or_else(|| key.clone(), |d| d.display_name);
+            return Err(format!("Requires {}", name));
+        }
+    }
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn interact(ctx: &ReducerContext, interactable_id: u64) -> Result<(), String> {
+    let Some(player) = ctx.db.player().identity().find(ctx.sender) else {
+        return Err("Player is not active".to_string());
+    };
+    if !player.alive {
+        return Err("Cannot interact while dead".to_string());
+    }
+    let Some(object) = ctx.db.interactable().id().find(interactable_id).filter(|o| o.instance_id == player.instance_id) else {
+        return Err("Nothing to interact with".to_string());
+    };
+    let dx = object.position.x - player.position.x;
+    let dz = object.position.z - player.position.z;
+    if (dx * dx + dz * dz).sqrt() > INTERACT_RANGE + player.hit_radius + object.half_extents.x.max(object.half_extents.z) {
+        return Err("Out of reach".to_string());
+    }
+    check_requirements(ctx, &player, &object)?;
+
+    match object.kind {
+        InteractableKind::Door => {
+            let open = !object.state;
+            set_door_open(ctx, object, open);
+            rebuild_nav_grid(ctx, player.instance_id);
+        }
+        InteractableKind::Lever => {
+            if pull_lever(ctx, object.id) {
+                rebuild_nav_grid(ctx, player.instance_id);
+            }
+        }
+        InteractableKind::Chest => loot_chest(ctx, &player, object)?,
+    }
+    Ok(())
+}
+
+fn require_instance(ctx: &ReducerContext, instance_id: u64) -> Result<(), String> {
+    if ctx.db.instance().id().find(instance_id).is_none() {
+        return Err("Instance not found".to_string());
+    }
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn add_door(ctx: &ReducerContext, position: Vector3, half_extents: Vector3, required_item: Option<String>, required_level: u32, instance_id: u64) -> Result<(), String> {
+    require_admin(ctx)?;
+    require_instance(ctx, instance_id)?;
+    if ![half_extents.x, half_extents.y, half_extents.z].iter().all(|e| e.is_finite() && *e > 0.0) {
+        return Err("Half extents must be positive".to_string());
+    }
+    let door = insert_door(ctx, Interactable { half_extents, required_item, required_level, ..interactable_of(InteractableKind::Door, position, instance_id) });
+    rebuild_nav_grid(ctx, instance_id);
+    record_admin_action(ctx, "add_door", None, format!("{} in instance {}", door.id, instance_id));
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn add_lever(ctx: &ReducerContext, position: Vector3, linked_ids: Vec<u64>, instance_id: u64) -> Result<(), String> {
+    require_admin(ctx)?;
+    require_instance(ctx, instance_id)?;
+    if linked_ids.iter().any(|id| ctx.db.interactable().id().find(*id).is_none_or(|o| o.instance_id != instance_id)) {
+        return Err("Linked objects must exist in the same instance".to_string());
+    }
+    let lever = ctx.db.interactable().insert(Interactable { linked_ids, ..interactable_of(InteractableKind::Lever, position, instance_id) });
+    record_admin_action(ctx, "add_lever", None, format!("{} in instance {} -> {:?}", lever.id, instance_id, lever.linked_ids));
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn add_chest(ctx: &ReducerContext, position: Vector3, loot: Vec<LootTableEntry>, required_item: Option<String>, required_level: u32, instance_id: u64) -> Result<(), String> {
+    require_admin(ctx)?;
+    require_instance(ctx, instance_id)?;
+    validate_loot_entries(ctx, &loot)?;
+    let chest = ctx.db.interactable().insert(Interactable { loot, required_item, required_level, ..interactable_of(InteractableKind::Chest, position, instance_id) });
+    record_admin_action(ctx, "add_chest", None, format!("{} in instance {}", chest.id, instance_id));
+    Ok(())
+}
+
+// Deletes one object along with its door collider
+pub fn delete_interactable(ctx: &ReducerContext, object: Interactable) {
+    if let Some(collider_id) = object.collider_id {
+        ctx.db.static_collider().id().delete(collider_id);
+    }
+    ctx.db.interactable().id().delete(object.id);
+}
+
+#[spacetimedb::reducer]
+pub fn remove_interactable(ctx: &ReducerContext, interactable_id: u64) -> Result<(), String> {
+    require_admin(ctx)?;
+    let Some(object) = ctx.db.interactable().id().find(interactable_id) else {
+        return Err("Interactable not found".to_string());
+    };
+    let (had_collider, instance_id) = (object.collider_id.is_some(), object.instance_id);
+    delete_interactable(ctx, object);
+    if had_collider {
+        rebuild_nav_grid(ctx, instance_id);
+    }
+    record_admin_action(ctx, "remove_interactable", None, format!("{}", interactable_id));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn object(id: u64, kind: InteractableKind, linked_ids: Vec<u64>) -> Interactable {
+        Interactable { id, linked_ids, ..interactable_of(kind, Vector3::default(), LOBBY_INSTANCE_ID) }
+    }
+
+    #[test]
+    fn a_lever_flips_everything_downstream_once_even_when_links_loop() {
+        let objects = vec![
+            object(1, InteractableKind::Lever, vec![2, 3, 5, 9]),
+            object(2, InteractableKind::Door, vec![]),
+            object(3, InteractableKind::Lever, vec![1, 4, 2]),
+            object(4, InteractableKind::Door, vec![]),
+            object(5, InteractableKind::Chest, vec![]),
+        ];
+        assert_eq!(lever_chain(&objects, 1), vec![1, 2, 3, 4], "chests and missing objects are skipped");
+        assert_eq!(lever_chain(&objects, 3), vec![3, 1, 4, 2]);
+    }
+}
@@ -0,0 +1,294 @@
+/*
+ * Vibe Coding Starter Pack: 3D Multiplayer - inventory.rs
+ *
+ * Item definitions, per-player inventories and equipment.
+ *
+ * Key components:
+ *    - ItemDefinition: Public table keyed by item_id (e.g. "gold", "iron_sword"); seeded in init.
+ *      Equippable items have a slot and stat modifiers; mounts a mount_speed_multiplier
+ *    - PlayerInventory: Public table of stacks owned by a player (at most INVENTORY_CAPACITY
+ *      stacks, each up to the item's max_stack); equipped rows are the worn items
+ *    - add_item_to_inventory: Stack-aware insert, returns how many didn't fit
+ *    - count_items / remove_items_from_inventory: Unequipped quantity of an item across stacks,
+ *      and taking some of it away (crafting.rs materials)
+ *    - recompute_equipment_stats: Writes equipment bonuses onto PlayerData (called on any change)
+ *    - grant_starter_items: Class starter kit, equipped on first registration
+ *
+ * Reducers:
+ *    - add_item: Admin grant; all-or-nothing (fails if the inventory can't hold everything)
+ *    - drop_item: Move some or all of a stack onto the ground as a dropped_item (loot.rs)
+ *    - equip_item / unequip_item: One item per EquipSlot; equipping replaces the slot's item
+ *
+ * Equipment stats (consumed by combat::apply_damage and max_health):
+ *    - Weapon damage_multiplier scales all damage the wearer deals
+ *    - Armor bonus_max_health is added to the class base_health; damage_reduction removes a
+ *      fraction of incoming damage (total capped at MAX_DAMAGE_REDUCTION)
+ */
+
+use spacetimedb::{Identity, ReducerContext, SpacetimeType, Table};
+
+use crate::admin::require_admin;
+use crate::classes::class_definition_for;
+use crate::common::{INVENTORY_CAPACITY, MAX_DAMAGE_REDUCTION};
+use crate::loot::spawn_dropped_item;
+use crate::player;
+
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq)]
+pub enum EquipSlot {
+    Weapon,
+    Armor,
+}
+
+#[spacetimedb::table(name = item_definition, public)]
+#[derive(Clone)]
+pub struct ItemDefinition {
+    #[primary_key]
+    pub item_id: String,
+    pub display_name: String,
+    pub max_stack: u32,
+    pub slot: Option<EquipSlot>, // None: not equippable
+    pub damage_multiplier: f32,  // weapons
+    pub bonus_max_health: i32,   // armor
+    pub damage_reduction: f32,   // armor, fraction of incoming damage ignored
+    pub mount_speed_multiplier: f32, // mounts (mounts.rs), 1.0 = not a mount
+}
+
+#[spacetimedb::table(name = player_inventory, public)]
+#[derive(Clone)]
+pub struct PlayerInventory {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    #[index(btree)]
+    pub owner: Identity,
+    pub item_id: String,
+    pub quantity: u32,
+    pub equipped: bool,
+}
+
+fn item(item_id: &str, display_name: &str, max_stack: u32) -> ItemDefinition {
+    ItemDefinition {
+        item_id: item_id.to_string(),
+        display_name: display_name.to_string(),
+        max_stack,
+        slot: None,
+        damage_multiplier: 1.0,
+        bonus_max_health: 0,
+        damage_reduction: 0.0,
+        mount_speed_multiplier: 1.0,
+    }
+}
+
+pub fn seed_item_definitions(ctx: &ReducerContext) {
+    if ctx.db.item_definition().count() > 0 {
+        return;
+    }
+    let defaults = [
+        item("gold", "Gold", 9999),
+        item("health_potion", "Health Potion", 10),
+        item("mana_potion", "Mana Potion", 10),
+        ItemDefinition { slot: Some(EquipSlot::Weapon), damage_multiplier: 1.1, ..item("apprentice_staff", "Apprentice Staff", 1) },
+        ItemDefinition { slot: Some(EquipSlot::Weapon), damage_multiplier: 1.25, ..item("iron_sword", "Iron Sword", 1) },
+        ItemDefinition { slot: Some(EquipSlot::Armor), bonus_max_health: 20, damage_reduction: 0.1, ..item("leather_armor", "Leather Armor", 1) },
+        ItemDefinition { slot: Some(EquipSlot::Armor), bonus_max_health: 50, damage_reduction: 0.25, ..item("plate_armor", "Plate Armor", 1) },
+        ItemDefinition { mount_speed_multiplier: 1.5, ..item("riding_horse", "Riding Horse", 1) },
+        ItemDefinition { mount_speed_multiplier: 1.8, ..item("dire_wolf", "Dire Wolf", 1) },
+        item("iron_ore", "Iron Ore", 50),
+        item("wood", "Wood", 50),
+        item("iron_ingot", "Iron Ingot", 20),
+    ];
+    let count = defaults.len();
+    for definition in defaults {
+        ctx.db.item_definition().insert(definition);
+    }
+    spacetimedb::log::info!("[INIT] Seeded {} item definitions.", count);
+}
+
+pub fn item_definition_for(ctx: &ReducerContext, item_id: &str) -> Option<ItemDefinition> {
+    ctx.db.item_definition().item_id().find(item_id.to_string())
+}
+
+// Tops up existing (unequipped) stacks first, then opens new ones while there is room.
+// Returns the quantity that did not fit.
+pub fn add_item_to_inventory(ctx: &ReducerContext, owner: Identity, item_id: &str, quantity: u32) -> Result<u32, String> {
+    let Some(definition) = item_definition_for(ctx, item_id) else {
+        return Err(format!("Unknown item '{}'", item_id));
+    };
+    let max_stack = definition.max_stack.max(1);
+    let mut remaining = quantity;
+
+    let stacks: Vec<PlayerInventory> = ctx.db.player_inventory().owner().filter(owner).collect();
+    for mut stack in stacks.iter().filter(|s| s.item_id == item_id && !s.equipped).cloned() {
+        if remaining == 0 {
+            break;
+        }
+        let added = remaining.min(max_stack.saturating_sub(stack.quantity));
+        if added > 0 {
+            stack.quantity += added;
+            remaining -= added;
+            ctx.db.player_inventory().id().update(stack);
+        }
+    }
+    let mut used_slots = stacks.len();
+    while remaining > 0 && used_slots < INVENTORY_CAPACITY {
+        let added = remaining.min(max_stack);
+        ctx.db.player_inventory().insert(PlayerInventory { id: 0, owner, item_id: item_id.to_string(), quantity: added, equipped: false });
+        remaining -= added;
+        used_slots += 1;
+    }
+    Ok(remaining)
+}
+
+pub fn count_items(ctx: &ReducerContext, owner: Identity, item_id: &str) -> u32 {
+    ctx.db.player_inventory().owner().filter(owner).filter(|s| s.item_id == item_id && !s.equipped).map(|s| s.quantity).sum()
+}
+
+// Takes from the smallest unequipped stacks first
+pub fn remove_items_from_inventory(ctx: &ReducerContext, owner: Identity, item_id: &str, quantity: u32) -> Result<(), String> {
+    if count_items(ctx, owner, item_id) < quantity {
+        return Err(format!("Not enough '{}'", item_id));
+    }
+    let mut stacks: Vec<PlayerInventory> = ctx.db.player_inventory().owner().filter(owner).filter(|s| s.item_id == item_id && !s.equipped).collect();
+    stacks.sort_by_key(|s| s.quantity);
+    let mut remaining = quantity;
+    for mut stack in stacks {
+        if remaining == 0 {
+            break;
+        }
+        let taken = remaining.min(stack.quantity);
+        remaining -= taken;
+        if taken == stack.quantity {
+            ctx.db.player_inventory().id().delete(stack.id);
+        } else {
+            stack.quantity -= taken;
+            ctx.db.player_inventory().id().update(stack);
+        }
+    }
+    Ok(())
+}
+
+pub fn recompute_equipment_stats(ctx: &ReducerContext, identity: Identity) {
+    let Some(mut player) = ctx.db.player().identity().find(identity) else {
+        return;
+    };
+    let mut damage_multiplier = 1.0;
+    let mut bonus_max_health = 0;
+    let mut damage_reduction = 0.0;
+    for worn in ctx.db.player_inventory().owner().filter(identity).filter(|i| i.equipped) {
+        if let Some(definition) = item_definition_for(ctx, &worn.item_id) {
+            damage_multiplier *= definition.damage_multiplier;
+            bonus_max_health += definition.bonus_max_health;
+            damage_reduction += definition.damage_reduction;
+        }
+    }
+    player.equipment_damage_multiplier = damage_multiplier;
+    player.equipment_damage_reduction = f32::min(damage_reduction, MAX_DAMAGE_REDUCTION);
+    player.max_health = (class_definition_for(ctx, &player.character_class).base_health + bonus_max_health).max(1);
+    player.health = player.health.min(player.max_health);
+    ctx.db.player().identity().update(player);
+}
+
+// Starter kit for brand new players, equipped right away
+pub fn grant_starter_items(ctx: &ReducerContext, identity: Identity, character_class: &str) {
+    if ctx.db.player_inventory().owner().filter(identity).next().is_some() {
+        return;
+    }
+    let kit: &[&str] = match character_class {
+        "Paladin" => &["iron_sword", "leather_armor"],
+        _ => &["apprentice_staff"],
+    };
+    for item_id in kit {
+        if item_definition_for(ctx, item_id).is_some() {
+            ctx.db.player_inventory().insert(PlayerInventory { id: 0, owner: identity, item_id: item_id.to_string(), quantity: 1, equipped: true });
+        }
+    }
+    recompute_equipment_stats(ctx, identity);
+}
+
+fn owned_stack(ctx: &ReducerContext, inventory_id: u64) -> Result<PlayerInventory, String> {
+    match ctx.db.player_inventory().id().find(inventory_id) {
+        Some(stack) if stack.owner == ctx.sender => Ok(stack),
+        _ => Err("Item not found in your inventory".to_string()),
+    }
+}
+
+#[spacetimedb::reducer]
+pub fn add_item(ctx: &ReducerContext, target: Identity, item_id: String, quantity: u32) -> Result<(), String> {
+    require_admin(ctx)?;
+    if quantity == 0 {
+        return Err("Quantity must be positive".to_string());
+    }
+    let leftover = add_item_to_inventory(ctx, target, &item_id, quantity)?;
+    if leftover > 0 {
+        return Err(format!("Inventory is full ({} of {} would not fit)", leftover, quantity));
+    }
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn drop_item(ctx: &ReducerContext, inventory_id: u64, quantity: u32) -> Result<(), String> {
+    let Some(player) = ctx.db.player().identity().find(ctx.sender) else {
+        return Err("Player is not active".to_string());
+    };
+    let mut stack = owned_stack(ctx, inventory_id)?;
+    if quantity == 0 || quantity > stack.quantity {
+        return Err(format!("You can drop between 1 and {}", stack.quantity));
+    }
+
+    spawn_dropped_item(ctx, player.instance_id, &stack.item_id, quantity, player.position);
+    let was_equipped = stack.equipped;
+    if quantity == stack.quantity {
+        ctx.db.player_inventory().id().delete(stack.id);
+    } else {
+        stack.quantity -= quantity;
+        ctx.db.player_inventory().id().update(stack);
+    }
+    if was_equipped {
+        recompute_equipment_stats(ctx, player.identity);
+    }
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn equip_item(ctx: &ReducerContext, inventory_id: u64) -> Result<(), String> {
+    let Some(player) = ctx.db.player().identity().find(ctx.sender) else {
+        return Err("Player is not active".to_string());
+    };
+    if !player.alive {
+        return Err("Cannot change equipment while dead".to_string());
+    }
+    let mut stack = owned_stack(ctx, inventory_id)?;
+    if stack.equipped {
+        return Ok(());
+    }
+    let Some(slot) = item_definition_for(ctx, &stack.item_id).and_then(|d| d.slot) else {
+        return Err("That item can't be equipped".to_string());
+    };
+    if stack.quantity != 1 {
+        return Err("Only single items can be equipped".to_string());
+    }
+
+    // Free the slot first
+    for mut worn in ctx.db.player_inventory().owner().filter(player.identity).filter(|i| i.equipped) {
+        if item_definition_for(ctx, &worn.item_id).and_then(|d| d.slot) == Some(slot) {
+            worn.equipped = false;
+            ctx.db.player_inventory().id().update(worn);
+        }
+    }
+    stack.equipped = true;
+    ctx.db.player_inventory().id().update(stack);
+    recompute_equipment_stats(ctx, player.identity);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn unequip_item(ctx: &ReducerContext, inventory_id: u64) -> Result<(), String> {
+    let mut stack = owned_stack(ctx, inventory_id)?;
+    if !stack.equipped {
+        return Ok(());
+    }
+    stack.equipped = false;
+    ctx.db.player_inventory().id().update(stack);
+    recompute_equipment_stats(ctx, ctx.sender);
+    Ok(())
+}
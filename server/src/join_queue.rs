@@ -0,0 +1,140 @@
+/*
+ * Vibe Coding Starter Pack: 3D Multiplayer - join_queue.rs
+ *
+ * Server capacity (GameConfig::max_players) and the queue of players waiting for a slot.
+ *
+ * Key components:
+ *    - JoinQueueEntry: Public table of waiting players with the username and class they
+ *      registered with. `position` (1 = next in) is renumbered whenever the queue changes
+ *    - enqueue: Called by register_player when the server is full (or others are already
+ *      waiting, so nobody skips the line)
+ *    - admit_queued_players: Lets the oldest entries in while there are free slots. Called
+ *      from register_player, identity_disconnected and every game_tick (covers max_players
+ *      being raised)
+ *    - leave_queue: Dropped from the queue on disconnect, or by the player with the
+ *      leave_join_queue reducer
+ */
+
+use spacetimedb::{Identity, ReducerContext, Table, Timestamp};
+
+use crate::config::get_game_config;
+use crate::player;
+
+#[spacetimedb::table(name = join_queue, public)]
+#[derive(Clone)]
+pub struct JoinQueueEntry {
+    #[primary_key]
+    pub identity: Identity,
+    pub username: String,
+    pub character_class: String,
+    pub queued_at: Timestamp,
+    pub position: u32,
+}
+
+// Slots left with `online` players connected (none when over capacity after max_players drops)
+fn free_slots(online: u64, max_players: u32) -> u64 {
+    (max_players as u64).saturating_sub(online)
+}
+
+pub fn server_full(ctx: &ReducerContext) -> bool {
+    free_slots(ctx.db.player().count(), get_game_config(ctx).max_players) == 0
+}
+
+pub fn has_waiting_players(ctx: &ReducerContext) -> bool {
+    ctx.db.join_queue().count() > 0
+}
+
+// Oldest first, which is also the order they get in
+fn sorted_by_arrival(mut entries: Vec<JoinQueueEntry>) -> Vec<JoinQueueEntry> {
+    entries.sort_by_key(|e| e.queued_at);
+    entries
+}
+
+fn queue_in_order(ctx: &ReducerContext) -> Vec<JoinQueueEntry> {
+    sorted_by_arrival(ctx.db.join_queue().iter().collect())
+}
+
+fn renumber_queue(ctx: &ReducerContext) {
+    for (index, mut entry) in queue_in_order(ctx).into_iter().enumerate() {
+        let position = index as u32 + 1;
+        if entry.position != position {
+            entry.position = position;
+            ctx.db.join_queue().identity().update(entry);
+        }
+    }
+}
+
+// Re-registering while queued keeps the place in line but takes the new name and class
+pub fn enqueue(ctx: &ReducerContext, identity: Identity, username: String, character_class: String) -> u32 {
+    match ctx.db.join_queue().identity().find(identity) {
+        Some(mut entry) => {
+            entry.username = username;
+            entry.character_class = character_class;
+            let position = entry.position;
+            ctx.db.join_queue().identity().update(entry);
+            position
+        }
+        None => {
+            let position = ctx.db.join_queue().count() as u32 + 1;
+            ctx.db.join_queue().insert(JoinQueueEntry { identity, username, character_class, queued_at: ctx.timestamp, position });
+            renumber_queue(ctx);
+            ctx.db.join_queue().identity().find(identity).map_or(position, |e| e.position)
+        }
+    }
+}
+
+pub fn leave_queue(ctx: &ReducerContext, identity: Identity) {
+    if ctx.db.join_queue().identity().delete(identity) {
+        renumber_queue(ctx);
+    }
+}
+
+pub fn admit_queued_players(ctx: &ReducerContext) {
+    let mut admitted = false;
+    for entry in queue_in_order(ctx) {
+        if server_full(ctx) {
+            break;
+        }
+        ctx.db.join_queue().identity().delete(entry.identity);
+        spacetimedb::log::info!("[QUEUE] Admitting {} ({}) from the join queue", entry.username, entry.identity);
+        crate::spawn_player(ctx, entry.identity, entry.username, entry.character_class);
+        admitted = true;
+    }
+    if admitted {
+        renumber_queue(ctx);
+    }
+}
+
+#[spacetimedb::reducer]
+pub fn leave_join_queue(ctx: &ReducerContext) -> Result<(), String> {
+    if ctx.db.join_queue().identity().find(ctx.sender).is_none() {
+        return Err("You are not in the join queue".to_string());
+    }
+    leave_queue(ctx, ctx.sender);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(id: u8, queued_at_secs: i64) -> JoinQueueEntry {
+        JoinQueueEntry {
+            identity: Identity::from_byte_array([id; 32]),
+            username: format!("player{}", id),
+            character_class: "Wizard".to_string(),
+            queued_at: Timestamp::from_micros_since_unix_epoch(queued_at_secs * 1_000_000),
+            position: 0,
+        }
+    }
+
+    #[test]
+    fn the_queue_lets_the_oldest_in_while_slots_are_free() {
+        assert_eq!(free_slots(8, 10), 2);
+        assert_eq!(free_slots(10, 10), 0);
+        assert_eq!(free_slots(12, 10), 0, "max_players lowered below the online count");
+
+        let order: Vec<String> = sorted_by_arrival(vec![entry(1, 30), entry(2, 10), entry(3, 20)]).into_iter().map(|e| e.username).collect();
+        assert_eq!(order, ["player2", "player3", "player1"]);
+    }
+}
@@ -0,0 +1,191 @@
+/*
+ * Vibe Coding Starter Pack: 3D Multiplayer - leaderboard.rs
+ *
+ * All-time rankings, recomputed on a slow schedule so clients subscribe to a short list
+ * instead of scanning every player.
+ *
+ * Key components:
+ *    - CareerStats: Public lifetime totals per identity, kept across matches and sessions
+ *      (player_stats is reset every match). Kills and deaths are added as they happen
+ *      (scoreboard::record_kill), wins when a round ends (record_match_win, players of the
+ *      winning team still in the lobby) and playtime by each recompute for players online
+ *    - LeaderboardEntry: Public ranked rows (category, rank, value), LEADERBOARD_SIZE per
+ *      LeaderboardCategory. Rank 1 is the best; ties are ordered by identity
+ *    - recompute_leaderboards: Scheduled reducer (every LEADERBOARD_INTERVAL_SECS) that
+ *      rebuilds every category from career_stats. K/D needs LEADERBOARD_MIN_KD_KILLS kills to
+ *      be ranked (deaths count as at least 1)
+ */
+
+use spacetimedb::{Identity, ReducerContext, ScheduleAt, SpacetimeType, Table};
+use std::time::Duration;
+
+use crate::common::{LOBBY_INSTANCE_ID, LEADERBOARD_INTERVAL_SECS, LEADERBOARD_MIN_KD_KILLS, LEADERBOARD_SIZE};
+use crate::scoreboard::player_stats;
+use crate::player;
+
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq)]
+pub enum LeaderboardCategory {
+    Kills,
+    KillDeathRatio,
+    Wins,
+    Playtime,
+}
+
+#[spacetimedb::table(name = career_stats, public)]
+#[derive(Clone)]
+pub struct CareerStats {
+    #[primary_key]
+    pub identity: Identity,
+    pub username: String,
+    pub kills: u64,
+    pub deaths: u64,
+    pub wins: u64,
+    pub playtime_secs: u64,
+}
+
+#[spacetimedb::table(name = leaderboard_entry, public)]
+#[derive(Clone)]
+pub struct LeaderboardEntry {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    #[index(btree)]
+    pub category: LeaderboardCategory,
+    pub rank: u32,
+    pub identity: Identity,
+    pub username: String,
+    pub value: f64, // kills, K/D ratio, wins or playtime in seconds
+}
+
+#[spacetimedb::table(name = leaderboard_schedule, scheduled(recompute_leaderboards))]
+pub struct LeaderboardSchedule {
+    #[primary_key]
+    #[auto_inc]
+    scheduled_id: u64,
+    scheduled_at: ScheduleAt,
+}
+
+// Called from init
+pub fn schedule_leaderboards(ctx: &ReducerContext) {
+    if ctx.db.leaderboard_schedule().count() == 0 {
+        ctx.db.leaderboard_schedule().insert(LeaderboardSchedule {
+            scheduled_id: 0,
+            scheduled_at: ScheduleAt::Interval(Duration::from_secs(LEADERBOARD_INTERVAL_SECS).into()),
+        });
+    }
+}
+
+fn update_career(ctx: &ReducerContext, identity: Identity, update: impl FnOnce(&mut CareerStats)) {
+    let username = ctx.db.player_stats().identity().find(identity).map(|s| s.username);
+    match ctx.db.career_stats().identity().find(identity) {
+        Some(mut career) => {
+            if let Some(username) = username {
+                career.username = username;
+            }
+            update(&mut career);
+            ctx.db.career_stats().identity().update(career);
+        }
+        None => {
+            let mut career = CareerStats { identity, username: username.unwrap_or_default(), kills: 0, deaths: 0, wins: 0, playtime_secs: 0 };
+            update(&mut career);
+            ctx.db.career_stats().insert(career);
+        }
+    }
+}
+
+pub fn record_career_kill(ctx: &ReducerContext, killer: Option<Identity>, victim: Identity) {
+    update_career(ctx, victim, |c| c.deaths += 1);
+    if let Some(killer) = killer {
+        update_career(ctx, killer, |c| c.kills += 1);
+    }
+}
+
+// Called from matches::finish_match
+pub fn record_match_win(ctx: &ReducerContext, winning_team: u8) {
+    let winners: Vec<Identity> = ctx.db.player().iter().filter(|p| p.team == winning_team && p.instance_id == LOBBY_INSTANCE_ID).map(|p| p.identity).collect();
+    for identity in winners {
+        update_career(ctx, identity, |c| c.wins += 1);
+    }
+}
+
+fn value_for(category: LeaderboardCategory, career: &CareerStats) -> Option<f64> {
+    match category {
+        LeaderboardCategory::Kills => Some(career.kills as f64),
+        LeaderboardCategory::KillDeathRatio => (career.kills >= LEADERBOARD_MIN_KD_KILLS).then(|| career.kills as f64 / career.deaths.max(1) as f64),
+        LeaderboardCategory::Wins => Some(career.wins as f64),
+        LeaderboardCategory::Playtime => Some(career.playtime_secs as f64),
+    }
+}
+
+// Best first, ties by identity; careers without a positive value aren't ranked
+fn top_careers(careers: &[CareerStats], category: LeaderboardCategory) -> Vec<(&CareerStats, f64)> {
+    let mut ranked: Vec<(&CareerStats, f64)> = careers.iter().filter_map(|c| value_for(category, c).filter(|v| *v > 0.0).map(|v| (c, v))).collect();
+    ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.identity.cmp(&b.0.identity)));
+    ranked.truncate(LEADERBOARD_SIZE);
+    ranked
+}
+
+#[spacetimedb::reducer]
+pub fn recompute_leaderboards(ctx: &ReducerContext, _schedule: LeaderboardSchedule) -> Result<(), String> {
+    if ctx.sender != ctx.identity() {
+        return Err("recompute_leaderboards may only be run by the scheduler".to_string());
+    }
+    let online: Vec<Identity> = ctx.db.player().iter().map(|p| p.identity).collect();
+    for identity in online {
+        update_career(ctx, identity, |c| c.playtime_secs += LEADERBOARD_INTERVAL_SECS);
+    }
+
+    for entry in ctx.db.leaderboard_entry().iter() {
+        ctx.db.leaderboard_entry().id().delete(entry.id);
+    }
+    let careers: Vec<CareerStats> = ctx.db.career_stats().iter().collect();
+    let categories = [LeaderboardCategory::Kills, LeaderboardCategory::KillDeathRatio, LeaderboardCategory::Wins, LeaderboardCategory::Playtime];
+    for category in categories {
+        for (index, (career, value)) in top_careers(&careers, category).into_iter().enumerate() {
+            ctx.db.leaderboard_entry().insert(LeaderboardEntry {
+                id: 0, // auto_inc
+                category,
+                rank: index as u32 + 1,
+                identity: career.identity,
+                username: career.username.clone(),
+                value,
+            });
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn career(id: u8, kills: u64, deaths: u64) -> CareerStats {
+        CareerStats { identity: Identity::from_byte_array([id; 32]), username: format!("player{}", id), kills, deaths, wins: 0, playtime_secs: 0 }
+    }
+
+    fn ranking(careers: &[CareerStats], category: LeaderboardCategory) -> Vec<(String, f64)> {
+        top_careers(careers, category).into_iter().map(|(c, v)| (c.username.clone(), v)).collect()
+    }
+
+    #[test]
+    fn rankings_put_the_best_first_and_break_ties_by_identity() {
+        let min = LEADERBOARD_MIN_KD_KILLS;
+        let careers = vec![career(3, min, 0), career(1, min * 2, 4), career(2, min, 0), career(4, min - 1, 0), career(5, 0, 3)];
+        let kills = ranking(&careers, LeaderboardCategory::Kills);
+        let names: Vec<&str> = kills.iter().map(|(n, _)| n.as_str()).collect();
+        assert_eq!(names, vec!["player1", "player2", "player3", "player4"], "no kills, no rank");
+
+        let kd = ranking(&careers, LeaderboardCategory::KillDeathRatio);
+        assert_eq!(kd.len(), 3, "below LEADERBOARD_MIN_KD_KILLS isn't ranked");
+        assert_eq!(kd[0], ("player2".to_string(), min as f64), "no deaths counts as one");
+        assert_eq!(kd[2], ("player1".to_string(), min as f64 / 2.0));
+    }
+
+    #[test]
+    fn rankings_are_cut_at_the_leaderboard_size() {
+        let careers: Vec<CareerStats> = (1..=(LEADERBOARD_SIZE as u8 + 5)).map(|id| career(id, id as u64, 0)).collect();
+        let kills = top_careers(&careers, LeaderboardCategory::Kills);
+        assert_eq!(kills.len(), LEADERBOARD_SIZE);
+        assert_eq!(kills[0].1, (LEADERBOARD_SIZE + 5) as f64);
+    }
+}
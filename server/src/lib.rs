@@ -1,18 +1,23 @@
-/**
+/*!
  * Vibe Coding Starter Pack: 3D Multiplayer - lib.rs
  * 
  * Main entry point for the SpacetimeDB module. This file contains:
  * 
  * 1. Database Schema:
  *    - PlayerData: Active player information
- *    - LoggedOutPlayerData: Persistent data for disconnected players
+ *    - LoggedOutPlayerData: Persistent data for disconnected players (persistence.rs)
  *    - GameTickSchedule: Periodic update scheduling
+ *    - TickState: Singleton row of server-wide counters (global projectile spawn window, last tick time)
+ *    - ProjectileData: Live projectiles (simulated in projectile_logic.rs)
+ *    - Subsystem tables live next to their logic (pickups.rs, events.rs, status_effects.rs, hazards.rs)
  * 
  * 2. Reducer Functions (Server Endpoints):
  *    - init: Module initialization and game tick scheduling
  *    - identity_connected/disconnected: Connection lifecycle management
  *    - register_player: Player registration with username and character class
- *    - update_player_input: Processes player movement and state updates
+ *    - update_player_input: Queues client input (drained one per tick by game_tick)
+ *    - spawn_projectile / fire_projectile: Cast the "bolt" spell or the spell for a ProjectileKind
+ *      (wrappers around spells::cast_spell, which takes a spell_id)
  *    - game_tick: Periodic update for game state (scheduled)
  * 
  * 3. Table Structure:
@@ -29,17 +34,137 @@
  * Related files:
  *    - common.rs: Shared data structures used in table definitions
  *    - player_logic.rs: Player movement and state update calculations
+ *    - projectile_logic.rs: Projectile specs and per-tick simulation
+ *    - pickups.rs: World pickups and collection
+ *    - events.rs: One-shot event tables for client feedback
+ *    - status_effects.rs: Timed player effects (Disarm, Slow, Stun, Burn, HealOverTime)
+ *    - combat.rs: Shared damage/heal pipeline
+ *    - hazards.rs: Lingering ground hazards (fire patches)
+ *    - environment.rs: Level features that move players (jump pads, one-way and moving platforms, water) and static colliders
+ *    - chat.rs: Chat channels (global/team/whisper), per-recipient delivery, block lists and cleanup
+ *    - scoreboard.rs: Per-match kills/deaths and match history
+ *    - visibility.rs: Stealth toggle, scanner reveals and minimap pings
+ *    - admin.rs: Admin identities, permission checks, audit log and player moderation reducers
+ *    - config.rs: Runtime-tunable settings (game_config singleton)
+ *    - bots.rs: PvE bot turrets and difficulty-scaled aim
+ *    - teams.rs: Team assignment and change_team swap rules
+ *    - spatial.rs: Per-tick spatial hash grid for projectile collision broadphase
+ *    - classes.rs: Character class stat definitions (class_definition table)
+ *    - spells.rs: Spell definitions, per-spell cooldowns and cast_spell
+ *    - npcs.rs: Monster spawners and idle/chase/attack AI
+ *    - pathfinding.rs: Nav grid derived from static colliders and A* for NPCs
+ *    - loot.rs: Death drops and pickup_item
+ *    - inventory.rs: Item definitions, stacked player inventories and weapon/armor equipment
+ *    - abilities.rs: Hotbar slots with per-ability cooldowns (use_ability)
+ *    - anticheat.rs: Input rate limiting, movement speed clamping and violation flags
+ *    - zones.rs: Named map regions with no_pvp / no_cast / regen_boost rules
+ *    - spawns.rs: Spawn point table and the spawn selection strategies
+ *    - matches.rs: Match lifecycle (Lobby, Countdown, InProgress, PostMatch), game modes, team scores and gameplay gating
+ *    - ctf.rs: Capture the Flag flags, pickup/drop/return and captures
+ *    - control_points.rs: King of the Hill control points, capture progress and score over time
+ *    - arenas.rs: Duel challenges, arenas and duel damage isolation
+ *    - join_queue.rs: max_players capacity and the queue of players waiting to join
+ *    - afk.rs: AFK detection from player activity and idle logouts
+ *    - retention.rs: Scheduled purge of long-gone logged out players and the ops log
+ *    - moderation.rs: Bans (checked on connect and register) and chat mutes
+ *    - audit.rs: Audit log of admin, moderation and anticheat actions with capped retention
+ *    - names.rs: Username length, charset, reserved-name and uniqueness rules
+ *    - customization.rs: change_username and customize_character (Appearance) with cooldowns
+ *    - persistence.rs: PersistentPlayerState, what LoggedOutPlayerData saves between sessions
+ *    - friends.rs: Friend requests, friendships and the per-player friend status feed
+ *    - party.rs: Parties, invites and party membership
+ *    - progression.rs: Kill XP, shared with nearby party members
+ *    - guilds.rs: Guilds, ranked membership and nameplate tags
+ *    - trading.rs: Two-phase player trades of items and gold
+ *    - shop.rs: Vendor NPCs buying and selling items for gold
+ *    - quests.rs: Quests with kill/collect/reach objectives and rewards
+ *    - achievements.rs: Milestone unlocks from combat, movement and social hooks
+ *    - daily_rewards.rs: Login streaks and claim_daily_reward
+ *    - leaderboard.rs: Career stats and scheduled top-N leaderboards
+ *    - ranking.rs: Team ELO ratings, placements and ranked seasons
+ *    - matchmaking.rs: MMR- and party-aware match queue and match instances
+ *    - instances.rs: Map instances (lobby and arenas) scoping players, projectiles, NPCs and zones
+ *    - dungeons.rs: Procedurally generated dungeon instances with spawners, a boss and chests
+ *    - bosses.rs: Scripted boss encounters with phases, scheduled abilities and shared loot
+ *    - threat.rs: Per-NPC threat tables for target selection, and taunting
+ *    - pets.rs: Summoned companions that follow their owner and attack the owner's target
+ *    - mounts.rs: Mount items that speed up movement until the rider is hit or dismounts
+ *    - vehicles.rs: Drivable vehicles with seats, simple physics and collision damage
+ *    - interactables.rs: Doors, levers and chests used through the interact reducer
+ *    - destructibles.rs: Breakable props that block movement, drop loot and respawn
+ *    - gathering.rs: Ore and wood resource nodes with gather charges and respawn timers
+ *    - crafting.rs: Crafting recipes and craft_item, turning materials into items
  */
 
 mod common;
 mod player_logic;
 mod physics;
+mod projectile_logic;
+mod pickups;
+mod events;
+mod status_effects;
+mod combat;
+mod hazards;
+mod environment;
+mod chat;
+mod scoreboard;
+mod visibility;
+mod admin;
+mod config;
+mod bots;
+mod teams;
+mod spatial;
+mod classes;
+mod spells;
+mod npcs;
+mod pathfinding;
+mod loot;
+mod inventory;
+mod abilities;
+mod anticheat;
+mod zones;
+mod spawns;
+mod matches;
+mod ctf;
+mod control_points;
+mod arenas;
+mod join_queue;
+mod afk;
+mod retention;
+mod moderation;
+mod audit;
+mod names;
+mod customization;
+mod persistence;
+mod friends;
+mod party;
+mod progression;
+mod guilds;
+mod trading;
+mod shop;
+mod quests;
+mod achievements;
+mod daily_rewards;
+mod leaderboard;
+mod ranking;
+mod matchmaking;
+mod instances;
+mod dungeons;
+mod bosses;
+mod threat;
+mod pets;
+mod mounts;
+mod vehicles;
+mod interactables;
+mod destructibles;
+mod gathering;
+mod crafting;
 
 use spacetimedb::{ReducerContext, Identity, Table, Timestamp, ScheduleAt};
 use std::time::Duration; // Import standard Duration
 
 // Use items from common module (structs are needed for table definitions)
-use crate::common::{Vector3, InputState, PROJECTILE_LIFETIME, PROJECTILE_SPEED, PROJECTILE_DAMAGE, PROJECTILE_RADIUS, PLAYER_RADIUS};
+use crate::common::{Vector3, InputState, ImpactBehavior, ProjectileKind, BufferedInput, JUMP_FORCE, INPUT_BUFFER_TICKS, AXE_MAX_AMMO, MAX_TICK_DELTA_SECS, StatusOnHit, ANTICHEAT_INPUT_BURST, CROUCH_HEIGHT, PLAYER_HEIGHT, MAX_BREATH_SECS, Appearance, DEFAULT_MMR, LOBBY_INSTANCE_ID};
 
 // --- Schema Definitions ---
 
@@ -56,6 +181,22 @@ pub struct ProjectileData {
     pub damage: i32,
     pub lifetime: f32,
     pub start_position: Vector3,
+    pub kind: ProjectileKind,
+    pub hits_pickups: bool,
+    pub bounces_remaining: u32, // public so clients can show remaining ricochets
+    pub pierce_remaining: u32,  // players it can still pass through
+    pub impact_behavior: ImpactBehavior,
+    pub last_hit_identity: Option<Identity>, // piercing shots skip the player they just passed through
+    pub gravity_scale: f32,      // 0 = flies straight
+    pub gravity_delay_secs: f32, // gravity only applies once age_secs passes this
+    pub age_secs: f32,
+    pub min_arm_distance: f32, // passes harmlessly through players until it has traveled this far
+    pub reveal_radius: f32,    // scanner pulse radius, 0 for normal projectiles
+    pub status_on_hit: Option<StatusOnHit>, // spell status effect applied on a direct hit
+    pub explosion_radius: f32, // > 0: explodes with falloff damage when spent (projectile_logic::explode)
+    pub homing_target: Option<Identity>, // picked at cast time, cleared when the target dies or leaves
+    pub homing_turn_rate: f32,           // radians per second, 0 = no homing
+    pub instance_id: u64,                // owner's map instance (instances.rs)
 }
 
 #[spacetimedb::table(name = player, public)]
@@ -83,7 +224,62 @@ pub struct PlayerData {
     forward_vector: Vector3,
     alive: bool,
     hit_radius: f32,
-    respawn_ticks_remaining: i32,
+    respawn_at: Option<Timestamp>, // set on death, game_tick respawns the player once it passes
+    health_regen_progress: f32, // fractional regen carried between ticks
+    mana_regen_progress: f32,
+    last_attacker: Option<Identity>,
+    killed_by: Option<Identity>, // set on death for the client's death camera, cleared on respawn
+    fall_distance: f32, // meters fallen since the last apex, turned into fall damage on landing
+    grounded: bool,     // standing on the ground or a platform (set by game_tick)
+    ult_charge: f32,    // 0..ULT_CHARGE_MAX, cast_ultimate requires a full bar
+    damaged_by: Vec<Identity>, // everyone who hit this player since they last respawned (assists)
+    is_stealthed: bool,        // hidden from enemy minimaps/nameplates unless revealed
+    revealed_until: Option<Timestamp>, // set by scanner projectiles; visible to enemies until then
+    input_queue: Vec<BufferedInput>,   // pending client inputs, at most INPUT_BUFFER_TICKS
+    axe_ammo: u32,                     // throwing axes in hand, refilled by axe pickups and on respawn
+    team: u8,                          // 0..TEAM_COUNT, assigned to the smaller team on register
+    last_team_change: Option<Timestamp>, // change_team cooldown
+    spread_heat: f32,                  // 0..1 bloom from sustained fire, widens shot spread
+    is_damaged: bool,                  // took damage since the last tick (hit-reaction animation)
+    last_cast_time: Option<Timestamp>, // per-class cast cooldown
+    last_damage_time: Option<Timestamp>, // regen is paused for a while after this
+    last_melee_time: Option<Timestamp>,  // melee_attack cooldown
+    last_gather_time: Option<Timestamp>, // gathering::gather_resource cooldown
+    last_hitscan_time: Option<Timestamp>, // fire_hitscan cooldown
+    last_heal_time: Option<Timestamp>,    // cast_heal cooldown
+    last_teleport_time: Option<Timestamp>, // portal cooldown (environment::portal_ready)
+    knockback_velocity: Vector3, // horizontal push from hits, decays with KNOCKBACK_FRICTION (vertical goes into vertical_velocity)
+    equipment_damage_multiplier: f32, // from the equipped weapon (inventory::recompute_equipment_stats)
+    equipment_damage_reduction: f32,  // from equipped armor, fraction of incoming damage ignored
+    last_input_time: Option<Timestamp>, // previous update_player_input call (anticheat rate limit)
+    input_allowance: f32,               // anticheat token bucket, inputs accepted right now
+    shield_amount: i32,                  // absorbed before health (combat::cast_shield)
+    shield_expires_at: Option<Timestamp>,
+    is_crouching: bool, // holding crouch, or stuck crouched under low geometry
+    is_swimming: bool,  // feet inside a water_volume
+    is_mounted: bool,   // riding a mount (mounts.rs)
+    mount_speed_multiplier: f32, // the mount's, 1.0 when not mounted
+    vehicle_id: Option<u64>, // seat taken in a vehicle (vehicles.rs)
+    breath_remaining: f32, // seconds of air left while the head is under water
+    current_zone: Option<String>, // name of the zone the player is in (zones.rs), for the UI
+    duel_id: Option<u64>,         // running duel (arenas.rs)
+    last_activity_time: Timestamp, // last input that did something (afk.rs)
+    is_afk: bool,
+    appearance: Appearance,
+    xp: u64, // progression.rs
+    gold: u64, // currency (trading.rs, shop.rs)
+    mmr: i32, // ranking.rs
+    placement_matches: u32, // rated rounds played this season
+    mmr_season: u32,
+    guild_tag: Option<String>, // guilds.rs, for nameplates
+    instance_id: u64, // map instance (instances.rs); rejoining players start in the lobby
+}
+
+impl PlayerData {
+    // Height of the collision cylinder (projectiles, hitscan, walls)
+    pub fn collision_height(&self) -> f32 {
+        if self.is_crouching { CROUCH_HEIGHT } else { PLAYER_HEIGHT }
+    }
 }
 
 #[spacetimedb::table(name = logged_out_player)]
@@ -91,17 +287,33 @@ pub struct PlayerData {
 pub struct LoggedOutPlayerData {
     #[primary_key]
     identity: Identity,
-    username: String,
-    character_class: String,
-    position: Vector3,
-    rotation: Vector3,
-    health: i32,
-    max_health: i32,
-    mana: i32,
-    max_mana: i32,
+    state: persistence::PersistentPlayerState,
     last_seen: Timestamp,
 }
 
+// Single row (id = 0) of server-wide bookkeeping, fetched via get_tick_state
+#[spacetimedb::table(name = tick_state)]
+#[derive(Clone)]
+pub struct TickState {
+    #[primary_key]
+    id: u32,
+    spawn_window_start: Timestamp,
+    spawns_in_window: u32,
+    last_tick_at: Option<Timestamp>, // game_tick derives delta_time from this
+}
+
+// Fetch the singleton tick_state row, creating it if this database predates the table
+pub fn get_tick_state(ctx: &ReducerContext) -> TickState {
+    ctx.db.tick_state().id().find(0).unwrap_or_else(|| {
+        ctx.db.tick_state().insert(TickState {
+            id: 0,
+            spawn_window_start: ctx.timestamp,
+            spawns_in_window: 0,
+            last_tick_at: None,
+        })
+    })
+}
+
 #[spacetimedb::table(name = game_tick_schedule, public, scheduled(game_tick))]
 pub struct GameTickSchedule {
     #[primary_key]
@@ -115,9 +327,10 @@ pub struct GameTickSchedule {
 #[spacetimedb::reducer(init)]
 pub fn init(ctx: &ReducerContext) -> Result<(), String> {
     spacetimedb::log::info!("[INIT] Initializing Vibe Multiplayer module...");
+    let tick_interval_ms = config::get_game_config(ctx).tick_interval_ms;
     if ctx.db.game_tick_schedule().count() == 0 {
-        spacetimedb::log::info!("[INIT] Scheduling initial game tick (every 50ms)...");
-        let loop_duration = Duration::from_millis(50);
+        spacetimedb::log::info!("[INIT] Scheduling initial game tick (every {}ms)...", tick_interval_ms);
+        let loop_duration = Duration::from_millis(tick_interval_ms as u64);
         let schedule = GameTickSchedule {
             scheduled_id: 0,
             scheduled_at: ScheduleAt::Interval(loop_duration.into()),
@@ -129,13 +342,50 @@ pub fn init(ctx: &ReducerContext) -> Result<(), String> {
     } else {
         spacetimedb::log::info!("[INIT] Game tick already scheduled.");
     }
+    get_tick_state(ctx);
+    pickups::seed_pickups(ctx);
+    environment::seed_environment(ctx);
+    npcs::seed_npc_spawners(ctx);
+    scoreboard::get_match_info(ctx);
+    matches::get_match_state(ctx);
+    ctf::seed_flags(ctx);
+    control_points::seed_control_points(ctx);
+    arenas::seed_arenas(ctx);
+    admin::seed_admin(ctx);
+    classes::seed_class_definitions(ctx);
+    spells::seed_spell_definitions(ctx);
+    inventory::seed_item_definitions(ctx);
+    shop::seed_vendors(ctx);
+    shop::seed_shop_listings(ctx);
+    quests::seed_quest_definitions(ctx);
+    achievements::seed_achievement_definitions(ctx);
+    instances::seed_instances(ctx);
+    zones::seed_zones(ctx);
+    spawns::seed_spawn_points(ctx);
+    chat::schedule_chat_cleanup(ctx);
+    retention::schedule_logged_out_cleanup(ctx);
+    audit::schedule_audit_cleanup(ctx);
+    leaderboard::schedule_leaderboards(ctx);
+    matchmaking::schedule_matchmaking(ctx);
+    dungeons::schedule_dungeon_checks(ctx);
+    npcs::schedule_npc_spawners(ctx);
+    vehicles::seed_vehicles(ctx);
+    interactables::seed_interactables(ctx);
+    loot::seed_loot_tables(ctx);
+    destructibles::seed_destructibles(ctx);
+    gathering::seed_resource_nodes(ctx);
+    crafting::seed_crafting_recipes(ctx);
     Ok(())
 }
 
 #[spacetimedb::reducer(client_connected)]
-pub fn identity_connected(ctx: &ReducerContext) {
+pub fn identity_connected(ctx: &ReducerContext) -> Result<(), String> {
     spacetimedb::log::info!("Client connected: {}", ctx.sender);
+    // Refusing the connection keeps banned identities out entirely
+    moderation::require_not_banned(ctx, ctx.sender)?;
+    daily_rewards::note_login(ctx, ctx.sender);
     // Player registration/re-joining happens in register_player reducer called by client
+    Ok(())
 }
 
 #[spacetimedb::reducer(client_disconnected)]
@@ -144,36 +394,47 @@ pub fn identity_disconnected(ctx: &ReducerContext) {
     spacetimedb::log::info!("Client disconnected: {}", player_identity);
     let logout_time: Timestamp = ctx.timestamp;
 
+    join_queue::leave_queue(ctx, player_identity);
+    if !log_out_player(ctx, player_identity) {
+        spacetimedb::log::warn!("Disconnect by player {} not found in active player table.", player_identity);
+        if let Some(mut logged_out_player) = ctx.db.logged_out_player().identity().find(player_identity) {
+            logged_out_player.last_seen = logout_time;
+            ctx.db.logged_out_player().identity().update(logged_out_player);
+            spacetimedb::log::warn!("Updated last_seen for already logged out player {}.", player_identity);
+        }
+    }
+    join_queue::admit_queued_players(ctx);
+}
+
+// Moves an active player to logged_out_player (on disconnect, or for idling too long in afk.rs)
+pub(crate) fn log_out_player(ctx: &ReducerContext, player_identity: Identity) -> bool {
+    arenas::forfeit_duel(ctx, player_identity);
+    party::remove_from_party(ctx, player_identity);
+    trading::cancel_trades_for(ctx, player_identity);
+    matchmaking::remove_from_matchmaking(ctx, player_identity);
+    pets::despawn_pet(ctx, player_identity);
+    vehicles::leave_vehicle(ctx, player_identity);
     if let Some(player) = ctx.db.player().identity().find(player_identity) {
         spacetimedb::log::info!("Moving player {} to logged_out_player table.", player_identity);
         let logged_out_player = LoggedOutPlayerData {
             identity: player.identity,
-            username: player.username.clone(),
-            character_class: player.character_class.clone(),
-            position: player.position.clone(),
-            rotation: player.rotation.clone(),
-            health: player.health,
-            max_health: player.max_health,
-            mana: player.mana,
-            max_mana: player.max_mana,
-            last_seen: logout_time,
+            state: player.persistent_state(),
+            last_seen: ctx.timestamp,
         };
         ctx.db.logged_out_player().insert(logged_out_player);
         ctx.db.player().identity().delete(player_identity);
+        status_effects::clear_status_effects(ctx, player_identity);
+        friends::refresh_friend_status(ctx, player_identity);
+        true
     } else {
-        spacetimedb::log::warn!("Disconnect by player {} not found in active player table.", player_identity);
-        if let Some(mut logged_out_player) = ctx.db.logged_out_player().identity().find(player_identity) {
-            logged_out_player.last_seen = logout_time;
-            ctx.db.logged_out_player().identity().update(logged_out_player);
-            spacetimedb::log::warn!("Updated last_seen for already logged out player {}.", player_identity);
-        }
+        false
     }
 }
 
 // --- Game Specific Reducers ---
 
 #[spacetimedb::reducer]
-pub fn register_player(ctx: &ReducerContext, username: String, character_class: String) {
+pub fn register_player(ctx: &ReducerContext, username: String, character_class: String) -> Result<(), String> {
     let player_identity: Identity = ctx.sender;
     spacetimedb::log::info!(
         "Registering player {} ({}) with class {}",
@@ -181,84 +442,129 @@ pub fn register_player(ctx: &ReducerContext, username: String, character_class:
         player_identity,
         character_class
     );
+    moderation::require_not_banned(ctx, player_identity)?;
 
     if ctx.db.player().identity().find(player_identity).is_some() {
         spacetimedb::log::warn!("Player {} is already active.", player_identity);
-        return;
+        return Ok(());
+    }
+    let username = names::validate_username(ctx, player_identity, &username)?;
+
+    if join_queue::server_full(ctx) || join_queue::has_waiting_players(ctx) {
+        let position = join_queue::enqueue(ctx, player_identity, username, character_class);
+        spacetimedb::log::info!("Server full, {} queued at position {}.", player_identity, position);
+        join_queue::admit_queued_players(ctx);
+        return Ok(());
     }
+    spawn_player(ctx, player_identity, username, character_class);
+    Ok(())
+}
 
-    // Assign color and position based on current player count
+// Puts a registered player into the world (rejoining from logged_out_player if they have been here before)
+pub(crate) fn spawn_player(ctx: &ReducerContext, player_identity: Identity, username: String, character_class: String) {
+    // Assign color based on current player count, position from the spawn points
     let player_count = ctx.db.player().iter().count();
     let colors = ["cyan", "magenta", "yellow", "lightgreen", "white", "orange"];
     let assigned_color = colors[player_count % colors.len()].to_string();
-    // Simple horizontal offset for spawning, start Y at 1.0
-    let spawn_position = Vector3 { x: (player_count as f32 * 5.0) - 2.5, y: 1.0, z: 0.0 };
+    let team = teams::smallest_team(ctx);
+    let spawn_position = spawns::choose_spawn_point(ctx, player_identity, team);
+
+    let saved = ctx.db.logged_out_player().identity().find(player_identity);
+    let class = classes::class_definition_for(ctx, &character_class);
+    let default_input = InputState {
+        forward: false, backward: false, left: false, right: false,
+        sprint: false, jump: false, attack: false, cast_spell: false,
+        crouch: false, sequence: 0
+    };
+    let mut new_player = PlayerData {
+        identity: player_identity,
+        username,
+        character_class,
+        position: spawn_position,
+        rotation: Vector3 { x: 0.0, y: 0.0, z: 0.0 },
+        health: class.base_health,
+        max_health: class.base_health,
+        mana: class.base_mana,
+        max_mana: class.base_mana,
+        current_animation: "idle".to_string(),
+        is_moving: false,
+        is_running: false,
+        is_attacking: false,
+        is_casting: false,
+        last_input_seq: 0,
+        input: default_input,
+        color: assigned_color,
+        vertical_velocity: 0.0,
+        forward_vector: Vector3 { x: 0.0, y: 0.0, z: -1.0 },
+        alive: true,
+        hit_radius: 0.8,
+        respawn_at: None,
+        health_regen_progress: 0.0,
+        mana_regen_progress: 0.0,
+        last_attacker: None,
+        killed_by: None,
+        fall_distance: 0.0,
+        grounded: true,
+        ult_charge: 0.0,
+        damaged_by: Vec::new(),
+        is_stealthed: false,
+        revealed_until: None,
+        input_queue: Vec::new(),
+        axe_ammo: AXE_MAX_AMMO,
+        team,
+        last_team_change: None,
+        spread_heat: 0.0,
+        is_damaged: false,
+        last_cast_time: None,
+        last_damage_time: None,
+        last_melee_time: None,
+        last_gather_time: None,
+        last_hitscan_time: None,
+        last_heal_time: None,
+        last_teleport_time: None,
+        knockback_velocity: Vector3::default(),
+        equipment_damage_multiplier: 1.0,
+        equipment_damage_reduction: 0.0,
+        last_input_time: None,
+        input_allowance: ANTICHEAT_INPUT_BURST,
+        shield_amount: 0,
+        shield_expires_at: None,
+        is_crouching: false,
+        is_mounted: false,
+        mount_speed_multiplier: 1.0,
+        vehicle_id: None,
+        is_swimming: false,
+        breath_remaining: MAX_BREATH_SECS,
+        current_zone: None,
+        duel_id: None,
+        last_activity_time: ctx.timestamp,
+        is_afk: false,
+        appearance: Appearance::default(),
+        xp: 0,
+        gold: 0,
+        mmr: DEFAULT_MMR,
+        placement_matches: 0,
+        mmr_season: config::get_game_config(ctx).ranked_season,
+        instance_id: LOBBY_INSTANCE_ID,
+        guild_tag: guilds::guild_tag_of(ctx, player_identity),
+    };
 
-    if let Some(logged_out_player) = ctx.db.logged_out_player().identity().find(player_identity) {
+    if let Some(saved) = saved {
         spacetimedb::log::info!("Player {} is rejoining.", player_identity);
-        let default_input = InputState {
-            forward: false, backward: false, left: false, right: false,
-            sprint: false, jump: false, attack: false, cast_spell: false,
-            sequence: 0
-        };
-        let rejoining_player = PlayerData {
-            identity: logged_out_player.identity,
-            username: logged_out_player.username.clone(),
-            character_class: logged_out_player.character_class.clone(),
-            position: spawn_position,
-            rotation: logged_out_player.rotation.clone(),
-            health: logged_out_player.health,
-            max_health: logged_out_player.max_health,
-            mana: logged_out_player.mana,
-            max_mana: logged_out_player.max_mana,
-            current_animation: "idle".to_string(),
-            is_moving: false,
-            is_running: false,
-            is_attacking: false,
-            is_casting: false,
-            last_input_seq: 0,
-            input: default_input,
-            color: assigned_color,
-            vertical_velocity: 0.0,
-            forward_vector: Vector3 { x: 0.0, y: 0.0, z: -1.0 },
-            alive: true,
-            hit_radius: 0.8,
-            respawn_ticks_remaining: 0,
-        };
-        ctx.db.player().insert(rejoining_player);
+        new_player.restore_persistent_state(saved.state);
+        new_player.position = spawn_position;
+        scoreboard::ensure_player_stats(ctx, player_identity, &new_player.username);
+        ctx.db.player().insert(new_player);
         ctx.db.logged_out_player().identity().delete(player_identity);
+        inventory::recompute_equipment_stats(ctx, player_identity);
     } else {
         spacetimedb::log::info!("Registering new player {}.", player_identity);
-        let default_input = InputState {
-            forward: false, backward: false, left: false, right: false,
-            sprint: false, jump: false, attack: false, cast_spell: false,
-            sequence: 0
-        };
-        ctx.db.player().insert(PlayerData {
-            identity: player_identity,
-            username,
-            character_class,
-            position: spawn_position,
-            rotation: Vector3 { x: 0.0, y: 0.0, z: 0.0 },
-            health: 100,
-            max_health: 100,
-            mana: 100,
-            max_mana: 100,
-            current_animation: "idle".to_string(),
-            is_moving: false,
-            is_running: false,
-            is_attacking: false,
-            is_casting: false,
-            last_input_seq: 0,
-            input: default_input,
-            color: assigned_color,
-            vertical_velocity: 0.0,
-            forward_vector: Vector3 { x: 0.0, y: 0.0, z: -1.0 },
-            alive: true,
-            hit_radius: 0.8,
-            respawn_ticks_remaining: 0,
-        });
+        scoreboard::ensure_player_stats(ctx, player_identity, &new_player.username);
+        let starter_class = new_player.character_class.clone();
+        ctx.db.player().insert(new_player);
+        inventory::grant_starter_items(ctx, player_identity, &starter_class);
     }
+    friends::refresh_friend_status(ctx, player_identity);
 }
 
 #[spacetimedb::reducer]
@@ -266,20 +572,22 @@ pub fn update_player_input(
     ctx: &ReducerContext,
     input: InputState,
     client_yaw: f32,
-    forward_vector: Vector3,
+    client_pitch: f32,
     client_animation: String,
 ) {
     if let Some(mut player) = ctx.db.player().identity().find(ctx.sender) {
-        // Handle jump trigger (rising edge)
-        if input.jump && !player.input.jump && player.position.y <= 0.01 {
-            player.vertical_velocity = 9.0; // JUMP_FORCE
+        if !anticheat::accept_input(ctx, &mut player) {
+            ctx.db.player().identity().update(player);
+            return;
+        }
+        let buffered = BufferedInput { input, client_yaw, client_pitch, client_animation };
+        afk::note_input(ctx, &mut player, &buffered);
+        if INPUT_BUFFER_TICKS == 0 {
+            player_logic::apply_client_input(&mut player, buffered);
+        } else {
+            // Queued and applied one per tick in game_tick
+            player_logic::queue_input(&mut player, buffered);
         }
-
-        player.rotation.y = client_yaw;
-        player.forward_vector = forward_vector.normalize();
-        player.input = input.clone();
-        player.last_input_seq = input.sequence;
-        player.current_animation = client_animation;
 
         // NO position calculation here anymore!
         ctx.db.player().identity().update(player);
@@ -289,181 +597,324 @@ pub fn update_player_input(
 }
 
 #[spacetimedb::reducer]
-pub fn spawn_projectile(ctx: &ReducerContext, hand_position: Vector3) {
-    let owner_identity = ctx.sender;
-
-    // 1️⃣ Fetch server-authoritative player data
-    let Some(player) = ctx.db.player().identity().find(owner_identity) else {
-        spacetimedb::log::warn!(
-            "Player {} tried to spawn projectile but is not active.", 
-            owner_identity
-        );
-        return;
+pub fn spawn_projectile(ctx: &ReducerContext, hand_position: Vector3) -> Result<(), String> {
+    spells::cast_spell(ctx, "bolt".to_string(), hand_position)
+}
+
+#[spacetimedb::reducer]
+pub fn fire_projectile(ctx: &ReducerContext, hand_position: Vector3, kind: ProjectileKind) -> Result<(), String> {
+    let Some(spell) = spells::spell_for_kind(ctx, kind) else {
+        return Err(format!("No spell casts {:?} projectiles", kind));
     };
+    spells::cast(ctx, hand_position, spell)
+}
 
-    // 2️⃣ Use stored forward vector from client (already normalized)
-    let direction_normalized = player.forward_vector;
-
-    // 3️⃣ Use hand position as spawn position
-    let spawn_pos = hand_position;
-
-    // 4️⃣ Insert projectile into database
-    ctx.db.projectile().insert(ProjectileData {
-        id: 0, // auto_inc
-        owner_identity,
-        position: spawn_pos,
-        direction: direction_normalized,
-        speed: PROJECTILE_SPEED,
-        damage: PROJECTILE_DAMAGE,
-        lifetime: PROJECTILE_LIFETIME,
-        start_position: spawn_pos,
-    });
-
-    spacetimedb::log::info!("Player {} spawned a projectile at {:?}", owner_identity, spawn_pos);
+// Seconds since the previous game_tick (the configured interval on the very first tick).
+// Clamped to MAX_TICK_DELTA_SECS so a stalled server (or a restart) doesn't teleport
+// players and projectiles through each other in one giant step.
+fn advance_tick_clock(ctx: &ReducerContext, fallback_secs: f32) -> f32 {
+    let mut state = get_tick_state(ctx);
+    let delta_time = tick_delta(state.last_tick_at, ctx.timestamp, fallback_secs);
+    state.last_tick_at = Some(ctx.timestamp);
+    ctx.db.tick_state().id().update(state);
+    delta_time
+}
+
+fn tick_delta(last_tick_at: Option<Timestamp>, now: Timestamp, fallback_secs: f32) -> f32 {
+    let elapsed = last_tick_at
+        .and_then(|last| now.duration_since(last))
+        .map_or(fallback_secs, |elapsed| elapsed.as_secs_f32());
+    if elapsed > MAX_TICK_DELTA_SECS {
+        spacetimedb::log::warn!("[TICK] Tick was {:.0}ms late, clamping step to {:.0}ms", (elapsed - fallback_secs) * 1000.0, MAX_TICK_DELTA_SECS * 1000.0);
+    }
+    elapsed.min(MAX_TICK_DELTA_SECS)
 }
 
 #[spacetimedb::reducer(update)]
 pub fn game_tick(ctx: &ReducerContext, _tick_info: GameTickSchedule) {
-    let delta_time: f32 = 0.050; // 50ms tick rate
+    let config = config::get_game_config(ctx);
+    let delta_time = advance_tick_clock(ctx, config.tick_interval_ms as f32 / 1000.0);
+    afk::update_afk(ctx);
+    join_queue::admit_queued_players(ctx);
+    matches::update_match_state(ctx, delta_time);
+    let match_phase = matches::get_match_state(ctx).phase;
     
     // --- Player Movement Simulation ---
-    for mut player in ctx.db.player().iter().filter(|p| p.alive) {
-        let yaw = player.rotation.y;
-        let input = &player.input;
-
-        // === RE-IMPLEMENT MOVEMENT HERE (DO NOT CALL calculate_new_position) ===
-        let speed = if input.sprint { 15.0 * 1.8 } else { 15.0 };
-        let cos_y = yaw.cos();
-        let sin_y = yaw.sin();
-
-        let forward = Vector3 { x: -sin_y, y: 0.0, z: -cos_y };
-        let right   = Vector3 { x:  cos_y, y: 0.0, z: -sin_y };
-
-        let mut dir = Vector3::default();
-        if input.forward  { dir = dir + forward; }
-        if input.backward { dir = dir - forward; }
-        if input.right    { dir = dir + right; }
-        if input.left     { dir = dir - right; }
-
-        let horiz_len = (dir.x*dir.x + dir.z*dir.z).sqrt();
-        if horiz_len > 0.01 {
-            dir.x /= horiz_len;
-            dir.z /= horiz_len;
+    let jump_pads = environment::load_jump_pads(ctx);
+    let platform_moves = environment::update_moving_platforms(ctx, delta_time);
+    let mut platforms = environment::load_platforms(ctx);
+    platforms.extend(platform_moves.iter().map(|m| m.end_surface()));
+    let colliders = environment::load_static_colliders(ctx);
+    let water_volumes = environment::load_water_volumes(ctx);
+    let portals = environment::load_portals(ctx);
+    let zones = zones::load_zones(ctx);
+    let player_gravity = config.player_gravity;
+    let class_definitions = classes::load_class_definitions(ctx);
+    // Lobby players are frozen during the countdown
+    for mut player in ctx.db.player().iter().filter(|p| p.alive && matches::movement_allowed(match_phase, p.instance_id)) {
+        player_logic::apply_next_input(&mut player);
+        // Seated players ride along in update_vehicles
+        if player.vehicle_id.is_some() {
+            ctx.db.player().identity().update(player);
+            continue;
         }
 
-        let mut new_pos = player.position;
-        new_pos.x += dir.x * speed * delta_time;
-        new_pos.z += dir.z * speed * delta_time;
+        let input = &player.input;
+        let water = environment::water_at(&water_volumes, player.instance_id, &player.position);
+        player.is_swimming = water.is_some();
+
+        // Crouch while held (crouch + jump is a platform drop instead); stay down without headroom.
+        // In water crouch dives instead
+        let wants_crouch = input.crouch && !input.jump && !player.is_swimming;
+        player.is_crouching = wants_crouch
+            || (player.is_crouching && !environment::has_headroom(&colliders, player.instance_id, &player.position, player.hit_radius, CROUCH_HEIGHT, PLAYER_HEIGHT));
+
+        let class_speed = classes::find_class(&class_definitions, &player.character_class).move_speed;
+        let speed_multiplier = status_effects::movement_speed_multiplier(ctx, player.identity);
+        let max_speed = player_logic::movement_speed(&player, class_speed, speed_multiplier) + player.knockback_velocity.length();
+        let carry = environment::carry_delta(&platform_moves, &player);
+        let mut new_pos = player_logic::calculate_new_position(&mut player, carry, class_speed, speed_multiplier, delta_time);
+        anticheat::clamp_displacement(ctx, player.identity, player.position, &mut new_pos, max_speed, delta_time);
+        let input = &player.input;
 
         // === VERTICAL PHYSICS (CRITICAL: this must run every tick) ===
-        player.vertical_velocity += -6.0 * delta_time; // GRAVITY
-
         // Jump (rising edge)
-        if input.jump && player.position.y <= 0.01 {
-            player.vertical_velocity = 9.0; // JUMP_FORCE
+        let start_vertical_speed = player.vertical_velocity.abs();
+        if player_logic::starts_jump(&player, status_effects::is_stunned(ctx, player.identity)) {
+            player.vertical_velocity = JUMP_FORCE;
+            achievements::on_jump(ctx, player.identity);
+        }
+        let drop_through = environment::drops_through(input);
+
+        // Landing is resolved below against platforms and jump pads, so no ground clamp here
+        let prev_y = player.position.y;
+        if let Some(water) = water {
+            // Buoyancy instead of gravity; water breaks any fall
+            player.vertical_velocity = environment::swim_vertical_velocity(water, player.position.y, input);
+            new_pos.y += player.vertical_velocity * delta_time;
+        } else {
+            physics::apply_gravity(&mut new_pos, &mut player.vertical_velocity, player_gravity, delta_time, None);
         }
 
-        new_pos.y += player.vertical_velocity * delta_time;
+        // Track distance fallen since the apex (rising resets it)
+        if player.is_swimming {
+            player.fall_distance = 0.0;
+        } else if player.vertical_velocity < 0.0 {
+            player.fall_distance += -player.vertical_velocity * delta_time;
+        } else {
+            player.fall_distance = 0.0;
+        }
 
-        if new_pos.y <= 0.0 {
-            new_pos.y = 0.0;
+        // Jump pads launch before the ground clamp so a pad landing never counts as a fall
+        environment::apply_jump_pads(&jump_pads, &mut player, &mut new_pos);
+
+        let mut landing_damage = 0;
+        let floor_y = environment::floor_height(&platforms, &colliders, player.instance_id, prev_y, &new_pos, drop_through);
+        player.grounded = false;
+        if new_pos.y <= floor_y {
+            new_pos.y = floor_y;
+            if player.vertical_velocity <= 0.0 {
+                player.grounded = true;
+                landing_damage = player_logic::fall_damage(player.fall_distance);
+                player.vertical_velocity = 0.0;
+                player.fall_distance = 0.0;
+            }
+        }
+
+        // Walls and other solid geometry
+        environment::resolve_player_colliders(&colliders, player.instance_id, &mut new_pos, player.hit_radius, player.collision_height());
+
+        // Impossible jumps are rejected: stay put and lose all momentum
+        let max_vertical_speed = start_vertical_speed.max(player.vertical_velocity.abs()).max(JUMP_FORCE);
+        if !anticheat::is_plausible_move(ctx, player.identity, player.position, new_pos, max_speed, max_vertical_speed, delta_time) {
+            new_pos = player.position;
             player.vertical_velocity = 0.0;
+            player.knockback_velocity = Vector3::default();
+            player.fall_distance = 0.0;
+            landing_damage = 0;
         }
 
         // === APPLY TO PLAYER ===
+        // Portals relocate after the plausibility check, so teleporting is never flagged
+        let entry = if environment::portal_ready(ctx, &player) { environment::portal_crossed(&portals, player.instance_id, player.position, new_pos) } else { None };
         player.position = new_pos;
+        if let Some(entry) = entry {
+            if let Some(exit_id) = environment::teleport_through(&portals, entry, &mut player) {
+                player.last_teleport_time = Some(ctx.timestamp);
+                events::emit_teleport(ctx, player.identity, entry.id, exit_id, new_pos, player.position);
+            }
+        }
+        let new_pos = player.position;
+        let current_zone = zones::zone_at(&zones, player.instance_id, &new_pos);
+        if player.is_swimming || current_zone.is_some_and(|z| z.no_mount) {
+            mounts::force_dismount(&mut player);
+        }
+        let zone = current_zone.map(|z| z.name.clone());
+        let entered_zone = if zone != player.current_zone { zone.clone() } else { None };
+        player.current_zone = zone;
+
+        let head = new_pos + Vector3 { x: 0.0, y: player.collision_height() * 0.9, z: 0.0 };
+        let submerged = environment::water_at(&water_volumes, player.instance_id, &head).is_some();
+        let drown_damage = player_logic::update_breath(&mut player, submerged, delta_time);
 
-        // Reset one-shot inputs
+        // Reset one-shot inputs and the hit-reaction / melee swing flags
+        player.is_damaged = false;
+        player.is_attacking = false;
         player.input.attack = false;
         player.input.cast_spell = false;
         player.input.jump = false; // ← VERY IMPORTANT: clear jump so it doesn't repeat
 
+        let identity = player.identity;
         ctx.db.player().identity().update(player);
+
+        if let Some(zone) = entered_zone {
+            quests::on_zone_entered(ctx, identity, &zone);
+        }
+        if landing_damage > 0 {
+            combat::apply_damage(ctx, identity, landing_damage, None);
+        }
+        if drown_damage > 0 {
+            combat::apply_damage(ctx, identity, drown_damage, None);
+        }
     }
+    player_logic::rescue_fallen_players(ctx);
 
     // --- Projectile Logic ---
-    for mut projectile in ctx.db.projectile().iter() {
-        let pos = projectile.position;
-        let next_pos = pos + projectile.direction * projectile.speed * delta_time;
-
-        // Collision Detection (Simple distance check against all players)
-        let mut hit = false;
-        for mut player in ctx.db.player().iter() {
-            // Skip if same player or player is dead
-            if player.identity != projectile.owner_identity && player.alive {
-                // Check distance using player's hit_radius
-                let dist = (player.position - next_pos).length();
-                if dist < (player.hit_radius + PROJECTILE_RADIUS) {
-                    hit = true;
-                    spacetimedb::log::info!("Projectile hit player: {}", player.username);
-                    
-                    // Apply damage
-                    player.health -= projectile.damage;
-                    if player.health < 0 {
-                        player.health = 0;
-                    }
-                    
-                    // Handle death - delayed respawn (3 seconds = 60 ticks at 50ms)
-                    if player.health <= 0 {
-                        player.alive = false;
-                        player.respawn_ticks_remaining = 60; // 3 seconds
-                        spacetimedb::log::info!("Player {} died! Respawning in 3 seconds...", player.username);
-                    }
-                    
-                    // Update player in database
-                    ctx.db.player().identity().update(player);
-                    break;
-                }
-            }
-        }
+    projectile_logic::enforce_projectile_budget(ctx);
+    projectile_logic::update_projectiles(ctx, delta_time);
+
+    // --- Pickups ---
+    pickups::update_pickups(ctx);
+
+    // --- Status Effects ---
+    status_effects::update_status_effects(ctx, delta_time);
+    combat::expire_shields(ctx);
+
+    // --- Ground Hazards ---
+    hazards::update_hazards(ctx, delta_time);
+    bots::update_bots(ctx, delta_time);
+    npcs::update_npcs(ctx, delta_time);
+    pets::update_pets(ctx, delta_time);
+    vehicles::update_vehicles(ctx, delta_time);
+    loot::despawn_dropped_items(ctx);
+    destructibles::update_destructibles(ctx);
+    gathering::update_resource_nodes(ctx);
+    arenas::update_duels(ctx);
+    party::expire_party_invites(ctx);
 
-        if hit || (pos - projectile.start_position).length() > 50.0 { // Max range 50m
-            ctx.db.projectile().id().delete(projectile.id);
-        } else {
-            // Update position
-            projectile.position = next_pos;
-            ctx.db.projectile().id().update(projectile);
+    // --- Respawn Logic ---
+    for mut player in ctx.db.player().iter() {
+        if !player.alive && player.respawn_at.is_some_and(|t| t <= ctx.timestamp) {
+            player_logic::respawn_player(ctx, &mut player);
+            ctx.db.player().identity().update(player);
         }
     }
     
-    // --- Respawn Logic ---
-    for mut player in ctx.db.player().iter() {
-        if !player.alive && player.respawn_ticks_remaining > 0 {
-            player.respawn_ticks_remaining -= 1;
-            
-            // Respawn when timer reaches 0
-            if player.respawn_ticks_remaining <= 0 {
-                player.alive = true;
-                player.health = player.max_health;
-                player.vertical_velocity = 0.0;
-                player.input.jump = false;
+    player_logic::update_players_logic(ctx, delta_time as f64);
+    events::prune_events(ctx);
+    visibility::prune_minimap_pings(ctx);
 
-                // Deterministic spawn positions
-                let mut identities: Vec<Identity> = ctx.db.player()
-                    .iter()
-                    .map(|p| p.identity)
-                    .collect();
-                identities.sort();
+    // spacetimedb::log::debug!("Game tick completed");
+}
 
-                let index = identities.iter()
-                    .position(|&id| id == player.identity)
-                    .unwrap_or(0);
+// Fixture for unit tests: a live, grounded player at the origin with 100 health and mana
+#[cfg(test)]
+pub(crate) fn test_player(id: u8) -> PlayerData {
+    PlayerData {
+        identity: Identity::from_byte_array([id; 32]),
+        username: format!("player{}", id),
+        character_class: "Wizard".to_string(),
+        position: Vector3 { x: 0.0, y: 0.0, z: 0.0 },
+        rotation: Vector3 { x: 0.0, y: 0.0, z: 0.0 },
+        health: 100,
+        max_health: 100,
+        mana: 100,
+        max_mana: 100,
+        current_animation: "idle".to_string(),
+        is_moving: false,
+        is_running: false,
+        is_attacking: false,
+        is_casting: false,
+        last_input_seq: 0,
+        input: InputState {
+            forward: false, backward: false, left: false, right: false,
+            sprint: false, jump: false, attack: false, cast_spell: false,
+            crouch: false, sequence: 0
+        },
+        color: "#ffffff".to_string(),
+        vertical_velocity: 0.0,
+        forward_vector: Vector3 { x: 0.0, y: 0.0, z: -1.0 },
+        alive: true,
+        hit_radius: 0.8,
+        respawn_at: None,
+        health_regen_progress: 0.0,
+        mana_regen_progress: 0.0,
+        last_attacker: None,
+        killed_by: None,
+        fall_distance: 0.0,
+        grounded: true,
+        damaged_by: Vec::new(),
+        ult_charge: 0.0,
+        is_stealthed: false,
+        revealed_until: None,
+        input_queue: Vec::new(),
+        axe_ammo: AXE_MAX_AMMO,
+        last_team_change: None,
+        team: 0,
+        spread_heat: 0.0,
+        is_damaged: false,
+        last_cast_time: None,
+        last_damage_time: None,
+        last_melee_time: None,
+        last_gather_time: None,
+        last_hitscan_time: None,
+        last_heal_time: None,
+        last_teleport_time: None,
+        knockback_velocity: Vector3::default(),
+        equipment_damage_multiplier: 1.0,
+        equipment_damage_reduction: 0.0,
+        input_allowance: ANTICHEAT_INPUT_BURST,
+        shield_amount: 0,
+        shield_expires_at: None,
+        is_crouching: false,
+        is_swimming: false,
+        is_mounted: false,
+        mount_speed_multiplier: 1.0,
+        vehicle_id: None,
+        breath_remaining: MAX_BREATH_SECS,
+        current_zone: None,
+        duel_id: None,
+        last_activity_time: Timestamp::UNIX_EPOCH,
+        is_afk: false,
+        appearance: Appearance::default(),
+        xp: 0,
+        gold: 0,
+        mmr: DEFAULT_MMR,
+        placement_matches: 0,
+        mmr_season: 1,
+        guild_tag: None,
+        instance_id: LOBBY_INSTANCE_ID,
+        last_input_time: None,
+    }
+}
 
-                player.position = Vector3 {
-                    x: (index as f32 * 5.0) - (identities.len() as f32 * 2.5),
-                    y: 1.0,
-                    z: 0.0,
-                };
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-                player.rotation.y = 0.0;
-                player.forward_vector = Vector3 { x: 0.0, y: 0.0, z: -1.0 };
+    #[test]
+    fn the_first_tick_uses_the_configured_interval_and_later_ticks_the_measured_gap() {
+        let now = Timestamp::from_micros_since_unix_epoch(10_000_000);
+        assert_eq!(tick_delta(None, now, 0.05), 0.05);
 
-                spacetimedb::log::info!("Player {} respawned at {:?}", player.username, player.position);
-            }
-            
-            ctx.db.player().identity().update(player);
-        }
+        let last = Timestamp::from_micros_since_unix_epoch(9_920_000);
+        assert!((tick_delta(Some(last), now, 0.05) - 0.08).abs() < 1e-6);
+    }
+
+    #[test]
+    fn a_stalled_tick_is_clamped_to_the_maximum_step() {
+        let last = Timestamp::from_micros_since_unix_epoch(0);
+        let now = Timestamp::from_micros_since_unix_epoch(2_000_000);
+        assert_eq!(tick_delta(Some(last), now, 0.05), MAX_TICK_DELTA_SECS);
     }
-    
-    // spacetimedb::log::debug!("Game tick completed");
 }
@@ -0,0 +1,255 @@
+/*
+ * Vibe Coding Starter Pack: 3D Multiplayer - loot.rs
+ *
+ * Items dropped on death (or by drop_item) and picked up on request into the player's
+ * inventory (inventory.rs).
+ *
+ * Key components:
+ *    - DroppedItem: Public table of items lying in the world until despawn_at; item_id refers
+ *      to item_definition
+ *    - spawn_dropped_item: Puts a stack on the ground (also used by inventory::drop_item)
+ *    - drop_npc_loot: Gold plus a chance of a potion when an NPC dies, and ogres may drop a
+ *      piece of equipment (npcs.rs)
+ *    - drop_player_loot: A dying player drops half their gold (combat.rs)
+ *    - LootTableEntry / roll_loot: Independent per-entry chance and quantity range, rolled for
+ *      interactable chests (interactables.rs)
+ *    - LootTable: Public table of named loot tables; drop_loot_table rolls one onto the ground
+ *      (destructibles.rs). Seeded in init, replaced with the set_loot_table admin reducer
+ *    - pickup_item: Reducer that moves a dropped item within LOOT_PICKUP_RANGE into the inventory;
+ *      whatever doesn't fit stays on the ground. Gold (CURRENCY_ITEM_ID) goes to PlayerData::gold
+ *      instead and is always picked up in full
+ *    - despawn_dropped_items: Per-tick cleanup of expired drops (called from game_tick)
+ *
+ * Unlike pickups.rs, loot is never collected automatically: clients call pickup_item.
+ */
+
+use spacetimedb::rand::Rng;
+use spacetimedb::{ReducerContext, SpacetimeType, Table, TimeDuration, Timestamp};
+
+use crate::admin::{record_admin_action, require_admin};
+use crate::common::{Vector3, CURRENCY_ITEM_ID, LOOT_DESPAWN_SECS, LOOT_OGRE_EQUIPMENT_CHANCE, LOOT_PICKUP_RANGE, LOOT_POTION_CHANCE};
+use crate::inventory::{add_item_to_inventory, item_definition, item_definition_for};
+use crate::npcs::NpcType;
+use crate::quests::on_item_collected;
+use crate::{player, PlayerData};
+
+#[spacetimedb::table(name = dropped_item, public)]
+#[derive(Clone)]
+pub struct DroppedItem {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub item_id: String,
+    pub quantity: u32,
+    pub position: Vector3,
+    pub despawn_at: Timestamp,
+    pub instance_id: u64,
+}
+
+#[derive(SpacetimeType, Clone, Debug, PartialEq)]
+pub struct LootTableEntry {
+    pub item_id: String,
+    pub min_quantity: u32,
+    pub max_quantity: u32,
+    pub chance: f32, // 0..1, rolled independently per entry
+}
+
+#[spacetimedb::table(name = loot_table, public)]
+#[derive(Clone)]
+pub struct LootTable {
+    #[primary_key]
+    pub id: String,
+    pub entries: Vec<LootTableEntry>,
+}
+
+fn entry(item_id: &str, min_quantity: u32, max_quantity: u32, chance: f32) -> LootTableEntry {
+    LootTableEntry { item_id: item_id.to_string(), min_quantity, max_quantity, chance }
+}
+
+pub fn seed_loot_tables(ctx: &ReducerContext) {
+    if ctx.db.loot_table().count() > 0 {
+        return;
+    }
+    let defaults = [
+        ("crate", vec![entry(CURRENCY_ITEM_ID, 2, 8, 1.0), entry("health_potion", 1, 1, 0.2)]),
+        ("barrel", vec![entry(CURRENCY_ITEM_ID, 1, 4, 0.8), entry("mana_potion", 1, 1, 0.2)]),
+    ];
+    for (id, entries) in defaults {
+        ctx.db.loot_table().insert(LootTable { id: id.to_string(), entries });
+    }
+    spacetimedb::log::info!("[INIT] Seeded loot tables.");
+}
+
+pub fn validate_loot_entries(ctx: &ReducerContext, entries: &[LootTableEntry]) -> Result<(), String> {
+    for entry in entries {
+        if entry.item_id != CURRENCY_ITEM_ID && item_definition_for(ctx, &entry.item_id).is_none() {
+            return Err(format!("Unknown item '{}'", entry.item_id));
+        }
+        if entry.max_quantity < entry.min_quantity || !(0.0..=1.0).contains(&entry.chance) {
+            return Err(format!("Invalid loot entry for '{}'", entry.item_id));
+        }
+    }
+    Ok(())
+}
+
+// (item_id, quantity) for every entry that came up
+pub fn roll_loot(ctx: &ReducerContext, entries: &[LootTableEntry]) -> Vec<(String, u32)> {
+    let mut rng = ctx.rng();
+    let mut rolled = Vec::new();
+    for entry in entries {
+        if !rng.gen_bool(entry.chance.clamp(0.0, 1.0) as f64) {
+            continue;
+        }
+        let quantity = rng.gen_range(entry.min_quantity..=entry.max_quantity.max(entry.min_quantity));
+        if quantity > 0 {
+            rolled.push((entry.item_id.clone(), quantity));
+        }
+    }
+    rolled
+}
+
+pub fn drop_loot_table(ctx: &ReducerContext, loot_table_id: &str, instance_id: u64, position: Vector3) {
+    let Some(table) = ctx.db.loot_table().id().find(loot_table_id.to_string()) else {
+        spacetimedb::log::warn!("Loot table '{}' not found", loot_table_id);
+        return;
+    };
+    for (item_id, quantity) in roll_loot(ctx, &table.entries) {
+        spawn_dropped_item(ctx, instance_id, &item_id, quantity, position);
+    }
+}
+
+pub fn spawn_dropped_item(ctx: &ReducerContext, instance_id: u64, item_id: &str, quantity: u32, position: Vector3) {
+    if quantity == 0 {
+        return;
+    }
+    // Scatter a little so several drops from one death don't stack on the same spot
+    let mut rng = ctx.rng();
+    let offset = Vector3 { x: rng.gen_range(-0.5..=0.5), y: 0.0, z: rng.gen_range(-0.5..=0.5) };
+    ctx.db.dropped_item().insert(DroppedItem {
+        id: 0, // auto_inc
+        item_id: item_id.to_string(),
+        quantity,
+        position: Vector3 { x: position.x, y: position.y.max(0.5), z: position.z } + offset,
+        despawn_at: ctx.timestamp + TimeDuration::from_micros((LOOT_DESPAWN_SECS * 1_000_000.0) as i64),
+        instance_id,
+    });
+}
+
+pub fn drop_npc_loot(ctx: &ReducerContext, npc_type: NpcType, instance_id: u64, position: Vector3) {
+    let mut rng = ctx.rng();
+    let gold = match npc_type {
+        NpcType::Goblin => rng.gen_range(5..=15),
+        NpcType::Ogre => rng.gen_range(30..=60),
+        NpcType::Boss => rng.gen_range(100..=200), // on top of each contributor's share (bosses.rs)
+    };
+    spawn_dropped_item(ctx, instance_id, CURRENCY_ITEM_ID, gold, position);
+    if rng.gen_bool(LOOT_POTION_CHANCE) {
+        let potion = if rng.gen_bool(0.5) { "health_potion" } else { "mana_potion" };
+        spawn_dropped_item(ctx, instance_id, potion, 1, position);
+    }
+    if npc_type == NpcType::Ogre && rng.gen_bool(LOOT_OGRE_EQUIPMENT_CHANCE) {
+        let mut equipment: Vec<String> = ctx.db.item_definition().iter().filter(|d| d.slot.is_some()).map(|d| d.item_id).collect();
+        equipment.sort();
+        if !equipment.is_empty() {
+            let pick = rng.gen_range(0..equipment.len());
+            spawn_dropped_item(ctx, instance_id, &equipment[pick], 1, position);
+        }
+    }
+}
+
+// The caller writes `victim` back
+pub fn drop_player_loot(ctx: &ReducerContext, victim: &mut PlayerData) {
+    let dropped = (victim.gold / 2).min(u32::MAX as u64) as u32;
+    victim.gold -= dropped as u64;
+    spawn_dropped_item(ctx, victim.instance_id, CURRENCY_ITEM_ID, dropped, victim.position);
+}
+
+// Horizontal distance only, measured from the edge of the player's hit cylinder
+fn in_pickup_reach(player: &PlayerData, instance_id: u64, position: Vector3) -> bool {
+    if instance_id != player.instance_id {
+        return false;
+    }
+    let dx = position.x - player.position.x;
+    let dz = position.z - player.position.z;
+    (dx * dx + dz * dz).sqrt() <= LOOT_PICKUP_RANGE + player.hit_radius
+}
+
+#[spacetimedb::reducer]
+pub fn pickup_item(ctx: &ReducerContext, item_id: u64) -> Result<(), String> {
+    let Some(player) = ctx.db.player().identity().find(ctx.sender) else {
+        return Err("Player is not active".to_string());
+    };
+    if !player.alive {
+        return Err("Cannot pick up items while dead".to_string());
+    }
+    let Some(mut item) = ctx.db.dropped_item().id().find(item_id) else {
+        return Err("Item is gone".to_string());
+    };
+    if !in_pickup_reach(&player, item.instance_id, item.position) {
+        return Err("Item is out of reach".to_string());
+    }
+
+    if item.item_id == CURRENCY_ITEM_ID {
+        let mut player = player;
+        player.gold = player.gold.saturating_add(item.quantity as u64);
+        spacetimedb::log::info!("Player {} picked up {} gold", player.username, item.quantity);
+        ctx.db.player().identity().update(player);
+        ctx.db.dropped_item().id().delete(item.id);
+        on_item_collected(ctx, ctx.sender, CURRENCY_ITEM_ID, item.quantity);
+        return Ok(());
+    }
+    let leftover = add_item_to_inventory(ctx, player.identity, &item.item_id, item.quantity)?;
+    if leftover == item.quantity {
+        return Err("Inventory is full".to_string());
+    }
+    spacetimedb::log::info!("Player {} picked up {} x{}", player.username, item.quantity - leftover, item.item_id);
+    on_item_collected(ctx, player.identity, &item.item_id, item.quantity - leftover);
+    if leftover > 0 {
+        item.quantity = leftover;
+        ctx.db.dropped_item().id().update(item);
+    } else {
+        ctx.db.dropped_item().id().delete(item.id);
+    }
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn set_loot_table(ctx: &ReducerContext, loot_table_id: String, entries: Vec<LootTableEntry>) -> Result<(), String> {
+    require_admin(ctx)?;
+    if loot_table_id.trim().is_empty() {
+        return Err("Loot table id must not be empty".to_string());
+    }
+    validate_loot_entries(ctx, &entries)?;
+    let table = LootTable { id: loot_table_id.clone(), entries };
+    if ctx.db.loot_table().id().find(loot_table_id.clone()).is_some() {
+        ctx.db.loot_table().id().update(table);
+    } else {
+        ctx.db.loot_table().insert(table);
+    }
+    record_admin_action(ctx, "set_loot_table", None, loot_table_id);
+    Ok(())
+}
+
+pub fn despawn_dropped_items(ctx: &ReducerContext) {
+    for item in ctx.db.dropped_item().iter() {
+        if item.despawn_at <= ctx.timestamp {
+            ctx.db.dropped_item().id().delete(item.id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::LOBBY_INSTANCE_ID;
+    use crate::test_player;
+
+    #[test]
+    fn items_are_picked_up_within_reach_of_the_players_edge() {
+        let player = test_player(1);
+        let reach = LOOT_PICKUP_RANGE + player.hit_radius;
+        assert!(in_pickup_reach(&player, LOBBY_INSTANCE_ID, Vector3 { x: reach - 0.1, y: 3.0, z: 0.0 }), "height doesn't matter");
+        assert!(!in_pickup_reach(&player, LOBBY_INSTANCE_ID, Vector3 { x: reach, y: 0.0, z: 0.5 }));
+        assert!(!in_pickup_reach(&player, LOBBY_INSTANCE_ID + 1, player.position), "drops in other instances are out of reach");
+    }
+}
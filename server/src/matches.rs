@@ -0,0 +1,366 @@
+/*
+ * Vibe Coding Starter Pack: 3D Multiplayer - matches.rs
+ *
+ * Match lifecycle: Lobby -> Countdown -> InProgress -> PostMatch -> Countdown (next round).
+ * The round only covers the lobby instance; arenas (instances.rs) ignore its phase. Matchmade
+ * matches (matchmaking.rs) run their own lifecycle there, alongside it.
+ *
+ * Key components:
+ *    - MatchState: Public singleton row (id = 0) with the current phase and when it ends
+ *    - GameMode: GameConfig::game_mode picks what scores (add_team_score) and the score target
+ *        - TeamDeathmatch: Each enemy kill scores 1 (scoreboard::record_kill)
+ *        - CaptureTheFlag: Flag captures score (ctf.rs, updated here every InProgress tick)
+ *        - KingOfTheHill: Owning a control point scores over time (control_points.rs, likewise)
+ *    - TeamScore: Public per-team round score
+ *    - MatchResult: Public record of each finished round (winner, final team scores, why it
+ *      ended), trimmed to MATCH_HISTORY_LIMIT rows. Finishing a round also updates career wins
+ *      (leaderboard.rs) and MMR (ranking.rs)
+ *    - update_match_state: Called at the start of every game_tick to advance the phase
+ *        - Lobby: Waits until MATCH_MIN_PLAYERS are in the lobby (or an admin calls start_match)
+ *        - Countdown: Every lobby player is reset (full health and mana, back at a spawn point),
+ *          flags go home, control points turn neutral and team scores are zeroed; after MATCH_COUNTDOWN_SECS the scoreboard is reset and play
+ *          begins
+ *        - InProgress: Ends when a team reaches the mode's score target or
+ *          match_time_limit_secs runs out (either can be 0 to disable it)
+ *        - PostMatch: Results stay up for POST_MATCH_SECS, then the next round counts down
+ *          automatically (back to Lobby if too few players are left)
+ *    - gameplay_enabled / require_in_progress: Gating used by combat::apply_damage, passive
+ *      regen and ult charge (player_logic.rs) and every casting/attack/ability reducer, by the
+ *      instance of the player involved
+ *    - movement_allowed: Only the countdown freezes lobby players in place; in the lobby phase
+ *      and after a round they can still walk around
+ *    - start_match / end_match: Admin reducers to skip the lobby or stop a running match early
+ *      (end_match is rejected outside InProgress)
+ */
+
+use spacetimedb::{ReducerContext, SpacetimeType, Table, TimeDuration, Timestamp};
+use std::time::Duration;
+
+use crate::admin::require_admin;
+use crate::arenas::end_all_duels;
+use crate::common::{LOBBY_INSTANCE_ID, MATCH_COUNTDOWN_SECS, MATCH_HISTORY_LIMIT, MATCH_MIN_PLAYERS, POST_MATCH_SECS, TEAM_COUNT};
+use crate::config::{get_game_config, GameConfig};
+use crate::control_points::{reset_control_points, update_control_points};
+use crate::ctf::{reset_flags, update_flags};
+use crate::leaderboard::record_match_win;
+use crate::player;
+use crate::player_logic::respawn_player;
+use crate::ranking::update_ratings;
+use crate::scoreboard::{archive_match, begin_match, get_match_info};
+use crate::status_effects::clear_status_effects;
+
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq)]
+pub enum MatchPhase {
+    Lobby,
+    Countdown,
+    InProgress,
+    PostMatch,
+}
+
+// Decides what scores team points and the score target (GameConfig)
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq)]
+pub enum GameMode {
+    TeamDeathmatch, // enemy kills, match_kill_target
+    CaptureTheFlag, // flag captures (ctf.rs), ctf_capture_target
+    KingOfTheHill,  // time holding control points (control_points.rs), koth_score_target
+}
+
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq)]
+pub enum MatchEndReason {
+    ScoreLimit,
+    TimeLimit,
+    Admin,   // end_match reducer
+    Forfeit, // every player of a team left a matchmade match (matchmaking.rs)
+}
+
+#[spacetimedb::table(name = match_state, public)]
+#[derive(Clone)]
+pub struct MatchState {
+    #[primary_key]
+    pub id: u32,
+    pub phase: MatchPhase,
+    pub phase_started_at: Timestamp,
+    pub phase_ends_at: Option<Timestamp>, // Countdown and PostMatch only
+}
+
+#[spacetimedb::table(name = team_score, public)]
+#[derive(Clone)]
+pub struct TeamScore {
+    #[primary_key]
+    pub team: u8,
+    pub score: u32,
+}
+
+#[spacetimedb::table(name = match_result, public)]
+#[derive(Clone)]
+pub struct MatchResult {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub match_number: u64,
+    pub winning_team: Option<u8>, // None on a draw
+    pub team_scores: Vec<u32>,    // indexed by team
+    pub reason: MatchEndReason,
+    pub duration_secs: u64,
+    pub ended_at: Timestamp,
+}
+
+pub fn get_match_state(ctx: &ReducerContext) -> MatchState {
+    ctx.db.match_state().id().find(0).unwrap_or_else(|| {
+        ctx.db.match_state().insert(MatchState { id: 0, phase: MatchPhase::Lobby, phase_started_at: ctx.timestamp, phase_ends_at: None })
+    })
+}
+
+fn after_secs(ctx: &ReducerContext, secs: f32) -> Timestamp {
+    ctx.timestamp + TimeDuration::from_micros((secs * 1_000_000.0) as i64)
+}
+
+fn enter_phase(ctx: &ReducerContext, phase: MatchPhase, duration_secs: Option<f32>) {
+    spacetimedb::log::info!("[MATCH] Phase -> {:?}", phase);
+    ctx.db.match_state().id().update(MatchState {
+        id: 0,
+        phase,
+        phase_started_at: ctx.timestamp,
+        phase_ends_at: duration_secs.map(|secs| after_secs(ctx, secs)),
+    });
+}
+
+// The match only runs in the lobby; arenas are always live
+pub fn gameplay_allowed(phase: MatchPhase, instance_id: u64) -> bool {
+    instance_id != LOBBY_INSTANCE_ID || phase == MatchPhase::InProgress
+}
+
+pub fn movement_allowed(phase: MatchPhase, instance_id: u64) -> bool {
+    instance_id != LOBBY_INSTANCE_ID || phase != MatchPhase::Countdown
+}
+
+pub fn gameplay_enabled(ctx: &ReducerContext, instance_id: u64) -> bool {
+    gameplay_allowed(get_match_state(ctx).phase, instance_id)
+}
+
+pub fn require_in_progress(ctx: &ReducerContext, instance_id: u64) -> Result<(), String> {
+    let phase = get_match_state(ctx).phase;
+    if gameplay_allowed(phase, instance_id) {
+        return Ok(());
+    }
+    match phase {
+        MatchPhase::Countdown => Err("The match hasn't started yet".to_string()),
+        _ => Err("No match in progress".to_string()),
+    }
+}
+
+fn lobby_player_count(ctx: &ReducerContext) -> u64 {
+    ctx.db.player().iter().filter(|p| p.instance_id == LOBBY_INSTANCE_ID).count() as u64
+}
+
+// Only counts while a match is running, so kills during the countdown or results don't score
+pub fn add_team_score(ctx: &ReducerContext, team: u8, points: u32) {
+    if get_match_state(ctx).phase != MatchPhase::InProgress {
+        return;
+    }
+    match ctx.db.team_score().team().find(team) {
+        Some(mut row) => {
+            row.score += points;
+            ctx.db.team_score().team().update(row);
+        }
+        None => {
+            ctx.db.team_score().insert(TeamScore { team, score: points });
+        }
+    }
+}
+
+fn team_scores(ctx: &ReducerContext) -> Vec<u32> {
+    (0..TEAM_COUNT).map(|team| ctx.db.team_score().team().find(team).map_or(0, |row| row.score)).collect()
+}
+
+fn reset_team_scores(ctx: &ReducerContext) {
+    for team in 0..TEAM_COUNT {
+        ctx.db.team_score().team().delete(team);
+        ctx.db.team_score().insert(TeamScore { team, score: 0 });
+    }
+}
+
+// Fresh start for every lobby player: alive at a spawn point, full health and mana, no effects
+fn reset_players_for_round(ctx: &ReducerContext) {
+    for mut player in ctx.db.player().iter().filter(|p| p.instance_id == LOBBY_INSTANCE_ID) {
+        respawn_player(ctx, &mut player);
+        player.mana = player.max_mana;
+        player.shield_amount = 0;
+        player.shield_expires_at = None;
+        clear_status_effects(ctx, player.identity);
+        ctx.db.player().identity().update(player);
+    }
+}
+
+fn start_countdown(ctx: &ReducerContext) {
+    end_all_duels(ctx);
+    reset_players_for_round(ctx);
+    reset_flags(ctx);
+    reset_control_points(ctx);
+    reset_team_scores(ctx);
+    enter_phase(ctx, MatchPhase::Countdown, Some(MATCH_COUNTDOWN_SECS));
+}
+
+// The team with the most points, or None when the lead is shared
+pub fn round_winner(scores: &[u32]) -> Option<u8> {
+    let best = scores.iter().copied().max()?;
+    let mut leaders = (0..scores.len()).filter(|t| scores[*t] == best);
+    let winner = leaders.next()?;
+    leaders.next().is_none().then_some(winner as u8)
+}
+
+fn finish_match(ctx: &ReducerContext, state: &MatchState, reason: MatchEndReason) {
+    let scores = team_scores(ctx);
+    let winning_team = round_winner(&scores);
+
+    ctx.db.match_result().insert(MatchResult {
+        id: 0, // auto_inc
+        match_number: get_match_info(ctx).match_number,
+        winning_team,
+        team_scores: scores,
+        reason,
+        duration_secs: ctx.timestamp.duration_since(state.phase_started_at).map_or(0, |d| d.as_secs()),
+        ended_at: ctx.timestamp,
+    });
+    let mut result_ids: Vec<u64> = ctx.db.match_result().iter().map(|r| r.id).collect();
+    if result_ids.len() > MATCH_HISTORY_LIMIT {
+        result_ids.sort();
+        for id in &result_ids[..result_ids.len() - MATCH_HISTORY_LIMIT] {
+            ctx.db.match_result().id().delete(*id);
+        }
+    }
+    spacetimedb::log::info!("[MATCH] Round over ({:?}), winner: {:?}", reason, winning_team);
+    if let Some(team) = winning_team {
+        record_match_win(ctx, team);
+    }
+    update_ratings(ctx, get_match_info(ctx).match_number, winning_team);
+
+    archive_match(ctx);
+    enter_phase(ctx, MatchPhase::PostMatch, Some(POST_MATCH_SECS));
+}
+
+fn score_target(config: &GameConfig) -> u32 {
+    match config.game_mode {
+        GameMode::TeamDeathmatch => config.match_kill_target,
+        GameMode::CaptureTheFlag => config.ctf_capture_target,
+        GameMode::KingOfTheHill => config.koth_score_target,
+    }
+}
+
+// Why the running round should end, if it should. A score target or time limit of 0 is off.
+fn round_over(scores: &[u32], elapsed: Duration, score_target: u32, time_limit_secs: f32) -> Option<MatchEndReason> {
+    if score_target > 0 && scores.iter().any(|s| *s >= score_target) {
+        return Some(MatchEndReason::ScoreLimit);
+    }
+    if time_limit_secs > 0.0 && elapsed >= Duration::from_secs_f32(time_limit_secs) {
+        return Some(MatchEndReason::TimeLimit);
+    }
+    None
+}
+
+fn win_condition(ctx: &ReducerContext, state: &MatchState, config: &GameConfig) -> Option<MatchEndReason> {
+    let elapsed = ctx.timestamp.duration_since(state.phase_started_at).unwrap_or_default();
+    round_over(&team_scores(ctx), elapsed, score_target(config), config.match_time_limit_secs)
+}
+
+// The phase to move to this tick, if any. `round_over` is why a running round ends.
+fn next_phase(phase: MatchPhase, timer_done: bool, players_online: u64, round_over: Option<MatchEndReason>) -> Option<MatchPhase> {
+    let enough_players = players_online >= MATCH_MIN_PLAYERS;
+    match phase {
+        MatchPhase::Lobby if enough_players => Some(MatchPhase::Countdown),
+        MatchPhase::Countdown if timer_done => Some(MatchPhase::InProgress),
+        MatchPhase::InProgress if round_over.is_some() => Some(MatchPhase::PostMatch),
+        MatchPhase::PostMatch if timer_done && enough_players => Some(MatchPhase::Countdown),
+        MatchPhase::PostMatch if timer_done => Some(MatchPhase::Lobby),
+        _ => None,
+    }
+}
+
+pub fn update_match_state(ctx: &ReducerContext, delta_time: f32) {
+    let state = get_match_state(ctx);
+    let timer_done = state.phase_ends_at.is_some_and(|ends_at| ctx.timestamp >= ends_at);
+    let round_over = if state.phase == MatchPhase::InProgress {
+        let config = get_game_config(ctx);
+        match config.game_mode {
+            GameMode::TeamDeathmatch => {}
+            GameMode::CaptureTheFlag => update_flags(ctx),
+            GameMode::KingOfTheHill => update_control_points(ctx, delta_time),
+        }
+        win_condition(ctx, &state, &config)
+    } else {
+        None
+    };
+    match (next_phase(state.phase, timer_done, lobby_player_count(ctx), round_over), round_over) {
+        (Some(MatchPhase::Countdown), _) => start_countdown(ctx),
+        (Some(MatchPhase::InProgress), _) => {
+            begin_match(ctx);
+            enter_phase(ctx, MatchPhase::InProgress, None);
+        }
+        (Some(MatchPhase::PostMatch), Some(reason)) => finish_match(ctx, &state, reason),
+        (Some(MatchPhase::Lobby), _) => enter_phase(ctx, MatchPhase::Lobby, None),
+        _ => {}
+    }
+}
+
+#[spacetimedb::reducer]
+pub fn start_match(ctx: &ReducerContext) -> Result<(), String> {
+    require_admin(ctx)?;
+    match get_match_state(ctx).phase {
+        MatchPhase::Lobby | MatchPhase::PostMatch => {
+            start_countdown(ctx);
+            Ok(())
+        }
+        phase => Err(format!("Match is already starting ({:?})", phase)),
+    }
+}
+
+#[spacetimedb::reducer]
+pub fn end_match(ctx: &ReducerContext) -> Result<(), String> {
+    require_admin(ctx)?;
+    require_in_progress(ctx, LOBBY_INSTANCE_ID)?;
+    finish_match(ctx, &get_match_state(ctx), MatchEndReason::Admin);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_cycle_through_their_phases() {
+        let enough = MATCH_MIN_PLAYERS;
+        assert_eq!(next_phase(MatchPhase::Lobby, false, enough - 1, None), None, "waiting for players");
+        assert_eq!(next_phase(MatchPhase::Lobby, false, enough, None), Some(MatchPhase::Countdown));
+        assert_eq!(next_phase(MatchPhase::Countdown, false, enough, None), None);
+        assert_eq!(next_phase(MatchPhase::Countdown, true, enough, None), Some(MatchPhase::InProgress));
+        assert_eq!(next_phase(MatchPhase::InProgress, false, enough, None), None);
+        assert_eq!(next_phase(MatchPhase::InProgress, false, enough, Some(MatchEndReason::TimeLimit)), Some(MatchPhase::PostMatch));
+        assert_eq!(next_phase(MatchPhase::PostMatch, true, enough, None), Some(MatchPhase::Countdown), "next round");
+        assert_eq!(next_phase(MatchPhase::PostMatch, true, enough - 1, None), Some(MatchPhase::Lobby));
+        assert!(!movement_allowed(MatchPhase::Countdown, LOBBY_INSTANCE_ID), "frozen on the start line");
+        assert!(movement_allowed(MatchPhase::Lobby, LOBBY_INSTANCE_ID) && movement_allowed(MatchPhase::PostMatch, LOBBY_INSTANCE_ID));
+    }
+
+    #[test]
+    fn only_the_lobby_follows_the_round_phase() {
+        let arena = LOBBY_INSTANCE_ID + 1;
+        assert!(gameplay_allowed(MatchPhase::InProgress, LOBBY_INSTANCE_ID));
+        assert!(!gameplay_allowed(MatchPhase::PostMatch, LOBBY_INSTANCE_ID));
+        assert!(gameplay_allowed(MatchPhase::PostMatch, arena) && gameplay_allowed(MatchPhase::Countdown, arena));
+        assert!(movement_allowed(MatchPhase::Countdown, arena), "arenas aren't frozen by the lobby countdown");
+    }
+
+    #[test]
+    fn rounds_end_on_the_score_target_or_the_time_limit() {
+        let minute = Duration::from_secs(60);
+        assert_eq!(round_over(&[10, 4], minute, 10, 0.0), Some(MatchEndReason::ScoreLimit));
+        assert_eq!(round_over(&[9, 4], minute, 10, 60.0), Some(MatchEndReason::TimeLimit));
+        assert_eq!(round_over(&[9, 4], minute, 10, 120.0), None);
+        assert_eq!(round_over(&[50, 4], minute * 60, 0, 0.0), None, "both disabled");
+
+        assert_eq!(round_winner(&[3, 7]), Some(1));
+        assert_eq!(round_winner(&[5, 5]), None, "draw");
+        assert_eq!(round_winner(&[]), None);
+    }
+}
+
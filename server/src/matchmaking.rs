@@ -0,0 +1,412 @@
+/*
+ * Vibe Coding Starter Pack: 3D Multiplayer - matchmaking.rs
+ *
+ * Queue for rated team matches, grouped by MMR (ranking.rs) and party.
+ *
+ * Key components:
+ *    - MatchmakingQueue: Public table of queued players. A party leader queues the whole
+ *      party (everyone online) as one group (group_id = the leader); solo players are a group
+ *      of one. queue_for_match / leave_queue are the reducers; leaving (or logging out) takes
+ *      the whole group out
+ *    - MatchInstance: Public record of each match the matcher put together, with its two teams,
+ *      their kill scores and, once it is over, the winner and why it ended
+ *    - run_matchmaker: Scheduled reducer (every MATCHMAKING_INTERVAL_SECS):
+ *        - Groups are tried oldest first. Each one collects the other groups whose average MMR
+ *          is within its band (MATCHMAKING_MMR_BAND, widened by MATCHMAKING_BAND_GROWTH per
+ *          second waited) until there are 2 * MATCHMAKING_TEAM_SIZE players. Parties always
+ *          stay on one team; teams are balanced by total MMR
+ *        - A formed match starts MATCHMAKING_START_DELAY_SECS later in a fresh arena
+ *          (instances.rs): players are moved to their team, then into the arena, and
+ *          respawned at full health and mana at one of the team's spawn points
+ *    - Each running match is checked by run_matchmaker independently of the lobby round
+ *      (matches.rs). Every enemy kill in its arena scores for the killer's team
+ *      (scoreboard::record_kill). It ends when a team reaches GameConfig::match_kill_target,
+ *      when match_time_limit_secs has passed since it started (either can be 0 to disable it),
+ *      or as a Forfeit once every player of a team has left the arena
+ *    - finish_match_instance: Rates the match (ranking::update_match_instance_ratings), marks it
+ *      Finished and sends its players back to the lobby, removing the arena; at most
+ *      MATCH_HISTORY_LIMIT finished instances are kept
+ *    - in_active_instance: A player counts as in a match (and can't queue again) while it is
+ *      starting, or while it runs and they are still in its arena
+ */
+
+use spacetimedb::{Identity, ReducerContext, ScheduleAt, SpacetimeType, Table, TimeDuration, Timestamp};
+use std::time::Duration;
+
+use crate::common::{MATCHMAKING_BAND_GROWTH, MATCHMAKING_INTERVAL_SECS, MATCHMAKING_MMR_BAND, MATCHMAKING_START_DELAY_SECS, MATCHMAKING_TEAM_SIZE, MATCH_HISTORY_LIMIT};
+use crate::config::get_game_config;
+use crate::party::{party, party_members, party_of};
+use crate::instances::{create_arena, move_to_instance, remove_instance};
+use crate::matches::{round_winner, MatchEndReason};
+use crate::ranking::update_match_instance_ratings;
+use crate::{player, PlayerData};
+
+#[spacetimedb::table(name = matchmaking_queue, public)]
+#[derive(Clone)]
+pub struct MatchmakingQueue {
+    #[primary_key]
+    pub identity: Identity,
+    #[index(btree)]
+    pub group_id: Identity, // identity of the player who queued the group
+    pub mmr: i32,
+    pub queued_at: Timestamp,
+}
+
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq)]
+pub enum MatchInstanceState {
+    Starting,
+    InProgress,
+    Finished,
+}
+
+#[spacetimedb::table(name = match_instance, public)]
+#[derive(Clone)]
+pub struct MatchInstance {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub state: MatchInstanceState,
+    pub teams: Vec<Vec<Identity>>, // index = team
+    pub average_mmr: i32,
+    pub created_at: Timestamp,
+    pub starts_at: Timestamp,
+    pub map_instance_id: Option<u64>, // arena the match is played in (instances.rs), once started
+    pub team_scores: Vec<u32>, // enemy kills, indexed by team
+    pub started_at: Option<Timestamp>,
+    pub winning_team: Option<u8>, // None on a draw or while running
+    pub end_reason: Option<MatchEndReason>,
+}
+
+#[spacetimedb::table(name = matchmaking_schedule, scheduled(run_matchmaker))]
+pub struct MatchmakingSchedule {
+    #[primary_key]
+    #[auto_inc]
+    scheduled_id: u64,
+    scheduled_at: ScheduleAt,
+}
+
+// Called from init
+pub fn schedule_matchmaking(ctx: &ReducerContext) {
+    if ctx.db.matchmaking_schedule().count() == 0 {
+        ctx.db.matchmaking_schedule().insert(MatchmakingSchedule {
+            scheduled_id: 0,
+            scheduled_at: ScheduleAt::Interval(Duration::from_secs(MATCHMAKING_INTERVAL_SECS).into()),
+        });
+    }
+}
+
+struct QueueGroup {
+    id: Identity,
+    members: Vec<Identity>,
+    mmr: f32,
+    queued_at: Timestamp,
+}
+
+pub fn in_active_instance(ctx: &ReducerContext, identity: Identity) -> bool {
+    let instance_id = ctx.db.player().identity().find(identity).map(|p| p.instance_id);
+    ctx.db.match_instance().iter().any(|m| {
+        let playing = match m.state {
+            MatchInstanceState::Starting => true,
+            MatchInstanceState::InProgress => m.map_instance_id.is_some() && m.map_instance_id == instance_id,
+            MatchInstanceState::Finished => false,
+        };
+        playing && m.teams.iter().any(|t| t.contains(&identity))
+    })
+}
+
+// Arena of the running match `identity` was matched into, if any
+pub fn match_arena_of(ctx: &ReducerContext, identity: Identity) -> Option<u64> {
+    ctx.db
+        .match_instance()
+        .iter()
+        .find(|m| m.state == MatchInstanceState::InProgress && m.teams.iter().any(|t| t.contains(&identity)))
+        .and_then(|m| m.map_instance_id)
+}
+
+pub fn remove_from_matchmaking(ctx: &ReducerContext, identity: Identity) {
+    if let Some(entry) = ctx.db.matchmaking_queue().identity().find(identity) {
+        ctx.db.matchmaking_queue().group_id().delete(entry.group_id);
+    }
+}
+
+fn queued_groups(ctx: &ReducerContext) -> Vec<QueueGroup> {
+    let mut groups: Vec<QueueGroup> = Vec::new();
+    for entry in ctx.db.matchmaking_queue().iter() {
+        match groups.iter_mut().find(|g| g.id == entry.group_id) {
+            Some(group) => {
+                group.members.push(entry.identity);
+                group.mmr += entry.mmr as f32;
+            }
+            None => groups.push(QueueGroup { id: entry.group_id, members: vec![entry.identity], mmr: entry.mmr as f32, queued_at: entry.queued_at }),
+        }
+    }
+    for group in &mut groups {
+        group.mmr /= group.members.len() as f32;
+    }
+    groups.sort_by_key(|g| g.queued_at);
+    groups
+}
+
+fn band_for(ctx: &ReducerContext, group: &QueueGroup) -> f32 {
+    let waited = ctx.timestamp.duration_since(group.queued_at).map_or(0.0, |d| d.as_secs_f32());
+    MATCHMAKING_MMR_BAND + waited * MATCHMAKING_BAND_GROWTH
+}
+
+// Splits groups into two teams of MATCHMAKING_TEAM_SIZE, biggest groups first, each onto the
+// team with the lower total MMR that still has room. None if they can't be packed
+fn assign_teams(groups: &[&QueueGroup]) -> Option<Vec<Vec<Identity>>> {
+    let mut order: Vec<&QueueGroup> = groups.to_vec();
+    order.sort_by(|a, b| b.members.len().cmp(&a.members.len()).then(b.mmr.total_cmp(&a.mmr)));
+    let mut teams: Vec<(Vec<Identity>, f32)> = vec![(Vec::new(), 0.0), (Vec::new(), 0.0)];
+    for group in order {
+        let size = group.members.len();
+        let team = (0..teams.len())
+            .filter(|t| teams[*t].0.len() + size <= MATCHMAKING_TEAM_SIZE)
+            .min_by(|a, b| teams[*a].1.total_cmp(&teams[*b].1))?;
+        teams[team].0.extend(group.members.iter().copied());
+        teams[team].1 += group.mmr * size as f32;
+    }
+    Some(teams.into_iter().map(|(members, _)| members).collect())
+}
+
+fn form_matches(ctx: &ReducerContext) {
+    let mut groups = queued_groups(ctx);
+    let needed = MATCHMAKING_TEAM_SIZE * 2;
+    let mut anchor = 0;
+    while anchor < groups.len() {
+        let band = band_for(ctx, &groups[anchor]);
+        let mut picked: Vec<usize> = vec![anchor];
+        let mut players = groups[anchor].members.len();
+        for (index, group) in groups.iter().enumerate() {
+            if index == anchor || players + group.members.len() > needed || (group.mmr - groups[anchor].mmr).abs() > band {
+                continue;
+            }
+            picked.push(index);
+            players += group.members.len();
+            if players == needed {
+                break;
+            }
+        }
+        let teams = (players == needed).then(|| assign_teams(&picked.iter().map(|i| &groups[*i]).collect::<Vec<_>>())).flatten();
+        let Some(teams) = teams else {
+            anchor += 1;
+            continue;
+        };
+
+        let team_count = teams.len();
+        let total_mmr: f32 = picked.iter().map(|i| groups[*i].mmr * groups[*i].members.len() as f32).sum();
+        for index in &picked {
+            ctx.db.matchmaking_queue().group_id().delete(groups[*index].id);
+        }
+        let instance = ctx.db.match_instance().insert(MatchInstance {
+            id: 0, // auto_inc
+            state: MatchInstanceState::Starting,
+            teams,
+            average_mmr: (total_mmr / needed as f32).round() as i32,
+            created_at: ctx.timestamp,
+            starts_at: ctx.timestamp + TimeDuration::from_micros((MATCHMAKING_START_DELAY_SECS * 1_000_000.0) as i64),
+            map_instance_id: None,
+            team_scores: vec![0; team_count],
+            started_at: None,
+            winning_team: None,
+            end_reason: None,
+        });
+        spacetimedb::log::info!("[MATCHMAKING] Formed match {} (average MMR {})", instance.id, instance.average_mmr);
+        picked.sort_unstable_by(|a, b| b.cmp(a));
+        for index in picked {
+            groups.remove(index);
+        }
+    }
+}
+
+fn start_instance(ctx: &ReducerContext, mut instance: MatchInstance) {
+    // Teams are stored first so respawning picks a spawn point of the new team
+    for (team, members) in instance.teams.iter().enumerate() {
+        for identity in members {
+            if let Some(mut player) = ctx.db.player().identity().find(*identity) {
+                player.team = team as u8;
+                ctx.db.player().identity().update(player);
+            }
+        }
+    }
+    let arena = create_arena(ctx, format!("Match {}", instance.id));
+    for identity in instance.teams.iter().flatten() {
+        if move_to_instance(ctx, *identity, arena.id).is_err() {
+            continue;
+        }
+        if let Some(mut player) = ctx.db.player().identity().find(*identity) {
+            player.mana = player.max_mana;
+            ctx.db.player().identity().update(player);
+        }
+    }
+    instance.map_instance_id = Some(arena.id);
+    instance.state = MatchInstanceState::InProgress;
+    instance.started_at = Some(ctx.timestamp);
+    spacetimedb::log::info!("[MATCHMAKING] Match {} started", instance.id);
+    ctx.db.match_instance().id().update(instance);
+}
+
+// Called from scoreboard::record_kill for kills outside the lobby
+pub fn record_match_instance_kill(ctx: &ReducerContext, map_instance_id: u64, team: u8) {
+    let running = ctx.db.match_instance().iter().find(|m| m.state == MatchInstanceState::InProgress && m.map_instance_id == Some(map_instance_id));
+    if let Some(mut instance) = running {
+        let Some(score) = instance.team_scores.get_mut(team as usize) else {
+            return;
+        };
+        *score += 1;
+        ctx.db.match_instance().id().update(instance);
+    }
+}
+
+// Whether a running match is over, and who won. `present` counts each team's players still in
+// the arena; a team with nobody left forfeits
+fn match_instance_end(scores: &[u32], present: &[usize], kill_target: u32, elapsed_secs: f32, time_limit_secs: f32) -> Option<(MatchEndReason, Option<u8>)> {
+    let mut remaining = (0..present.len()).filter(|t| present[*t] > 0);
+    match (remaining.next(), remaining.next()) {
+        (Some(_), Some(_)) => {}
+        (last_team, _) => return Some((MatchEndReason::Forfeit, last_team.map(|t| t as u8))),
+    }
+    if kill_target > 0 && scores.iter().any(|s| *s >= kill_target) {
+        return Some((MatchEndReason::ScoreLimit, round_winner(scores)));
+    }
+    if time_limit_secs > 0.0 && elapsed_secs >= time_limit_secs {
+        return Some((MatchEndReason::TimeLimit, round_winner(scores)));
+    }
+    None
+}
+
+fn update_match_instances(ctx: &ReducerContext) {
+    let config = get_game_config(ctx);
+    for instance in ctx.db.match_instance().iter().filter(|m| m.state == MatchInstanceState::InProgress) {
+        let present: Vec<usize> = instance
+            .teams
+            .iter()
+            .map(|members| members.iter().filter(|id| ctx.db.player().identity().find(**id).is_some_and(|p| Some(p.instance_id) == instance.map_instance_id)).count())
+            .collect();
+        let elapsed_secs = instance.started_at.and_then(|t| ctx.timestamp.duration_since(t)).map_or(0.0, |d| d.as_secs_f32());
+        if let Some((reason, winning_team)) = match_instance_end(&instance.team_scores, &present, config.match_kill_target, elapsed_secs, config.match_time_limit_secs) {
+            finish_match_instance(ctx, instance, reason, winning_team);
+        }
+    }
+}
+
+pub fn finish_match_instance(ctx: &ReducerContext, mut instance: MatchInstance, reason: MatchEndReason, winning_team: Option<u8>) {
+    update_match_instance_ratings(ctx, instance.id, &instance.teams, winning_team);
+    if let Some(arena) = instance.map_instance_id {
+        remove_instance(ctx, arena);
+    }
+    spacetimedb::log::info!("[MATCHMAKING] Match {} over ({:?}), winner: {:?}", instance.id, reason, winning_team);
+    instance.state = MatchInstanceState::Finished;
+    instance.winning_team = winning_team;
+    instance.end_reason = Some(reason);
+    ctx.db.match_instance().id().update(instance);
+
+    let mut finished: Vec<u64> = ctx.db.match_instance().iter().filter(|m| m.state == MatchInstanceState::Finished).map(|m| m.id).collect();
+    if finished.len() > MATCH_HISTORY_LIMIT {
+        finished.sort();
+        for id in &finished[..finished.len() - MATCH_HISTORY_LIMIT] {
+            ctx.db.match_instance().id().delete(*id);
+        }
+    }
+}
+
+#[spacetimedb::reducer]
+pub fn run_matchmaker(ctx: &ReducerContext, _schedule: MatchmakingSchedule) -> Result<(), String> {
+    if ctx.sender != ctx.identity() {
+        return Err("run_matchmaker may only be run by the scheduler".to_string());
+    }
+    form_matches(ctx);
+    let due: Vec<MatchInstance> = ctx.db.match_instance().iter().filter(|m| m.state == MatchInstanceState::Starting && m.starts_at <= ctx.timestamp).collect();
+    for instance in due {
+        start_instance(ctx, instance);
+    }
+    update_match_instances(ctx);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn queue_for_match(ctx: &ReducerContext) -> Result<(), String> {
+    if ctx.db.player().identity().find(ctx.sender).is_none() {
+        return Err("Player is not active".to_string());
+    }
+    let mut members = party_members(ctx, ctx.sender);
+    if members.is_empty() {
+        members.push(ctx.sender);
+    } else {
+        let leader = party_of(ctx, ctx.sender).and_then(|id| ctx.db.party().id().find(id)).map(|p| p.leader);
+        if leader != Some(ctx.sender) {
+            return Err("Only the party leader can queue the party".to_string());
+        }
+    }
+    let members: Vec<PlayerData> = members.into_iter().filter_map(|m| ctx.db.player().identity().find(m)).collect();
+    if members.len() > MATCHMAKING_TEAM_SIZE {
+        return Err(format!("Parties of more than {} can't queue", MATCHMAKING_TEAM_SIZE));
+    }
+    for member in &members {
+        if ctx.db.matchmaking_queue().identity().find(member.identity).is_some() {
+            return Err(format!("{} is already queued", member.username));
+        }
+        if in_active_instance(ctx, member.identity) {
+            return Err(format!("{} is already in a match", member.username));
+        }
+    }
+    for member in &members {
+        ctx.db.matchmaking_queue().insert(MatchmakingQueue { identity: member.identity, group_id: ctx.sender, mmr: member.mmr, queued_at: ctx.timestamp });
+    }
+    spacetimedb::log::info!("[MATCHMAKING] {} queued with {} player(s)", ctx.sender, members.len());
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn leave_queue(ctx: &ReducerContext) -> Result<(), String> {
+    if ctx.db.matchmaking_queue().identity().find(ctx.sender).is_none() {
+        return Err("You are not queued".to_string());
+    }
+    remove_from_matchmaking(ctx, ctx.sender);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn group(first: u8, size: u8, mmr: f32) -> QueueGroup {
+        let members: Vec<Identity> = (first..first + size).map(|id| Identity::from_byte_array([id; 32])).collect();
+        QueueGroup { id: members[0], members, mmr, queued_at: Timestamp::from_micros_since_unix_epoch(0) }
+    }
+
+    fn ids(team: &[Identity]) -> Vec<u8> {
+        let mut ids: Vec<u8> = team.iter().map(|i| i.to_byte_array()[0]).collect();
+        ids.sort();
+        ids
+    }
+
+    #[test]
+    fn teams_are_packed_biggest_group_first_and_balanced_by_mmr() {
+        assert_eq!(MATCHMAKING_TEAM_SIZE, 2, "the cases below assume teams of two");
+        let solos = [group(1, 1, 1000.0), group(2, 1, 1300.0), group(3, 1, 1100.0), group(4, 1, 1200.0)];
+        let teams = assign_teams(&solos.iter().collect::<Vec<_>>()).unwrap();
+        assert_eq!((ids(&teams[0]), ids(&teams[1])), (vec![1, 2], vec![3, 4]), "1300 + 1000 against 1200 + 1100");
+
+        let with_party = [group(1, 1, 1500.0), group(2, 2, 1000.0), group(4, 1, 1200.0)];
+        let teams = assign_teams(&with_party.iter().collect::<Vec<_>>()).unwrap();
+        assert_eq!((ids(&teams[0]), ids(&teams[1])), (vec![2, 3], vec![1, 4]), "the party stays together");
+
+        let too_big = [group(1, 3, 1000.0), group(4, 1, 1000.0)];
+        assert!(assign_teams(&too_big.iter().collect::<Vec<_>>()).is_none());
+    }
+
+    #[test]
+    fn a_team_with_nobody_left_forfeits() {
+        assert_eq!(match_instance_end(&[5, 0], &[0, 2], 20, 10.0, 600.0), Some((MatchEndReason::Forfeit, Some(1))));
+        assert_eq!(match_instance_end(&[0, 0], &[0, 0], 20, 10.0, 600.0), Some((MatchEndReason::Forfeit, None)));
+    }
+
+    #[test]
+    fn matches_end_on_the_kill_target_or_time_limit() {
+        assert_eq!(match_instance_end(&[3, 1], &[2, 2], 20, 10.0, 600.0), None);
+        assert_eq!(match_instance_end(&[20, 1], &[2, 2], 20, 10.0, 600.0), Some((MatchEndReason::ScoreLimit, Some(0))));
+        assert_eq!(match_instance_end(&[4, 4], &[2, 2], 20, 600.0, 600.0), Some((MatchEndReason::TimeLimit, None)));
+        assert_eq!(match_instance_end(&[4, 4], &[2, 2], 0, 6000.0, 0.0), None);
+    }
+}
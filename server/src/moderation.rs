@@ -0,0 +1,171 @@
+/*
+ * Vibe Coding Starter Pack: 3D Multiplayer - moderation.rs
+ *
+ * Bans and mutes, both optionally temporary.
+ *
+ * Key components:
+ *    - Ban: Private table of banned identities. identity_connected refuses the connection and
+ *      register_player refuses to register while a ban is active
+ *    - Mute: Private table of muted identities; chat::send_chat rejects their messages
+ *    - expires_at: None for a permanent ban/mute. Expired rows are ignored by the checks and
+ *      removed the next time the identity is seen. Durations that overflow a Timestamp are
+ *      rejected
+ *    - ban_player / unban_player / mute_player / unmute_player: Admin reducers, recorded as
+ *      Moderation entries in the audit log. Banning an active player also kicks them
+ */
+
+use spacetimedb::{Identity, ReducerContext, Table, TimeDuration, Timestamp};
+
+use crate::admin::require_admin;
+use crate::audit::{record_audit, AuditCategory};
+use crate::player;
+
+#[spacetimedb::table(name = ban)]
+#[derive(Clone)]
+pub struct Ban {
+    #[primary_key]
+    pub identity: Identity,
+    pub reason: String,
+    pub issued_by: Identity,
+    pub created_at: Timestamp,
+    pub expires_at: Option<Timestamp>,
+}
+
+#[spacetimedb::table(name = mute)]
+#[derive(Clone)]
+pub struct Mute {
+    #[primary_key]
+    pub identity: Identity,
+    pub reason: String,
+    pub issued_by: Identity,
+    pub created_at: Timestamp,
+    pub expires_at: Option<Timestamp>,
+}
+
+// When a ban/mute issued at `now` lifts. Durations too long to represent are rejected
+// instead of wrapping around into the past
+fn expiry(now: Timestamp, duration_secs: Option<u64>) -> Result<Option<Timestamp>, String> {
+    let Some(secs) = duration_secs else {
+        return Ok(None);
+    };
+    i64::try_from(secs)
+        .ok()
+        .and_then(|secs| secs.checked_mul(1_000_000))
+        .and_then(|micros| now.checked_add(TimeDuration::from_micros(micros)))
+        .map(Some)
+        .ok_or_else(|| "Duration is too long, leave it empty for a permanent one".to_string())
+}
+
+fn is_active(now: Timestamp, expires_at: Option<Timestamp>) -> bool {
+    expires_at.is_none_or(|at| now < at)
+}
+
+fn record_moderation(ctx: &ReducerContext, action: &str, target: Identity, details: String) {
+    record_audit(ctx, Some(ctx.sender), AuditCategory::Moderation, action, Some(target), details);
+}
+
+pub fn require_not_banned(ctx: &ReducerContext, identity: Identity) -> Result<(), String> {
+    let Some(ban) = ctx.db.ban().identity().find(identity) else {
+        return Ok(());
+    };
+    if !is_active(ctx.timestamp, ban.expires_at) {
+        ctx.db.ban().identity().delete(identity);
+        return Ok(());
+    }
+    Err(match ban.expires_at {
+        Some(_) => format!("You are temporarily banned: {}", ban.reason),
+        None => format!("You are banned: {}", ban.reason),
+    })
+}
+
+pub fn require_not_muted(ctx: &ReducerContext, identity: Identity) -> Result<(), String> {
+    let Some(mute) = ctx.db.mute().identity().find(identity) else {
+        return Ok(());
+    };
+    if !is_active(ctx.timestamp, mute.expires_at) {
+        ctx.db.mute().identity().delete(identity);
+        return Ok(());
+    }
+    Err("You are muted".to_string())
+}
+
+#[spacetimedb::reducer]
+pub fn ban_player(ctx: &ReducerContext, target: Identity, duration_secs: Option<u64>, reason: String) -> Result<(), String> {
+    require_admin(ctx)?;
+    if target == ctx.sender {
+        return Err("You can't ban yourself".to_string());
+    }
+    let ban = Ban { identity: target, reason: reason.clone(), issued_by: ctx.sender, created_at: ctx.timestamp, expires_at: expiry(ctx.timestamp, duration_secs)? };
+    if ctx.db.ban().identity().find(target).is_some() {
+        ctx.db.ban().identity().update(ban);
+    } else {
+        ctx.db.ban().insert(ban);
+    }
+    if ctx.db.player().identity().find(target).is_some() {
+        crate::log_out_player(ctx, target);
+    }
+    crate::join_queue::leave_queue(ctx, target);
+    record_moderation(ctx, "ban_player", target, format!("{:?}s: {}", duration_secs, reason));
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn unban_player(ctx: &ReducerContext, target: Identity) -> Result<(), String> {
+    require_admin(ctx)?;
+    if !ctx.db.ban().identity().delete(target) {
+        return Err("That player is not banned".to_string());
+    }
+    record_moderation(ctx, "unban_player", target, String::new());
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn mute_player(ctx: &ReducerContext, target: Identity, duration_secs: Option<u64>, reason: String) -> Result<(), String> {
+    require_admin(ctx)?;
+    let mute = Mute { identity: target, reason: reason.clone(), issued_by: ctx.sender, created_at: ctx.timestamp, expires_at: expiry(ctx.timestamp, duration_secs)? };
+    if ctx.db.mute().identity().find(target).is_some() {
+        ctx.db.mute().identity().update(mute);
+    } else {
+        ctx.db.mute().insert(mute);
+    }
+    record_moderation(ctx, "mute_player", target, format!("{:?}s: {}", duration_secs, reason));
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn unmute_player(ctx: &ReducerContext, target: Identity) -> Result<(), String> {
+    require_admin(ctx)?;
+    if !ctx.db.mute().identity().delete(target) {
+        return Err("That player is not muted".to_string());
+    }
+    record_moderation(ctx, "unmute_player", target, String::new());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn durations_are_added_to_the_issue_time() {
+        let now = Timestamp::from_micros_since_unix_epoch(1_000_000);
+        assert_eq!(expiry(now, None), Ok(None));
+        assert_eq!(expiry(now, Some(60)), Ok(Some(Timestamp::from_micros_since_unix_epoch(61_000_000))));
+    }
+
+    #[test]
+    fn timed_bans_and_mutes_lapse_and_permanent_ones_never_do() {
+        let later = |secs: i64| Timestamp::from_micros_since_unix_epoch(1_000_000 + secs * 1_000_000);
+        assert!(is_active(later(59), Some(later(60))));
+        assert!(!is_active(later(60), Some(later(60))));
+        assert!(is_active(later(1_000_000), None), "permanent");
+    }
+
+    #[test]
+    fn out_of_range_durations_are_rejected_instead_of_wrapping() {
+        let now = Timestamp::from_micros_since_unix_epoch(1_000_000);
+        assert!(expiry(now, Some(u64::MAX)).is_err());
+        assert!(expiry(now, Some(i64::MAX as u64 / 1_000_000 + 1)).is_err());
+        assert!(expiry(now, Some(i64::MAX as u64 / 1_000_000)).is_err()); // fits in micros, but not after `now`
+    }
+}
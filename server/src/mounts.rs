@@ -0,0 +1,98 @@
+/*
+ * Vibe Coding Starter Pack: 3D Multiplayer - mounts.rs
+ *
+ * Riding mounts for faster travel.
+ *
+ * Key components:
+ *    - Mounts are inventory items (inventory.rs) whose definition has a mount_speed_multiplier
+ *      above 1.0, so the mounts a player owns are saved with the rest of their inventory
+ *    - mount: Reducer that gets on a mount from the caller's inventory. Not allowed while dead,
+ *      swimming, crouching, inside a no_mount zone (zones.rs) or within
+ *      MOUNT_COMBAT_LOCKOUT_SECS of taking damage
+ *    - dismount: Reducer to get off again
+ *    - PlayerData::is_mounted / mount_speed_multiplier: player_logic::movement_speed multiplies
+ *      ground movement speed by the multiplier while mounted
+ *    - force_dismount: Knocks a rider off; combat::apply_damage calls it on any damage taken,
+ *      game_tick on entering water or a no_mount zone
+ */
+
+use spacetimedb::ReducerContext;
+
+use crate::common::MOUNT_COMBAT_LOCKOUT_SECS;
+use crate::inventory::{item_definition_for, player_inventory};
+use crate::zones::{load_zones, zone_at};
+use crate::{player, PlayerData};
+
+// Returns true if the player was mounted. The caller writes `player` back
+pub fn force_dismount(player: &mut PlayerData) -> bool {
+    if !player.is_mounted {
+        return false;
+    }
+    player.is_mounted = false;
+    player.mount_speed_multiplier = 1.0;
+    true
+}
+
+#[spacetimedb::reducer]
+pub fn mount(ctx: &ReducerContext, inventory_id: u64) -> Result<(), String> {
+    let Some(mut player) = ctx.db.player().identity().find(ctx.sender) else {
+        return Err("Player is not active".to_string());
+    };
+    if !player.alive {
+        return Err("Cannot mount while dead".to_string());
+    }
+    if player.is_swimming || player.is_crouching {
+        return Err("Cannot mount here".to_string());
+    }
+    let Some(stack) = ctx.db.player_inventory().id().find(inventory_id).filter(|s| s.owner == ctx.sender) else {
+        return Err("Item not found".to_string());
+    };
+    let Some(definition) = item_definition_for(ctx, &stack.item_id).filter(|d| d.mount_speed_multiplier > 1.0) else {
+        return Err("That item is not a mount".to_string());
+    };
+    if let Some(zone) = zone_at(&load_zones(ctx), player.instance_id, &player.position).filter(|z| z.no_mount) {
+        return Err(format!("Mounts are not allowed in {}", zone.name));
+    }
+    let in_combat = player
+        .last_damage_time
+        .is_some_and(|t| ctx.timestamp.duration_since(t).is_some_and(|d| d.as_secs_f32() < MOUNT_COMBAT_LOCKOUT_SECS));
+    if in_combat {
+        return Err("Cannot mount while in combat".to_string());
+    }
+    player.is_mounted = true;
+    player.mount_speed_multiplier = definition.mount_speed_multiplier;
+    spacetimedb::log::info!("Player {} mounted {}", player.username, definition.display_name);
+    ctx.db.player().identity().update(player);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn dismount(ctx: &ReducerContext) -> Result<(), String> {
+    let Some(mut player) = ctx.db.player().identity().find(ctx.sender) else {
+        return Err("Player is not active".to_string());
+    };
+    if !force_dismount(&mut player) {
+        return Err("You are not mounted".to_string());
+    }
+    ctx.db.player().identity().update(player);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::player_logic::movement_speed;
+    use crate::test_player;
+
+    #[test]
+    fn riders_move_faster_until_they_are_knocked_off() {
+        let mut rider = PlayerData { is_mounted: true, mount_speed_multiplier: 1.5, ..test_player(1) };
+        assert_eq!(movement_speed(&rider, 6.0, 1.0), 9.0);
+        assert_eq!(movement_speed(&rider, 6.0, 0.5), 4.5, "slows still apply on a mount");
+
+        assert!(force_dismount(&mut rider));
+        assert!(!rider.is_mounted);
+        assert_eq!(movement_speed(&rider, 6.0, 1.0), 6.0);
+        assert!(!force_dismount(&mut rider), "already on foot");
+    }
+}
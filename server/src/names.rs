@@ -0,0 +1,70 @@
+/*
+ * Vibe Coding Starter Pack: 3D Multiplayer - names.rs
+ *
+ * Username rules, enforced by register_player.
+ *
+ * Key components:
+ *    - validate_username: Trims the name and checks, in order:
+ *        - Length: USERNAME_MIN_LEN..=USERNAME_MAX_LEN characters
+ *        - Charset: ASCII letters, digits, '_' and '-'
+ *        - Reserved: RESERVED_USERNAMES (compared case-insensitively)
+ *        - Uniqueness: Case-insensitive against active players, logged out players and the
+ *          join queue, ignoring the caller's own rows
+ *      Each failure has its own message so the client can tell the player what to fix
+ */
+
+use spacetimedb::{Identity, ReducerContext, Table};
+
+use crate::common::{RESERVED_USERNAMES, USERNAME_MAX_LEN, USERNAME_MIN_LEN};
+use crate::join_queue::join_queue;
+use crate::{logged_out_player, player};
+
+fn name_taken(ctx: &ReducerContext, identity: Identity, name: &str) -> bool {
+    let same = |other: &str| other.eq_ignore_ascii_case(name);
+    ctx.db.player().iter().any(|p| p.identity != identity && same(&p.username))
+        || ctx.db.logged_out_player().iter().any(|p| p.identity != identity && same(&p.state.username))
+        || ctx.db.join_queue().iter().any(|e| e.identity != identity && same(&e.username))
+}
+
+// Everything but uniqueness; returns the trimmed name
+fn check_format(username: &str) -> Result<&str, String> {
+    let name = username.trim();
+    let length = name.chars().count();
+    if !(USERNAME_MIN_LEN..=USERNAME_MAX_LEN).contains(&length) {
+        return Err(format!("Username must be {} to {} characters", USERNAME_MIN_LEN, USERNAME_MAX_LEN));
+    }
+    if !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+        return Err("Username may only contain letters, digits, '_' and '-'".to_string());
+    }
+    if RESERVED_USERNAMES.iter().any(|reserved| reserved.eq_ignore_ascii_case(name)) {
+        return Err(format!("\"{}\" is reserved", name));
+    }
+    Ok(name)
+}
+
+// Returns the trimmed name
+pub fn validate_username(ctx: &ReducerContext, identity: Identity, username: &str) -> Result<String, String> {
+    let name = check_format(username)?;
+    if name_taken(ctx, identity, name) {
+        return Err(format!("\"{}\" is already taken", name));
+    }
+    Ok(name.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn usernames_are_trimmed_and_checked_for_length_charset_and_reserved_names() {
+        assert_eq!(check_format("  Wizard_01 "), Ok("Wizard_01"));
+        assert_eq!(check_format("ab-c"), Ok("ab-c"));
+        assert!(check_format("ab").is_err());
+        assert!(check_format("   ab   ").is_err(), "length counts after trimming");
+        assert!(check_format(&"x".repeat(USERNAME_MAX_LEN + 1)).is_err());
+        assert!(check_format("two words").is_err());
+        assert!(check_format("émile").is_err());
+        assert!(check_format("Admin").is_err());
+        assert!(check_format("SYSTEM").is_err());
+    }
+}
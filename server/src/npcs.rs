@@ -0,0 +1,404 @@
+/*
+ * Vibe Coding Starter Pack: 3D Multiplayer - npcs.rs
+ *
+ * Server-driven monsters that hunt players in melee range.
+ *
+ * Key components:
+ *    - NpcType / NpcSpec: Stats per monster type (health, speed, melee damage and reach)
+ *    - Npc: Public table of living monsters; rows are deleted when they die (dropping loot)
+ *    - NpcSpawner: Public table of spawn points that keep up to max_alive NPCs of one type
+ *      within spawn_radius, spawning a replacement every respawn_secs while below the cap.
+ *      NPCs live in their spawner's map instance and only hunt players there (instances.rs)
+ *    - refill_npc_spawners: Scheduled reducer (every NPC_SPAWNER_INTERVAL_SECS) that tops up
+ *      spawners while fewer than GameConfig::max_npcs NPCs are alive. The most depleted
+ *      spawners go first, so a full server refills every region evenly; boss adds
+ *      (bosses.rs) ignore the cap
+ *    - update_npcs: Per-tick AI pass (called from game_tick)
+ *    - damage_npc: Entry point for player projectiles (projectile_logic, ImpactTarget::Npc)
+ *    - add_npc_spawner / remove_npc_spawner: Admin reducers
+ *
+ * AI states:
+ *    - Idle: Nobody to go after (no living player within NPC_AGGRO_RANGE, nobody on the threat
+ *      table within NPC_LEASH_RANGE)
+ *    - Chase: Walks straight at its target when the nav grid line is clear, otherwise follows
+ *      an A* path (pathfinding.rs) cached in `path` and recomputed at most every
+ *      NPC_REPATH_SECS; still pushed out of static colliders like players. The target is
+ *      picked from the NPC's threat table every tick (threat.rs)
+ *    - Attack: Target within reach; melee damage every attack_cooldown_secs (environmental
+ *      damage, so no kill credit)
+ */
+
+use spacetimedb::rand::Rng;
+use spacetimedb::{Identity, ReducerContext, ScheduleAt, SpacetimeType, Table};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::achievements::on_npc_kill;
+use crate::admin::require_admin;
+use crate::bosses::{boss_damage_multiplier, on_boss_damaged, on_boss_killed};
+use crate::combat::apply_damage;
+use crate::config::get_game_config;
+use crate::common::{Vector3, LOBBY_INSTANCE_ID, PLAYER_HEIGHT, NPC_KILL_XP, NPC_REPATH_SECS, NPC_SPAWNER_INTERVAL_SECS, NPC_WAYPOINT_RADIUS, THREAT_PER_DAMAGE};
+use crate::dungeons::on_dungeon_npc_killed;
+use crate::environment::{load_static_colliders, resolve_player_colliders};
+use crate::instances::instance;
+use crate::loot::drop_npc_loot;
+use crate::pathfinding::get_nav_grid;
+use crate::pets::{set_pet_target, PetTarget};
+use crate::progression::award_kill_xp;
+use crate::quests::on_npc_killed;
+use crate::threat::{add_threat, clear_threat, select_target};
+use crate::{player, PlayerData};
+
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq)]
+pub enum NpcType {
+    Goblin, // fast and fragile
+    Ogre,   // slow, tanky, hits hard
+    Boss,   // scripted encounter (bosses.rs)
+}
+
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq)]
+pub enum NpcState {
+    Idle,
+    Chase,
+    Attack,
+}
+
+pub struct NpcSpec {
+    pub max_health: i32,
+    pub move_speed: f32,
+    pub hit_radius: f32,
+    pub attack_damage: i32,
+    pub attack_range: f32, // reach from the NPC's edge to the target's edge
+    pub attack_cooldown_secs: f32,
+}
+
+impl NpcSpec {
+    pub fn for_type(npc_type: NpcType) -> Self {
+        match npc_type {
+            NpcType::Goblin => NpcSpec {
+                max_health: 60,
+                move_speed: 4.0,
+                hit_radius: 0.6,
+                attack_damage: 8,
+                attack_range: 0.8,
+                attack_cooldown_secs: 1.0,
+            },
+            NpcType::Ogre => NpcSpec {
+                max_health: 200,
+                move_speed: 2.5,
+                hit_radius: 1.0,
+                attack_damage: 25,
+                attack_range: 1.2,
+                attack_cooldown_secs: 2.0,
+            },
+            NpcType::Boss => NpcSpec {
+                max_health: 1500,
+                move_speed: 2.0,
+                hit_radius: 1.5,
+                attack_damage: 30,
+                attack_range: 1.5,
+                attack_cooldown_secs: 2.0,
+            },
+        }
+    }
+}
+
+#[spacetimedb::table(name = npc, public)]
+#[derive(Clone)]
+pub struct Npc {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub npc_type: NpcType,
+    pub position: Vector3,
+    pub yaw: f32,
+    pub health: i32,
+    pub max_health: i32,
+    pub state: NpcState,
+    pub target: Option<Identity>,
+    pub attack_timer: f32, // seconds until the next swing is allowed
+    pub path: Vec<Vector3>, // remaining A* waypoints towards the target (empty in the open)
+    pub repath_timer: f32,
+    #[index(btree)]
+    pub spawner_id: u64,
+    pub instance_id: u64, // the spawner's
+}
+
+#[spacetimedb::table(name = npc_spawner, public)]
+#[derive(Clone)]
+pub struct NpcSpawner {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub npc_type: NpcType,
+    pub position: Vector3,
+    pub max_alive: u32,
+    pub respawn_secs: f32,
+    pub respawn_timer: f32, // counts down while below max_alive
+    pub instance_id: u64,
+    pub spawn_radius: f32, // NPCs appear anywhere within this distance of position
+}
+
+#[spacetimedb::table(name = npc_spawner_schedule, scheduled(refill_npc_spawners))]
+pub struct NpcSpawnerSchedule {
+    #[primary_key]
+    #[auto_inc]
+    scheduled_id: u64,
+    scheduled_at: ScheduleAt,
+}
+
+pub fn seed_npc_spawners(ctx: &ReducerContext) {
+    if ctx.db.npc_spawner().count() > 0 {
+        return;
+    }
+    ctx.db.npc_spawner().insert(NpcSpawner {
+        id: 0, // auto_inc
+        npc_type: NpcType::Goblin,
+        position: Vector3 { x: -20.0, y: 0.0, z: 20.0 },
+        max_alive: 3,
+        respawn_secs: 10.0,
+        respawn_timer: 0.0,
+        instance_id: LOBBY_INSTANCE_ID,
+        spawn_radius: 4.0,
+    });
+    spacetimedb::log::info!("[INIT] Seeded default NPC spawner.");
+}
+
+// Called from init
+pub fn schedule_npc_spawners(ctx: &ReducerContext) {
+    if ctx.db.npc_spawner_schedule().count() == 0 {
+        ctx.db.npc_spawner_schedule().insert(NpcSpawnerSchedule {
+            scheduled_id: 0,
+            scheduled_at: ScheduleAt::Interval(Duration::from_secs(NPC_SPAWNER_INTERVAL_SECS).into()),
+        });
+    }
+}
+
+pub fn load_npcs(ctx: &ReducerContext) -> Vec<Npc> {
+    ctx.db.npc().iter().collect()
+}
+
+fn spawn_npc(ctx: &ReducerContext, spawner: &NpcSpawner) {
+    let max_health = NpcSpec::for_type(spawner.npc_type).max_health;
+    let mut rng = ctx.rng();
+    let angle = rng.gen_range(0.0..std::f32::consts::TAU);
+    let distance = spawner.spawn_radius * rng.gen_range(0.0f32..=1.0).sqrt(); // uniform over the disc
+    let position = spawner.position + Vector3 { x: angle.cos() * distance, y: 0.0, z: angle.sin() * distance };
+    insert_npc(ctx, spawner.npc_type, position, max_health, spawner.id, spawner.instance_id);
+}
+
+// spawner_id 0 for NPCs placed without a spawner (auto_inc ids start at 1)
+pub fn insert_npc(ctx: &ReducerContext, npc_type: NpcType, position: Vector3, max_health: i32, spawner_id: u64, instance_id: u64) -> Npc {
+    ctx.db.npc().insert(Npc {
+        id: 0, // auto_inc
+        npc_type,
+        position,
+        yaw: 0.0,
+        health: max_health,
+        max_health,
+        state: NpcState::Idle,
+        target: None,
+        attack_timer: 0.0,
+        path: Vec::new(),
+        repath_timer: 0.0,
+        spawner_id,
+        instance_id,
+    })
+}
+
+// Player projectile damage. Returns true if the NPC died (its row is deleted).
+pub fn damage_npc(ctx: &ReducerContext, npc_id: u64, amount: i32, attacker: Identity) -> bool {
+    let Some(mut npc) = ctx.db.npc().id().find(npc_id) else {
+        return false;
+    };
+    if amount <= 0 {
+        return false;
+    }
+    npc.health -= amount;
+    on_boss_damaged(ctx, &npc, amount, attacker);
+    if npc.health <= 0 {
+        spacetimedb::log::info!("{:?} {} killed by {}", npc.npc_type, npc.id, attacker);
+        ctx.db.npc().id().delete(npc.id);
+        clear_threat(ctx, npc.id);
+        drop_npc_loot(ctx, npc.npc_type, npc.instance_id, npc.position);
+        award_kill_xp(ctx, attacker, NPC_KILL_XP);
+        on_npc_killed(ctx, attacker, npc.npc_type);
+        on_npc_kill(ctx, attacker);
+        on_dungeon_npc_killed(ctx, npc.id);
+        on_boss_killed(ctx, &npc);
+        return true;
+    }
+    add_threat(ctx, npc.id, attacker, amount as f32 * THREAT_PER_DAMAGE);
+    set_pet_target(ctx, attacker, PetTarget::Npc(npc.id));
+    ctx.db.npc().id().update(npc);
+    false
+}
+
+pub fn horizontal_distance(a: Vector3, b: Vector3) -> f32 {
+    let dx = a.x - b.x;
+    let dz = a.z - b.z;
+    (dx * dx + dz * dz).sqrt()
+}
+
+#[spacetimedb::reducer]
+pub fn refill_npc_spawners(ctx: &ReducerContext, _schedule: NpcSpawnerSchedule) -> Result<(), String> {
+    if ctx.sender != ctx.identity() {
+        return Err("refill_npc_spawners may only be run by the scheduler".to_string());
+    }
+    let cap = get_game_config(ctx).max_npcs as u64;
+    let below: Vec<(u32, NpcSpawner)> = ctx.db.npc_spawner()
+        .iter()
+        .filter_map(|s| {
+            let alive = ctx.db.npc().spawner_id().filter(s.id).count() as u32;
+            (alive < s.max_alive).then(|| (s.max_alive - alive, s))
+        })
+        .collect();
+    for (spawner, spawn) in plan_refills(below, ctx.db.npc().count(), cap) {
+        if spawn {
+            spawn_npc(ctx, &spawner);
+        }
+        ctx.db.npc_spawner().id().update(spawner);
+    }
+    Ok(())
+}
+
+// Advances the timer of every spawner below max_alive (paired with how many NPCs it is missing)
+// by one schedule interval. Ready spawners missing the most NPCs refill first while the global
+// cap allows; the flag says whether to spawn one now
+fn plan_refills(mut below: Vec<(u32, NpcSpawner)>, mut alive_total: u64, cap: u64) -> Vec<(NpcSpawner, bool)> {
+    below.sort_by_key(|(missing, s)| (Reverse(*missing), s.id));
+    below
+        .into_iter()
+        .map(|(_, mut spawner)| {
+            spawner.respawn_timer = (spawner.respawn_timer - NPC_SPAWNER_INTERVAL_SECS as f32).max(0.0);
+            let spawn = spawner.respawn_timer <= 0.0 && alive_total < cap;
+            if spawn {
+                alive_total += 1;
+                spawner.respawn_timer = spawner.respawn_secs;
+            }
+            (spawner, spawn)
+        })
+        .collect()
+}
+
+pub fn update_npcs(ctx: &ReducerContext, delta_time: f32) {
+    if ctx.db.npc().count() == 0 {
+        return;
+    }
+    let players: Vec<PlayerData> = ctx.db.player().iter().filter(|p| p.alive).collect();
+    let colliders = load_static_colliders(ctx);
+    let mut nav_grids = HashMap::new();
+
+    for mut npc in ctx.db.npc().iter() {
+        let spec = NpcSpec::for_type(npc.npc_type);
+        npc.attack_timer = (npc.attack_timer - delta_time).max(0.0);
+        npc.repath_timer = (npc.repath_timer - delta_time).max(0.0);
+
+        let Some(target) = select_target(ctx, &npc, &players) else {
+            if npc.target.is_some() {
+                clear_threat(ctx, npc.id);
+            }
+            npc.state = NpcState::Idle;
+            npc.target = None;
+            npc.path.clear();
+            ctx.db.npc().id().update(npc);
+            continue;
+        };
+        npc.target = Some(target.identity);
+
+        let to_target = Vector3 { x: target.position.x - npc.position.x, y: 0.0, z: target.position.z - npc.position.z };
+        let distance = to_target.length();
+
+        let reach = spec.hit_radius + spec.attack_range + target.hit_radius;
+        if distance <= reach {
+            npc.state = NpcState::Attack;
+            npc.path.clear();
+            if distance > 0.001 {
+                npc.yaw = (-to_target.x).atan2(-to_target.z); // same convention as players: forward is -Z
+            }
+            if npc.attack_timer <= 0.0 {
+                apply_damage(ctx, target.identity, spec.attack_damage * boss_damage_multiplier(ctx, npc.id), None);
+                npc.attack_timer = spec.attack_cooldown_secs;
+            }
+        } else {
+            npc.state = NpcState::Chase;
+            let nav_grid = nav_grids.entry(npc.instance_id).or_insert_with(|| get_nav_grid(ctx, npc.instance_id));
+            let waypoint = if nav_grid.line_clear(npc.position, target.position) {
+                npc.path.clear();
+                target.position
+            } else {
+                if npc.path.is_empty() || npc.repath_timer <= 0.0 {
+                    npc.path = nav_grid.find_path(npc.position, target.position).unwrap_or_default();
+                    npc.repath_timer = NPC_REPATH_SECS;
+                }
+                while npc.path.first().is_some_and(|w| horizontal_distance(*w, npc.position) < NPC_WAYPOINT_RADIUS) {
+                    npc.path.remove(0);
+                }
+                npc.path.first().copied().unwrap_or(target.position)
+            };
+            let heading = Vector3 { x: waypoint.x - npc.position.x, y: 0.0, z: waypoint.z - npc.position.z };
+            if heading.length() > 0.001 {
+                npc.yaw = (-heading.x).atan2(-heading.z);
+            }
+            let step = (spec.move_speed * delta_time).min(distance - reach).min(heading.length());
+            npc.position = npc.position + heading.normalize() * step;
+            resolve_player_colliders(&colliders, npc.instance_id, &mut npc.position, spec.hit_radius, PLAYER_HEIGHT);
+        }
+        ctx.db.npc().id().update(npc);
+    }
+}
+
+#[spacetimedb::reducer]
+pub fn add_npc_spawner(ctx: &ReducerContext, npc_type: NpcType, position: Vector3, max_alive: u32, respawn_secs: f32, instance_id: u64, spawn_radius: f32) -> Result<(), String> {
+    require_admin(ctx)?;
+    if !respawn_secs.is_finite() || respawn_secs < 0.0 {
+        return Err("Respawn time must be a non-negative number of seconds".to_string());
+    }
+    if !spawn_radius.is_finite() || spawn_radius < 0.0 {
+        return Err("Spawn radius must be a non-negative distance".to_string());
+    }
+    if ctx.db.instance().id().find(instance_id).is_none() {
+        return Err("Instance not found".to_string());
+    }
+    let spawner = ctx.db.npc_spawner().insert(NpcSpawner { id: 0, npc_type, position, max_alive, respawn_secs, respawn_timer: 0.0, instance_id, spawn_radius });
+    spacetimedb::log::info!("Added {:?} spawner {} at {:?}", npc_type, spawner.id, position);
+    Ok(())
+}
+
+// Removes the spawner and every NPC it spawned
+#[spacetimedb::reducer]
+pub fn remove_npc_spawner(ctx: &ReducerContext, spawner_id: u64) -> Result<(), String> {
+    require_admin(ctx)?;
+    if !ctx.db.npc_spawner().id().delete(spawner_id) {
+        return Err("Spawner not found".to_string());
+    }
+    for npc in ctx.db.npc().spawner_id().filter(spawner_id) {
+        clear_threat(ctx, npc.id);
+    }
+    ctx.db.npc().spawner_id().delete(spawner_id);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spawner(id: u64, respawn_timer: f32) -> NpcSpawner {
+        NpcSpawner { id, npc_type: NpcType::Goblin, position: Vector3::default(), max_alive: 3, respawn_secs: 30.0, respawn_timer, instance_id: LOBBY_INSTANCE_ID, spawn_radius: 0.0 }
+    }
+
+    #[test]
+    fn the_emptiest_ready_spawners_refill_first_under_the_npc_cap() {
+        let interval = NPC_SPAWNER_INTERVAL_SECS as f32;
+        let below = vec![(1, spawner(1, 0.0)), (3, spawner(2, 0.0)), (2, spawner(3, interval + 5.0))];
+        let plan = plan_refills(below, 9, 10);
+        let order: Vec<(u64, bool)> = plan.iter().map(|(s, spawn)| (s.id, *spawn)).collect();
+        assert_eq!(order, vec![(2, true), (3, false), (1, false)], "one slot left under the cap, spawner 3 isn't ready yet");
+        assert_eq!(plan[0].0.respawn_timer, 30.0, "restarts after spawning");
+        assert_eq!(plan[1].0.respawn_timer, 5.0);
+        assert_eq!(plan[2].0.respawn_timer, 0.0, "waits ready for room under the cap");
+    }
+
+}
@@ -0,0 +1,180 @@
+/*
+ * Vibe Coding Starter Pack: 3D Multiplayer - party.rs
+ *
+ * Small groups of players who share kill XP and a chat channel.
+ *
+ * Key components:
+ *    - Party: Public table of parties and their leader
+ *    - PartyMember: Public table mapping each member to their party (a player is in at most
+ *      one party)
+ *    - PartyInvite: Public table of open invites, expiring after PARTY_INVITE_SECS
+ *    - invite_to_party: Reducer; inviting while not in a party creates one with you as leader.
+ *      Only the leader invites, and the party can't grow past GameConfig::max_party_size
+ *    - accept_party_invite / leave_party: Reducers. When the leader leaves the longest-standing
+ *      member takes over; a party left with one member is disbanded. Logging out leaves too
+ *    - party_members: Members of a player's party (ChatChannel::Party, progression.rs XP sharing)
+ *    - expire_party_invites: Called from game_tick
+ */
+
+use spacetimedb::{Identity, ReducerContext, Table, Timestamp};
+use std::time::Duration;
+
+use crate::common::PARTY_INVITE_SECS;
+use crate::config::get_game_config;
+use crate::player;
+
+#[spacetimedb::table(name = party, public)]
+#[derive(Clone)]
+pub struct Party {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub leader: Identity,
+    pub created_at: Timestamp,
+}
+
+#[spacetimedb::table(name = party_member, public)]
+#[derive(Clone)]
+pub struct PartyMember {
+    #[primary_key]
+    pub identity: Identity,
+    #[index(btree)]
+    pub party_id: u64,
+    pub joined_at: Timestamp,
+}
+
+#[spacetimedb::table(name = party_invite, public)]
+#[derive(Clone)]
+pub struct PartyInvite {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub party_id: u64,
+    pub inviter: Identity,
+    #[index(btree)]
+    pub invitee: Identity,
+    pub created_at: Timestamp,
+}
+
+pub fn party_of(ctx: &ReducerContext, identity: Identity) -> Option<u64> {
+    ctx.db.party_member().identity().find(identity).map(|m| m.party_id)
+}
+
+// Everyone in `identity`'s party, including them; empty when not in a party
+pub fn party_members(ctx: &ReducerContext, identity: Identity) -> Vec<Identity> {
+    match party_of(ctx, identity) {
+        Some(party_id) => ctx.db.party_member().party_id().filter(party_id).map(|m| m.identity).collect(),
+        None => Vec::new(),
+    }
+}
+
+fn join(ctx: &ReducerContext, identity: Identity, party_id: u64) {
+    ctx.db.party_member().insert(PartyMember { identity, party_id, joined_at: ctx.timestamp });
+}
+
+fn disband(ctx: &ReducerContext, party_id: u64) {
+    for member in ctx.db.party_member().party_id().filter(party_id) {
+        ctx.db.party_member().identity().delete(member.identity);
+    }
+    for invite in ctx.db.party_invite().iter().filter(|i| i.party_id == party_id) {
+        ctx.db.party_invite().id().delete(invite.id);
+    }
+    ctx.db.party().id().delete(party_id);
+    spacetimedb::log::info!("[PARTY] Party {} disbanded", party_id);
+}
+
+pub fn remove_from_party(ctx: &ReducerContext, identity: Identity) {
+    ctx.db.party_invite().invitee().delete(identity);
+    let Some(party_id) = party_of(ctx, identity) else {
+        return;
+    };
+    ctx.db.party_member().identity().delete(identity);
+    let mut remaining: Vec<PartyMember> = ctx.db.party_member().party_id().filter(party_id).collect();
+    if remaining.len() <= 1 {
+        disband(ctx, party_id);
+        return;
+    }
+    if let Some(mut party) = ctx.db.party().id().find(party_id) {
+        if party.leader == identity {
+            remaining.sort_by_key(|m| m.joined_at);
+            party.leader = remaining[0].identity;
+            ctx.db.party().id().update(party);
+        }
+    }
+}
+
+pub fn expire_party_invites(ctx: &ReducerContext) {
+    let ttl = Duration::from_secs_f32(PARTY_INVITE_SECS);
+    for invite in ctx.db.party_invite().iter() {
+        if ctx.timestamp.duration_since(invite.created_at).is_some_and(|age| age >= ttl) {
+            ctx.db.party_invite().id().delete(invite.id);
+        }
+    }
+}
+
+#[spacetimedb::reducer]
+pub fn invite_to_party(ctx: &ReducerContext, target: Identity) -> Result<(), String> {
+    if target == ctx.sender {
+        return Err("You can't invite yourself".to_string());
+    }
+    if ctx.db.player().identity().find(ctx.sender).is_none() {
+        return Err("Player is not active".to_string());
+    }
+    if ctx.db.player().identity().find(target).is_none() {
+        return Err("That player is not online".to_string());
+    }
+    if party_of(ctx, target).is_some() {
+        return Err("That player is already in a party".to_string());
+    }
+    let party_id = match party_of(ctx, ctx.sender) {
+        Some(party_id) => {
+            if ctx.db.party().id().find(party_id).is_some_and(|p| p.leader != ctx.sender) {
+                return Err("Only the party leader can invite".to_string());
+            }
+            party_id
+        }
+        None => {
+            let party = ctx.db.party().insert(Party { id: 0, leader: ctx.sender, created_at: ctx.timestamp });
+            join(ctx, ctx.sender, party.id);
+            party.id
+        }
+    };
+    let max_size = get_game_config(ctx).max_party_size as usize;
+    if ctx.db.party_member().party_id().filter(party_id).count() >= max_size {
+        return Err(format!("Parties are limited to {} players", max_size));
+    }
+    if ctx.db.party_invite().invitee().filter(target).any(|i| i.party_id == party_id) {
+        return Err("That player is already invited".to_string());
+    }
+    ctx.db.party_invite().insert(PartyInvite { id: 0, party_id, inviter: ctx.sender, invitee: target, created_at: ctx.timestamp });
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn accept_party_invite(ctx: &ReducerContext, party_id: u64) -> Result<(), String> {
+    if !ctx.db.party_invite().invitee().filter(ctx.sender).any(|i| i.party_id == party_id) {
+        return Err("No invite to that party".to_string());
+    }
+    if party_of(ctx, ctx.sender).is_some() {
+        return Err("Leave your current party first".to_string());
+    }
+    if ctx.db.party().id().find(party_id).is_none() {
+        return Err("That party no longer exists".to_string());
+    }
+    let max_size = get_game_config(ctx).max_party_size as usize;
+    if ctx.db.party_member().party_id().filter(party_id).count() >= max_size {
+        return Err("That party is full".to_string());
+    }
+    ctx.db.party_invite().invitee().delete(ctx.sender);
+    join(ctx, ctx.sender, party_id);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn leave_party(ctx: &ReducerContext) -> Result<(), String> {
+    if party_of(ctx, ctx.sender).is_none() {
+        return Err("You are not in a party".to_string());
+    }
+    remove_from_party(ctx, ctx.sender);
+    Ok(())
+}
@@ -0,0 +1,259 @@
+/*
+ * Vibe Coding Starter Pack: 3D Multiplayer - pathfinding.rs
+ *
+ * Navigation grid and A* search for NPCs.
+ *
+ * Key components:
+ *    - NavGrid: Private row per map instance (instances.rs) with a walkability bitmap of
+ *      NAV_CELL_SIZE cells covering +/- NAV_GRID_HALF_SIZE around the origin. Derived from the
+ *      instance's static_collider rows: a cell is blocked if its center is within
+ *      NAV_AGENT_RADIUS of a collider's footprint (colliders floating above head height are
+ *      ignored)
+ *    - rebuild_nav_grid: Recomputes an instance's bitmap; called whenever its static colliders
+ *      change
+ *    - get_nav_grid: Read helper, builds the grid on first access
+ *    - remove_nav_grid: Called when the instance's geometry is removed (environment.rs)
+ *    - NavGrid::find_path: 8-connected A* (no corner cutting), capped at NAV_MAX_EXPANSIONS,
+ *      returning world-space waypoints with collinear points dropped and the exact goal last
+ *    - NavGrid::line_clear: Straight-line walkability check so NPCs skip A* in the open
+ *
+ * Positions outside the grid, and blocked goals, have no path; callers fall back to walking
+ * straight at the goal.
+ */
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use spacetimedb::{ReducerContext, Table};
+
+use crate::common::{Vector3, NAV_AGENT_RADIUS, NAV_CELL_SIZE, NAV_GRID_HALF_SIZE, NAV_MAX_EXPANSIONS, PLAYER_HEIGHT};
+use crate::environment::static_collider;
+
+const STRAIGHT_COST: u32 = 10;
+const DIAGONAL_COST: u32 = 14;
+
+#[spacetimedb::table(name = nav_grid)]
+#[derive(Clone)]
+pub struct NavGrid {
+    #[primary_key]
+    pub instance_id: u64,
+    pub origin_x: f32, // world position of cell (0, 0)'s corner
+    pub origin_z: f32,
+    pub cell_size: f32,
+    pub width: u32,
+    pub depth: u32,
+    pub blocked: Vec<bool>, // width * depth, row-major by z
+}
+
+fn build_nav_grid(ctx: &ReducerContext, instance_id: u64) -> NavGrid {
+    let width = (NAV_GRID_HALF_SIZE * 2.0 / NAV_CELL_SIZE).ceil() as u32;
+    let mut grid = NavGrid {
+        instance_id,
+        origin_x: -NAV_GRID_HALF_SIZE,
+        origin_z: -NAV_GRID_HALF_SIZE,
+        cell_size: NAV_CELL_SIZE,
+        width,
+        depth: width,
+        blocked: vec![false; (width * width) as usize],
+    };
+    for collider in ctx.db.static_collider().iter().filter(|c| c.instance_id == instance_id) {
+        if collider.center.y - collider.half_extents.y >= PLAYER_HEIGHT {
+            continue; // overhead, walk underneath
+        }
+        let reach_x = collider.half_extents.x + NAV_AGENT_RADIUS;
+        let reach_z = collider.half_extents.z + NAV_AGENT_RADIUS;
+        for z in 0..grid.depth {
+            for x in 0..grid.width {
+                let center = grid.cell_center(x, z);
+                if (center.x - collider.center.x).abs() < reach_x && (center.z - collider.center.z).abs() < reach_z {
+                    let index = grid.index(x, z);
+                    grid.blocked[index] = true;
+                }
+            }
+        }
+    }
+    grid
+}
+
+pub fn rebuild_nav_grid(ctx: &ReducerContext, instance_id: u64) {
+    let grid = build_nav_grid(ctx, instance_id);
+    let blocked = grid.blocked.iter().filter(|b| **b).count();
+    if ctx.db.nav_grid().instance_id().find(instance_id).is_some() {
+        ctx.db.nav_grid().instance_id().update(grid);
+    } else {
+        ctx.db.nav_grid().insert(grid);
+    }
+    spacetimedb::log::info!("Nav grid for instance {} rebuilt ({} blocked cells)", instance_id, blocked);
+}
+
+pub fn get_nav_grid(ctx: &ReducerContext, instance_id: u64) -> NavGrid {
+    ctx.db.nav_grid().instance_id().find(instance_id).unwrap_or_else(|| ctx.db.nav_grid().insert(build_nav_grid(ctx, instance_id)))
+}
+
+pub fn remove_nav_grid(ctx: &ReducerContext, instance_id: u64) {
+    ctx.db.nav_grid().instance_id().delete(instance_id);
+}
+
+impl NavGrid {
+    fn index(&self, x: u32, z: u32) -> usize {
+        (z * self.width + x) as usize
+    }
+
+    fn cell_center(&self, x: u32, z: u32) -> Vector3 {
+        Vector3 {
+            x: self.origin_x + (x as f32 + 0.5) * self.cell_size,
+            y: 0.0,
+            z: self.origin_z + (z as f32 + 0.5) * self.cell_size,
+        }
+    }
+
+    fn cell_of(&self, position: Vector3) -> Option<(u32, u32)> {
+        let x = ((position.x - self.origin_x) / self.cell_size).floor();
+        let z = ((position.z - self.origin_z) / self.cell_size).floor();
+        if !(x >= 0.0 && z >= 0.0 && x < self.width as f32 && z < self.depth as f32) {
+            return None;
+        }
+        Some((x as u32, z as u32))
+    }
+
+    fn is_walkable(&self, x: i64, z: i64) -> bool {
+        x >= 0 && z >= 0 && x < self.width as i64 && z < self.depth as i64 && !self.blocked[self.index(x as u32, z as u32)]
+    }
+
+    // Samples every half cell along the segment; false if any sample is blocked or off-grid
+    pub fn line_clear(&self, from: Vector3, to: Vector3) -> bool {
+        let delta = Vector3 { x: to.x - from.x, y: 0.0, z: to.z - from.z };
+        let steps = (delta.length() / (self.cell_size * 0.5)).ceil().max(1.0) as u32;
+        (0..=steps).all(|i| {
+            let point = from + delta * (i as f32 / steps as f32);
+            self.cell_of(point).is_some_and(|(x, z)| !self.blocked[self.index(x, z)])
+        })
+    }
+
+    pub fn find_path(&self, start: Vector3, goal: Vector3) -> Option<Vec<Vector3>> {
+        let (start_x, start_z) = self.cell_of(start)?;
+        let (goal_x, goal_z) = self.cell_of(goal)?;
+        if self.blocked[self.index(goal_x, goal_z)] {
+            return None;
+        }
+        let heuristic = |x: u32, z: u32| {
+            let dx = x.abs_diff(goal_x);
+            let dz = z.abs_diff(goal_z);
+            STRAIGHT_COST * dx.max(dz) + (DIAGONAL_COST - STRAIGHT_COST) * dx.min(dz)
+        };
+
+        let cells = self.blocked.len();
+        let mut cost = vec![u32::MAX; cells];
+        let mut came_from = vec![usize::MAX; cells];
+        let mut closed = vec![false; cells];
+        let mut open = BinaryHeap::new();
+        let start_index = self.index(start_x, start_z);
+        let goal_index = self.index(goal_x, goal_z);
+        cost[start_index] = 0;
+        open.push(Reverse((heuristic(start_x, start_z), start_index)));
+
+        let mut expansions = 0;
+        while let Some(Reverse((_, current))) = open.pop() {
+            if current == goal_index {
+                return Some(self.reconstruct(&came_from, current, goal));
+            }
+            if closed[current] {
+                continue;
+            }
+            closed[current] = true;
+            expansions += 1;
+            if expansions > NAV_MAX_EXPANSIONS {
+                return None;
+            }
+
+            let (cx, cz) = ((current as u32 % self.width) as i64, (current as u32 / self.width) as i64);
+            for (dx, dz) in [(-1, -1), (0, -1), (1, -1), (-1, 0), (1, 0), (-1, 1), (0, 1), (1, 1)] {
+                let (nx, nz) = (cx + dx, cz + dz);
+                if !self.is_walkable(nx, nz) {
+                    continue;
+                }
+                let diagonal = dx != 0 && dz != 0;
+                if diagonal && !(self.is_walkable(cx + dx, cz) && self.is_walkable(cx, cz + dz)) {
+                    continue; // no squeezing past a blocked corner
+                }
+                let next = self.index(nx as u32, nz as u32);
+                let step = if diagonal { DIAGONAL_COST } else { STRAIGHT_COST };
+                let tentative = cost[current] + step;
+                if tentative < cost[next] {
+                    cost[next] = tentative;
+                    came_from[next] = current;
+                    open.push(Reverse((tentative + heuristic(nx as u32, nz as u32), next)));
+                }
+            }
+        }
+        None
+    }
+
+    fn reconstruct(&self, came_from: &[usize], goal_index: usize, goal: Vector3) -> Vec<Vector3> {
+        let mut indices = vec![goal_index];
+        while let Some(&previous) = came_from.get(*indices.last().unwrap()).filter(|p| **p != usize::MAX) {
+            indices.push(previous);
+        }
+        indices.pop(); // the start cell, which the NPC is already in
+        indices.reverse();
+
+        // Keep only the cells where the direction changes
+        let cell = |index: usize| ((index as u32 % self.width) as i64, (index as u32 / self.width) as i64);
+        let mut waypoints = Vec::new();
+        for (i, &index) in indices.iter().enumerate() {
+            let is_turn = match (i.checked_sub(1).map(|p| indices[p]), indices.get(i + 1)) {
+                (Some(prev), Some(&next)) => {
+                    let (p, c, n) = (cell(prev), cell(index), cell(next));
+                    (c.0 - p.0, c.1 - p.1) != (n.0 - c.0, n.1 - c.1)
+                }
+                _ => true,
+            };
+            if is_turn {
+                let (x, z) = cell(index);
+                waypoints.push(self.cell_center(x as u32, z as u32));
+            }
+        }
+        if let Some(last) = waypoints.last_mut() {
+            *last = Vector3 { x: goal.x, y: 0.0, z: goal.z };
+        }
+        waypoints
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::LOBBY_INSTANCE_ID;
+
+    // 10x10 cells of 1m from the origin, with a wall along z = 5 open only at x = 9
+    fn walled_grid() -> NavGrid {
+        let mut grid = NavGrid { instance_id: LOBBY_INSTANCE_ID, origin_x: 0.0, origin_z: 0.0, cell_size: 1.0, width: 10, depth: 10, blocked: vec![false; 100] };
+        for x in 0..9 {
+            let index = grid.index(x, 5);
+            grid.blocked[index] = true;
+        }
+        grid
+    }
+
+    #[test]
+    fn paths_go_around_walls_through_the_gap() {
+        let grid = walled_grid();
+        let start = Vector3 { x: 1.5, y: 0.0, z: 1.5 };
+        let goal = Vector3 { x: 1.5, y: 0.0, z: 8.5 };
+        assert!(!grid.line_clear(start, goal));
+
+        let path = grid.find_path(start, goal).expect("the gap is reachable");
+        assert_eq!(*path.last().unwrap(), goal);
+        assert!(path.iter().any(|p| p.x > 9.0 && p.z > 4.0 && p.z < 6.0), "passes through the gap: {:?}", path);
+        assert!(path.len() < 8, "only turns are kept as waypoints: {:?}", path);
+    }
+
+    #[test]
+    fn blocked_or_off_grid_goals_have_no_path() {
+        let grid = walled_grid();
+        let start = Vector3 { x: 1.5, y: 0.0, z: 1.5 };
+        assert!(grid.find_path(start, Vector3 { x: 3.5, y: 0.0, z: 5.5 }).is_none());
+        assert!(grid.find_path(start, Vector3 { x: 30.0, y: 0.0, z: 1.5 }).is_none());
+        assert!(grid.line_clear(start, Vector3 { x: 8.5, y: 0.0, z: 1.5 }));
+    }
+}
@@ -0,0 +1,111 @@
+/*
+ * Vibe Coding Starter Pack: 3D Multiplayer - persistence.rs
+ *
+ * What a player keeps between sessions.
+ *
+ * Key components:
+ *    - PersistentPlayerState: Everything saved in LoggedOutPlayerData::state when a player logs
+ *      out (lib.rs log_out_player) and restored onto the fresh PlayerData when they rejoin
+ *      (spawn_player). New progression fields (XP, levels, unlocks) belong here; add them
+ *      to the struct, persistent_state and restore_persistent_state together
+ *    - Rejoining players still get a fresh spawn point, team and per-session state
+ *      (cooldowns, ammo, ultimate charge); the saved position is kept for reference
+ *    - Kept in their own identity-keyed tables instead, so they persist without this
+ *      struct: inventory and equipment (player_inventory), scoreboard stats (player_stats),
+ *      rename/customize cooldowns (customization_cooldown), quests (player_quest_progress)
+ *      achievements (player_achievement, achievement_counter) and login streaks (login_streak)
+ *
+ * Migration: LoggedOutPlayerData's columns are this struct, so changing it changes that
+ * table's schema, which SpacetimeDB's automatic migration can't apply to existing rows
+ * (only adding new tables is). When changing the struct either republish with
+ * `spacetime publish --delete-data` (saved players are lost), or keep the old table
+ * alongside a new one, copy the rows over in init and drop the old table in a later
+ * release. Either way, regenerate the client bindings.
+ */
+
+use spacetimedb::SpacetimeType;
+
+use crate::common::{Appearance, Vector3};
+use crate::PlayerData;
+
+#[derive(SpacetimeType, Clone, Debug)]
+pub struct PersistentPlayerState {
+    pub username: String,
+    pub character_class: String,
+    pub position: Vector3,
+    pub rotation: Vector3,
+    pub health: i32,
+    pub max_health: i32,
+    pub mana: i32,
+    pub max_mana: i32,
+    pub appearance: Appearance,
+    pub xp: u64,
+    pub gold: u64,
+    pub mmr: i32,
+    pub placement_matches: u32,
+    pub mmr_season: u32,
+}
+
+impl PlayerData {
+    pub fn persistent_state(&self) -> PersistentPlayerState {
+        PersistentPlayerState {
+            username: self.username.clone(),
+            character_class: self.character_class.clone(),
+            position: self.position,
+            rotation: self.rotation,
+            health: self.health,
+            max_health: self.max_health,
+            mana: self.mana,
+            max_mana: self.max_mana,
+            appearance: self.appearance.clone(),
+            xp: self.xp,
+            gold: self.gold,
+            mmr: self.mmr,
+            placement_matches: self.placement_matches,
+            mmr_season: self.mmr_season,
+        }
+    }
+
+    pub fn restore_persistent_state(&mut self, state: PersistentPlayerState) {
+        self.username = state.username;
+        self.character_class = state.character_class;
+        self.position = state.position;
+        self.rotation = state.rotation;
+        self.health = state.health;
+        self.max_health = state.max_health;
+        self.mana = state.mana;
+        self.max_mana = state.max_mana;
+        self.appearance = state.appearance;
+        self.xp = state.xp;
+        self.gold = state.gold;
+        self.mmr = state.mmr;
+        self.placement_matches = state.placement_matches;
+        self.mmr_season = state.mmr_season;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_player;
+
+    #[test]
+    fn saved_state_survives_a_round_trip_and_leaves_session_state_alone() {
+        let mut veteran = test_player(1);
+        veteran.username = "Veteran".to_string();
+        veteran.health = 42;
+        veteran.max_mana = 180;
+        veteran.appearance.model_variant = 2;
+        veteran.rotation.y = 1.5;
+        let saved = veteran.persistent_state();
+
+        let mut fresh = test_player(1);
+        fresh.ult_charge = 0.25;
+        fresh.restore_persistent_state(saved);
+        assert_eq!(fresh.username, "Veteran");
+        assert_eq!(fresh.health, 42);
+        assert_eq!(fresh.max_mana, 180);
+        assert_eq!(fresh.appearance.model_variant, 2);
+        assert_eq!(fresh.rotation.y, 1.5);
+        assert_eq!(fresh.ult_charge, 0.25, "per-session state is not part of the save");
+    }
+}
@@ -0,0 +1,263 @@
+/*
+ * Vibe Coding Starter Pack: 3D Multiplayer - pets.rs
+ *
+ * Companions that follow their owner around and join in on whatever the owner is fighting.
+ *
+ * Key components:
+ *    - PetType / PetSpec: Stats per companion (health, speed, bite damage and reach, mana cost)
+ *    - Pet: Public table, at most one pet per player. `target` is the player or NPC the owner
+ *      last damaged (set from combat::apply_damage and npcs::damage_npc)
+ *    - update_pets: Per-tick pass (called from game_tick). A pet goes after its target while
+ *      that target is alive, in the owner's instance and within PET_LEASH_RANGE of the owner,
+ *      attacking every attack_cooldown_secs with the damage credited to the owner; otherwise
+ *      it trails PET_FOLLOW_DISTANCE behind the owner. Pets farther than PET_TELEPORT_DISTANCE
+ *      away (or left in another instance) are put back at the owner's side
+ *    - damage_pets_in_radius: Explosions and boss slams hurt pets too; a pet at 0 health dies
+ *    - summon_pet / dismiss_pet: Reducers. Summoning costs mana and replaces the current pet
+ *    - despawn_pet: Called from log_out_player
+ */
+
+use spacetimedb::{Identity, ReducerContext, SpacetimeType, Table};
+
+use crate::combat::apply_damage;
+use crate::common::{Vector3, PET_FOLLOW_DISTANCE, PET_LEASH_RANGE, PET_TELEPORT_DISTANCE, PLAYER_HEIGHT};
+use crate::environment::{load_static_colliders, resolve_player_colliders};
+use crate::npcs::{damage_npc, horizontal_distance, npc};
+use crate::zones::pvp_blocked;
+use crate::{player, PlayerData};
+
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq)]
+pub enum PetType {
+    Wolf, // sturdy melee biter
+    Imp,  // fragile, attacks from range
+}
+
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq)]
+pub enum PetTarget {
+    Player(Identity),
+    Npc(u64),
+}
+
+pub struct PetSpec {
+    pub max_health: i32,
+    pub move_speed: f32,
+    pub hit_radius: f32,
+    pub attack_damage: i32,
+    pub attack_range: f32, // reach from the pet's edge
+    pub attack_cooldown_secs: f32,
+    pub mana_cost: i32,
+}
+
+impl PetSpec {
+    pub fn for_type(pet_type: PetType) -> Self {
+        match pet_type {
+            PetType::Wolf => PetSpec {
+                max_health: 80,
+                move_speed: 7.0,
+                hit_radius: 0.5,
+                attack_damage: 6,
+                attack_range: 1.0,
+                attack_cooldown_secs: 1.0,
+                mana_cost: 30,
+            },
+            PetType::Imp => PetSpec {
+                max_health: 50,
+                move_speed: 6.0,
+                hit_radius: 0.4,
+                attack_damage: 4,
+                attack_range: 6.0,
+                attack_cooldown_secs: 1.2,
+                mana_cost: 25,
+            },
+        }
+    }
+}
+
+#[spacetimedb::table(name = pet, public)]
+#[derive(Clone)]
+pub struct Pet {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    #[unique]
+    pub owner: Identity,
+    pub pet_type: PetType,
+    pub position: Vector3,
+    pub yaw: f32,
+    pub health: i32,
+    pub max_health: i32,
+    pub target: Option<PetTarget>,
+    pub attack_timer: f32, // seconds until the next attack is allowed
+    pub instance_id: u64,  // always the owner's
+}
+
+// Called whenever `owner` damages something
+pub fn set_pet_target(ctx: &ReducerContext, owner: Identity, target: PetTarget) {
+    if let Some(mut pet) = ctx.db.pet().owner().find(owner) {
+        if pet.target != Some(target) {
+            pet.target = Some(target);
+            ctx.db.pet().id().update(pet);
+        }
+    }
+}
+
+pub fn despawn_pet(ctx: &ReducerContext, owner: Identity) {
+    ctx.db.pet().owner().delete(owner);
+}
+
+// Position and reach of the pet's target, if it may still attack it
+fn target_position(ctx: &ReducerContext, pet: &Pet, owner: &PlayerData) -> Option<(Vector3, f32)> {
+    let (position, radius, instance_id) = match pet.target? {
+        PetTarget::Player(identity) => {
+            let victim = ctx.db.player().identity().find(identity).filter(|p| p.alive && p.identity != owner.identity)?;
+            if pvp_blocked(ctx, Some(owner.identity), identity) {
+                return None;
+            }
+            (victim.position, victim.hit_radius, victim.instance_id)
+        }
+        PetTarget::Npc(npc_id) => {
+            let target = ctx.db.npc().id().find(npc_id)?;
+            (target.position, 0.6, target.instance_id)
+        }
+    };
+    (instance_id == owner.instance_id && horizontal_distance(position, owner.position) <= PET_LEASH_RANGE).then_some((position, radius))
+}
+
+// Where a pet `max_step` away from closing in to `stop_at` of `goal` ends up; None once it is there
+fn step_toward(position: Vector3, goal: Vector3, stop_at: f32, max_step: f32) -> Option<Vector3> {
+    let to_goal = Vector3 { x: goal.x - position.x, y: 0.0, z: goal.z - position.z };
+    let distance = to_goal.length();
+    if distance <= stop_at {
+        return None;
+    }
+    Some(position + to_goal.normalize() * max_step.min(distance - stop_at))
+}
+
+pub fn update_pets(ctx: &ReducerContext, delta_time: f32) {
+    if ctx.db.pet().count() == 0 {
+        return;
+    }
+    let colliders = load_static_colliders(ctx);
+    for mut pet in ctx.db.pet().iter() {
+        let Some(owner) = ctx.db.player().identity().find(pet.owner) else {
+            continue;
+        };
+        let spec = PetSpec::for_type(pet.pet_type);
+        pet.attack_timer = (pet.attack_timer - delta_time).max(0.0);
+
+        if pet.instance_id != owner.instance_id || horizontal_distance(pet.position, owner.position) > PET_TELEPORT_DISTANCE {
+            pet.instance_id = owner.instance_id;
+            pet.position = owner.position;
+            pet.target = None;
+        }
+
+        let chase = if owner.alive { target_position(ctx, &pet, &owner) } else { None };
+        if chase.is_none() {
+            pet.target = None;
+        }
+        let (goal, stop_at) = match chase {
+            Some((position, radius)) => (position, spec.hit_radius + spec.attack_range + radius),
+            None => (owner.position, PET_FOLLOW_DISTANCE),
+        };
+
+        let to_goal = Vector3 { x: goal.x - pet.position.x, y: 0.0, z: goal.z - pet.position.z };
+        if to_goal.length() > 0.001 {
+            pet.yaw = (-to_goal.x).atan2(-to_goal.z); // same convention as players: forward is -Z
+        }
+        if let Some(position) = step_toward(pet.position, goal, stop_at, spec.move_speed * delta_time) {
+            pet.position = position;
+            resolve_player_colliders(&colliders, pet.instance_id, &mut pet.position, spec.hit_radius, PLAYER_HEIGHT);
+        } else if chase.is_some() && pet.attack_timer <= 0.0 {
+            pet.attack_timer = spec.attack_cooldown_secs;
+            match pet.target {
+                Some(PetTarget::Player(identity)) => {
+                    apply_damage(ctx, identity, spec.attack_damage, Some(pet.owner));
+                }
+                Some(PetTarget::Npc(npc_id)) => {
+                    damage_npc(ctx, npc_id, spec.attack_damage, pet.owner);
+                }
+                None => {}
+            }
+        }
+        pet.position.y = owner.position.y;
+        ctx.db.pet().id().update(pet);
+    }
+}
+
+// Pets of `attacker` are spared
+pub fn damage_pets_in_radius(ctx: &ReducerContext, instance_id: u64, center: Vector3, radius: f32, amount: i32, attacker: Option<Identity>) {
+    for mut pet in ctx.db.pet().iter() {
+        if pet.instance_id != instance_id || Some(pet.owner) == attacker || (pet.position - center).length() > radius {
+            continue;
+        }
+        if attacker.is_some_and(|a| pvp_blocked(ctx, Some(a), pet.owner)) {
+            continue;
+        }
+        pet.health -= amount;
+        if pet.health <= 0 {
+            spacetimedb::log::info!("{:?} pet {} of {} died", pet.pet_type, pet.id, pet.owner);
+            ctx.db.pet().id().delete(pet.id);
+        } else {
+            ctx.db.pet().id().update(pet);
+        }
+    }
+}
+
+#[spacetimedb::reducer]
+pub fn summon_pet(ctx: &ReducerContext, pet_type: PetType) -> Result<(), String> {
+    let Some(mut owner) = ctx.db.player().identity().find(ctx.sender) else {
+        return Err("Player is not active".to_string());
+    };
+    if !owner.alive {
+        return Err("Cannot summon while dead".to_string());
+    }
+    let spec = PetSpec::for_type(pet_type);
+    if owner.mana < spec.mana_cost {
+        return Err("Not enough mana".to_string());
+    }
+    owner.mana -= spec.mana_cost;
+    despawn_pet(ctx, owner.identity);
+    let behind = Vector3 { x: owner.rotation.y.sin(), y: 0.0, z: owner.rotation.y.cos() } * PET_FOLLOW_DISTANCE;
+    ctx.db.pet().insert(Pet {
+        id: 0, // auto_inc
+        owner: owner.identity,
+        pet_type,
+        position: owner.position + behind,
+        yaw: owner.rotation.y,
+        health: spec.max_health,
+        max_health: spec.max_health,
+        target: None,
+        attack_timer: 0.0,
+        instance_id: owner.instance_id,
+    });
+    spacetimedb::log::info!("Player {} summoned a {:?}", owner.username, pet_type);
+    ctx.db.player().identity().update(owner);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn dismiss_pet(ctx: &ReducerContext) -> Result<(), String> {
+    if !ctx.db.pet().owner().delete(ctx.sender) {
+        return Err("You have no pet".to_string());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pets_close_in_on_their_goal_without_overshooting_the_stop_distance() {
+        let goal = Vector3 { x: 10.0, y: 0.0, z: 0.0 };
+        let step = step_toward(Vector3::default(), goal, 2.0, 3.0).unwrap();
+        assert!((step.x - 3.0).abs() < 1e-5 && step.z == 0.0);
+
+        let arrived = step_toward(Vector3 { x: 7.0, y: 0.0, z: 0.0 }, goal, 2.0, 3.0).unwrap();
+        assert!((arrived.x - 8.0).abs() < 1e-5, "stops at the stop distance");
+
+        assert!(step_toward(arrived, goal, 2.0, 3.0).is_none(), "within reach it stays put");
+        let above = Vector3 { x: 9.0, y: 5.0, z: 0.0 };
+        assert!(step_toward(above, goal, 2.0, 3.0).is_none(), "height is ignored");
+    }
+}
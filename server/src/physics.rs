@@ -1,9 +1,167 @@
-use crate::common::{Vector3, PLAYER_RADIUS, PLAYER_HEIGHT, PROJECTILE_RADIUS};
+use crate::common::{Vector3, PLAYER_HEIGHT, PROJECTILE_RADIUS};
+use crate::environment::StaticCollider;
+use crate::events::ImpactTarget;
+use crate::PlayerData;
 
-pub fn check_collision(player_pos: &Vector3, projectile_pos: &Vector3) -> bool {
+// Shared vertical integration for every mover (players, projectiles, thrown weapons).
+// Gravity comes from GameConfig per entity class; 0.0 means the entity flies.
+// With `ground`, the position is clamped to it and true is returned on touchdown.
+pub fn apply_gravity(pos: &mut Vector3, vertical_velocity: &mut f32, gravity: f32, delta_time: f32, ground: Option<f32>) -> bool {
+    *vertical_velocity += gravity * delta_time;
+    pos.y += *vertical_velocity * delta_time;
+    match ground {
+        Some(ground_y) if pos.y <= ground_y => {
+            pos.y = ground_y;
+            *vertical_velocity = 0.0;
+            true
+        }
+        _ => false,
+    }
+}
+
+// Unit aim vector for a yaw (forward = -Z) and a pitch (positive looks up)
+pub fn aim_direction(yaw: f32, pitch: f32) -> Vector3 {
+    Vector3 { x: -yaw.sin() * pitch.cos(), y: pitch.sin(), z: -yaw.cos() * pitch.cos() }
+}
+
+// Moves `pos` by the horizontal knockback velocity, then decays it exponentially by `friction`
+pub fn integrate_knockback(pos: &mut Vector3, velocity: &mut Vector3, friction: f32, delta_time: f32) {
+    if velocity.x == 0.0 && velocity.z == 0.0 {
+        return;
+    }
+    pos.x += velocity.x * delta_time;
+    pos.z += velocity.z * delta_time;
+    *velocity = *velocity * (-friction * delta_time).exp();
+    if velocity.length() < 0.05 {
+        *velocity = Vector3::default();
+    }
+}
+
+// Push a vertical cylinder (feet at pos.y) out of an axis-aligned box along the shallower
+// horizontal axis. Only overlaps below the box top count, so standing on it is not a collision.
+// Returns true if the position was corrected.
+pub fn push_out_of_box(pos: &mut Vector3, radius: f32, height: f32, center: &Vector3, half: &Vector3) -> bool {
+    let top = center.y + half.y;
+    let bottom = center.y - half.y;
+    if pos.y >= top - 0.01 || pos.y + height <= bottom {
+        return false;
+    }
+    let dx = pos.x - center.x;
+    let dz = pos.z - center.z;
+    let overlap_x = half.x + radius - dx.abs();
+    let overlap_z = half.z + radius - dz.abs();
+    if overlap_x <= 0.0 || overlap_z <= 0.0 {
+        return false;
+    }
+    if overlap_x < overlap_z {
+        pos.x += if dx >= 0.0 { overlap_x } else { -overlap_x };
+    } else {
+        pos.z += if dz >= 0.0 { overlap_z } else { -overlap_z };
+    }
+    true
+}
+
+// Where the segment from -> to first enters an axis-aligned box, as a fraction 0..=1 of its
+// length (slab test). A segment starting inside the box enters at 0.
+pub fn segment_box_entry(from: &Vector3, to: &Vector3, center: &Vector3, half: &Vector3) -> Option<f32> {
+    let delta = *to - *from;
+    let axes = [
+        (from.x, delta.x, center.x, half.x),
+        (from.y, delta.y, center.y, half.y),
+        (from.z, delta.z, center.z, half.z),
+    ];
+    let mut t_enter = 0.0_f32;
+    let mut t_exit = 1.0_f32;
+    for (origin, step, mid, extent) in axes {
+        let (low, high) = (mid - extent, mid + extent);
+        if step.abs() < f32::EPSILON {
+            if origin < low || origin > high {
+                return None;
+            }
+            continue;
+        }
+        let (t1, t2) = ((low - origin) / step, (high - origin) / step);
+        t_enter = t_enter.max(t1.min(t2));
+        t_exit = t_exit.min(t1.max(t2));
+        if t_enter > t_exit {
+            return None;
+        }
+    }
+    Some(t_enter)
+}
+
+// Distance along a unit-length ray at which it enters an upright cylinder of `height` standing
+// on `base` (None if it misses within max_distance). A ray starting inside hits at 0.
+pub fn ray_cylinder_entry(origin: Vector3, direction: Vector3, max_distance: f32, base: Vector3, radius: f32, height: f32) -> Option<f32> {
+    let offset = origin - base;
+    let (mut t_enter, mut t_exit) = (0.0_f32, max_distance);
+
+    let a = direction.x * direction.x + direction.z * direction.z;
+    let c = offset.x * offset.x + offset.z * offset.z - radius * radius;
+    if a < f32::EPSILON {
+        if c > 0.0 {
+            return None; // vertical ray outside the circle
+        }
+    } else {
+        let b = 2.0 * (offset.x * direction.x + offset.z * direction.z);
+        let discriminant = b * b - 4.0 * a * c;
+        if discriminant < 0.0 {
+            return None;
+        }
+        let root = discriminant.sqrt();
+        t_enter = t_enter.max((-b - root) / (2.0 * a));
+        t_exit = t_exit.min((-b + root) / (2.0 * a));
+    }
+
+    if direction.y.abs() < f32::EPSILON {
+        if offset.y < 0.0 || offset.y > height {
+            return None;
+        }
+    } else {
+        let (t1, t2) = (-offset.y / direction.y, (height - offset.y) / direction.y);
+        t_enter = t_enter.max(t1.min(t2));
+        t_exit = t_exit.min(t1.max(t2));
+    }
+    (t_enter <= t_exit).then_some(t_enter)
+}
+
+// Nearest player (cylinder) or static collider along the ray, with its distance.
+// Players in `players` are all candidates; callers filter out the shooter.
+pub fn raycast(origin: Vector3, direction: Vector3, max_distance: f32, players: &[PlayerData], colliders: &[StaticCollider]) -> Option<(ImpactTarget, f32)> {
+    let end = origin + direction * max_distance;
+    let player_hits = players.iter().filter_map(|p| {
+        ray_cylinder_entry(origin, direction, max_distance, p.position, p.hit_radius, p.collision_height()).map(|d| (ImpactTarget::Player(p.identity), d))
+    });
+    let collider_hits = colliders.iter().filter_map(|c| {
+        segment_box_entry(&origin, &end, &c.center, &c.half_extents).map(|t| (ImpactTarget::Geometry(c.id), t * max_distance))
+    });
+    player_hits.chain(collider_hits).min_by(|a, b| a.1.total_cmp(&b.1))
+}
+
+// Melee hit test: is `target_pos` within `range` of the attacker and inside the horizontal
+// arc of +/- half_angle_radians around the attacker's facing (yaw, forward = -Z)?
+pub fn in_melee_arc(attacker_pos: Vector3, yaw: f32, target_pos: Vector3, range: f32, half_angle_radians: f32) -> bool {
+    let dx = target_pos.x - attacker_pos.x;
+    let dz = target_pos.z - attacker_pos.z;
+    let distance = (dx * dx + dz * dz).sqrt();
+    if distance > range || (target_pos.y - attacker_pos.y).abs() > PLAYER_HEIGHT {
+        return false;
+    }
+    if distance < 0.001 {
+        return true; // overlapping players are always hit
+    }
+    let forward_x = -yaw.sin();
+    let forward_z = -yaw.cos();
+    let cos_angle = (dx * forward_x + dz * forward_z) / distance;
+    cos_angle >= half_angle_radians.cos()
+}
+
+// Projectile vs player: the player is a vertical cylinder of `hit_radius` and `height`
+// (PlayerData::collision_height) standing on player_pos
+pub fn check_collision(player_pos: &Vector3, hit_radius: f32, height: f32, projectile_pos: &Vector3) -> bool {
     // 1. Clamp projectile Y to be within the player's vertical range (Cylinder)
     let player_bottom = player_pos.y;
-    let player_top = player_pos.y + PLAYER_HEIGHT;
+    let player_top = player_pos.y + height;
     
     // Find the closest point on the player's vertical axis to the projectile
     let closest_y = projectile_pos.y.max(player_bottom).min(player_top);
@@ -16,7 +174,195 @@ pub fn check_collision(player_pos: &Vector3, projectile_pos: &Vector3) -> bool {
     let distance_sq = dx * dx + dy * dy + dz * dz;
 
     // 3. Check if distance is less than sum of radii
-    let hit_radius = PLAYER_RADIUS + PROJECTILE_RADIUS;
+    let hit_radius = hit_radius + PROJECTILE_RADIUS;
     
     distance_sq <= (hit_radius * hit_radius)
 }
+
+fn dot(a: Vector3, b: Vector3) -> f32 {
+    a.x * b.x + a.y * b.y + a.z * b.z
+}
+
+// Mirror `direction` about a surface with unit `normal`
+pub fn reflect(direction: Vector3, normal: Vector3) -> Vector3 {
+    direction - normal * (2.0 * dot(direction, normal))
+}
+
+// Outward normal of the box face closest to `point` (a point on or near its surface)
+pub fn box_surface_normal(point: Vector3, center: Vector3, half: Vector3) -> Vector3 {
+    let offset = point - center;
+    let (nx, ny, nz) = (offset.x / half.x.max(1e-4), offset.y / half.y.max(1e-4), offset.z / half.z.max(1e-4));
+    if nx.abs() >= ny.abs() && nx.abs() >= nz.abs() {
+        Vector3 { x: nx.signum(), y: 0.0, z: 0.0 }
+    } else if ny.abs() >= nz.abs() {
+        Vector3 { x: 0.0, y: ny.signum(), z: 0.0 }
+    } else {
+        Vector3 { x: 0.0, y: 0.0, z: nz.signum() }
+    }
+}
+
+// Turns unit vector `current` towards unit vector `desired` by at most `max_angle` radians
+pub fn steer_towards(current: Vector3, desired: Vector3, max_angle: f32) -> Vector3 {
+    let angle = dot(current, desired).clamp(-1.0, 1.0).acos();
+    if angle <= max_angle || angle < 1e-4 {
+        return desired;
+    }
+    let t = max_angle / angle;
+    let sin_angle = angle.sin();
+    if sin_angle < 1e-4 {
+        return current; // exactly opposite: no preferred way to turn
+    }
+    (current * (((1.0 - t) * angle).sin() / sin_angle) + desired * ((t * angle).sin() / sin_angle)).normalize()
+}
+
+// Is `target` within `half_angle` radians of the unit `direction` as seen from `origin`?
+pub fn in_cone(origin: Vector3, direction: Vector3, target: Vector3, half_angle: f32) -> bool {
+    let to_target = (target - origin).normalize();
+    dot(direction, to_target) >= half_angle.cos()
+}
+
+// Swept check_collision over the segment a projectile covers in one tick, so fast projectiles
+// can't step over a player between ticks. Tests the point of the segment closest to the
+// player's vertical axis (segment-segment closest points).
+pub fn check_swept_collision(player_pos: &Vector3, hit_radius: f32, height: f32, from: &Vector3, to: &Vector3) -> bool {
+    let axis = Vector3 { x: 0.0, y: height, z: 0.0 };
+    let path = *to - *from;
+    let offset = *player_pos - *from;
+    let path_len_sq = dot(path, path);
+    if path_len_sq <= f32::EPSILON {
+        return check_collision(player_pos, hit_radius, height, to);
+    }
+
+    let axis_len_sq = dot(axis, axis);
+    let b = dot(axis, path);
+    let c = dot(axis, offset);
+    let f = dot(path, offset);
+    let denom = axis_len_sq * path_len_sq - b * b;
+    let along_axis = if denom > f32::EPSILON { ((b * f - c * path_len_sq) / denom).clamp(0.0, 1.0) } else { 0.0 };
+    let along_path = ((b * along_axis + f) / path_len_sq).clamp(0.0, 1.0);
+
+    check_collision(player_pos, hit_radius, height, &(*from + path * along_path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::{LOBBY_INSTANCE_ID, MELEE_ARC_DEGREES, MELEE_RANGE};
+
+    // One second of 50ms ticks from rest at y = 100. Returns (height, vertical velocity)
+    fn fall_for_a_second(gravity: f32) -> (f32, f32) {
+        let mut pos = Vector3 { x: 0.0, y: 100.0, z: 0.0 };
+        let mut velocity = 0.0;
+        for _ in 0..20 {
+            assert!(!apply_gravity(&mut pos, &mut velocity, gravity, 0.05, Some(0.0)));
+        }
+        (pos.y, velocity)
+    }
+
+    #[test]
+    fn each_entity_class_falls_at_its_configured_rate() {
+        // Players, projectiles and thrown weapons as GameConfig might set them
+        let mut drops = Vec::new();
+        for gravity in [-9.0, -3.0, -6.0] {
+            let (y, velocity) = fall_for_a_second(gravity);
+            assert!((velocity - gravity).abs() < 1e-4);
+            let drop = 100.0 - y;
+            // Semi-implicit Euler lands a little past the exact g/2 after one second
+            assert!((drop - -gravity * 0.5).abs() < -gravity * 0.05 + 1e-4, "{gravity}: fell {drop}");
+            drops.push(drop);
+        }
+        assert!(drops[0] > drops[2] && drops[2] > drops[1]);
+    }
+
+    #[test]
+    fn flying_entities_never_fall() {
+        assert_eq!(fall_for_a_second(0.0), (100.0, 0.0));
+    }
+
+    // A 10m one-tick pass along z at height y, `side` metres to the side of a player at the origin
+    fn passes(side: f32, y: f32) -> bool {
+        let from = Vector3 { x: side, y, z: -5.0 };
+        let to = Vector3 { x: side, y, z: 5.0 };
+        check_swept_collision(&Vector3::default(), 0.5, PLAYER_HEIGHT, &from, &to)
+    }
+
+    #[test]
+    fn grazing_passes_hit_up_to_exactly_the_combined_radius() {
+        let reach = 0.5 + PROJECTILE_RADIUS;
+        assert!(passes(reach, 1.0), "tangent");
+        assert!(passes(reach - 0.01, 1.0), "just inside");
+        assert!(!passes(reach + 0.01, 1.0), "just outside");
+        // Neither end of the segment is near the player, so a per-tick point check would miss all of these
+        assert!(!check_collision(&Vector3::default(), 0.5, PLAYER_HEIGHT, &Vector3 { x: 0.0, y: 1.0, z: -5.0 }));
+    }
+
+    #[test]
+    fn grazing_over_the_head_reaches_the_combined_radius_above_the_top() {
+        let reach = 0.5 + PROJECTILE_RADIUS;
+        assert!(passes(0.0, PLAYER_HEIGHT + reach - 0.01));
+        assert!(!passes(0.0, PLAYER_HEIGHT + reach + 0.01));
+    }
+
+    #[test]
+    fn landing_clamps_to_the_ground() {
+        let mut pos = Vector3 { x: 0.0, y: 0.1, z: 0.0 };
+        let mut velocity = -5.0;
+        assert!(apply_gravity(&mut pos, &mut velocity, -6.0, 0.05, Some(0.0)));
+        assert_eq!((pos.y, velocity), (0.0, 0.0));
+    }
+
+    #[test]
+    fn melee_swings_hit_in_front_within_range_only() {
+        let attacker = Vector3 { x: 0.0, y: 0.0, z: 0.0 };
+        let half_angle = MELEE_ARC_DEGREES.to_radians() / 2.0;
+        let at = |x: f32, y: f32, z: f32| in_melee_arc(attacker, 0.0, Vector3 { x, y, z }, MELEE_RANGE, half_angle);
+
+        assert!(at(0.0, 0.0, -2.0), "straight ahead (yaw 0 faces -Z)");
+        assert!(at(1.0, 0.0, -1.5), "inside the arc");
+        assert!(!at(2.0, 0.0, -0.5), "beside the attacker, outside the arc");
+        assert!(!at(0.0, 0.0, 2.0), "behind");
+        assert!(!at(0.0, 0.0, -MELEE_RANGE - 0.1), "out of reach");
+        assert!(!at(0.0, PLAYER_HEIGHT + 0.5, -1.0), "too far above");
+        // Turning around swaps front and back
+        assert!(in_melee_arc(attacker, std::f32::consts::PI, Vector3 { x: 0.0, y: 0.0, z: 2.0 }, MELEE_RANGE, half_angle));
+    }
+
+    #[test]
+    fn hitscan_rays_stop_at_whatever_they_meet_first() {
+        let mut near = crate::test_player(1);
+        near.position = Vector3 { x: 0.0, y: 0.0, z: -10.0 };
+        let mut far = crate::test_player(2);
+        far.position = Vector3 { x: 0.0, y: 0.0, z: -20.0 };
+        let wall = StaticCollider { id: 3, center: Vector3 { x: 0.0, y: 1.0, z: -15.0 }, half_extents: Vector3 { x: 2.0, y: 2.0, z: 0.5 }, instance_id: LOBBY_INSTANCE_ID };
+        let origin = Vector3 { x: 0.0, y: 1.0, z: 0.0 };
+        let aim = Vector3 { x: 0.0, y: 0.0, z: -1.0 };
+        let players = [far.clone(), near.clone()];
+
+        let (target, distance) = raycast(origin, aim, 50.0, &players, std::slice::from_ref(&wall)).unwrap();
+        assert_eq!(target, ImpactTarget::Player(near.identity));
+        assert!((distance - (10.0 - near.hit_radius)).abs() < 1e-3);
+        // The wall shields the far player
+        let (target, distance) = raycast(origin, aim, 50.0, &[far], std::slice::from_ref(&wall)).unwrap();
+        assert_eq!(target, ImpactTarget::Geometry(wall.id));
+        assert!((distance - 14.5).abs() < 1e-3);
+        // Out of range is a miss
+        assert!(raycast(origin, aim, 9.0, &players, &[wall]).is_none());
+    }
+
+    #[test]
+    fn homing_turns_at_most_the_turn_rate_and_locks_on_when_close() {
+        let ahead = Vector3 { x: 0.0, y: 0.0, z: -1.0 };
+        let right = Vector3 { x: 1.0, y: 0.0, z: 0.0 };
+        let turned = steer_towards(ahead, right, 0.5);
+        assert!((dot(turned, ahead).acos() - 0.5).abs() < 1e-4, "turned exactly the allowed angle");
+        assert!((turned.length() - 1.0).abs() < 1e-5);
+        assert!(turned.x > 0.0, "towards the target");
+        assert_eq!(steer_towards(ahead, right, 2.0), right, "within reach it snaps onto the target");
+
+        let origin = Vector3::default();
+        let cone = 20f32.to_radians();
+        assert!(in_cone(origin, ahead, Vector3 { x: 1.0, y: 0.0, z: -10.0 }, cone));
+        assert!(!in_cone(origin, ahead, Vector3 { x: 10.0, y: 0.0, z: -10.0 }, cone));
+        assert!(!in_cone(origin, ahead, Vector3 { x: 0.0, y: 0.0, z: 10.0 }, cone), "behind");
+    }
+}
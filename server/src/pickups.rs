@@ -0,0 +1,152 @@
+/*
+ * Vibe Coding Starter Pack: 3D Multiplayer - pickups.rs
+ *
+ * World pickups (health packs, mana orbs) that players collect by walking over them.
+ *
+ * Key components:
+ *    - PickupData: Public table of pickups; inactive rows are waiting to respawn at respawn_at
+ *    - seed_pickups: Places the default pickups the first time the module initializes
+ *    - update_pickups: Per-tick collection and respawning (called from game_tick)
+ *    - stops_projectile / deny_pickup: Used by projectile_logic when a Breaker shot destroys a
+ *      destructible pickup
+ *    - drop_pickup: One-shot pickup left in the world (e.g. a thrown axe that missed)
+ *
+ * Projectiles ignore pickups unless both sides opt in: the projectile's spec must have
+ * `hits_pickups` and the pickup must be `destructible`. Everything else passes through.
+ */
+
+use spacetimedb::{ReducerContext, Table, TimeDuration, Timestamp};
+
+use crate::common::{PickupKind, Vector3, AXE_MAX_AMMO, PICKUP_RADIUS, PROJECTILE_RADIUS, LOBBY_INSTANCE_ID};
+use crate::player;
+
+#[spacetimedb::table(name = pickup, public)]
+#[derive(Clone)]
+pub struct PickupData {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub kind: PickupKind,
+    pub position: Vector3,
+    pub amount: i32,
+    pub destructible: bool,          // can be destroyed by projectiles flagged with hits_pickups
+    pub active: bool,                // false while waiting to respawn
+    pub respawn_secs: f32,           // 0 = one-shot, row is deleted once used
+    pub respawn_at: Option<Timestamp>, // set while inactive
+}
+
+pub fn seed_pickups(ctx: &ReducerContext) {
+    if ctx.db.pickup().count() > 0 {
+        return;
+    }
+    let defaults = [
+        (PickupKind::Health, Vector3 { x: 10.0, y: 0.5, z: -10.0 }, 50, true),
+        (PickupKind::Health, Vector3 { x: -10.0, y: 0.5, z: -10.0 }, 50, false),
+        (PickupKind::Mana, Vector3 { x: 0.0, y: 0.5, z: -20.0 }, 50, false),
+    ];
+    for (kind, position, amount, destructible) in defaults {
+        ctx.db.pickup().insert(PickupData {
+            id: 0, // auto_inc
+            kind,
+            position,
+            amount,
+            destructible,
+            active: true,
+            respawn_secs: 10.0,
+            respawn_at: None,
+        });
+    }
+    spacetimedb::log::info!("[INIT] Seeded {} pickups.", defaults.len());
+}
+
+// Place a one-shot, indestructible pickup (deleted once collected)
+pub fn drop_pickup(ctx: &ReducerContext, kind: PickupKind, position: Vector3, amount: i32) -> PickupData {
+    ctx.db.pickup().insert(PickupData {
+        id: 0, // auto_inc
+        kind,
+        position: Vector3 { x: position.x, y: position.y.max(0.5), z: position.z },
+        amount,
+        destructible: false,
+        active: true,
+        respawn_secs: 0.0,
+        respawn_at: None,
+    })
+}
+
+// Consume a pickup: one-shot pickups are deleted, respawning ones go inactive
+fn consume_pickup(ctx: &ReducerContext, mut pickup: PickupData) {
+    if pickup.respawn_secs <= 0.0 {
+        ctx.db.pickup().id().delete(pickup.id);
+    } else {
+        pickup.active = false;
+        pickup.respawn_at = Some(ctx.timestamp + TimeDuration::from_micros((pickup.respawn_secs * 1_000_000.0) as i64));
+        ctx.db.pickup().id().update(pickup);
+    }
+}
+
+// Whether a projectile at `point` hits this pickup; both sides have to opt in
+pub fn stops_projectile(pickup: &PickupData, hits_pickups: bool, point: Vector3) -> bool {
+    hits_pickups && pickup.active && pickup.destructible && (pickup.position - point).length() < PICKUP_RADIUS + PROJECTILE_RADIUS
+}
+
+// Destroy a pickup without granting it to anyone (projectile impact)
+pub fn deny_pickup(ctx: &ReducerContext, pickup: PickupData) {
+    spacetimedb::log::info!("Pickup {} destroyed by projectile", pickup.id);
+    consume_pickup(ctx, pickup);
+}
+
+pub fn update_pickups(ctx: &ReducerContext) {
+    for mut pickup in ctx.db.pickup().iter() {
+        if !pickup.active {
+            if pickup.respawn_at.is_some_and(|t| t <= ctx.timestamp) {
+                pickup.active = true;
+                pickup.respawn_at = None;
+                ctx.db.pickup().id().update(pickup);
+            }
+            continue;
+        }
+
+        let collector = ctx.db.player().iter().find(|p| {
+            p.alive && p.instance_id == LOBBY_INSTANCE_ID && (p.position - pickup.position).length() < PICKUP_RADIUS + p.hit_radius
+        });
+        if let Some(mut player) = collector {
+            match pickup.kind {
+                PickupKind::Health => player.health = (player.health + pickup.amount).min(player.max_health),
+                PickupKind::Mana => player.mana = (player.mana + pickup.amount).min(player.max_mana),
+                PickupKind::Axe => player.axe_ammo = (player.axe_ammo + pickup.amount.max(0) as u32).min(AXE_MAX_AMMO),
+            }
+            spacetimedb::log::info!("Player {} collected pickup {}", player.username, pickup.id);
+            ctx.db.player().identity().update(player);
+            consume_pickup(ctx, pickup);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pickup_at(position: Vector3, destructible: bool) -> PickupData {
+        PickupData { id: 1, kind: PickupKind::Health, position, amount: 50, destructible, active: true, respawn_secs: 10.0, respawn_at: None }
+    }
+
+    #[test]
+    fn breaker_shots_hit_destructible_pickups_only() {
+        let at = Vector3 { x: 10.0, y: 0.5, z: -10.0 };
+        assert!(stops_projectile(&pickup_at(at, true), true, at));
+        assert!(!stops_projectile(&pickup_at(at, false), true, at));
+    }
+
+    #[test]
+    fn ordinary_shots_pass_through_pickups() {
+        let at = Vector3 { x: 10.0, y: 0.5, z: -10.0 };
+        assert!(!stops_projectile(&pickup_at(at, true), false, at));
+    }
+
+    #[test]
+    fn respawning_pickups_are_not_hit() {
+        let at = Vector3 { x: 10.0, y: 0.5, z: -10.0 };
+        let waiting = PickupData { active: false, ..pickup_at(at, true) };
+        assert!(!stops_projectile(&waiting, true, at));
+    }
+}
@@ -1,4 +1,4 @@
-/**
+/*!
  * Vibe Coding Starter Pack: 3D Multiplayer - player_logic.rs
  * 
  * This file contains the core movement and player state update logic.
@@ -8,6 +8,9 @@
  * 
  * 1. Movement Calculation:
  *    - calculate_new_position: Computes player movement based on input and rotation
+ *      (called per player by game_tick), starting from where a moving platform carried the
+ *      player, scaled by status-effect slows, plus knockback from hits and explosions
+ *      decaying with friction
  *    - Vector math for converting input to movement direction
  *    - Direction normalization and speed application
  * 
@@ -16,9 +19,28 @@
  *    - Handles position, animation, and derived state (is_moving, is_running)
  *    - Translates raw input to game state
  * 
+ *    - apply_client_input: Applies one queued client input (drained per tick by game_tick);
+ *      pitch is clamped to MAX_AIM_PITCH and forward_vector (used for aiming) is rebuilt
+ *      from yaw and pitch. It never jumps; starts_jump is the game_tick check (grounded, not
+ *      crouching, not stunned). queue_input / apply_next_input keep at most INPUT_BUFFER_TICKS
+ *      inputs waiting, oldest dropped first, and apply them one per tick in order
+ *    - spawn_position_for: Spawn point for respawns and rescues (spawns::choose_spawn_point with
+ *      the player's team, or the entrance room inside a dungeon, dungeons.rs)
+ *    - respawn_player: Revives a player at a spawn point (respawn timer, round resets)
+ *    - rescue_fallen_players: Kill-plane safety net (called from game_tick after movement)
+ *    - fall_damage: Landing damage from the fall distance accumulated in game_tick
+ *    - update_breath: Spends breath while the head is under water and recovers it above;
+ *      out of breath, returns DROWN_DAMAGE every DROWN_INTERVAL_SECS
+ * 
  * 3. Game Tick:
- *    - update_players_logic: Placeholder for periodic player updates
- *    - Currently empty as players are updated directly through input
+ *    - update_players_logic: Periodic per-player systems (called from game_tick)
+ *    - Passive health/mana regeneration using per-class rates from the class_definition table,
+ *      paused for GameConfig::regen_delay_secs after taking damage. Like passive ult charge it
+ *      only runs while gameplay is enabled for the player's instance (matches::gameplay_allowed)
+ *    - Spread heat (bloom) recovery while not firing: every shot adds SPREAD_HEAT_PER_SHOT
+ *      (heat_after_shot), which widens the aim by up to SPREAD_MAX_RADIANS (spread_radians)
+ *      and cools off over time (cooled_heat)
+ *    - check_cast: Per-class cast cooldown and mana cost check used by fire_projectile
  *    - Can be extended for server-side simulation (AI, physics, etc.)
  * 
  * Extension points:
@@ -32,11 +54,19 @@
  *    - lib.rs: Calls into this module's functions from reducers
  */
 
-use spacetimedb::ReducerContext;
+use spacetimedb::{ReducerContext, Table, Timestamp};
+use std::time::Duration;
 // Import common structs and constants
-use crate::common::{Vector3, InputState, PLAYER_SPEED, SPRINT_MULTIPLIER, GRAVITY, JUMP_FORCE};
-// Import the PlayerData struct definition (assuming it's in lib.rs or common.rs)
-use crate::PlayerData;
+use crate::common::{Vector3, InputState, PLAYER_SPEED, SPRINT_MULTIPLIER, CROUCH_SPEED_MULTIPLIER, SWIM_SPEED_MULTIPLIER, GRAVITY, MAX_AIM_PITCH, FALL_DAMAGE_MIN_HEIGHT, FALL_DAMAGE_PER_METER, ULT_CHARGE_MAX, ULT_CHARGE_PER_SEC, BufferedInput, INPUT_BUFFER_TICKS, KILL_PLANE_PENALTY, Y_KILL_PLANE, SPREAD_HEAT_PER_SHOT, SPREAD_HEAT_RECOVERY_PER_SEC, SPREAD_MAX_RADIANS, KNOCKBACK_FRICTION, BREATH_RECOVERY_PER_SEC, DROWN_DAMAGE, DROWN_INTERVAL_SECS, MAX_BREATH_SECS, AXE_MAX_AMMO};
+// Import the PlayerData struct definition and its table accessor
+use crate::classes::{find_class, load_class_definitions, ClassDefinition};
+use crate::config::get_game_config;
+use crate::dungeons::dungeon_entrance;
+use crate::matches::{gameplay_allowed, get_match_state};
+use crate::physics::{aim_direction, integrate_knockback};
+use crate::spawns::choose_spawn_point;
+use crate::zones::{load_zones, regen_boost};
+use crate::{player, PlayerData};
 
 // Fortnite-style movement calculation using yaw only, with vertical velocity in PlayerData
 // MOVED TO GAME_TICK IN LIB.RS
@@ -107,6 +137,60 @@ use crate::PlayerData;
 //     new_pos
 // }
 
+// Input-driven speed: swim, crouch or sprint on top of the class speed, then slows and stuns
+pub fn movement_speed(player: &PlayerData, move_speed: f32, speed_multiplier: f32) -> f32 {
+    let speed = if player.is_swimming {
+        move_speed * SWIM_SPEED_MULTIPLIER
+    } else if player.is_crouching {
+        move_speed * CROUCH_SPEED_MULTIPLIER
+    } else if player.input.sprint {
+        move_speed * SPRINT_MULTIPLIER
+    } else {
+        move_speed
+    };
+    let mount = if player.is_mounted { player.mount_speed_multiplier } else { 1.0 };
+    speed * mount * speed_multiplier
+}
+
+// Horizontal movement for one tick, called from game_tick (vertical physics stays there).
+// A moving platform's `carry` (environment::carry_delta) moves the player first, vertically
+// too, so the tick starts from where the platform took them. Input moves the player along
+// their yaw at `move_speed` (swim, crouch, sprint or mount applied by movement_speed) scaled by
+// `speed_multiplier` (slows and stuns). Knockback is added on top, regardless of input or
+// stuns, and decays with KNOCKBACK_FRICTION.
+pub fn calculate_new_position(player: &mut PlayerData, carry: Option<Vector3>, move_speed: f32, speed_multiplier: f32, delta_time: f32) -> Vector3 {
+    if let Some(delta) = carry {
+        player.position = player.position + delta;
+    }
+    let input = &player.input;
+    let speed = movement_speed(player, move_speed, speed_multiplier);
+
+    // Build forward/right from yaw (convention: forward is -z)
+    let cos_yaw = player.rotation.y.cos();
+    let sin_yaw = player.rotation.y.sin();
+    let forward = Vector3 { x: -sin_yaw, y: 0.0, z: -cos_yaw };
+    let right   = Vector3 { x:  cos_yaw, y: 0.0, z: -sin_yaw };
+
+    let mut dir = Vector3::default();
+    if input.forward  { dir = dir + forward; }
+    if input.backward { dir = dir - forward; }
+    if input.right    { dir = dir + right; }
+    if input.left     { dir = dir - right; }
+
+    let horiz_len = (dir.x * dir.x + dir.z * dir.z).sqrt();
+    if horiz_len > 0.01 {
+        dir.x /= horiz_len;
+        dir.z /= horiz_len;
+    }
+
+    let mut new_pos = player.position;
+    new_pos.x += dir.x * speed * delta_time;
+    new_pos.z += dir.z * speed * delta_time;
+
+    integrate_knockback(&mut new_pos, &mut player.knockback_velocity, KNOCKBACK_FRICTION, delta_time);
+    new_pos
+}
+
 // Note: Animation determination is currently handled client-side
 // You could implement server-side animation logic here if needed
 // For example:
@@ -172,9 +256,439 @@ pub fn update_input_state(player: &mut PlayerData, input: InputState, client_ani
     player.is_casting = input.cast_spell;
 }
 
+// Apply one client input update (drained from the input queue by game_tick)
+pub fn apply_client_input(player: &mut PlayerData, buffered: BufferedInput) {
+    let input = buffered.input;
+    // Aim is derived on the server from yaw and a clamped pitch
+    let pitch = if buffered.client_pitch.is_finite() { buffered.client_pitch.clamp(-MAX_AIM_PITCH, MAX_AIM_PITCH) } else { 0.0 };
+    player.rotation.x = pitch;
+    player.rotation.y = buffered.client_yaw;
+    player.forward_vector = aim_direction(buffered.client_yaw, pitch);
+    player.last_input_seq = input.sequence;
+    player.input = input;
+    player.current_animation = buffered.client_animation;
+}
+
+// Jumping happens only in game_tick, so a stun can't be dodged by the input path.
+// Crouch + jump is a platform drop, not a jump, and a crouching player can't jump.
+pub fn starts_jump(player: &PlayerData, stunned: bool) -> bool {
+    player.input.jump && !player.input.crouch && !player.is_crouching && player.grounded && !stunned
+}
+
+pub fn queue_input(player: &mut PlayerData, buffered: BufferedInput) {
+    if player.input_queue.len() >= INPUT_BUFFER_TICKS {
+        player.input_queue.remove(0);
+    }
+    player.input_queue.push(buffered);
+}
+
+// Oldest queued input first; nothing happens once the queue is empty
+pub fn apply_next_input(player: &mut PlayerData) {
+    if !player.input_queue.is_empty() {
+        let next = player.input_queue.remove(0);
+        apply_client_input(player, next);
+    }
+}
+
+// Returns drowning damage to apply this tick
+pub fn update_breath(player: &mut PlayerData, submerged: bool, delta_time: f32) -> i32 {
+    if !submerged {
+        player.breath_remaining = (player.breath_remaining + BREATH_RECOVERY_PER_SEC * delta_time).min(MAX_BREATH_SECS);
+        return 0;
+    }
+    player.breath_remaining -= delta_time;
+    if player.breath_remaining > 0.0 {
+        return 0;
+    }
+    player.breath_remaining += DROWN_INTERVAL_SECS; // next gasp
+    DROWN_DAMAGE
+}
+
+// Damage taken when landing after falling `fall_distance` meters since the last apex
+pub fn fall_damage(fall_distance: f32) -> i32 {
+    if fall_distance <= FALL_DAMAGE_MIN_HEIGHT {
+        return 0;
+    }
+    ((fall_distance - FALL_DAMAGE_MIN_HEIGHT) * FALL_DAMAGE_PER_METER).round() as i32
+}
+
+// Adds `amount` to a fractional accumulator and moves whole points into `value`
+fn regenerate(value: &mut i32, max: i32, progress: &mut f32, amount: f32) {
+    if *value >= max {
+        *progress = 0.0;
+        return;
+    }
+    *progress += amount;
+    let whole = progress.floor();
+    if whole >= 1.0 {
+        *value = (*value + whole as i32).min(max);
+        *progress -= whole;
+    }
+}
+
+// Health and mana at the class's per-second rates; `scale` is delta time
+fn regenerate_stats(player: &mut PlayerData, stats: &ClassDefinition, scale: f32) {
+    regenerate(&mut player.health, player.max_health, &mut player.health_regen_progress, stats.health_regen_per_sec * scale);
+    regenerate(&mut player.mana, player.max_mana, &mut player.mana_regen_progress, stats.mana_regen_per_sec * scale);
+}
+
+pub fn spread_radians(spread_heat: f32) -> f32 {
+    spread_heat * SPREAD_MAX_RADIANS
+}
+
+pub fn heat_after_shot(spread_heat: f32) -> f32 {
+    (spread_heat + SPREAD_HEAT_PER_SHOT).min(1.0)
+}
+
+pub fn cooled_heat(spread_heat: f32, delta_time: f32) -> f32 {
+    (spread_heat - SPREAD_HEAT_RECOVERY_PER_SEC * delta_time).max(0.0)
+}
+
+// Class cast cooldown (since last_cast_time) and mana check, before anything is spent
+pub fn check_cast(player: &PlayerData, now: Timestamp, cooldown_secs: f32, mana_cost: i32) -> Result<(), String> {
+    let on_cooldown = player.last_cast_time
+        .and_then(|last| now.duration_since(last))
+        .is_some_and(|elapsed| elapsed < Duration::from_secs_f32(cooldown_secs));
+    if on_cooldown {
+        return Err("Cast is on cooldown".to_string());
+    }
+    if player.mana < mana_cost {
+        return Err("Not enough mana".to_string());
+    }
+    Ok(())
+}
+
+// Spawn point for a (re)spawning player: a dungeon's entrance, otherwise chosen for their team
+pub fn spawn_position_for(ctx: &ReducerContext, player: &PlayerData) -> Vector3 {
+    dungeon_entrance(ctx, player.instance_id).unwrap_or_else(|| choose_spawn_point(ctx, player.identity, player.team))
+}
+
+// Brings a player back to life at a spawn point with full health and no leftover momentum
+pub fn respawn_player(ctx: &ReducerContext, player: &mut PlayerData) {
+    player.alive = true;
+    player.respawn_at = None;
+    player.health = player.max_health;
+    player.vertical_velocity = 0.0;
+    player.knockback_velocity = Vector3::default();
+    player.fall_distance = 0.0;
+    player.is_crouching = false;
+    player.is_swimming = false;
+    player.breath_remaining = MAX_BREATH_SECS;
+    player.input.jump = false;
+
+    player.position = spawn_position_for(ctx, player);
+
+    player.rotation.x = 0.0;
+    player.rotation.y = 0.0;
+    player.forward_vector = Vector3 { x: 0.0, y: 0.0, z: -1.0 };
+    player.last_attacker = None;
+    player.killed_by = None;
+    player.damaged_by.clear();
+    player.is_stealthed = false;
+    player.revealed_until = None;
+    player.input_queue.clear();
+    player.axe_ammo = AXE_MAX_AMMO;
+
+    spacetimedb::log::info!("Player {} respawned at {:?}", player.username, player.position);
+}
+
+// Safety net: players below Y_KILL_PLANE (or with a non-finite position from a physics bug)
+// are teleported back to their spawn with a small, never-lethal health penalty.
+pub fn rescue_fallen_players(ctx: &ReducerContext) {
+    for mut player in ctx.db.player().iter().filter(out_of_world) {
+        spacetimedb::log::warn!("Player {} fell out of the world at {:?}, rescuing", player.username, player.position);
+        let spawn = spawn_position_for(ctx, &player);
+        rescue(&mut player, spawn);
+        ctx.db.player().identity().update(player);
+    }
+}
+
+fn out_of_world(player: &PlayerData) -> bool {
+    let pos = player.position;
+    !(pos.x.is_finite() && pos.y.is_finite() && pos.z.is_finite()) || pos.y < Y_KILL_PLANE
+}
+
+fn rescue(player: &mut PlayerData, spawn: Vector3) {
+    player.position = spawn;
+    player.vertical_velocity = 0.0;
+    player.fall_distance = 0.0;
+    if player.alive {
+        player.health = (player.health - KILL_PLANE_PENALTY).max(1);
+    }
+}
+
+// True while the player took damage less than `regen_delay` ago
+fn regen_paused(player: &PlayerData, now: Timestamp, regen_delay: Duration) -> bool {
+    player.last_damage_time
+        .and_then(|last| now.duration_since(last))
+        .is_some_and(|elapsed| elapsed < regen_delay)
+}
+
 // Update players logic (called from game_tick)
-pub fn update_players_logic(_ctx: &ReducerContext, _delta_time: f64) {
-    // In the simplified starter pack, we don't need to do anything in the game tick
-    // for players as they're updated directly through the update_player_input reducer
-    // This function is a placeholder for future expansion
+// Movement is still simulated directly in game_tick; this handles slower per-player systems.
+pub fn update_players_logic(ctx: &ReducerContext, delta_time: f64) {
+    let delta_time = delta_time as f32;
+    let config = get_game_config(ctx);
+    let regen_delay = Duration::from_secs_f32(config.regen_delay_secs.max(0.0));
+    let classes = load_class_definitions(ctx);
+    let zones = load_zones(ctx);
+    // Same gate as damage, so nobody tops up or charges their ultimate between rounds
+    let phase = get_match_state(ctx).phase;
+
+    // --- Passive regeneration (rates come from the player's class), ult charge and bloom recovery over time ---
+    for mut player in ctx.db.player().iter().filter(|p| p.alive) {
+        let gameplay_enabled = gameplay_allowed(phase, player.instance_id);
+        let topped_up = player.health >= player.max_health && player.mana >= player.max_mana
+            && player.health_regen_progress == 0.0 && player.mana_regen_progress == 0.0
+            && player.ult_charge >= ULT_CHARGE_MAX;
+        if (topped_up || !gameplay_enabled) && player.spread_heat == 0.0 {
+            continue; // nothing to do, skip the row update
+        }
+        player.spread_heat = cooled_heat(player.spread_heat, delta_time);
+        if gameplay_enabled {
+            player.ult_charge = (player.ult_charge + ULT_CHARGE_PER_SEC * delta_time).min(ULT_CHARGE_MAX);
+        }
+        if gameplay_enabled && !regen_paused(&player, ctx.timestamp, regen_delay) {
+            let stats = find_class(&classes, &player.character_class);
+            let scale = config.regen_rate_multiplier * regen_boost(&zones, &player) * delta_time;
+            regenerate_stats(&mut player, &stats, scale);
+        }
+        ctx.db.player().identity().update(player);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::classes::default_class_definitions;
+    use crate::common::DEFAULT_REGEN_DELAY_SECS;
+    use crate::test_player;
+
+    #[test]
+    fn regeneration_waits_out_the_delay_after_damage() {
+        let hit_at = Timestamp::from_micros_since_unix_epoch(1_000_000);
+        let after = |secs: i64| Timestamp::from_micros_since_unix_epoch(1_000_000 + secs * 1_000_000);
+        let delay = Duration::from_secs_f32(DEFAULT_REGEN_DELAY_SECS);
+        let mut player = test_player(1);
+        assert!(!regen_paused(&player, hit_at, delay), "never damaged");
+
+        player.last_damage_time = Some(hit_at);
+        assert!(regen_paused(&player, after(1), delay));
+        assert!(regen_paused(&player, after(DEFAULT_REGEN_DELAY_SECS as i64 - 1), delay));
+        assert!(!regen_paused(&player, after(DEFAULT_REGEN_DELAY_SECS as i64 + 1), delay));
+    }
+
+    #[test]
+    fn casts_need_the_class_cooldown_to_pass_and_enough_mana() {
+        let stats = find_class(&default_class_definitions(), "Wizard");
+        let start = Timestamp::from_micros_since_unix_epoch(1_000_000);
+        let after = |secs: f32| Timestamp::from_micros_since_unix_epoch(1_000_000 + (secs * 1_000_000.0) as i64);
+        let mut caster = test_player(1);
+        assert_eq!(check_cast(&caster, start, stats.cast_cooldown_secs, stats.mana_cost_per_cast), Ok(()));
+
+        caster.last_cast_time = Some(start);
+        caster.mana -= stats.mana_cost_per_cast;
+        let early = after(stats.cast_cooldown_secs * 0.5);
+        assert_eq!(check_cast(&caster, early, stats.cast_cooldown_secs, stats.mana_cost_per_cast), Err("Cast is on cooldown".to_string()));
+        let ready = after(stats.cast_cooldown_secs + 0.01);
+        assert_eq!(check_cast(&caster, ready, stats.cast_cooldown_secs, stats.mana_cost_per_cast), Ok(()));
+
+        caster.mana = stats.mana_cost_per_cast - 1;
+        assert_eq!(check_cast(&caster, ready, stats.cast_cooldown_secs, stats.mana_cost_per_cast), Err("Not enough mana".to_string()));
+        assert_eq!(check_cast(&caster, ready, stats.cast_cooldown_secs, 0), Ok(()), "free casts (thrown axes) only need the cooldown");
+    }
+
+    #[test]
+    fn sustained_fire_widens_the_spread_and_a_pause_tightens_it() {
+        let mut heat = 0.0;
+        let mut spreads = vec![spread_radians(heat)];
+        for _ in 0..10 {
+            heat = heat_after_shot(cooled_heat(heat, 0.1)); // ten shots a second
+            spreads.push(spread_radians(heat));
+        }
+        assert!(spreads.windows(2).all(|w| w[1] >= w[0]), "{spreads:?}");
+        assert!(spreads[10] > spreads[1]);
+        assert!(spreads[10] <= SPREAD_MAX_RADIANS);
+
+        let after_pause = spread_radians(cooled_heat(heat, 0.5));
+        assert!(after_pause < spreads[10]);
+        assert_eq!(spread_radians(cooled_heat(heat, 1.0 / SPREAD_HEAT_RECOVERY_PER_SEC)), 0.0, "a long pause resets it");
+    }
+
+    fn wounded(class: &str) -> PlayerData {
+        PlayerData { character_class: class.to_string(), health: 50, mana: 50, ..test_player(1) }
+    }
+
+    // Ten one-second ticks at the class's own rates
+    fn regen_for_ten_seconds(player: &mut PlayerData) {
+        let classes = default_class_definitions();
+        let stats = find_class(&classes, &player.character_class);
+        for _ in 0..10 {
+            regenerate_stats(player, &stats, 1.0);
+        }
+    }
+
+    fn buffered(sequence: i32, client_yaw: f32) -> BufferedInput {
+        let input = InputState { forward: true, backward: false, left: false, right: false, sprint: false, jump: false, attack: false, cast_spell: false, crouch: false, sequence };
+        BufferedInput { input, client_yaw, client_pitch: 0.0, client_animation: "run".to_string() }
+    }
+
+    #[test]
+    fn queued_inputs_apply_one_per_tick_in_order() {
+        let mut player = test_player(1);
+        for sequence in 1..=3 {
+            queue_input(&mut player, buffered(sequence, sequence as f32 * 0.1));
+        }
+        for sequence in 1..=3 {
+            apply_next_input(&mut player);
+            assert_eq!(player.last_input_seq, sequence);
+            assert_eq!(player.rotation.y, sequence as f32 * 0.1);
+        }
+        assert!(player.input_queue.is_empty());
+        apply_next_input(&mut player);
+        assert_eq!(player.last_input_seq, 3, "an empty queue keeps the last input");
+    }
+
+    #[test]
+    fn a_full_queue_drops_its_oldest_input() {
+        let mut player = test_player(1);
+        for sequence in 1..=INPUT_BUFFER_TICKS as i32 + 1 {
+            queue_input(&mut player, buffered(sequence, 0.0));
+        }
+        assert_eq!(player.input_queue.len(), INPUT_BUFFER_TICKS);
+        apply_next_input(&mut player);
+        assert_eq!(player.last_input_seq, 2);
+    }
+
+    #[test]
+    fn aim_follows_yaw_and_a_clamped_pitch() {
+        let mut player = test_player(1);
+        let mut input = buffered(1, std::f32::consts::FRAC_PI_2);
+        input.client_pitch = 3.0;
+        apply_client_input(&mut player, input);
+        assert_eq!(player.rotation.x, MAX_AIM_PITCH);
+        let aim = player.forward_vector;
+        assert!((aim.length() - 1.0).abs() < 1e-5);
+        assert!(aim.x < 0.0 && aim.z.abs() < 1e-5, "yaw pi/2 faces -x");
+        assert!((aim.y - MAX_AIM_PITCH.sin()).abs() < 1e-5);
+
+        let mut input = buffered(2, 0.0);
+        input.client_pitch = f32::NAN;
+        apply_client_input(&mut player, input);
+        assert_eq!(player.rotation.x, 0.0);
+        assert!((player.forward_vector.z - -1.0).abs() < 1e-5, "bad pitch aims level");
+    }
+
+    #[test]
+    fn held_breath_runs_out_then_drowning_hurts_once_per_interval() {
+        let mut player = test_player(1);
+        player.breath_remaining = MAX_BREATH_SECS;
+        let ticks = (MAX_BREATH_SECS / 0.5) as usize;
+        let damage: Vec<i32> = (0..ticks + 4).map(|_| update_breath(&mut player, true, 0.5)).collect();
+        assert!(damage[..ticks - 1].iter().all(|d| *d == 0), "{damage:?}");
+        assert_eq!(damage[ticks - 1..].iter().sum::<i32>(), DROWN_DAMAGE * 3, "{damage:?}");
+
+        assert_eq!(update_breath(&mut player, false, 1.0), 0);
+        assert!(player.breath_remaining > 0.0);
+        assert_eq!(update_breath(&mut player, false, 60.0), 0);
+        assert_eq!(player.breath_remaining, MAX_BREATH_SECS, "surfacing refills it");
+    }
+
+    fn running_forward() -> PlayerData {
+        let mut player = test_player(1);
+        player.input.forward = true;
+        player
+    }
+
+    #[test]
+    fn movement_follows_yaw_and_scales_with_sprint_and_slows() {
+        let mut player = running_forward();
+        let step = calculate_new_position(&mut player, None, 10.0, 1.0, 0.1);
+        assert!((step.z - -1.0).abs() < 1e-5 && step.x.abs() < 1e-5, "forward is -z at yaw 0");
+
+        let slowed = calculate_new_position(&mut player, None, 10.0, 0.5, 0.1);
+        assert!((slowed.z - -0.5).abs() < 1e-5);
+        assert_eq!(calculate_new_position(&mut player, None, 10.0, 0.0, 0.1), player.position, "stunned");
+
+        player.input.sprint = true;
+        let sprinting = calculate_new_position(&mut player, None, 10.0, 1.0, 0.1);
+        assert!((sprinting.z - -SPRINT_MULTIPLIER).abs() < 1e-5);
+    }
+
+    #[test]
+    fn knockback_pushes_through_stuns_and_dies_out_with_friction() {
+        let mut player = test_player(1);
+        player.knockback_velocity = Vector3 { x: 10.0, y: 0.0, z: 0.0 };
+        let pushed = calculate_new_position(&mut player, None, 10.0, 0.0, 0.1);
+        assert!((pushed.x - 1.0).abs() < 1e-5, "moved by the push before it decays");
+        assert!((player.knockback_velocity.x - 10.0 * (-KNOCKBACK_FRICTION * 0.1).exp()).abs() < 1e-4);
+
+        for _ in 0..40 {
+            player.position = calculate_new_position(&mut player, None, 10.0, 0.0, 0.1);
+        }
+        assert_eq!(player.knockback_velocity, Vector3::default(), "settles to a stop");
+    }
+
+    #[test]
+    fn stunned_players_cannot_jump_and_inputs_never_jump_on_their_own() {
+        let mut jump = buffered(1, 0.0);
+        jump.input.jump = true;
+        let mut player = test_player(1);
+        apply_client_input(&mut player, jump);
+        assert_eq!(player.vertical_velocity, 0.0);
+
+        player.grounded = true;
+        assert!(starts_jump(&player, false));
+        assert!(!starts_jump(&player, true), "stunned");
+        player.is_crouching = true;
+        assert!(!starts_jump(&player, false), "crouching");
+        player.is_crouching = false;
+        player.grounded = false;
+        assert!(!starts_jump(&player, false), "airborne");
+    }
+
+    #[test]
+    fn players_below_the_kill_plane_are_rescued_to_a_spawn_point() {
+        let spawn = Vector3 { x: 10.0, y: 1.0, z: -4.0 };
+        let mut fallen = PlayerData { position: Vector3 { y: Y_KILL_PLANE - 1.0, ..Vector3::default() }, vertical_velocity: -30.0, fall_distance: 60.0, ..test_player(1) };
+        assert!(out_of_world(&fallen));
+        rescue(&mut fallen, spawn);
+        assert!(!out_of_world(&fallen));
+        assert_eq!(fallen.position, spawn);
+        assert_eq!((fallen.vertical_velocity, fallen.fall_distance), (0.0, 0.0));
+        assert_eq!(fallen.health, fallen.max_health - KILL_PLANE_PENALTY);
+
+        // The penalty never kills
+        fallen.health = 1;
+        rescue(&mut fallen, spawn);
+        assert_eq!(fallen.health, 1);
+    }
+
+    #[test]
+    fn broken_positions_count_as_out_of_the_world() {
+        assert!(out_of_world(&PlayerData { position: Vector3 { x: f32::NAN, y: 0.0, z: 0.0 }, ..test_player(1) }));
+        assert!(!out_of_world(&PlayerData { position: Vector3 { y: Y_KILL_PLANE, ..Vector3::default() }, ..test_player(1) }));
+    }
+
+    #[test]
+    fn each_class_regenerates_at_its_own_rates() {
+        let mut wizard = wounded("Wizard");
+        let mut paladin = wounded("Paladin");
+        regen_for_ten_seconds(&mut wizard);
+        regen_for_ten_seconds(&mut paladin);
+        // Wizard: 1 health / 5 mana per second, Paladin: 4 health / 1.5 mana per second
+        assert_eq!((wizard.health, wizard.mana), (60, 100));
+        assert_eq!((paladin.health, paladin.mana), (90, 65));
+    }
+
+    #[test]
+    fn fractional_regen_carries_over_between_ticks() {
+        let mut paladin = wounded("Paladin");
+        let classes = default_class_definitions();
+        let stats = find_class(&classes, "Paladin");
+        regenerate_stats(&mut paladin, &stats, 1.0);
+        assert_eq!(paladin.mana, 51);
+        regenerate_stats(&mut paladin, &stats, 1.0);
+        assert_eq!(paladin.mana, 53);
+    }
 }
@@ -0,0 +1,80 @@
+/*
+ * Vibe Coding Starter Pack: 3D Multiplayer - progression.rs
+ *
+ * Experience points, kept between sessions (persistence.rs).
+ *
+ * Key components:
+ *    - award_kill_xp: Called for player kills (combat::apply_damage, PLAYER_KILL_XP) and NPC
+ *      kills (npcs::damage_npc, NPC_KILL_XP). The killer gets the full amount; living party
+ *      members (party.rs) within PARTY_XP_SHARE_RADIUS of the killer get PARTY_XP_SHARE of it
+ *    - add_xp: Direct grant to one active player (also quest rewards, quests.rs)
+ *    - level_for_xp: Level derived from XP (one level per XP_PER_LEVEL), e.g. for
+ *      interactables.rs level requirements
+ */
+
+use spacetimedb::{Identity, ReducerContext};
+
+use crate::common::{PARTY_XP_SHARE, PARTY_XP_SHARE_RADIUS, XP_PER_LEVEL};
+use crate::party::party_members;
+use crate::{player, PlayerData};
+
+pub fn level_for_xp(xp: u64) -> u32 {
+    (xp / XP_PER_LEVEL).saturating_add(1).min(u32::MAX as u64) as u32
+}
+
+pub fn add_xp(ctx: &ReducerContext, identity: Identity, amount: u64) {
+    if amount == 0 {
+        return;
+    }
+    if let Some(mut player) = ctx.db.player().identity().find(identity) {
+        player.xp += amount;
+        ctx.db.player().identity().update(player);
+    }
+}
+
+pub fn award_kill_xp(ctx: &ReducerContext, killer: Identity, amount: u64) {
+    let Some(killer_player) = ctx.db.player().identity().find(killer) else {
+        return;
+    };
+    add_xp(ctx, killer, amount);
+    for member in party_members(ctx, killer) {
+        if member == killer {
+            continue;
+        }
+        if let Some(member_player) = ctx.db.player().identity().find(member) {
+            add_xp(ctx, member, party_share(&killer_player, &member_player, amount));
+        }
+    }
+}
+
+// What a party member earns from the killer's `amount`: nothing if dead or too far away
+fn party_share(killer: &PlayerData, member: &PlayerData, amount: u64) -> u64 {
+    let nearby = member.instance_id == killer.instance_id && (member.position - killer.position).length() <= PARTY_XP_SHARE_RADIUS;
+    if member.alive && nearby {
+        (amount as f32 * PARTY_XP_SHARE) as u64
+    } else {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_player;
+
+    #[test]
+    fn party_members_share_xp_only_while_alive_and_nearby() {
+        let killer = test_player(1);
+        let mut member = test_player(2);
+        member.position = killer.position;
+        member.position.x += PARTY_XP_SHARE_RADIUS - 1.0;
+        assert_eq!(party_share(&killer, &member, 100), (100.0 * PARTY_XP_SHARE) as u64);
+
+        member.position.x += 2.0;
+        assert_eq!(party_share(&killer, &member, 100), 0, "out of range");
+
+        member.position = killer.position;
+        member.alive = false;
+        assert_eq!(party_share(&killer, &member, 100), 0, "dead members get nothing");
+    }
+}
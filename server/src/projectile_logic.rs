@@ -0,0 +1,983 @@
+/*
+ * Vibe Coding Starter Pack: 3D Multiplayer - projectile_logic.rs
+ *
+ * Projectile spawning parameters and the per-tick projectile simulation.
+ *
+ * Key components:
+ *
+ * 1. Projectile Specs:
+ *    - ProjectileSpec: Speed/damage/lifetime, collision flags and ImpactBehavior per ProjectileKind
+ *    - spawn_projectile_of_kind: Shared insert path used by the spawn reducers (also writes the spawn log)
+ *    - consume_global_spawn_budget: Server-wide projectiles-per-second limit (tick_state)
+ *    - jitter_direction: Random aim offset (player bloom, bot aim error)
+ *    - acquire_homing_target: At spawn, homing projectiles lock onto the nearest visible enemy
+ *      within HOMING_ACQUIRE_RANGE and a HOMING_ACQUIRE_HALF_ANGLE_DEGREES cone of their aim
+ *
+ * 2. Simulation (update_projectiles, called from game_tick):
+ *    - enforce_projectile_budget runs first: past PROJECTILE_SOFT_LIMIT it warns and culls
+ *      the projectiles closest to expiring (oldest first) down to PROJECTILE_CULL_TARGET
+ *    - Homing projectiles turn towards their target's chest by at most homing_turn_rate per
+ *      second (physics::steer_towards) before moving
+ *    - Moves projectiles along their direction, bending it with gravity once
+ *      age_secs passes gravity_delay_secs (for "floaty then drop" grenades).
+ *      Gravity is physics::apply_gravity with the projectile or thrown-weapon value from GameConfig
+ *    - Static colliders (environment.rs) truncate the tick's path at the first wall it enters;
+ *      a wall contact is ImpactTarget::Geometry, and damages the wall if it is a destructible prop
+ *    - Finds the first contact this tick: a player (direct-hit damage, broadphase via
+ *      spatial::SpatialGrid, swept cylinder test along the tick's path via
+ *      physics::check_swept_collision), an NPC (player projectiles only), a destructible pickup (flagged kinds only) or the ground
+ *      (a descending projectile reaching the floor under it: y = 0 or a one-way platform top,
+ *      snapped onto that surface so ground explosions go off where it landed)
+ *    - Scanner-style projectiles (reveal_radius > 0) reveal stealthed enemies along their path
+ *    - Damaging projectiles ping their shooter on the minimap of enemies they narrowly miss
+ *    - Emits a ProjectileImpactEvent and hands the contact to handle_impact; a projectile
+ *      that runs out of lifetime or range goes through handle_impact too (with no contact)
+ *    - lifetime counts down by the real tick delta; a projectile is removed when it runs out
+ *      or travels past PROJECTILE_MAX_RANGE, whichever comes first (at PROJECTILE_SPEED the
+ *      range limit is reached after ~3.3s, before the default 5s lifetime)
+ *
+ * 3. Impact Dispatch (handle_impact):
+ *    - The only place impact behaviors are implemented; returns whether the projectile survives.
+ *      impact_effect decides what a behavior does at a contact, handle_impact carries it out
+ *    - Counters come before the behavior: while pierce_remaining > 0 a player hit passes through
+ *      (damaged once, last_hit_identity), while bounces_remaining > 0 the ground and static
+ *      colliders are ricocheted off, keeping BOUNCE_SPEED_RETAINED of the speed
+ *    - The caller owns the single deletion site, so new behaviors only touch the dispatcher
+ *    - Projectiles with an explosion_radius explode wherever they are spent (contact, range
+ *      or lifetime): falloff damage to players found via the spatial grid (blast_damage),
+ *      friendly fire per GameConfig, full damage to destructibles in range, and an
+ *      ExplosionEvent for VFX
+ *
+ * Related files:
+ *    - common.rs: ProjectileKind, ImpactBehavior and projectile constants
+ *    - spells.rs: Spell definitions that override a kind's spec when cast
+ *    - combat.rs: Damage/heal helpers used by impacts
+ *    - hazards.rs: Fire patches left by LingerFire
+ *    - pickups.rs: Pickup table and deny_pickup
+ *    - events.rs: Impact event table
+ *    - visibility.rs: Stealth reveal used by Scanner
+ */
+
+use spacetimedb::rand::Rng;
+use spacetimedb::{Identity, ReducerContext, Table, Timestamp};
+
+use crate::combat::{alive_players_in_radius, apply_damage, apply_knockback, heal_player};
+use crate::common::{
+    ImpactBehavior, PickupKind, ProjectileKind, StatusEffectKind, StatusOnHit, Vector3, LOBBY_INSTANCE_ID, EXPLOSION_KNOCKBACK, EXPLOSION_KNOCKBACK_LIFT, FROST_SLOW_AMOUNT, FROST_SLOW_SECS, FIRE_PATCH_DAMAGE, FIRE_PATCH_RADIUS,
+    GLOBAL_SPAWN_RATE, HEAL_SPLASH_RADIUS, HOMING_ACQUIRE_HALF_ANGLE_DEGREES, HOMING_ACQUIRE_RANGE, PLAYER_HEIGHT, PROJECTILE_CULL_TARGET, PROJECTILE_PROACTIVE_CULL, PROJECTILE_SOFT_LIMIT, HEX_DISARM_SECS, MAX_PLAYER_HIT_RADIUS, PROJECTILE_DAMAGE, PROJECTILE_LIFETIME,
+    KNOCKBACK_PER_DAMAGE, PROJECTILE_RADIUS, PROJECTILE_SPEED, SCANNER_REVEAL_RADIUS, SHARD_SPAWN_OFFSET, AXE_MAX_AMMO, BOUNCE_SPEED_RETAINED,
+};
+use crate::config::get_game_config;
+use crate::destructibles::{damage_destructible_collider, damage_destructibles_in_radius};
+use crate::events::{emit_explosion, emit_projectile_impact, log_projectile_spawn, ImpactTarget};
+use crate::environment::{first_collider_hit, floor_height, load_platforms, load_static_colliders, static_collider};
+use crate::hazards::spawn_fire_patch;
+use crate::npcs::{damage_npc, load_npcs, npc, Npc, NpcSpec};
+use crate::physics::{apply_gravity, box_surface_normal, check_swept_collision, in_cone, reflect, steer_towards};
+use crate::pickups::{deny_pickup, drop_pickup, pickup, stops_projectile};
+use crate::instances::instance_of;
+use crate::pets::damage_pets_in_radius;
+use crate::spatial::SpatialGrid;
+use crate::status_effects::apply_status_effect;
+use crate::visibility::{ping_near_misses, reveal_stealthed_near};
+use crate::{get_tick_state, player, projectile, tick_state, PlayerData, ProjectileData, TickState};
+use std::time::Duration;
+
+pub const PROJECTILE_MAX_RANGE: f32 = 50.0;
+const BOUNCE_SURFACE_OFFSET: f32 = 0.01; // keeps a ricochet from touching the same surface next tick
+
+// Tunable parameters for one projectile kind
+pub struct ProjectileSpec {
+    pub speed: f32,
+    pub damage: i32,
+    pub lifetime: f32,
+    pub hits_pickups: bool, // collision layer: only flagged projectiles touch destructible pickups
+    pub bounce_count: u32, // ricochets off the ground and static colliders before it is spent
+    pub pierce_count: u32, // players it passes through (damaging each) before it is spent
+    pub impact: ImpactBehavior,
+    pub gravity_scale: f32,
+    pub gravity_delay_secs: f32,
+    pub min_arm_distance: f32,
+    pub reveal_radius: f32, // > 0: reveals stealthed enemies within this distance of the flight path
+    pub status_on_hit: Option<StatusOnHit>, // applied to a player hit directly (spells.rs), on top of `impact`
+    pub explosion_radius: f32, // > 0: falloff damage around wherever the projectile is spent
+    pub homing_turn_rate: f32, // > 0: locks onto an enemy at spawn and steers towards it (radians/sec)
+}
+
+impl ProjectileSpec {
+    pub fn for_kind(kind: ProjectileKind) -> Self {
+        let base = ProjectileSpec {
+            speed: PROJECTILE_SPEED,
+            damage: PROJECTILE_DAMAGE,
+            lifetime: PROJECTILE_LIFETIME,
+            hits_pickups: false,
+            bounce_count: 0,
+            pierce_count: 0,
+            impact: ImpactBehavior::Stop,
+            gravity_scale: 0.0,
+            gravity_delay_secs: 0.0,
+            min_arm_distance: 0.0,
+            reveal_radius: 0.0,
+            status_on_hit: None,
+            explosion_radius: 0.0,
+            homing_turn_rate: 0.0,
+        };
+        match kind {
+            ProjectileKind::Bolt => base,
+            ProjectileKind::Breaker => ProjectileSpec { damage: PROJECTILE_DAMAGE / 2, hits_pickups: true, ..base },
+            ProjectileKind::Bouncer => ProjectileSpec {
+                damage: PROJECTILE_DAMAGE * 3 / 5,
+                bounce_count: 3,
+                ..base
+            },
+            ProjectileKind::Hex => ProjectileSpec {
+                speed: PROJECTILE_SPEED * 1.2,
+                damage: PROJECTILE_DAMAGE / 5,
+                impact: ImpactBehavior::Afflict(StatusOnHit { kind: StatusEffectKind::Disarm, magnitude: 1.0, duration_secs: HEX_DISARM_SECS }),
+                ..base
+            },
+            ProjectileKind::Fireball => ProjectileSpec {
+                speed: PROJECTILE_SPEED * 0.8,
+                damage: PROJECTILE_DAMAGE / 2,
+                explosion_radius: 4.0,
+                ..base
+            },
+            ProjectileKind::Cluster => ProjectileSpec { damage: PROJECTILE_DAMAGE / 2, impact: ImpactBehavior::Split(6), ..base },
+            ProjectileKind::Napalm => ProjectileSpec {
+                speed: PROJECTILE_SPEED * 0.8,
+                damage: PROJECTILE_DAMAGE / 5,
+                impact: ImpactBehavior::LingerFire(4.0),
+                ..base
+            },
+            ProjectileKind::Mender => ProjectileSpec { damage: 0, impact: ImpactBehavior::Heal(25), ..base },
+            ProjectileKind::Lance => ProjectileSpec {
+                speed: PROJECTILE_SPEED * 1.5,
+                damage: PROJECTILE_DAMAGE * 3 / 5,
+                pierce_count: 2,
+                ..base
+            },
+            ProjectileKind::Shard => ProjectileSpec { damage: PROJECTILE_DAMAGE / 5, lifetime: 0.5, ..base },
+            ProjectileKind::Grenade => ProjectileSpec {
+                speed: PROJECTILE_SPEED * 0.7,
+                damage: PROJECTILE_DAMAGE / 2,
+                explosion_radius: 5.0,
+                gravity_scale: 1.0,
+                gravity_delay_secs: 0.6,
+                ..base
+            },
+            ProjectileKind::FrostBolt => ProjectileSpec {
+                damage: PROJECTILE_DAMAGE * 2 / 5,
+                impact: ImpactBehavior::Afflict(StatusOnHit { kind: StatusEffectKind::Slow, magnitude: FROST_SLOW_AMOUNT, duration_secs: FROST_SLOW_SECS }),
+                ..base
+            },
+            ProjectileKind::Boulder => ProjectileSpec {
+                speed: PROJECTILE_SPEED * 0.6,
+                damage: PROJECTILE_DAMAGE * 2,
+                min_arm_distance: 4.0,
+                ..base
+            },
+            ProjectileKind::Axe => ProjectileSpec {
+                speed: PROJECTILE_SPEED * 0.9,
+                damage: PROJECTILE_DAMAGE * 6 / 5,
+                impact: ImpactBehavior::Retrievable,
+                gravity_scale: 1.0,
+                ..base
+            },
+            ProjectileKind::Scanner => ProjectileSpec {
+                speed: PROJECTILE_SPEED * 1.3,
+                damage: 0,
+                reveal_radius: SCANNER_REVEAL_RADIUS,
+                ..base
+            },
+        }
+    }
+}
+
+// Reserve one slot in the current one-second spawn window, shared by every player.
+// Protects the tick from coordinated spam that per-player limits can't catch.
+pub fn consume_global_spawn_budget(ctx: &ReducerContext) -> Result<(), String> {
+    let mut state = get_tick_state(ctx);
+    take_spawn_slot(&mut state, ctx.timestamp)?;
+    ctx.db.tick_state().id().update(state);
+    Ok(())
+}
+
+fn take_spawn_slot(state: &mut TickState, now: Timestamp) -> Result<(), String> {
+    let window_elapsed = now
+        .duration_since(state.spawn_window_start)
+        .is_none_or(|elapsed| elapsed >= Duration::from_secs(1));
+    if window_elapsed {
+        state.spawn_window_start = now;
+        state.spawns_in_window = 0;
+    }
+    if state.spawns_in_window >= GLOBAL_SPAWN_RATE {
+        return Err(format!("Server projectile limit reached ({} per second), try again shortly", GLOBAL_SPAWN_RATE));
+    }
+    state.spawns_in_window += 1;
+    Ok(())
+}
+
+pub fn spawn_projectile_of_kind(
+    ctx: &ReducerContext,
+    owner_identity: Identity,
+    position: Vector3,
+    direction: Vector3,
+    kind: ProjectileKind,
+) -> ProjectileData {
+    spawn_scaled_projectile(ctx, owner_identity, position, direction, kind, 1.0)
+}
+
+// Same as spawn_projectile_of_kind with the spec damage scaled (class projectile_damage_multiplier)
+pub fn spawn_scaled_projectile(
+    ctx: &ReducerContext,
+    owner_identity: Identity,
+    position: Vector3,
+    direction: Vector3,
+    kind: ProjectileKind,
+    damage_multiplier: f32,
+) -> ProjectileData {
+    spawn_projectile_with_spec(ctx, owner_identity, position, direction, kind, ProjectileSpec::for_kind(kind), damage_multiplier)
+}
+
+// Spawn with an explicit spec (a spell's tuning rather than the kind's defaults)
+pub fn spawn_projectile_with_spec(
+    ctx: &ReducerContext,
+    owner_identity: Identity,
+    position: Vector3,
+    direction: Vector3,
+    kind: ProjectileKind,
+    spec: ProjectileSpec,
+    damage_multiplier: f32,
+) -> ProjectileData {
+    let homing_target = if spec.homing_turn_rate > 0.0 { acquire_homing_target(ctx, owner_identity, position, direction) } else { None };
+    let projectile = ctx.db.projectile().insert(ProjectileData {
+        instance_id: instance_of(ctx, owner_identity),
+        ..projectile_from_spec(owner_identity, position, direction, kind, spec, damage_multiplier, homing_target)
+    });
+    log_projectile_spawn(ctx, projectile.id, owner_identity, position, direction * projectile.speed, kind);
+    projectile
+}
+
+// A fresh lobby row for the spec, not yet inserted
+fn projectile_from_spec(owner_identity: Identity, position: Vector3, direction: Vector3, kind: ProjectileKind, spec: ProjectileSpec, damage_multiplier: f32, homing_target: Option<Identity>) -> ProjectileData {
+    ProjectileData {
+        id: 0, // auto_inc
+        owner_identity,
+        position,
+        direction,
+        speed: spec.speed,
+        damage: (spec.damage as f32 * damage_multiplier).round() as i32,
+        lifetime: spec.lifetime,
+        start_position: position,
+        kind,
+        hits_pickups: spec.hits_pickups,
+        bounces_remaining: spec.bounce_count,
+        pierce_remaining: spec.pierce_count,
+        impact_behavior: spec.impact,
+        last_hit_identity: None,
+        gravity_scale: spec.gravity_scale,
+        gravity_delay_secs: spec.gravity_delay_secs,
+        age_secs: 0.0,
+        min_arm_distance: spec.min_arm_distance,
+        reveal_radius: spec.reveal_radius,
+        status_on_hit: spec.status_on_hit,
+        explosion_radius: spec.explosion_radius,
+        homing_target,
+        homing_turn_rate: spec.homing_turn_rate,
+        instance_id: LOBBY_INSTANCE_ID,
+    }
+}
+
+fn is_armed(projectile: &ProjectileData, next_pos: Vector3) -> bool {
+    (next_pos - projectile.start_position).length() >= projectile.min_arm_distance
+}
+
+// Whether the pos -> next_pos segment goes through a player the projectile may hit
+fn passes_through(projectile: &ProjectileData, player: &PlayerData, pos: Vector3, next_pos: Vector3) -> bool {
+    player.identity != projectile.owner_identity
+        && Some(player.identity) != projectile.last_hit_identity
+        && check_swept_collision(&player.position, player.hit_radius, player.collision_height(), &pos, &next_pos)
+}
+
+// Nearest living, unstealthed enemy (other team) inside the acquisition cone
+pub fn acquire_homing_target(ctx: &ReducerContext, owner_identity: Identity, position: Vector3, direction: Vector3) -> Option<Identity> {
+    let owner_team = ctx.db.player().identity().find(owner_identity).map(|p| p.team);
+    let half_angle = HOMING_ACQUIRE_HALF_ANGLE_DEGREES.to_radians();
+    alive_players_in_radius(ctx, instance_of(ctx, owner_identity), position, HOMING_ACQUIRE_RANGE)
+        .into_iter()
+        .filter(|p| p.identity != owner_identity && Some(p.team) != owner_team && !p.is_stealthed)
+        .filter(|p| in_cone(position, direction, homing_aim_point(p.position), half_angle))
+        .min_by(|a, b| (a.position - position).length().total_cmp(&(b.position - position).length()))
+        .map(|p| p.identity)
+}
+
+fn homing_aim_point(player_position: Vector3) -> Vector3 {
+    player_position + Vector3 { x: 0.0, y: PLAYER_HEIGHT * 0.5, z: 0.0 }
+}
+
+// Rotate a unit direction by random yaw/pitch offsets within +/- max_error (radians).
+// Used for player bloom (spread_heat) and bot aim error.
+pub fn jitter_direction(direction: Vector3, max_error: f32, rng: &mut impl Rng) -> Vector3 {
+    if max_error <= 0.0 {
+        return direction;
+    }
+    let yaw = direction.x.atan2(direction.z) + rng.gen_range(-max_error..=max_error);
+    let pitch = direction.y.clamp(-1.0, 1.0).asin() + rng.gen_range(-max_error..=max_error);
+    Vector3 { x: pitch.cos() * yaw.sin(), y: pitch.sin(), z: pitch.cos() * yaw.cos() }
+}
+
+// First thing the projectile touches at `next_pos` this tick, if any
+fn find_contact(ctx: &ReducerContext, grid: &SpatialGrid, npcs: &[Npc], projectile: &ProjectileData, pos: Vector3, next_pos: Vector3, ground_y: f32) -> Option<ImpactTarget> {
+    // Players (skip owner, dead players and whoever a piercing shot just went through).
+    // Unarmed projectiles (inside min_arm_distance of their spawn) pass through players entirely.
+    // The whole pos -> next_pos segment is tested so fast shots can't tunnel; if it crosses
+    // several players or NPCs, the one nearest the start of the segment is hit.
+    // The grid is a start-of-tick snapshot, so liveness is re-checked against the table.
+    let armed = is_armed(projectile, next_pos);
+    let midpoint = pos + (next_pos - pos) * 0.5;
+    let reach = (next_pos - pos).length() * 0.5 + MAX_PLAYER_HIT_RADIUS + PROJECTILE_RADIUS;
+    let candidates = if armed { grid.query(projectile.instance_id, midpoint, reach) } else { Vec::new() };
+    let hit_player = candidates
+        .into_iter()
+        .filter(|p| passes_through(projectile, p, pos, next_pos) && ctx.db.player().identity().find(p.identity).is_some_and(|current| current.alive))
+        .min_by(|a, b| (a.position - pos).length().total_cmp(&(b.position - pos).length()));
+
+    // NPCs only take damage from player projectiles (not bot turrets)
+    let hit_npc = if armed && projectile.owner_identity != ctx.identity() {
+        npcs.iter()
+            .filter(|n| {
+                n.instance_id == projectile.instance_id
+                    && check_swept_collision(&n.position, NpcSpec::for_type(n.npc_type).hit_radius, PLAYER_HEIGHT, &pos, &next_pos)
+                    && ctx.db.npc().id().find(n.id).is_some()
+            })
+            .min_by(|a, b| (a.position - pos).length().total_cmp(&(b.position - pos).length()))
+    } else {
+        None
+    };
+    match (hit_player, hit_npc) {
+        (Some(player), Some(npc)) if (npc.position - pos).length() < (player.position - pos).length() => {
+            return Some(ImpactTarget::Npc(npc.id));
+        }
+        (Some(player), _) => return Some(ImpactTarget::Player(player.identity)),
+        (None, Some(npc)) => return Some(ImpactTarget::Npc(npc.id)),
+        (None, None) => {}
+    }
+
+    // Pickup collision layer (flagged projectiles vs destructible pickups only)
+    if projectile.hits_pickups && projectile.instance_id == LOBBY_INSTANCE_ID {
+        if let Some(target) = ctx.db.pickup().iter().find(|p| stops_projectile(p, projectile.hits_pickups, next_pos)) {
+            return Some(ImpactTarget::Pickup(target.id));
+        }
+    }
+
+    if next_pos.y <= ground_y && projectile.direction.y < 0.0 {
+        return Some(ImpactTarget::Ground);
+    }
+    None
+}
+
+// Keep the tick cost bounded when projectile counts run away
+pub fn enforce_projectile_budget(ctx: &ReducerContext) {
+    let live = ctx.db.projectile().count() as usize;
+    if live <= PROJECTILE_SOFT_LIMIT {
+        return;
+    }
+    spacetimedb::log::warn!("[TICK] {} live projectiles exceeds soft limit of {}", live, PROJECTILE_SOFT_LIMIT);
+    if !PROJECTILE_PROACTIVE_CULL {
+        return;
+    }
+
+    let culled = projectiles_to_cull(ctx.db.projectile().iter().map(|p| (p.lifetime, p.id)).collect());
+    for id in &culled {
+        ctx.db.projectile().id().delete(*id);
+    }
+    spacetimedb::log::warn!("[TICK] Culled {} projectiles down to {}", culled.len(), PROJECTILE_CULL_TARGET);
+}
+
+// Ids of the (lifetime, id) pairs to delete to get back to PROJECTILE_CULL_TARGET.
+// Expiring-soonest first, ties broken by age (lower id = older)
+fn projectiles_to_cull(mut candidates: Vec<(f32, u64)>) -> Vec<u64> {
+    candidates.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+    let cull_count = candidates.len().saturating_sub(PROJECTILE_CULL_TARGET);
+    candidates.into_iter().take(cull_count).map(|(_, id)| id).collect()
+}
+
+// Thrown weapons fall with GameConfig::thrown_gravity instead of projectile_gravity
+fn is_thrown(kind: ProjectileKind) -> bool {
+    matches!(kind, ProjectileKind::Axe | ProjectileKind::Grenade)
+}
+
+// One tick of flight from the current position, falling only once age_secs passes
+// gravity_delay_secs. Returns the new position; speed and direction follow the fall
+fn flight_step(projectile: &mut ProjectileData, class_gravity: f32, delta_time: f32) -> Vector3 {
+    let mut velocity = projectile.direction * projectile.speed;
+    let mut next_pos = projectile.position + Vector3 { x: velocity.x, y: 0.0, z: velocity.z } * delta_time;
+    let falling = projectile.gravity_scale != 0.0 && projectile.age_secs > projectile.gravity_delay_secs;
+    let gravity = if falling { class_gravity * projectile.gravity_scale } else { 0.0 };
+    apply_gravity(&mut next_pos, &mut velocity.y, gravity, delta_time, None);
+    if gravity != 0.0 {
+        projectile.speed = velocity.length();
+        projectile.direction = velocity.normalize();
+    }
+    next_pos
+}
+
+pub fn update_projectiles(ctx: &ReducerContext, delta_time: f32) {
+    let config = get_game_config(ctx);
+    let grid = SpatialGrid::build(ctx);
+    let colliders = load_static_colliders(ctx);
+    let platforms = load_platforms(ctx);
+    let npcs = load_npcs(ctx);
+    for mut projectile in ctx.db.projectile().iter() {
+        projectile.age_secs += delta_time;
+        projectile.lifetime -= delta_time;
+        let pos = projectile.position;
+        if let Some(target_identity) = projectile.homing_target {
+            match ctx.db.player().identity().find(target_identity).filter(|t| t.alive) {
+                Some(target) => {
+                    let desired = (homing_aim_point(target.position) - pos).normalize();
+                    projectile.direction = steer_towards(projectile.direction, desired, projectile.homing_turn_rate * delta_time);
+                }
+                None => projectile.homing_target = None,
+            }
+        }
+        let class_gravity = if is_thrown(projectile.kind) { config.thrown_gravity } else { config.projectile_gravity };
+        let mut next_pos = flight_step(&mut projectile, class_gravity, delta_time);
+        // Static geometry cuts this tick's path short; anything beyond the wall is never reached
+        let wall = first_collider_hit(&colliders, projectile.instance_id, pos, next_pos);
+        if let Some((_, entry)) = wall {
+            next_pos = pos + (next_pos - pos) * entry;
+        }
+        // Collider tops are already Geometry hits, so only the ground and platforms count here
+        let ground_y = floor_height(&platforms, &[], projectile.instance_id, pos.y, &next_pos, false);
+        projectile.position = next_pos;
+
+        if projectile.reveal_radius > 0.0 {
+            reveal_stealthed_near(ctx, projectile.instance_id, next_pos, projectile.reveal_radius, projectile.owner_identity);
+        }
+
+        let mut survives = true;
+        let contact = find_contact(ctx, &grid, &npcs, &projectile, pos, next_pos, ground_y).or(wall.map(|(id, _)| ImpactTarget::Geometry(id)));
+        if contact == Some(ImpactTarget::Ground) {
+            next_pos.y = ground_y;
+            projectile.position = next_pos;
+        }
+        let hit_identity = match contact {
+            Some(ImpactTarget::Player(identity)) => Some(identity),
+            _ => None,
+        };
+        if projectile.damage > 0 {
+            ping_near_misses(ctx, &grid, projectile.instance_id, next_pos, projectile.owner_identity, projectile.start_position, hit_identity);
+        }
+        if let Some(target) = contact {
+            match target {
+                ImpactTarget::Player(identity) => {
+                    spacetimedb::log::info!("Projectile {} hit player {}", projectile.id, identity);
+                    if apply_damage(ctx, identity, projectile.damage, Some(projectile.owner_identity)).is_some() {
+                        let flight = Vector3 { x: projectile.direction.x, y: 0.0, z: projectile.direction.z }.normalize();
+                        apply_knockback(ctx, identity, flight * (projectile.damage as f32 * KNOCKBACK_PER_DAMAGE));
+                    }
+                    // Skipped if the hit just killed them
+                    let survived = ctx.db.player().identity().find(identity).is_some_and(|p| p.alive);
+                    if let Some(status) = projectile.status_on_hit.filter(|_| survived) {
+                        apply_status_effect(ctx, identity, status.kind, status.magnitude, status.duration_secs, Some(projectile.owner_identity));
+                    }
+                }
+                ImpactTarget::Pickup(pickup_id) => {
+                    if let Some(target) = ctx.db.pickup().id().find(pickup_id) {
+                        deny_pickup(ctx, target);
+                    }
+                }
+                ImpactTarget::Npc(npc_id) => {
+                    damage_npc(ctx, npc_id, projectile.damage, projectile.owner_identity);
+                }
+                ImpactTarget::Geometry(collider_id) => {
+                    damage_destructible_collider(ctx, collider_id, projectile.instance_id, projectile.damage);
+                }
+                ImpactTarget::Ground => {}
+            }
+            emit_projectile_impact(ctx, projectile.id, projectile.owner_identity, next_pos, target);
+            survives = handle_impact(ctx, &grid, &mut projectile, next_pos, Some(target));
+        }
+        if survives && expired(&projectile, pos) {
+            survives = handle_impact(ctx, &grid, &mut projectile, next_pos, None);
+        }
+
+        // Single deletion site: spent by impact, expired or out of range
+        if !survives {
+            ctx.db.projectile().id().delete(projectile.id);
+        } else {
+            ctx.db.projectile().id().update(projectile);
+        }
+    }
+}
+
+// Out of lifetime, or started this tick past PROJECTILE_MAX_RANGE from where it was fired
+fn expired(projectile: &ProjectileData, pos: Vector3) -> bool {
+    projectile.lifetime <= 0.0 || (pos - projectile.start_position).length() > PROJECTILE_MAX_RANGE
+}
+
+// What an ImpactBehavior does at one contact, worked out before anything is written
+#[derive(Debug, PartialEq)]
+enum ImpactEffect {
+    None,
+    Pierce(Identity),      // through this player, spending a pierce
+    Ricochet(Option<u64>), // off the ground (None) or this static collider, spending a bounce
+    Shards(Vec<(Vector3, Vector3)>), // spawn position and direction of each
+    FirePatch(f32),                  // duration
+    Heal(i32),                       // every living player in HEAL_SPLASH_RADIUS
+    Afflict(Identity, StatusOnHit),
+    ReturnAxe, // to the thrower
+    DropAxe,   // as a pickup where it landed
+}
+
+fn impact_effect(projectile: &ProjectileData, impact_pos: Vector3, target: ImpactTarget) -> ImpactEffect {
+    match target {
+        ImpactTarget::Player(identity) if projectile.pierce_remaining > 0 => return ImpactEffect::Pierce(identity),
+        ImpactTarget::Ground if projectile.bounces_remaining > 0 => return ImpactEffect::Ricochet(None),
+        ImpactTarget::Geometry(collider_id) if projectile.bounces_remaining > 0 => return ImpactEffect::Ricochet(Some(collider_id)),
+        _ => {}
+    }
+    match projectile.impact_behavior {
+        ImpactBehavior::Stop => ImpactEffect::None,
+        ImpactBehavior::Split(count) => {
+            let origin = Vector3 { x: impact_pos.x, y: impact_pos.y.max(0.5), z: impact_pos.z };
+            let shards = (0..count)
+                .map(|i| {
+                    let angle = i as f32 / count as f32 * std::f32::consts::TAU;
+                    let direction = Vector3 { x: angle.cos(), y: 0.0, z: angle.sin() };
+                    (origin + direction * SHARD_SPAWN_OFFSET, direction)
+                })
+                .collect();
+            ImpactEffect::Shards(shards)
+        }
+        ImpactBehavior::LingerFire(duration) => ImpactEffect::FirePatch(duration),
+        ImpactBehavior::Heal(amount) => ImpactEffect::Heal(amount),
+        ImpactBehavior::Afflict(status) => match target {
+            ImpactTarget::Player(identity) => ImpactEffect::Afflict(identity, status),
+            _ => ImpactEffect::None,
+        },
+        ImpactBehavior::Retrievable => match target {
+            ImpactTarget::Player(_) | ImpactTarget::Npc(_) => ImpactEffect::ReturnAxe,
+            _ => ImpactEffect::DropAxe,
+        },
+    }
+}
+
+// Falloff damage around `center` when a projectile with an explosion_radius is spent
+fn explode(ctx: &ReducerContext, grid: &SpatialGrid, projectile: &ProjectileData, center: Vector3) {
+    let owner = projectile.owner_identity;
+    let owner_team = ctx.db.player().identity().find(owner).map(|p| p.team);
+    let friendly_fire = get_game_config(ctx).friendly_fire;
+    let mut victims = 0;
+    for hit in blast_hits(grid, projectile, center, owner_team, friendly_fire) {
+        // Snapshot may be stale (killed earlier this tick, e.g. by the direct hit)
+        if !ctx.db.player().identity().find(hit.victim).is_some_and(|p| p.alive) {
+            continue;
+        }
+        if apply_damage(ctx, hit.victim, hit.damage, Some(owner)).is_some() {
+            apply_knockback(ctx, hit.victim, hit.knockback);
+            victims += 1;
+        }
+    }
+    damage_pets_in_radius(ctx, projectile.instance_id, center, projectile.explosion_radius, projectile.damage, Some(owner));
+    emit_explosion(ctx, projectile.id, owner, center, projectile.explosion_radius, victims);
+    damage_destructibles_in_radius(ctx, projectile.instance_id, center, projectile.explosion_radius, projectile.damage);
+}
+
+#[derive(Debug, PartialEq)]
+struct BlastHit {
+    victim: Identity,
+    damage: i32,
+    knockback: Vector3, // away from the center and slightly upwards
+}
+
+// Who a blast hurts and how hard: full damage and push at the center down to none at the
+// edge. The shooter is never hurt; teammates only with friendly fire on. Bots
+// (module-owned projectiles) have no team and hurt everyone in range.
+fn blast_hits(grid: &SpatialGrid, projectile: &ProjectileData, center: Vector3, owner_team: Option<u8>, friendly_fire: bool) -> Vec<BlastHit> {
+    let radius = projectile.explosion_radius;
+    grid.query(projectile.instance_id, center, radius + MAX_PLAYER_HIT_RADIUS)
+        .into_iter()
+        .filter(|candidate| candidate.identity != projectile.owner_identity)
+        .filter(|candidate| friendly_fire || owner_team != Some(candidate.team))
+        .filter_map(|candidate| {
+            let distance = (candidate.position - center).length();
+            if distance > radius + candidate.hit_radius {
+                return None;
+            }
+            let falloff = 1.0 - (distance / radius).min(1.0);
+            let damage = (projectile.damage as f32 * falloff).round() as i32;
+            let away = Vector3 { x: candidate.position.x - center.x, y: 0.0, z: candidate.position.z - center.z }.normalize();
+            let strength = EXPLOSION_KNOCKBACK * falloff;
+            let knockback = away * strength + Vector3 { x: 0.0, y: strength * EXPLOSION_KNOCKBACK_LIFT, z: 0.0 };
+            (damage > 0).then_some(BlastHit { victim: candidate.identity, damage, knockback })
+        })
+        .collect()
+}
+
+// Run the projectile's ImpactBehavior at a contact (None: it ran out of lifetime or range).
+// Returns true if the projectile keeps flying; a spent projectile with an explosion_radius explodes.
+pub fn handle_impact(ctx: &ReducerContext, grid: &SpatialGrid, projectile: &mut ProjectileData, impact_pos: Vector3, target: Option<ImpactTarget>) -> bool {
+    let keeps_flying = match target {
+        Some(target) => apply_impact_effect(ctx, projectile, impact_pos, target),
+        None => false,
+    };
+    if !keeps_flying && projectile.explosion_radius > 0.0 {
+        explode(ctx, grid, projectile, impact_pos);
+    }
+    keeps_flying
+}
+
+fn apply_impact_effect(ctx: &ReducerContext, projectile: &mut ProjectileData, impact_pos: Vector3, target: ImpactTarget) -> bool {
+    let owner = projectile.owner_identity;
+    match impact_effect(projectile, impact_pos, target) {
+        ImpactEffect::None => false,
+        ImpactEffect::Pierce(identity) => {
+            projectile.pierce_remaining -= 1;
+            projectile.last_hit_identity = Some(identity);
+            true
+        }
+        ImpactEffect::Ricochet(collider_id) => {
+            let normal = collider_id
+                .and_then(|id| ctx.db.static_collider().id().find(id))
+                .map_or(Vector3 { x: 0.0, y: 1.0, z: 0.0 }, |c| box_surface_normal(impact_pos, c.center, c.half_extents));
+            ricochet(projectile, impact_pos, normal)
+        }
+        ImpactEffect::Shards(shards) => {
+            for (position, direction) in shards {
+                spawn_projectile_of_kind(ctx, owner, position, direction, ProjectileKind::Shard);
+            }
+            false
+        }
+        ImpactEffect::FirePatch(duration) => {
+            spawn_fire_patch(ctx, owner, projectile.instance_id, impact_pos, FIRE_PATCH_RADIUS, FIRE_PATCH_DAMAGE, duration);
+            false
+        }
+        ImpactEffect::Heal(amount) => {
+            for ally in alive_players_in_radius(ctx, projectile.instance_id, impact_pos, HEAL_SPLASH_RADIUS) {
+                heal_player(ctx, ally.identity, amount);
+            }
+            false
+        }
+        ImpactEffect::Afflict(identity, status) => {
+            // Only living players are afflicted (the direct hit may have just killed them)
+            if ctx.db.player().identity().find(identity).is_some_and(|p| p.alive) {
+                apply_status_effect(ctx, identity, status.kind, status.magnitude, status.duration_secs, Some(owner));
+            }
+            false
+        }
+        ImpactEffect::ReturnAxe => {
+            if let Some(mut thrower) = ctx.db.player().identity().find(owner) {
+                thrower.axe_ammo = (thrower.axe_ammo + 1).min(AXE_MAX_AMMO);
+                ctx.db.player().identity().update(thrower);
+            }
+            false
+        }
+        ImpactEffect::DropAxe => {
+            drop_pickup(ctx, PickupKind::Axe, impact_pos, 1);
+            false
+        }
+    }
+}
+
+// Reflect off the surface with this normal, spending one bounce; false once none are left
+fn ricochet(projectile: &mut ProjectileData, impact_pos: Vector3, normal: Vector3) -> bool {
+    if projectile.bounces_remaining == 0 {
+        return false;
+    }
+    projectile.bounces_remaining -= 1;
+    projectile.direction = reflect(projectile.direction, normal);
+    projectile.speed *= BOUNCE_SPEED_RETAINED;
+    projectile.position = impact_pos + normal * BOUNCE_SURFACE_OFFSET;
+    projectile.last_hit_identity = None;
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::{GRAVITY, PLAYER_HEIGHT};
+    use crate::test_player;
+
+    fn projectile_of(kind: ProjectileKind) -> ProjectileData {
+        let direction = Vector3 { x: 0.0, y: -0.5, z: -1.0 }.normalize();
+        projectile_from_spec(Identity::from_byte_array([1; 32]), Vector3 { x: 0.0, y: 1.0, z: 0.0 }, direction, kind, ProjectileSpec::for_kind(kind), 1.0, None)
+    }
+
+    #[test]
+    fn bouncer_spends_one_bounce_per_ricochet() {
+        let up = Vector3 { x: 0.0, y: 1.0, z: 0.0 };
+        let mut projectile = projectile_of(ProjectileKind::Bouncer);
+        assert_eq!(projectile.bounces_remaining, 3);
+        for expected in [2, 1, 0] {
+            projectile.direction.y = -projectile.direction.y.abs(); // falling back down
+            let speed = projectile.speed;
+            assert!(ricochet(&mut projectile, Vector3::default(), up));
+            assert_eq!(projectile.bounces_remaining, expected);
+            assert!(projectile.direction.y > 0.0, "reflected off the ground");
+            assert!(projectile.position.y > 0.0, "pushed back above the ground");
+            assert_eq!(projectile.speed, speed * BOUNCE_SPEED_RETAINED);
+        }
+        // Out of bounces: the next contact is an impact, which deletes the projectile
+        assert!(!ricochet(&mut projectile, Vector3::default(), up));
+    }
+
+    #[test]
+    fn every_impact_behavior_dispatches_to_its_effect() {
+        let at = Vector3 { x: 3.0, y: 0.0, z: 4.0 };
+        let victim = Identity::from_byte_array([2; 32]);
+        let with = |impact_behavior| ProjectileData { impact_behavior, ..projectile_of(ProjectileKind::Bolt) };
+        assert_eq!(impact_effect(&with(ImpactBehavior::Stop), at, ImpactTarget::Player(victim)), ImpactEffect::None);
+        assert_eq!(impact_effect(&with(ImpactBehavior::LingerFire(4.0)), at, ImpactTarget::Ground), ImpactEffect::FirePatch(4.0));
+        assert_eq!(impact_effect(&with(ImpactBehavior::Heal(25)), at, ImpactTarget::Ground), ImpactEffect::Heal(25));
+        let frost = StatusOnHit { kind: StatusEffectKind::Slow, magnitude: FROST_SLOW_AMOUNT, duration_secs: FROST_SLOW_SECS };
+        assert_eq!(impact_effect(&with(ImpactBehavior::Afflict(frost)), at, ImpactTarget::Player(victim)), ImpactEffect::Afflict(victim, frost));
+        assert_eq!(impact_effect(&with(ImpactBehavior::Afflict(frost)), at, ImpactTarget::Ground), ImpactEffect::None);
+    }
+
+    #[test]
+    fn pierces_and_bounces_are_spent_before_the_behavior_runs() {
+        let at = Vector3::default();
+        let victim = Identity::from_byte_array([2; 32]);
+        let lance = ProjectileData { impact_behavior: ImpactBehavior::LingerFire(4.0), pierce_remaining: 1, ..projectile_of(ProjectileKind::Bolt) };
+        assert_eq!(impact_effect(&lance, at, ImpactTarget::Player(victim)), ImpactEffect::Pierce(victim));
+        assert_eq!(impact_effect(&lance, at, ImpactTarget::Ground), ImpactEffect::FirePatch(4.0), "piercing is for players only");
+        let spent = ProjectileData { pierce_remaining: 0, ..lance };
+        assert_eq!(impact_effect(&spent, at, ImpactTarget::Player(victim)), ImpactEffect::FirePatch(4.0));
+
+        let bouncer = projectile_of(ProjectileKind::Bouncer);
+        assert_eq!(impact_effect(&bouncer, at, ImpactTarget::Ground), ImpactEffect::Ricochet(None));
+        assert_eq!(impact_effect(&bouncer, at, ImpactTarget::Geometry(7)), ImpactEffect::Ricochet(Some(7)));
+        assert_eq!(impact_effect(&bouncer, at, ImpactTarget::Player(victim)), ImpactEffect::None, "players stop bouncers");
+        let spent = ProjectileData { bounces_remaining: 0, ..bouncer };
+        assert_eq!(impact_effect(&spent, at, ImpactTarget::Ground), ImpactEffect::None);
+    }
+
+    #[test]
+    fn split_fans_shards_out_evenly_above_the_ground() {
+        let projectile = ProjectileData { impact_behavior: ImpactBehavior::Split(6), ..projectile_of(ProjectileKind::Bolt) };
+        let ImpactEffect::Shards(shards) = impact_effect(&projectile, Vector3::default(), ImpactTarget::Ground) else {
+            panic!("Split should spawn shards");
+        };
+        assert_eq!(shards.len(), 6);
+        for (position, direction) in &shards {
+            assert!((direction.length() - 1.0).abs() < 1e-5);
+            assert_eq!(position.y, 0.5);
+            assert!(((*position - Vector3 { x: 0.0, y: 0.5, z: 0.0 }).length() - SHARD_SPAWN_OFFSET).abs() < 1e-4);
+        }
+        let sum = shards.iter().fold(Vector3::default(), |acc, (_, d)| acc + *d);
+        assert!(sum.length() < 1e-4, "directions cancel out");
+    }
+
+    fn player_at(id: u8, team: u8, x: f32) -> PlayerData {
+        let mut player = test_player(id);
+        player.team = team;
+        player.position = Vector3 { x, y: 0.0, z: 0.0 };
+        player
+    }
+
+    #[test]
+    fn explosions_fall_off_with_distance_and_spare_the_shooter() {
+        let fireball = projectile_of(ProjectileKind::Fireball); // owned by test_player(1)
+        let radius = fireball.explosion_radius;
+        let grid = SpatialGrid::from_players(vec![
+            player_at(1, 0, 0.0),
+            player_at(2, 1, 0.0),
+            player_at(3, 1, radius / 2.0),
+            player_at(4, 1, radius + 5.0),       // outside the blast, same grid neighborhood
+            player_at(5, 1, radius * 20.0),      // cells the query never looks at
+        ]);
+        let hits = blast_hits(&grid, &fireball, Vector3::default(), Some(0), false);
+
+        let hit_on = |id: u8| hits.iter().find(|hit| hit.victim == test_player(id).identity);
+        assert!(hit_on(1).is_none(), "the shooter");
+        assert_eq!(hit_on(2).unwrap().damage, fireball.damage);
+        let halfway = hit_on(3).unwrap();
+        assert_eq!(halfway.damage, (fireball.damage as f32 / 2.0).round() as i32);
+        assert!(halfway.knockback.x > 0.0 && halfway.knockback.y > 0.0, "pushed away and up");
+        assert!((halfway.knockback.x - EXPLOSION_KNOCKBACK / 2.0).abs() < 1e-4);
+        assert!(hit_on(4).is_none());
+        assert!(hit_on(5).is_none());
+    }
+
+    #[test]
+    fn friendly_fire_decides_whether_teammates_are_caught() {
+        let grenade = projectile_of(ProjectileKind::Grenade);
+        let grid = SpatialGrid::from_players(vec![player_at(2, 0, 1.0), player_at(3, 1, 1.0)]);
+        let caught = |owner_team, friendly_fire| blast_hits(&grid, &grenade, Vector3::default(), owner_team, friendly_fire).len();
+        assert_eq!(caught(Some(0), false), 1, "only the enemy");
+        assert_eq!(caught(Some(0), true), 2);
+        assert_eq!(caught(None, false), 2, "bots have no team");
+    }
+
+    #[test]
+    fn spent_bouncers_stop_at_the_ground() {
+        let projectile = ProjectileData { bounces_remaining: 0, ..projectile_of(ProjectileKind::Bouncer) };
+        assert_eq!(impact_effect(&projectile, Vector3::default(), ImpactTarget::Ground), ImpactEffect::None);
+    }
+
+    #[test]
+    fn spawns_over_the_global_rate_wait_for_the_next_window() {
+        let start = Timestamp::UNIX_EPOCH;
+        let mut state = TickState { id: 0, spawn_window_start: start, spawns_in_window: 0, last_tick_at: None };
+        let later = |millis: u64| start + Duration::from_millis(millis);
+        for i in 0..GLOBAL_SPAWN_RATE {
+            assert!(take_spawn_slot(&mut state, later(u64::from(i) % 1000)).is_ok());
+        }
+        assert!(take_spawn_slot(&mut state, later(999)).is_err());
+        assert!(take_spawn_slot(&mut state, later(999)).is_err(), "rejected spawns don't reset the window");
+        assert!(take_spawn_slot(&mut state, later(1000)).is_ok());
+        assert_eq!(state.spawns_in_window, 1);
+        assert_eq!(state.spawn_window_start, later(1000));
+    }
+
+    #[test]
+    fn culling_trims_the_soonest_to_expire_down_to_the_target() {
+        // Lifetimes cycle through 10 values so plenty of projectiles tie
+        let live = PROJECTILE_SOFT_LIMIT as u64 + 1;
+        let candidates: Vec<(f32, u64)> = (0..live).map(|id| ((id % 10) as f32 * 0.5, id)).collect();
+        let culled = projectiles_to_cull(candidates.clone());
+
+        assert_eq!(candidates.len() - culled.len(), PROJECTILE_CULL_TARGET);
+        let longest_culled = culled.iter().map(|id| candidates[*id as usize].0).fold(0.0, f32::max);
+        for (lifetime, id) in &candidates {
+            if !culled.contains(id) {
+                assert!(*lifetime >= longest_culled, "kept {id} expiring before a culled one");
+            }
+        }
+        // Within a lifetime the oldest go first
+        let tied: Vec<u64> = culled.iter().copied().filter(|id| candidates[*id as usize].0 == longest_culled).collect();
+        assert!(tied.windows(2).all(|w| w[0] < w[1]));
+        assert!(candidates.iter().filter(|(l, id)| *l == longest_culled && !culled.contains(id)).all(|(_, id)| id > tied.last().unwrap()));
+    }
+
+    #[test]
+    fn nothing_is_culled_at_or_under_the_target() {
+        assert!(projectiles_to_cull((0..PROJECTILE_CULL_TARGET as u64).map(|id| (1.0, id)).collect()).is_empty());
+    }
+
+    #[test]
+    fn grenades_fly_level_until_the_gravity_delay_then_fall() {
+        let mut grenade = projectile_of(ProjectileKind::Grenade);
+        grenade.direction = Vector3 { x: 0.0, y: 0.0, z: -1.0 };
+        let launch_y = grenade.position.y;
+        let delta_time = 0.05;
+        let mut previous_drop = 0.0;
+        for _ in 0..40 {
+            grenade.age_secs += delta_time;
+            grenade.position = flight_step(&mut grenade, GRAVITY, delta_time);
+            let drop = launch_y - grenade.position.y;
+            if grenade.age_secs <= grenade.gravity_delay_secs {
+                assert_eq!(drop, 0.0, "fell at {}s", grenade.age_secs);
+                assert_eq!(grenade.direction.y, 0.0);
+            } else {
+                assert!(drop > previous_drop, "stopped falling at {}s", grenade.age_secs);
+                assert!(grenade.direction.y < 0.0);
+            }
+            previous_drop = drop;
+        }
+        assert!(previous_drop > 0.0);
+    }
+
+    #[test]
+    fn frost_bolts_damage_and_slow_the_player_they_hit() {
+        let bolt = projectile_of(ProjectileKind::FrostBolt);
+        let victim = Identity::from_byte_array([2; 32]);
+        assert!(bolt.damage > 0);
+        let slow = StatusOnHit { kind: StatusEffectKind::Slow, magnitude: FROST_SLOW_AMOUNT, duration_secs: FROST_SLOW_SECS };
+        assert_eq!(impact_effect(&bolt, bolt.position, ImpactTarget::Player(victim)), ImpactEffect::Afflict(victim, slow));
+    }
+
+    #[test]
+    fn projectiles_only_hurt_a_player_they_spawned_inside_once_armed() {
+        let mut boulder = projectile_of(ProjectileKind::Boulder);
+        boulder.direction = Vector3 { x: 0.0, y: 0.0, z: -1.0 };
+        boulder.start_position = boulder.position;
+        let mut target = crate::test_player(2);
+        let along = |metres: f32| boulder.start_position + boulder.direction * metres;
+
+        // Spawned inside the target: the segment goes through them but the boulder isn't armed
+        target.position = Vector3 { z: -0.2, ..Vector3::default() };
+        assert!(passes_through(&boulder, &target, along(0.0), along(1.0)));
+        assert!(!is_armed(&boulder, along(1.0)));
+
+        // The same target further down the flight path is hit once the boulder has armed
+        target.position = Vector3 { y: 0.0, ..along(boulder.min_arm_distance + 1.0) };
+        let (pos, next_pos) = (along(boulder.min_arm_distance + 0.5), along(boulder.min_arm_distance + 1.5));
+        assert!(is_armed(&boulder, next_pos));
+        assert!(passes_through(&boulder, &target, pos, next_pos));
+    }
+
+    #[test]
+    fn thrown_axes_arc_hit_directly_and_are_left_behind_on_a_miss() {
+        let mut axe = projectile_of(ProjectileKind::Axe);
+        axe.direction = Vector3 { x: 0.0, y: 0.3, z: -1.0 }.normalize();
+        assert!(is_thrown(axe.kind));
+
+        // Rises, peaks and comes back down under thrown gravity
+        let launch_y = axe.position.y;
+        let mut peak = launch_y;
+        for _ in 0..30 {
+            axe.age_secs += 0.05;
+            axe.position = flight_step(&mut axe, GRAVITY, 0.05);
+            peak = peak.max(axe.position.y);
+        }
+        assert!(peak > launch_y);
+        assert!(axe.position.y < peak && axe.direction.y < 0.0);
+
+        // A direct hit damages the target and the axe goes back to the thrower
+        let mut target = crate::test_player(2);
+        target.position = axe.position + axe.direction * 0.5 - Vector3 { x: 0.0, y: PLAYER_HEIGHT * 0.5, z: 0.0 };
+        assert!(axe.damage > 0);
+        assert!(passes_through(&axe, &target, axe.position, axe.position + axe.direction));
+        assert_eq!(impact_effect(&axe, axe.position, ImpactTarget::Player(target.identity)), ImpactEffect::ReturnAxe);
+        // A miss leaves it on the ground as a pickup
+        assert_eq!(impact_effect(&axe, axe.position, ImpactTarget::Ground), ImpactEffect::DropAxe);
+    }
+
+    // Flies level until expired; returns (seconds flown, lifetime left)
+    fn fly_until_expired(mut projectile: ProjectileData) -> (f32, f32) {
+        projectile.direction = Vector3 { x: 0.0, y: 0.0, z: -1.0 };
+        projectile.start_position = projectile.position;
+        let delta_time = 0.05;
+        let mut flown = 0.0;
+        loop {
+            let pos = projectile.position;
+            projectile.age_secs += delta_time;
+            projectile.lifetime -= delta_time;
+            projectile.position = flight_step(&mut projectile, 0.0, delta_time);
+            flown += delta_time;
+            if expired(&projectile, pos) {
+                return (flown, projectile.lifetime);
+            }
+        }
+    }
+
+    #[test]
+    fn default_projectiles_reach_max_range_before_their_lifetime() {
+        const { assert!(PROJECTILE_SPEED * PROJECTILE_LIFETIME > PROJECTILE_MAX_RANGE) };
+        let (flown, lifetime_left) = fly_until_expired(projectile_of(ProjectileKind::Bolt));
+        assert!(lifetime_left > 0.0, "ended by range, not lifetime");
+        assert!((flown - PROJECTILE_MAX_RANGE / PROJECTILE_SPEED).abs() < 0.15, "flew {flown}s");
+    }
+
+    #[test]
+    fn slow_or_short_lived_projectiles_expire_before_max_range() {
+        let boulder = projectile_of(ProjectileKind::Boulder);
+        assert!(boulder.speed * boulder.lifetime < PROJECTILE_MAX_RANGE);
+        for kind in [ProjectileKind::Boulder, ProjectileKind::Shard] {
+            let projectile = projectile_of(kind);
+            let lifetime = projectile.lifetime;
+            let (flown, lifetime_left) = fly_until_expired(projectile);
+            assert!(lifetime_left <= 0.0, "{kind:?} ended by range");
+            assert!((flown - lifetime).abs() < 0.06, "{kind:?} flew {flown}s of {lifetime}s");
+        }
+    }
+
+    #[test]
+    fn non_bouncing_kinds_impact_on_first_contact() {
+        let mut projectile = projectile_of(ProjectileKind::Bolt);
+        assert!(!ricochet(&mut projectile, Vector3::default(), Vector3 { x: 0.0, y: 1.0, z: 0.0 }));
+    }
+}
@@ -0,0 +1,295 @@
+/*
+ * Vibe Coding Starter Pack: 3D Multiplayer - quests.rs
+ *
+ * Quests with objectives that advance as players fight, loot and explore.
+ *
+ * Key components:
+ *    - QuestDefinition: Public table keyed by quest_id; seeded in init. Each quest has a list of
+ *      objectives plus XP and item rewards. Repeatable quests can be accepted again once done
+ *    - QuestObjectiveKind:
+ *        - KillNpc: Kill `count` monsters of a type (npcs::damage_npc)
+ *        - CollectItem: Pick up `count` of an item after accepting (loot::pickup_item; gold
+ *          counts too)
+ *        - ReachZone: Walk into the named zone (zones.rs, checked when current_zone changes in
+ *          game_tick, or on accepting while inside it); count is ignored
+ *    - PlayerQuestProgress: Public table, one row per player and quest, with a progress count
+ *      per objective. Completed rows are kept so non-repeatable quests can't be taken again
+ *    - accept_quest / abandon_quest: Reducers; at most MAX_ACTIVE_QUESTS at once
+ *    - Rewards are granted as soon as the last objective is met: XP through
+ *      progression::add_xp and items into the inventory (whatever doesn't fit is dropped at
+ *      the player's feet)
+ */
+
+use spacetimedb::{Identity, ReducerContext, SpacetimeType, Table, Timestamp};
+
+use crate::common::MAX_ACTIVE_QUESTS;
+use crate::inventory::add_item_to_inventory;
+use crate::loot::spawn_dropped_item;
+use crate::npcs::NpcType;
+use crate::player;
+use crate::progression::add_xp;
+
+#[derive(SpacetimeType, Clone, Debug, PartialEq)]
+pub enum QuestObjectiveKind {
+    KillNpc(NpcType),
+    CollectItem(String),
+    ReachZone(String),
+}
+
+#[derive(SpacetimeType, Clone, Debug, PartialEq)]
+pub struct QuestObjective {
+    pub kind: QuestObjectiveKind,
+    pub count: u32,
+}
+
+#[derive(SpacetimeType, Clone, Debug, PartialEq)]
+pub struct QuestItemReward {
+    pub item_id: String,
+    pub quantity: u32,
+}
+
+#[spacetimedb::table(name = quest_definition, public)]
+#[derive(Clone)]
+pub struct QuestDefinition {
+    #[primary_key]
+    pub quest_id: String,
+    pub title: String,
+    pub description: String,
+    pub objectives: Vec<QuestObjective>,
+    pub reward_xp: u64,
+    pub reward_items: Vec<QuestItemReward>,
+    pub repeatable: bool,
+}
+
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq)]
+pub enum QuestStatus {
+    Active,
+    Completed,
+}
+
+#[spacetimedb::table(name = player_quest_progress, public)]
+#[derive(Clone)]
+pub struct PlayerQuestProgress {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    #[index(btree)]
+    pub owner: Identity,
+    pub quest_id: String,
+    pub progress: Vec<u32>, // one entry per objective, same order as the definition
+    pub status: QuestStatus,
+    pub accepted_at: Timestamp,
+    pub completed_at: Option<Timestamp>,
+}
+
+fn objective(kind: QuestObjectiveKind, count: u32) -> QuestObjective {
+    QuestObjective { kind, count }
+}
+
+fn reward(item_id: &str, quantity: u32) -> QuestItemReward {
+    QuestItemReward { item_id: item_id.to_string(), quantity }
+}
+
+pub fn seed_quest_definitions(ctx: &ReducerContext) {
+    if ctx.db.quest_definition().count() > 0 {
+        return;
+    }
+    let defaults = [
+        QuestDefinition {
+            quest_id: "goblin_cull".to_string(),
+            title: "Goblin Cull".to_string(),
+            description: "Thin out the goblins roaming the wilds.".to_string(),
+            objectives: vec![objective(QuestObjectiveKind::KillNpc(NpcType::Goblin), 5)],
+            reward_xp: 150,
+            reward_items: vec![reward("health_potion", 2)],
+            repeatable: true,
+        },
+        QuestDefinition {
+            quest_id: "ogre_slayer".to_string(),
+            title: "Ogre Slayer".to_string(),
+            description: "Bring down an ogre and claim its share of gold.".to_string(),
+            objectives: vec![
+                objective(QuestObjectiveKind::KillNpc(NpcType::Ogre), 1),
+                objective(QuestObjectiveKind::CollectItem("gold".to_string()), 50),
+            ],
+            reward_xp: 300,
+            reward_items: vec![reward("leather_armor", 1)],
+            repeatable: false,
+        },
+        QuestDefinition {
+            quest_id: "pilgrimage".to_string(),
+            title: "Pilgrimage".to_string(),
+            description: "Find your way to the Sanctuary.".to_string(),
+            objectives: vec![objective(QuestObjectiveKind::ReachZone("Sanctuary".to_string()), 1)],
+            reward_xp: 50,
+            reward_items: vec![reward("mana_potion", 1)],
+            repeatable: false,
+        },
+    ];
+    let count = defaults.len();
+    for definition in defaults {
+        ctx.db.quest_definition().insert(definition);
+    }
+    spacetimedb::log::info!("[INIT] Seeded {} quest definitions.", count);
+}
+
+fn progress_for(ctx: &ReducerContext, owner: Identity, quest_id: &str) -> Option<PlayerQuestProgress> {
+    ctx.db.player_quest_progress().owner().filter(owner).find(|q| q.quest_id == quest_id)
+}
+
+fn grant_rewards(ctx: &ReducerContext, owner: Identity, definition: &QuestDefinition) {
+    add_xp(ctx, owner, definition.reward_xp);
+    for item in &definition.reward_items {
+        match add_item_to_inventory(ctx, owner, &item.item_id, item.quantity) {
+            Ok(0) => {}
+            Ok(leftover) => {
+                if let Some(player) = ctx.db.player().identity().find(owner) {
+                    spawn_dropped_item(ctx, player.instance_id, &item.item_id, leftover, player.position);
+                }
+            }
+            Err(e) => spacetimedb::log::warn!("Quest {} reward {} skipped: {}", definition.quest_id, item.item_id, e),
+        }
+    }
+}
+
+// Adds `advance`'s amount to each unfinished objective, capped at its count. None when nothing
+// moved, otherwise whether every objective is now met
+fn advance_progress(objectives: &[QuestObjective], progress: &mut [u32], advance: impl Fn(&QuestObjectiveKind) -> u32) -> Option<bool> {
+    let mut changed = false;
+    for (objective, progress) in objectives.iter().zip(progress.iter_mut()) {
+        let target = objective.count.max(1);
+        let amount = advance(&objective.kind);
+        if amount > 0 && *progress < target {
+            *progress = progress.saturating_add(amount).min(target);
+            changed = true;
+        }
+    }
+    changed.then(|| objectives.iter().zip(progress.iter()).all(|(o, p)| *p >= o.count.max(1)))
+}
+
+// Applies `advance` to each active quest of `owner`, completing those whose objectives are all met
+fn advance_objectives(ctx: &ReducerContext, owner: Identity, advance: impl Fn(&QuestObjectiveKind) -> u32) {
+    let active: Vec<PlayerQuestProgress> = ctx.db.player_quest_progress().owner().filter(owner).filter(|q| q.status == QuestStatus::Active).collect();
+    for mut quest in active {
+        let Some(definition) = ctx.db.quest_definition().quest_id().find(quest.quest_id.clone()) else {
+            continue;
+        };
+        let Some(done) = advance_progress(&definition.objectives, &mut quest.progress, &advance) else {
+            continue;
+        };
+        if done {
+            quest.status = QuestStatus::Completed;
+            quest.completed_at = Some(ctx.timestamp);
+        }
+        ctx.db.player_quest_progress().id().update(quest);
+        if done {
+            grant_rewards(ctx, owner, &definition);
+            spacetimedb::log::info!("Player {} completed quest {}", owner, definition.quest_id);
+        }
+    }
+}
+
+pub fn on_npc_killed(ctx: &ReducerContext, killer: Identity, npc_type: NpcType) {
+    advance_objectives(ctx, killer, |kind| match kind {
+        QuestObjectiveKind::KillNpc(t) if *t == npc_type => 1,
+        _ => 0,
+    });
+}
+
+pub fn on_item_collected(ctx: &ReducerContext, owner: Identity, item_id: &str, quantity: u32) {
+    advance_objectives(ctx, owner, |kind| match kind {
+        QuestObjectiveKind::CollectItem(id) if id == item_id => quantity,
+        _ => 0,
+    });
+}
+
+pub fn on_zone_entered(ctx: &ReducerContext, identity: Identity, zone: &str) {
+    advance_objectives(ctx, identity, |kind| match kind {
+        QuestObjectiveKind::ReachZone(name) if name == zone => u32::MAX,
+        _ => 0,
+    });
+}
+
+#[spacetimedb::reducer]
+pub fn accept_quest(ctx: &ReducerContext, quest_id: String) -> Result<(), String> {
+    let Some(player) = ctx.db.player().identity().find(ctx.sender) else {
+        return Err("Player is not active".to_string());
+    };
+    let Some(definition) = ctx.db.quest_definition().quest_id().find(quest_id.clone()) else {
+        return Err(format!("Unknown quest '{}'", quest_id));
+    };
+    let active = ctx.db.player_quest_progress().owner().filter(ctx.sender).filter(|q| q.status == QuestStatus::Active).count();
+    let existing = progress_for(ctx, ctx.sender, &quest_id);
+    match &existing {
+        Some(q) if q.status == QuestStatus::Active => return Err("You are already on that quest".to_string()),
+        Some(_) if !definition.repeatable => return Err("You have already completed that quest".to_string()),
+        _ => {}
+    }
+    if active >= MAX_ACTIVE_QUESTS {
+        return Err(format!("You can have at most {} active quests", MAX_ACTIVE_QUESTS));
+    }
+    let quest = PlayerQuestProgress {
+        id: existing.as_ref().map_or(0, |q| q.id), // 0: auto_inc
+        owner: ctx.sender,
+        quest_id,
+        progress: vec![0; definition.objectives.len()],
+        status: QuestStatus::Active,
+        accepted_at: ctx.timestamp,
+        completed_at: None,
+    };
+    if existing.is_some() {
+        ctx.db.player_quest_progress().id().update(quest);
+    } else {
+        ctx.db.player_quest_progress().insert(quest);
+    }
+    // Accepting while already standing in the target zone counts as reaching it
+    if let Some(zone) = player.current_zone {
+        on_zone_entered(ctx, ctx.sender, &zone);
+    }
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn abandon_quest(ctx: &ReducerContext, quest_id: String) -> Result<(), String> {
+    match progress_for(ctx, ctx.sender, &quest_id) {
+        Some(q) if q.status == QuestStatus::Active => {
+            ctx.db.player_quest_progress().id().delete(q.id);
+            Ok(())
+        }
+        _ => Err("You are not on that quest".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn objectives_advance_up_to_their_count_and_complete_together() {
+        let objectives = vec![
+            objective(QuestObjectiveKind::KillNpc(NpcType::Ogre), 1),
+            objective(QuestObjectiveKind::CollectItem("gold".to_string()), 50),
+        ];
+        let gold = |amount: u32| move |kind: &QuestObjectiveKind| match kind {
+            QuestObjectiveKind::CollectItem(id) if id == "gold" => amount,
+            _ => 0,
+        };
+        let ogre = |kind: &QuestObjectiveKind| u32::from(*kind == QuestObjectiveKind::KillNpc(NpcType::Ogre));
+        let mut progress = vec![0, 0];
+
+        assert_eq!(advance_progress(&objectives, &mut progress, gold(30)), Some(false));
+        assert_eq!(advance_progress(&objectives, &mut progress, gold(40)), Some(false));
+        assert_eq!(progress, vec![0, 50], "capped at the count");
+        assert_eq!(advance_progress(&objectives, &mut progress, gold(5)), None, "already met");
+        assert_eq!(advance_progress(&objectives, &mut progress, |_| 0), None);
+        assert_eq!(advance_progress(&objectives, &mut progress, ogre), Some(true));
+    }
+
+    #[test]
+    fn reaching_a_zone_completes_regardless_of_count() {
+        let objectives = vec![objective(QuestObjectiveKind::ReachZone("Sanctuary".to_string()), 0)];
+        let mut progress = vec![0];
+        assert_eq!(advance_progress(&objectives, &mut progress, |_| u32::MAX), Some(true));
+        assert_eq!(progress, vec![1]);
+    }
+}
@@ -0,0 +1,190 @@
+/*
+ * Vibe Coding Starter Pack: 3D Multiplayer - ranking.rs
+ *
+ * Team ELO ratings (MMR), updated when a round ends.
+ *
+ * Key components:
+ *    - PlayerData::mmr / placement_matches / mmr_season: Saved in PersistentPlayerState. New
+ *      players start at DEFAULT_MMR
+ *    - update_ratings: Called from matches::finish_match. Each player is rated against the
+ *      average MMR of the other teams: expected = 1 / (1 + 10^((opponents - own team) / 400)),
+ *      and their rating moves by K * (score - expected) with score 1 for a win, 0 for a loss
+ *      and 0.5 for a draw. Only lobby players are rated; those in an arena are left to
+ *      its match. Skipped unless at least two teams have players in the lobby
+ *    - update_match_instance_ratings: Same rating pass for a finished matchmade match
+ *      (matchmaking.rs), over everyone still online from its teams
+ *    - Placement: The first MMR_PLACEMENT_MATCHES rounds of a season use
+ *      MMR_PLACEMENT_K_FACTOR instead of MMR_K_FACTOR, so new ratings settle quickly
+ *    - Seasons: GameConfig::ranked_season; start_ranked_season (admin) begins the next one.
+ *      A player whose mmr_season is behind is reset to DEFAULT_MMR with placements to play
+ *      again the next time they are rated
+ *    - RankedResult: Public per-player rating change for each rated round, trimmed to
+ *      RANKED_RESULT_LIMIT rows
+ */
+
+use spacetimedb::{Identity, ReducerContext, Table, Timestamp};
+
+use crate::admin::{record_admin_action, require_admin};
+use crate::common::{DEFAULT_MMR, LOBBY_INSTANCE_ID, MMR_K_FACTOR, MMR_PLACEMENT_K_FACTOR, MMR_PLACEMENT_MATCHES, RANKED_RESULT_LIMIT, TEAM_COUNT};
+use crate::config::{game_config, get_game_config};
+use crate::{player, PlayerData};
+
+#[spacetimedb::table(name = ranked_result, public)]
+#[derive(Clone)]
+pub struct RankedResult {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub match_number: u64,            // lobby round (scoreboard.rs), 0 for matchmade matches
+    pub match_instance_id: Option<u64>, // matchmade match (matchmaking.rs)
+    pub season: u32,
+    #[index(btree)]
+    pub identity: Identity,
+    pub team: u8,
+    pub old_mmr: i32,
+    pub new_mmr: i32,
+    pub placement: bool, // rated with the placement K factor
+    pub created_at: Timestamp,
+}
+
+fn expected_score(own: f32, opponents: f32) -> f32 {
+    1.0 / (1.0 + 10f32.powf((opponents - own) / 400.0))
+}
+
+// score: 1 for a win, 0 for a loss, 0.5 for a draw
+fn rating_delta(own: f32, opponents: f32, score: f32, placement: bool) -> i32 {
+    let k = if placement { MMR_PLACEMENT_K_FACTOR } else { MMR_K_FACTOR };
+    (k * (score - expected_score(own, opponents))).round() as i32
+}
+
+// Brings a player's rating into the current season
+fn sync_season(player: &mut PlayerData, season: u32) {
+    if player.mmr_season != season {
+        player.mmr = DEFAULT_MMR;
+        player.placement_matches = 0;
+        player.mmr_season = season;
+    }
+}
+
+pub fn update_ratings(ctx: &ReducerContext, match_number: u64, winning_team: Option<u8>) {
+    let players: Vec<(PlayerData, u8)> = ctx.db.player().iter().filter(|p| p.team < TEAM_COUNT && p.instance_id == LOBBY_INSTANCE_ID).map(|p| {
+        let team = p.team;
+        (p, team)
+    }).collect();
+    rate_players(ctx, match_number, None, players, winning_team);
+}
+
+// `teams` is indexed by team, like MatchInstance::teams
+pub fn update_match_instance_ratings(ctx: &ReducerContext, match_instance_id: u64, teams: &[Vec<Identity>], winning_team: Option<u8>) {
+    let players: Vec<(PlayerData, u8)> = teams
+        .iter()
+        .enumerate()
+        .flat_map(|(team, members)| members.iter().filter_map(move |identity| ctx.db.player().identity().find(*identity).map(|p| (p, team as u8))))
+        .collect();
+    rate_players(ctx, 0, Some(match_instance_id), players, winning_team);
+}
+
+fn rate_players(ctx: &ReducerContext, match_number: u64, match_instance_id: Option<u64>, mut players: Vec<(PlayerData, u8)>, winning_team: Option<u8>) {
+    let season = get_game_config(ctx).ranked_season;
+    for (player, _) in &mut players {
+        sync_season(player, season);
+    }
+
+    let team_count = players.iter().map(|(_, team)| *team as usize + 1).max().unwrap_or(0);
+    let mut totals = vec![(0.0f32, 0u32); team_count];
+    for (player, team) in &players {
+        let total = &mut totals[*team as usize];
+        total.0 += player.mmr as f32;
+        total.1 += 1;
+    }
+    if totals.iter().filter(|(_, count)| *count > 0).count() < 2 {
+        return;
+    }
+    let average = |team: usize| totals[team].0 / totals[team].1 as f32;
+    let opponents_average = |team: usize| {
+        let others: Vec<f32> = (0..totals.len()).filter(|t| *t != team && totals[*t].1 > 0).map(average).collect();
+        others.iter().sum::<f32>() / others.len() as f32
+    };
+
+    for (mut player, team) in players {
+        let team = team as usize;
+        let score = match winning_team {
+            Some(winner) if winner as usize == team => 1.0,
+            Some(_) => 0.0,
+            None => 0.5,
+        };
+        let placement = player.placement_matches < MMR_PLACEMENT_MATCHES;
+        let old_mmr = player.mmr;
+        let delta = rating_delta(average(team), opponents_average(team), score, placement);
+        player.mmr = (old_mmr + delta).max(0);
+        player.placement_matches = player.placement_matches.saturating_add(1);
+        ctx.db.ranked_result().insert(RankedResult {
+            id: 0, // auto_inc
+            match_number,
+            match_instance_id,
+            season,
+            identity: player.identity,
+            team: team as u8,
+            old_mmr,
+            new_mmr: player.mmr,
+            placement,
+            created_at: ctx.timestamp,
+        });
+        ctx.db.player().identity().update(player);
+    }
+
+    let mut ids: Vec<u64> = ctx.db.ranked_result().iter().map(|r| r.id).collect();
+    if ids.len() > RANKED_RESULT_LIMIT {
+        ids.sort();
+        for id in &ids[..ids.len() - RANKED_RESULT_LIMIT] {
+            ctx.db.ranked_result().id().delete(*id);
+        }
+    }
+}
+
+#[spacetimedb::reducer]
+pub fn start_ranked_season(ctx: &ReducerContext) -> Result<(), String> {
+    require_admin(ctx)?;
+    let mut config = get_game_config(ctx);
+    config.ranked_season += 1;
+    let season = config.ranked_season;
+    ctx.db.game_config().id().update(config);
+    // Online players see the reset right away; everyone else when they are next rated
+    for mut player in ctx.db.player().iter() {
+        sync_season(&mut player, season);
+        ctx.db.player().identity().update(player);
+    }
+    record_admin_action(ctx, "start_ranked_season", None, format!("season {}", season));
+    spacetimedb::log::info!("[RANKED] Season {} started", season);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_player;
+
+    #[test]
+    fn upsets_move_ratings_more_than_expected_results() {
+        assert_eq!(expected_score(1500.0, 1500.0), 0.5);
+        assert!((expected_score(1700.0, 1500.0) + expected_score(1500.0, 1700.0) - 1.0).abs() < 1e-6);
+
+        let favourite_wins = rating_delta(1700.0, 1500.0, 1.0, false);
+        let underdog_wins = rating_delta(1500.0, 1700.0, 1.0, false);
+        assert!(favourite_wins > 0 && underdog_wins > favourite_wins);
+        assert_eq!(rating_delta(1500.0, 1500.0, 0.0, false), -rating_delta(1500.0, 1500.0, 1.0, false));
+        assert_eq!(rating_delta(1500.0, 1500.0, 0.5, false), 0, "an even draw changes nothing");
+        assert!(rating_delta(1500.0, 1500.0, 1.0, true) > rating_delta(1500.0, 1500.0, 1.0, false), "placements move faster");
+    }
+
+    #[test]
+    fn a_new_season_resets_ratings_and_placements() {
+        let mut player = test_player(1);
+        player.mmr = 1900;
+        player.placement_matches = 12;
+        sync_season(&mut player, 1);
+        assert_eq!((player.mmr, player.placement_matches), (1900, 12), "same season");
+        sync_season(&mut player, 2);
+        assert_eq!((player.mmr, player.placement_matches, player.mmr_season), (DEFAULT_MMR, 0, 2));
+    }
+}
@@ -0,0 +1,179 @@
+/*
+ * Vibe Coding Starter Pack: 3D Multiplayer - retention.rs
+ *
+ * Cleanup of saved data for players who haven't been back in a long time.
+ *
+ * Key components:
+ *    - prune_logged_out_players: Scheduled reducer (every LOGGED_OUT_CLEANUP_INTERVAL_SECS)
+ *      that purges logged_out_player rows whose last_seen is older than
+ *      GameConfig::logged_out_retention_secs (0 keeps them forever)
+ *    - purge_logged_out_player: Admin reducer to purge one identity on request
+ *    - Purging removes the saved character, inventory, friendships and friend list
+ *      entries, guild membership (friends.rs, guilds.rs), quest and achievement
+ *      progress, login streak, customization cooldowns and the player's own block list.
+ *      Match stats and history, ranked results, bans/mutes and audit entries are kept
+ *      on purpose
+ *    - OpsLog: Private table recording each cleanup run that removed rows, capped at
+ *      OPS_LOG_LIMIT entries (oldest dropped first)
+ */
+
+use spacetimedb::{Identity, ReducerContext, ScheduleAt, SpacetimeType, Table, Timestamp};
+use std::time::Duration;
+
+use crate::achievements::{achievement_counter, player_achievement};
+use crate::admin::{record_admin_action, require_admin};
+use crate::chat::player_block;
+use crate::common::{LOGGED_OUT_CLEANUP_INTERVAL_SECS, OPS_LOG_LIMIT};
+use crate::config::get_game_config;
+use crate::customization::customization_cooldown;
+use crate::daily_rewards::login_streak;
+use crate::friends::purge_friend_data;
+use crate::guilds::purge_guild_data;
+use crate::inventory::player_inventory;
+use crate::logged_out_player;
+use crate::quests::player_quest_progress;
+
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq)]
+pub enum OpsAction {
+    LoggedOutRetention, // scheduled prune_logged_out_players run
+    LoggedOutPurge,     // purge_logged_out_player admin request
+}
+
+#[spacetimedb::table(name = ops_log)]
+#[derive(Clone)]
+pub struct OpsLog {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub action: OpsAction,
+    pub requested_by: Option<Identity>, // None for scheduled runs
+    pub rows_removed: u32,
+    pub created_at: Timestamp,
+}
+
+#[spacetimedb::table(name = logged_out_cleanup_schedule, scheduled(prune_logged_out_players))]
+pub struct LoggedOutCleanupSchedule {
+    #[primary_key]
+    #[auto_inc]
+    scheduled_id: u64,
+    scheduled_at: ScheduleAt,
+}
+
+// Called from init
+pub fn schedule_logged_out_cleanup(ctx: &ReducerContext) {
+    if ctx.db.logged_out_cleanup_schedule().count() == 0 {
+        ctx.db.logged_out_cleanup_schedule().insert(LoggedOutCleanupSchedule {
+            scheduled_id: 0,
+            scheduled_at: ScheduleAt::Interval(Duration::from_secs(LOGGED_OUT_CLEANUP_INTERVAL_SECS).into()),
+        });
+    }
+}
+
+fn log_op(ctx: &ReducerContext, action: OpsAction, requested_by: Option<Identity>, rows_removed: u32) {
+    ctx.db.ops_log().insert(OpsLog { id: 0, action, requested_by, rows_removed, created_at: ctx.timestamp });
+    let ids: Vec<u64> = ctx.db.ops_log().iter().map(|entry| entry.id).collect();
+    for id in over_limit(ids, OPS_LOG_LIMIT) {
+        ctx.db.ops_log().id().delete(id);
+    }
+}
+
+// The oldest ids (lowest, auto_inc) beyond the newest `limit`
+fn over_limit(mut ids: Vec<u64>, limit: usize) -> Vec<u64> {
+    ids.sort();
+    let excess = ids.len().saturating_sub(limit);
+    ids.truncate(excess);
+    ids
+}
+
+fn expired(last_seen: Timestamp, now: Timestamp, retention: Duration) -> bool {
+    now.duration_since(last_seen).is_some_and(|age| age > retention)
+}
+
+// Returns the number of rows removed (the saved character plus everything else keyed to it)
+fn purge_saved_player(ctx: &ReducerContext, identity: Identity) -> u32 {
+    if !ctx.db.logged_out_player().identity().delete(identity) {
+        return 0;
+    }
+    let mut removed = 1;
+    for stack in ctx.db.player_inventory().owner().filter(identity) {
+        ctx.db.player_inventory().id().delete(stack.id);
+        removed += 1;
+    }
+    for progress in ctx.db.player_quest_progress().owner().filter(identity) {
+        ctx.db.player_quest_progress().id().delete(progress.id);
+        removed += 1;
+    }
+    for unlocked in ctx.db.player_achievement().owner().filter(identity) {
+        ctx.db.player_achievement().id().delete(unlocked.id);
+        removed += 1;
+    }
+    for block in ctx.db.player_block().blocker().filter(identity) {
+        ctx.db.player_block().id().delete(block.id);
+        removed += 1;
+    }
+    removed += ctx.db.achievement_counter().identity().delete(identity) as u32;
+    removed += ctx.db.login_streak().identity().delete(identity) as u32;
+    removed += ctx.db.customization_cooldown().identity().delete(identity) as u32;
+    removed + purge_friend_data(ctx, identity) + purge_guild_data(ctx, identity)
+}
+
+#[spacetimedb::reducer]
+pub fn prune_logged_out_players(ctx: &ReducerContext, _schedule: LoggedOutCleanupSchedule) -> Result<(), String> {
+    if ctx.sender != ctx.identity() {
+        return Err("prune_logged_out_players may only be run by the scheduler".to_string());
+    }
+    let retention_secs = get_game_config(ctx).logged_out_retention_secs;
+    if retention_secs == 0 {
+        return Ok(());
+    }
+    let retention = Duration::from_secs(retention_secs);
+    let expired: Vec<Identity> = ctx.db.logged_out_player()
+        .iter()
+        .filter(|p| expired(p.last_seen, ctx.timestamp, retention))
+        .map(|p| p.identity)
+        .collect();
+
+    let mut removed = 0;
+    for identity in &expired {
+        removed += purge_saved_player(ctx, *identity);
+    }
+    if removed > 0 {
+        log_op(ctx, OpsAction::LoggedOutRetention, None, removed);
+        spacetimedb::log::info!("[OPS] Purged {} logged out players not seen for {}s ({} rows)", expired.len(), retention_secs, removed);
+    }
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn purge_logged_out_player(ctx: &ReducerContext, identity: Identity) -> Result<(), String> {
+    require_admin(ctx)?;
+    let removed = purge_saved_player(ctx, identity);
+    if removed == 0 {
+        return Err("No logged out player with that identity".to_string());
+    }
+    log_op(ctx, OpsAction::LoggedOutPurge, Some(ctx.sender), removed);
+    record_admin_action(ctx, "purge_logged_out_player", Some(identity), format!("{} rows", removed));
+    spacetimedb::log::info!("[OPS] {} purged logged out player {} ({} rows)", ctx.sender, identity, removed);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_the_newest_ops_log_entries_are_kept() {
+        assert_eq!(over_limit(vec![5, 1, 4, 2, 3], 3), vec![1, 2]);
+        assert!(over_limit(vec![1, 2], 3).is_empty());
+    }
+
+    #[test]
+    fn saved_players_expire_after_the_retention_period() {
+        let day = Duration::from_secs(24 * 3600);
+        let last_seen = Timestamp::from_micros_since_unix_epoch(0);
+        let after_days = |days: i64| Timestamp::from_micros_since_unix_epoch(days * 24 * 3600 * 1_000_000);
+        assert!(!expired(last_seen, after_days(29), day * 30));
+        assert!(expired(last_seen, after_days(31), day * 30));
+        assert!(!expired(after_days(31), last_seen, day * 30), "clock went backwards");
+    }
+}
@@ -0,0 +1,260 @@
+/*
+ * Vibe Coding Starter Pack: 3D Multiplayer - scoreboard.rs
+ *
+ * Per-match scoreboard and the history of finished matches.
+ *
+ * Key components:
+ *    - PlayerStats: Public per-player kills/deaths/assists for the current match
+ *    - MatchInfo: Singleton row (id = 0) with the running match number and start time
+ *    - MatchHistory: Snapshot of the final scoreboard for each finished match,
+ *      trimmed to MATCH_HISTORY_LIMIT rows (oldest dropped first)
+ *    - KillFeed: Public log of recent deaths for the HUD, trimmed to KILL_FEED_LIMIT rows
+ *    - record_kill: Called from combat::apply_damage on every death; enemy kills count towards
+ *      career stats (leaderboard.rs), score for the killer's team in team deathmatch
+ *      (matches::add_team_score) in the lobby and, inside an arena, for the killer's side of
+ *      its matchmade match (matchmaking::record_match_instance_kill)
+ *    - archive_match: Snapshots the scoreboard into match_history and bumps the match number
+ *    - begin_match: Resets the scoreboard and stamps the start time
+ *
+ * When matches start and end is decided by the lifecycle in matches.rs.
+ */
+
+use spacetimedb::{Identity, ReducerContext, SpacetimeType, Table, Timestamp};
+
+use crate::common::{KILL_FEED_LIMIT, LOBBY_INSTANCE_ID, MATCH_HISTORY_LIMIT};
+use crate::config::get_game_config;
+use crate::leaderboard::record_career_kill;
+use crate::matches::{add_team_score, GameMode};
+use crate::matchmaking::record_match_instance_kill;
+use crate::player;
+
+#[spacetimedb::table(name = player_stats, public)]
+#[derive(Clone)]
+pub struct PlayerStats {
+    #[primary_key]
+    pub identity: Identity,
+    pub username: String,
+    pub kills: u32,
+    pub deaths: u32,
+    pub assists: u32,
+}
+
+#[spacetimedb::table(name = match_info, public)]
+#[derive(Clone)]
+pub struct MatchInfo {
+    #[primary_key]
+    pub id: u32,
+    pub match_number: u64,
+    pub started_at: Timestamp,
+}
+
+#[derive(SpacetimeType, Clone, Debug)]
+pub struct MatchPlayerResult {
+    pub identity: Identity,
+    pub username: String,
+    pub kills: u32,
+    pub deaths: u32,
+    pub assists: u32,
+}
+
+#[spacetimedb::table(name = match_history, public)]
+#[derive(Clone)]
+pub struct MatchHistory {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub match_number: u64,
+    pub started_at: Timestamp,
+    pub ended_at: Timestamp,
+    pub duration_secs: u64,
+    pub results: Vec<MatchPlayerResult>, // sorted by kills, best first
+}
+
+#[spacetimedb::table(name = kill_feed, public)]
+#[derive(Clone)]
+pub struct KillFeedEntry {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub killer: Option<Identity>, // None for environmental deaths (falls) and suicides
+    pub killer_name: String,
+    pub victim: Identity,
+    pub victim_name: String,
+    pub assisters: Vec<Identity>,
+    pub created_at: Timestamp,
+}
+
+fn player_name(ctx: &ReducerContext, identity: Identity) -> String {
+    ctx.db.player_stats().identity().find(identity).map(|s| s.username).unwrap_or_default()
+}
+
+fn push_kill_feed(ctx: &ReducerContext, killer: Option<Identity>, assisters: &[Identity], victim: Identity) {
+    ctx.db.kill_feed().insert(KillFeedEntry {
+        id: 0, // auto_inc
+        killer,
+        killer_name: killer.map(|k| player_name(ctx, k)).unwrap_or_default(),
+        victim,
+        victim_name: player_name(ctx, victim),
+        assisters: assisters.to_vec(),
+        created_at: ctx.timestamp,
+    });
+
+    let ids: Vec<u64> = ctx.db.kill_feed().iter().map(|e| e.id).collect();
+    for id in oldest_beyond(ids, KILL_FEED_LIMIT) {
+        ctx.db.kill_feed().id().delete(id);
+    }
+}
+
+// Ids to drop so only the newest `limit` remain (auto_inc ids grow with insertion order)
+fn oldest_beyond(mut ids: Vec<u64>, limit: usize) -> Vec<u64> {
+    ids.sort();
+    let excess = ids.len().saturating_sub(limit);
+    ids.truncate(excess);
+    ids
+}
+
+pub fn get_match_info(ctx: &ReducerContext) -> MatchInfo {
+    ctx.db.match_info().id().find(0).unwrap_or_else(|| {
+        ctx.db.match_info().insert(MatchInfo { id: 0, match_number: 1, started_at: ctx.timestamp })
+    })
+}
+
+// Make sure a stats row exists for an active player (keeps their score if they rejoin mid-match)
+pub fn ensure_player_stats(ctx: &ReducerContext, identity: Identity, username: &str) {
+    if let Some(mut stats) = ctx.db.player_stats().identity().find(identity) {
+        if stats.username != username {
+            stats.username = username.to_string();
+            ctx.db.player_stats().identity().update(stats);
+        }
+    } else {
+        ctx.db.player_stats().insert(PlayerStats { identity, username: username.to_string(), kills: 0, deaths: 0, assists: 0 });
+    }
+}
+
+pub fn record_kill(ctx: &ReducerContext, killer: Option<Identity>, assisters: &[Identity], victim: Identity) {
+    let killer = killer.filter(|k| *k != victim);
+    push_kill_feed(ctx, killer, assisters, victim);
+    record_career_kill(ctx, killer, victim);
+
+    if let Some(mut stats) = ctx.db.player_stats().identity().find(victim) {
+        stats.deaths += 1;
+        ctx.db.player_stats().identity().update(stats);
+    }
+    for assister in assisters {
+        if let Some(mut stats) = ctx.db.player_stats().identity().find(*assister) {
+            stats.assists += 1;
+            ctx.db.player_stats().identity().update(stats);
+        }
+    }
+
+    let Some(killer) = killer else {
+        return;
+    };
+    if let Some(mut stats) = ctx.db.player_stats().identity().find(killer) {
+        stats.kills += 1;
+        ctx.db.player_stats().identity().update(stats);
+    }
+
+    let Some(victim) = ctx.db.player().identity().find(victim) else {
+        return;
+    };
+    let Some(team) = ctx.db.player().identity().find(killer).map(|p| p.team).filter(|t| *t != victim.team) else {
+        return;
+    };
+    if victim.instance_id != LOBBY_INSTANCE_ID {
+        record_match_instance_kill(ctx, victim.instance_id, team);
+    } else if get_game_config(ctx).game_mode == GameMode::TeamDeathmatch {
+        // Team deathmatch: every kill of an enemy scores a point for the killer's team
+        add_team_score(ctx, team, 1);
+    }
+}
+
+// The scoreboard is left as is so it can be shown until the next match begins
+pub fn archive_match(ctx: &ReducerContext) {
+    let info = get_match_info(ctx);
+    let entry = ctx.db.match_history().insert(history_entry(&info, ctx.db.player_stats().iter(), ctx.timestamp));
+    spacetimedb::log::info!("[MATCH] Match {} ended after {}s", info.match_number, entry.duration_secs);
+
+    // Bound retained history
+    let mut history_ids: Vec<u64> = ctx.db.match_history().iter().map(|h| h.id).collect();
+    if history_ids.len() > MATCH_HISTORY_LIMIT {
+        history_ids.sort();
+        for id in &history_ids[..history_ids.len() - MATCH_HISTORY_LIMIT] {
+            ctx.db.match_history().id().delete(*id);
+        }
+    }
+
+    ctx.db.match_info().id().update(MatchInfo {
+        id: 0,
+        match_number: info.match_number + 1,
+        started_at: info.started_at,
+    });
+}
+
+pub fn begin_match(ctx: &ReducerContext) {
+    // Reset the scoreboard for the new match; drop rows for players who have left
+    for mut stats in ctx.db.player_stats().iter() {
+        if ctx.db.player().identity().find(stats.identity).is_none() {
+            ctx.db.player_stats().identity().delete(stats.identity);
+            continue;
+        }
+        stats.kills = 0;
+        stats.deaths = 0;
+        stats.assists = 0;
+        ctx.db.player_stats().identity().update(stats);
+    }
+
+    let mut info = get_match_info(ctx);
+    info.started_at = ctx.timestamp;
+    ctx.db.match_info().id().update(info);
+}
+
+fn history_entry(info: &MatchInfo, stats: impl Iterator<Item = PlayerStats>, ended_at: Timestamp) -> MatchHistory {
+    let mut results: Vec<MatchPlayerResult> = stats
+        .map(|s| MatchPlayerResult { identity: s.identity, username: s.username, kills: s.kills, deaths: s.deaths, assists: s.assists })
+        .collect();
+    results.sort_by(|a, b| b.kills.cmp(&a.kills).then(a.deaths.cmp(&b.deaths)));
+    MatchHistory {
+        id: 0, // auto_inc
+        match_number: info.match_number,
+        started_at: info.started_at,
+        ended_at,
+        duration_secs: ended_at.duration_since(info.started_at).map_or(0, |d| d.as_secs()),
+        results,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn stats(n: u8, kills: u32, deaths: u32, assists: u32) -> PlayerStats {
+        PlayerStats { identity: Identity::from_byte_array([n; 32]), username: format!("player{n}"), kills, deaths, assists }
+    }
+
+    #[test]
+    fn the_kill_feed_keeps_only_the_newest_entries() {
+        let ids: Vec<u64> = (1..=KILL_FEED_LIMIT as u64 + 3).rev().collect();
+        assert_eq!(oldest_beyond(ids, KILL_FEED_LIMIT), vec![1, 2, 3]);
+        assert!(oldest_beyond(vec![1, 2], KILL_FEED_LIMIT).is_empty());
+    }
+
+    #[test]
+    fn ending_a_match_records_the_final_scores() {
+        let info = MatchInfo { id: 0, match_number: 7, started_at: Timestamp::UNIX_EPOCH };
+        let ended_at = Timestamp::UNIX_EPOCH + Duration::from_secs(300);
+        let scoreboard = vec![stats(1, 3, 2, 1), stats(2, 5, 4, 0), stats(3, 3, 1, 2)];
+        let entry = history_entry(&info, scoreboard.into_iter(), ended_at);
+
+        assert_eq!(entry.match_number, 7);
+        assert_eq!(entry.duration_secs, 300);
+        assert_eq!(entry.ended_at, ended_at);
+        // Most kills first, fewer deaths breaking the tie
+        let ranked: Vec<(String, u32, u32, u32)> = entry.results.into_iter().map(|r| (r.username, r.kills, r.deaths, r.assists)).collect();
+        assert_eq!(
+            ranked,
+            vec![("player2".to_string(), 5, 4, 0), ("player3".to_string(), 3, 1, 2), ("player1".to_string(), 3, 2, 1)]
+        );
+    }
+}
@@ -0,0 +1,223 @@
+/*
+ * Vibe Coding Starter Pack: 3D Multiplayer - shop.rs
+ *
+ * Vendor NPCs that buy and sell items for gold (PlayerData::gold, saved in
+ * PersistentPlayerState).
+ *
+ * Key components:
+ *    - Vendor: Public table of shopkeepers placed in the world; seeded in init. Unlike npcs.rs
+ *      monsters they have no AI and can't be attacked
+ *    - ShopListing: Public table keyed by item_id with buy and sell prices, seeded in init from
+ *      item_definition (price_for; sell price is SHOP_SELL_RATIO of the buy price). Gold itself
+ *      is currency and is never listed
+ *    - buy_item / sell_item: Reducers; the player must be alive and within VENDOR_RANGE of the
+ *      vendor. Buying fails without enough gold or room for the whole quantity; equipped items
+ *      can't be sold
+ *    - ShopTransaction: Public table recording every purchase and sale
+ *
+ * Loot gold (the CURRENCY_ITEM_ID dropped item) is credited straight to PlayerData::gold when
+ * picked up (loot.rs).
+ */
+
+use spacetimedb::{Identity, ReducerContext, SpacetimeType, Table, Timestamp};
+
+use crate::common::{Vector3, CURRENCY_ITEM_ID, LOBBY_INSTANCE_ID, SHOP_SELL_RATIO, VENDOR_RANGE};
+use crate::inventory::{add_item_to_inventory, item_definition, player_inventory, EquipSlot, ItemDefinition};
+use crate::{player, PlayerData};
+
+#[spacetimedb::table(name = vendor, public)]
+#[derive(Clone)]
+pub struct Vendor {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub name: String,
+    pub position: Vector3,
+    pub yaw: f32,
+}
+
+#[spacetimedb::table(name = shop_listing, public)]
+#[derive(Clone)]
+pub struct ShopListing {
+    #[primary_key]
+    pub item_id: String,
+    pub buy_price: u64,  // gold per item when buying from a vendor
+    pub sell_price: u64, // gold per item when selling to a vendor
+}
+
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq)]
+pub enum ShopAction {
+    Buy,
+    Sell,
+}
+
+#[spacetimedb::table(name = shop_transaction, public)]
+#[derive(Clone)]
+pub struct ShopTransaction {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    #[index(btree)]
+    pub player: Identity,
+    pub vendor_id: u64,
+    pub action: ShopAction,
+    pub item_id: String,
+    pub quantity: u32,
+    pub gold: u64, // total paid (Buy) or received (Sell)
+    pub created_at: Timestamp,
+}
+
+pub fn seed_vendors(ctx: &ReducerContext) {
+    if ctx.db.vendor().count() > 0 {
+        return;
+    }
+    ctx.db.vendor().insert(Vendor { id: 0, name: "Merchant".to_string(), position: Vector3 { x: 0.0, y: 1.0, z: -8.0 }, yaw: 0.0 });
+    spacetimedb::log::info!("[INIT] Seeded vendors.");
+}
+
+// Equipment is priced from its stats, everything else is a consumable
+fn price_for(definition: &ItemDefinition) -> u64 {
+    match definition.slot {
+        Some(EquipSlot::Weapon) => 100 + ((definition.damage_multiplier - 1.0).max(0.0) * 1000.0) as u64,
+        Some(EquipSlot::Armor) => 100 + definition.bonus_max_health.max(0) as u64 * 2 + (definition.damage_reduction * 1000.0) as u64,
+        None if definition.mount_speed_multiplier > 1.0 => 200 + ((definition.mount_speed_multiplier - 1.0) * 1000.0) as u64,
+        None => 25,
+    }
+}
+
+fn sell_price(buy_price: u64) -> u64 {
+    ((buy_price as f32 * SHOP_SELL_RATIO) as u64).max(1)
+}
+
+// Called from init after seed_item_definitions
+pub fn seed_shop_listings(ctx: &ReducerContext) {
+    if ctx.db.shop_listing().count() > 0 {
+        return;
+    }
+    let mut count = 0;
+    for definition in ctx.db.item_definition().iter().filter(|d| d.item_id != CURRENCY_ITEM_ID) {
+        let buy_price = price_for(&definition);
+        ctx.db.shop_listing().insert(ShopListing {
+            item_id: definition.item_id,
+            buy_price,
+            sell_price: sell_price(buy_price),
+        });
+        count += 1;
+    }
+    spacetimedb::log::info!("[INIT] Seeded {} shop listings.", count);
+}
+
+fn shopper_at_vendor(ctx: &ReducerContext, vendor_id: u64) -> Result<PlayerData, String> {
+    let Some(player) = ctx.db.player().identity().find(ctx.sender) else {
+        return Err("Player is not active".to_string());
+    };
+    if !player.alive {
+        return Err("Cannot trade while dead".to_string());
+    }
+    let Some(vendor) = ctx.db.vendor().id().find(vendor_id) else {
+        return Err("Vendor not found".to_string());
+    };
+    if player.instance_id != LOBBY_INSTANCE_ID || (player.position - vendor.position).length() > VENDOR_RANGE + player.hit_radius {
+        return Err(format!("You are too far from {}", vendor.name));
+    }
+    Ok(player)
+}
+
+fn listing_for(ctx: &ReducerContext, item_id: &str) -> Result<ShopListing, String> {
+    ctx.db.shop_listing().item_id().find(item_id.to_string()).ok_or_else(|| format!("Vendors don't trade '{}'", item_id))
+}
+
+fn record_transaction(ctx: &ReducerContext, vendor_id: u64, action: ShopAction, item_id: &str, quantity: u32, gold: u64) {
+    ctx.db.shop_transaction().insert(ShopTransaction {
+        id: 0, // auto_inc
+        player: ctx.sender,
+        vendor_id,
+        action,
+        item_id: item_id.to_string(),
+        quantity,
+        gold,
+        created_at: ctx.timestamp,
+    });
+}
+
+#[spacetimedb::reducer]
+pub fn buy_item(ctx: &ReducerContext, vendor_id: u64, item_id: String, quantity: u32) -> Result<(), String> {
+    if quantity == 0 {
+        return Err("Quantity must be positive".to_string());
+    }
+    let mut player = shopper_at_vendor(ctx, vendor_id)?;
+    let listing = listing_for(ctx, &item_id)?;
+    let cost = listing.buy_price.saturating_mul(quantity as u64);
+    if player.gold < cost {
+        return Err(format!("That costs {} gold; you have {}", cost, player.gold));
+    }
+    // All-or-nothing: an Err here rolls back the partially added stacks
+    let leftover = add_item_to_inventory(ctx, player.identity, &item_id, quantity)?;
+    if leftover > 0 {
+        return Err(format!("Inventory is full ({} of {} would not fit)", leftover, quantity));
+    }
+    player.gold -= cost;
+    ctx.db.player().identity().update(player);
+    record_transaction(ctx, vendor_id, ShopAction::Buy, &item_id, quantity, cost);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn sell_item(ctx: &ReducerContext, vendor_id: u64, inventory_id: u64, quantity: u32) -> Result<(), String> {
+    let mut player = shopper_at_vendor(ctx, vendor_id)?;
+    let Some(mut stack) = ctx.db.player_inventory().id().find(inventory_id).filter(|s| s.owner == ctx.sender) else {
+        return Err("Item not found in your inventory".to_string());
+    };
+    if stack.equipped {
+        return Err("Unequip the item before selling it".to_string());
+    }
+    if quantity == 0 || quantity > stack.quantity {
+        return Err(format!("You can sell between 1 and {}", stack.quantity));
+    }
+    let listing = listing_for(ctx, &stack.item_id)?;
+    let earned = listing.sell_price.saturating_mul(quantity as u64);
+    let item_id = stack.item_id.clone();
+    if quantity == stack.quantity {
+        ctx.db.player_inventory().id().delete(stack.id);
+    } else {
+        stack.quantity -= quantity;
+        ctx.db.player_inventory().id().update(stack);
+    }
+    player.gold = player.gold.saturating_add(earned);
+    ctx.db.player().identity().update(player);
+    record_transaction(ctx, vendor_id, ShopAction::Sell, &item_id, quantity, earned);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn definition(slot: Option<EquipSlot>, damage_multiplier: f32, bonus_max_health: i32, damage_reduction: f32) -> ItemDefinition {
+        ItemDefinition {
+            item_id: "test_item".to_string(),
+            display_name: "Test Item".to_string(),
+            max_stack: 1,
+            slot,
+            damage_multiplier,
+            bonus_max_health,
+            damage_reduction,
+            mount_speed_multiplier: 1.0,
+        }
+    }
+
+    #[test]
+    fn better_equipment_costs_more_and_vendors_buy_back_for_less() {
+        let potion = price_for(&definition(None, 1.0, 0, 0.0));
+        let staff = price_for(&definition(Some(EquipSlot::Weapon), 1.1, 0, 0.0));
+        let sword = price_for(&definition(Some(EquipSlot::Weapon), 1.25, 0, 0.0));
+        let leather = price_for(&definition(Some(EquipSlot::Armor), 1.0, 20, 0.1));
+        let plate = price_for(&definition(Some(EquipSlot::Armor), 1.0, 50, 0.25));
+        assert!(potion < staff && staff < sword);
+        assert!(leather < plate);
+
+        assert_eq!(sell_price(100), (100.0 * SHOP_SELL_RATIO) as u64);
+        assert!(sell_price(sword) < sword);
+        assert_eq!(sell_price(1), 1, "nothing sells for 0 gold");
+    }
+}
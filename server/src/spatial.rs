@@ -0,0 +1,94 @@
+/*
+ * Vibe Coding Starter Pack: 3D Multiplayer - spatial.rs
+ *
+ * Broadphase for projectile-vs-player queries.
+ *
+ * SpatialGrid buckets living players into SPATIAL_CELL_SIZE cells on the XZ plane. It is
+ * built once per tick after movement (players don't move while projectiles are simulated),
+ * so each projectile only tests players in the cells its query radius overlaps instead of
+ * every player on the server.
+ *
+ * The grid holds a snapshot: callers still do the exact distance test, and anything that
+ * depends on state changed earlier in the same tick (e.g. a player killed by another
+ * projectile) must be re-checked against the table.
+ */
+
+use std::collections::HashMap;
+
+use spacetimedb::{ReducerContext, Table};
+
+use crate::common::{Vector3, SPATIAL_CELL_SIZE};
+use crate::{player, PlayerData};
+
+pub struct SpatialGrid {
+    cells: HashMap<(i32, i32), Vec<usize>>,
+    players: Vec<PlayerData>,
+}
+
+fn cell_coord(value: f32) -> i32 {
+    (value / SPATIAL_CELL_SIZE).floor() as i32
+}
+
+impl SpatialGrid {
+    pub fn build(ctx: &ReducerContext) -> Self {
+        Self::from_players(ctx.db.player().iter().filter(|p| p.alive).collect())
+    }
+
+    pub(crate) fn from_players(players: Vec<PlayerData>) -> Self {
+        let mut cells: HashMap<(i32, i32), Vec<usize>> = HashMap::new();
+        for (index, player) in players.iter().enumerate() {
+            cells.entry((cell_coord(player.position.x), cell_coord(player.position.z))).or_default().push(index);
+        }
+        SpatialGrid { cells, players }
+    }
+
+    // Players of `instance_id` in every cell overlapped by a square of half-width `radius`
+    // around `center`. A superset of the players within `radius`; callers do the exact test.
+    pub fn query(&self, instance_id: u64, center: Vector3, radius: f32) -> Vec<&PlayerData> {
+        if self.players.is_empty() || !center.x.is_finite() || !center.z.is_finite() {
+            return Vec::new();
+        }
+        let mut found = Vec::new();
+        for cx in cell_coord(center.x - radius)..=cell_coord(center.x + radius) {
+            for cz in cell_coord(center.z - radius)..=cell_coord(center.z + radius) {
+                if let Some(indices) = self.cells.get(&(cx, cz)) {
+                    found.extend(indices.iter().map(|i| &self.players[*i]).filter(|p| p.instance_id == instance_id));
+                }
+            }
+        }
+        found
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::{LOBBY_INSTANCE_ID, MAX_PLAYER_HIT_RADIUS, PROJECTILE_RADIUS};
+    use crate::test_player;
+
+    fn player_at(id: u8, x: f32, z: f32) -> PlayerData {
+        let mut player = test_player(id);
+        player.position = Vector3 { x, y: 0.0, z };
+        player
+    }
+
+    #[test]
+    fn queries_only_return_players_in_overlapped_cells() {
+        let grid = SpatialGrid::from_players(vec![
+            player_at(1, 0.5, 0.5),
+            player_at(2, SPATIAL_CELL_SIZE + 0.5, 0.5), // next cell over
+            player_at(3, SPATIAL_CELL_SIZE * 5.0, SPATIAL_CELL_SIZE * 5.0),
+            PlayerData { instance_id: LOBBY_INSTANCE_ID + 1, ..player_at(4, 0.5, 0.5) }, // same spot, other instance
+        ]);
+
+        // A projectile near the shared cell edge sees both neighbours but not the far player
+        let edge = Vector3 { x: SPATIAL_CELL_SIZE - 0.1, y: 1.0, z: 0.5 };
+        let mut found: Vec<u8> = grid.query(LOBBY_INSTANCE_ID, edge, MAX_PLAYER_HIT_RADIUS + PROJECTILE_RADIUS).iter().map(|p| p.identity.to_byte_array()[0]).collect();
+        found.sort();
+        assert_eq!(found, vec![1, 2]);
+
+        let far_corner = Vector3 { x: -SPATIAL_CELL_SIZE * 3.0, y: 0.0, z: -SPATIAL_CELL_SIZE * 3.0 };
+        assert!(grid.query(LOBBY_INSTANCE_ID, far_corner, 1.0).is_empty());
+        assert!(grid.query(LOBBY_INSTANCE_ID, Vector3 { x: f32::NAN, y: 0.0, z: 0.0 }, 1.0).is_empty());
+    }
+}
@@ -0,0 +1,166 @@
+/*
+ * Vibe Coding Starter Pack: 3D Multiplayer - spawns.rs
+ *
+ * Where players appear when they join, respawn or get rescued from the kill plane.
+ *
+ * Key components:
+ *    - SpawnPoint: Public table of spawn positions, optionally reserved for one team.
+ *      last_used_at drives the rotation so consecutive spawns spread over the points
+ *    - SpawnStrategy: How choose_spawn_point picks a point (GameConfig::spawn_strategy,
+ *      changed with config::set_spawn_strategy)
+ *        - RoundRobin: Least recently used point, ignoring teams
+ *        - TeamBased: Least recently used point of the player's team, falling back to
+ *          neutral points and then any point
+ *        - FarthestFromEnemies: Of the points the player's team may use, the one farthest
+ *          from the nearest living enemy (round-robin when there are no enemies)
+ *    - choose_spawn_point: Spawn position for a player of `team`; with no spawn points the old
+ *      line-up along the X axis is used
+ *    - add_spawn_point / remove_spawn_point: Admin reducers
+ */
+
+use spacetimedb::{Identity, ReducerContext, SpacetimeType, Table, Timestamp};
+
+use crate::admin::require_admin;
+use crate::common::{Vector3, TEAM_COUNT};
+use crate::config::get_game_config;
+use crate::player;
+
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq)]
+pub enum SpawnStrategy {
+    RoundRobin,
+    TeamBased,
+    FarthestFromEnemies,
+}
+
+#[spacetimedb::table(name = spawn_point, public)]
+#[derive(Clone)]
+pub struct SpawnPoint {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub position: Vector3,
+    pub team: Option<u8>, // None: any team may spawn here
+    pub last_used_at: Option<Timestamp>,
+}
+
+pub fn seed_spawn_points(ctx: &ReducerContext) {
+    if ctx.db.spawn_point().count() > 0 {
+        return;
+    }
+    for (team, x) in [(0, -10.0), (1, 10.0)] {
+        for z in [-5.0, 5.0] {
+            ctx.db.spawn_point().insert(SpawnPoint { id: 0, position: Vector3 { x, y: 1.0, z }, team: Some(team), last_used_at: None });
+        }
+    }
+    spacetimedb::log::info!("[INIT] Seeded default spawn points.");
+}
+
+fn least_recently_used(points: &[SpawnPoint]) -> Option<&SpawnPoint> {
+    points.iter().min_by_key(|p| (p.last_used_at, p.id))
+}
+
+// Points reserved for `team`, else neutral ones, else all of them
+fn team_points(points: &[SpawnPoint], team: u8, include_neutral: bool) -> Vec<SpawnPoint> {
+    let own: Vec<SpawnPoint> = points
+        .iter()
+        .filter(|p| p.team == Some(team) || (include_neutral && p.team.is_none()))
+        .cloned()
+        .collect();
+    if !own.is_empty() {
+        return own;
+    }
+    let neutral: Vec<SpawnPoint> = points.iter().filter(|p| p.team.is_none()).cloned().collect();
+    if !neutral.is_empty() {
+        return neutral;
+    }
+    points.to_vec()
+}
+
+// The point whose nearest enemy is farthest away; least recently used when there are no enemies
+fn farthest_from_enemies<'a>(points: &'a [SpawnPoint], enemies: &[Vector3]) -> Option<&'a SpawnPoint> {
+    if enemies.is_empty() {
+        return least_recently_used(points);
+    }
+    let safety = |point: &SpawnPoint| enemies.iter().map(|e| (*e - point.position).length()).fold(f32::MAX, f32::min);
+    points.iter().max_by(|a, b| safety(a).total_cmp(&safety(b)))
+}
+
+pub fn choose_spawn_point(ctx: &ReducerContext, identity: Identity, team: u8) -> Vector3 {
+    let points: Vec<SpawnPoint> = ctx.db.spawn_point().iter().collect();
+    if points.is_empty() {
+        let index = ctx.db.player().iter().filter(|p| p.identity != identity).count();
+        return Vector3 { x: (index as f32 * 5.0) - 2.5, y: 1.0, z: 0.0 };
+    }
+
+    let chosen = match get_game_config(ctx).spawn_strategy {
+        SpawnStrategy::RoundRobin => least_recently_used(&points).cloned(),
+        SpawnStrategy::TeamBased => least_recently_used(&team_points(&points, team, false)).cloned(),
+        SpawnStrategy::FarthestFromEnemies => {
+            let candidates = team_points(&points, team, true);
+            let enemies: Vec<Vector3> = ctx.db.player()
+                .iter()
+                .filter(|p| p.alive && p.identity != identity && p.team != team)
+                .map(|p| p.position)
+                .collect();
+            farthest_from_enemies(&candidates, &enemies).cloned()
+        }
+    };
+
+    let mut chosen = chosen.unwrap_or_else(|| points[0].clone());
+    let position = chosen.position;
+    chosen.last_used_at = Some(ctx.timestamp);
+    ctx.db.spawn_point().id().update(chosen);
+    position
+}
+
+#[spacetimedb::reducer]
+pub fn add_spawn_point(ctx: &ReducerContext, position: Vector3, team: Option<u8>) -> Result<(), String> {
+    require_admin(ctx)?;
+    if team.is_some_and(|t| t >= TEAM_COUNT) {
+        return Err(format!("Team must be below {}", TEAM_COUNT));
+    }
+    if !(position.x.is_finite() && position.y.is_finite() && position.z.is_finite()) {
+        return Err("Spawn position must be finite".to_string());
+    }
+    let point = ctx.db.spawn_point().insert(SpawnPoint { id: 0, position, team, last_used_at: None });
+    spacetimedb::log::info!("Added spawn point {} at {:?} (team {:?})", point.id, position, team);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn remove_spawn_point(ctx: &ReducerContext, id: u64) -> Result<(), String> {
+    require_admin(ctx)?;
+    if !ctx.db.spawn_point().id().delete(id) {
+        return Err("Spawn point not found".to_string());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(id: u64, x: f32, team: Option<u8>, used_at: Option<i64>) -> SpawnPoint {
+        let last_used_at = used_at.map(Timestamp::from_micros_since_unix_epoch);
+        SpawnPoint { id, position: Vector3 { x, y: 1.0, z: 0.0 }, team, last_used_at }
+    }
+
+    #[test]
+    fn spawns_rotate_through_the_teams_points() {
+        let points = [point(1, -10.0, Some(0), Some(5)), point(2, -5.0, Some(0), None), point(3, 10.0, Some(1), None), point(4, 0.0, None, None)];
+        assert_eq!(least_recently_used(&points).map(|p| p.id), Some(2), "never used comes first");
+        let ids = |v: Vec<SpawnPoint>| v.iter().map(|p| p.id).collect::<Vec<_>>();
+        assert_eq!(ids(team_points(&points, 0, false)), vec![1, 2]);
+        assert_eq!(ids(team_points(&points, 1, true)), vec![3, 4]);
+        assert_eq!(ids(team_points(&points[..2], 1, false)), vec![1, 2], "no own or neutral points, any will do");
+    }
+
+    #[test]
+    fn farthest_spawn_maximizes_distance_to_the_nearest_enemy() {
+        let points = [point(1, -10.0, None, None), point(2, 0.0, None, None), point(3, 10.0, None, Some(5))];
+        let enemies = [Vector3 { x: -9.0, y: 1.0, z: 0.0 }, Vector3 { x: 9.0, y: 1.0, z: 0.0 }];
+        assert_eq!(farthest_from_enemies(&points, &enemies).map(|p| p.id), Some(2));
+        assert_eq!(farthest_from_enemies(&points, &enemies[..1]).map(|p| p.id), Some(3));
+        assert_eq!(farthest_from_enemies(&points, &[]).map(|p| p.id), Some(1), "round-robin without enemies");
+    }
+}
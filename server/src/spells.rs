@@ -0,0 +1,322 @@
+/*
+ * Vibe Coding Starter Pack: 3D Multiplayer - spells.rs
+ *
+ * Castable spells, stored as data so they can be retuned without code changes.
+ *
+ * Key components:
+ *    - SpellDefinition: Public table keyed by spell_id (e.g. "bolt", "fireball"); seeded in init
+ *      from the ProjectileSpec of each castable ProjectileKind. The kind picks the visuals and
+ *      flight behavior (bounce, split, gravity...); the row overrides the numbers
+ *    - SpellCooldown: Public table of when each caster may cast a spell again
+ *    - cast_spell: Reducer that casts by spell_id (spawn_projectile/fire_projectile delegate to it)
+ *    - set_spell_definition: Admin reducer to add or retune a spell at runtime
+ *
+ * Casting rules (cast):
+ *    - Rejected while dead, disarmed or stunned; thrown axes also need axe ammo
+ *    - The client's hand_position must be within MAX_HAND_OFFSET of the caster
+ *    - The caster class's cast_cooldown_secs is a global cooldown shared by every spell;
+ *      cooldown_secs is tracked per spell on top of it
+ *    - Mana cost is the class's mana_cost_per_cast plus the spell's mana_cost (axes are free)
+ *    - Damage is scaled by the class's projectile_damage_multiplier
+ *    - aoe_radius > 0 becomes the projectile's explosion_radius; status_effect is applied to
+ *      the player it hits directly
+ *    - gravity_scale > 0 lobs the projectile on a parabolic arc (scaled GameConfig gravity);
+ *      it is spent, and explodes if it has an aoe_radius, when it lands
+ *    - pierce_count / bounce_count: players passed through and ricochets before it is spent
+ *    - homing_turn_rate > 0 locks onto the nearest enemy in front of the caster when cast and
+ *      curves towards it (projectile_logic::acquire_homing_target)
+ */
+
+use spacetimedb::{Identity, ReducerContext, Table, TimeDuration, Timestamp};
+
+use crate::admin::require_admin;
+use crate::classes::class_definition_for;
+use crate::common::{ImpactBehavior, ProjectileKind, StatusOnHit, Vector3, MAX_HAND_OFFSET};
+use crate::matches::require_in_progress;
+use crate::player;
+use crate::player_logic::{check_cast, heat_after_shot, spread_radians};
+use crate::projectile_logic::{consume_global_spawn_budget, jitter_direction, spawn_projectile_with_spec, ProjectileSpec};
+use crate::status_effects::{is_stunned, require_armed};
+use crate::visibility::break_stealth;
+use crate::zones::require_can_cast;
+
+#[spacetimedb::table(name = spell_definition, public)]
+#[derive(Clone)]
+pub struct SpellDefinition {
+    #[primary_key]
+    pub spell_id: String,
+    pub kind: ProjectileKind,
+    pub projectile_speed: f32,
+    pub damage: i32,
+    pub mana_cost: i32,     // on top of the class's mana_cost_per_cast
+    pub cooldown_secs: f32, // per spell, on top of the class's global cast cooldown
+    pub aoe_radius: f32,    // > 0: explodes when spent, with falloff damage in this radius
+    pub status_effect: Option<StatusOnHit>,
+    pub gravity_scale: f32, // 0 flies straight
+    pub homing_turn_rate: f32, // radians per second, 0 = no homing
+    pub pierce_count: u32,
+    pub bounce_count: u32,
+}
+
+#[spacetimedb::table(name = spell_cooldown, public)]
+#[derive(Clone)]
+pub struct SpellCooldown {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    #[index(btree)]
+    pub caster: Identity,
+    pub spell_id: String,
+    pub ready_at: Timestamp,
+}
+
+impl SpellDefinition {
+    // Default tuning: the kind's built-in ProjectileSpec plus a cost and cooldown
+    fn from_kind(spell_id: &str, kind: ProjectileKind, mana_cost: i32, cooldown_secs: f32) -> Self {
+        let spec = ProjectileSpec::for_kind(kind);
+        SpellDefinition {
+            spell_id: spell_id.to_string(),
+            kind,
+            projectile_speed: spec.speed,
+            damage: spec.damage,
+            mana_cost,
+            cooldown_secs,
+            aoe_radius: spec.explosion_radius,
+            status_effect: match spec.impact {
+                ImpactBehavior::Afflict(status) => Some(status),
+                _ => None,
+            },
+            gravity_scale: spec.gravity_scale,
+            homing_turn_rate: spec.homing_turn_rate,
+            pierce_count: spec.pierce_count,
+            bounce_count: spec.bounce_count,
+        }
+    }
+
+    // The kind's spec with this spell's numbers; the row decides explosion and status effect
+    pub fn projectile_spec(&self) -> ProjectileSpec {
+        let mut spec = ProjectileSpec::for_kind(self.kind);
+        spec.speed = self.projectile_speed;
+        spec.damage = self.damage;
+        if matches!(spec.impact, ImpactBehavior::Afflict(_)) {
+            spec.impact = ImpactBehavior::Stop;
+        }
+        spec.explosion_radius = self.aoe_radius;
+        spec.status_on_hit = self.status_effect;
+        spec.gravity_scale = self.gravity_scale;
+        spec.homing_turn_rate = self.homing_turn_rate;
+        spec.pierce_count = self.pierce_count;
+        spec.bounce_count = self.bounce_count;
+        spec
+    }
+}
+
+pub fn seed_spell_definitions(ctx: &ReducerContext) {
+    if ctx.db.spell_definition().count() > 0 {
+        return;
+    }
+    // Shard is only spawned by Cluster impacts, so it has no spell
+    let defaults = [
+        SpellDefinition::from_kind("bolt", ProjectileKind::Bolt, 0, 0.0),
+        SpellDefinition::from_kind("breaker", ProjectileKind::Breaker, 0, 0.0),
+        SpellDefinition::from_kind("bouncer", ProjectileKind::Bouncer, 5, 0.0),
+        SpellDefinition::from_kind("hex", ProjectileKind::Hex, 10, 3.0),
+        SpellDefinition::from_kind("fireball", ProjectileKind::Fireball, 15, 1.5),
+        SpellDefinition::from_kind("cluster", ProjectileKind::Cluster, 15, 2.0),
+        SpellDefinition::from_kind("napalm", ProjectileKind::Napalm, 15, 3.0),
+        SpellDefinition::from_kind("mender", ProjectileKind::Mender, 20, 4.0),
+        SpellDefinition::from_kind("lance", ProjectileKind::Lance, 10, 1.0),
+        SpellDefinition::from_kind("grenade", ProjectileKind::Grenade, 15, 2.0),
+        SpellDefinition::from_kind("frost_bolt", ProjectileKind::FrostBolt, 10, 1.0),
+        SpellDefinition::from_kind("boulder", ProjectileKind::Boulder, 20, 2.5),
+        SpellDefinition::from_kind("scanner", ProjectileKind::Scanner, 5, 5.0),
+        SpellDefinition::from_kind("axe", ProjectileKind::Axe, 0, 0.0),
+        SpellDefinition { homing_turn_rate: 2.5, ..SpellDefinition::from_kind("seeker", ProjectileKind::Bolt, 10, 2.0) },
+    ];
+    let count = defaults.len();
+    for definition in defaults {
+        ctx.db.spell_definition().insert(definition);
+    }
+    spacetimedb::log::info!("[INIT] Seeded {} spell definitions.", count);
+}
+
+// Spell used by fire_projectile for a bare ProjectileKind (lowest spell_id if several share it)
+pub fn spell_for_kind(ctx: &ReducerContext, kind: ProjectileKind) -> Option<SpellDefinition> {
+    ctx.db.spell_definition().iter().filter(|s| s.kind == kind).min_by(|a, b| a.spell_id.cmp(&b.spell_id))
+}
+
+// The client picks the spawn point, so keep it near the caster
+fn hand_within_reach(caster_position: Vector3, hand_position: Vector3) -> bool {
+    let hand_offset = (hand_position - caster_position).length();
+    hand_offset.is_finite() && hand_offset <= MAX_HAND_OFFSET
+}
+
+fn find_cooldown(ctx: &ReducerContext, caster: Identity, spell_id: &str) -> Option<SpellCooldown> {
+    ctx.db.spell_cooldown().caster().filter(caster).find(|c| c.spell_id == spell_id)
+}
+
+#[spacetimedb::reducer]
+pub fn cast_spell(ctx: &ReducerContext, spell_id: String, hand_position: Vector3) -> Result<(), String> {
+    let Some(spell) = ctx.db.spell_definition().spell_id().find(spell_id.clone()) else {
+        return Err(format!("Unknown spell '{}'", spell_id));
+    };
+    cast(ctx, hand_position, spell)
+}
+
+pub fn cast(ctx: &ReducerContext, hand_position: Vector3, spell: SpellDefinition) -> Result<(), String> {
+    let caster_identity = ctx.sender;
+    let Some(caster) = ctx.db.player().identity().find(caster_identity) else {
+        spacetimedb::log::warn!("Player {} tried to cast {} but is not active.", caster_identity, spell.spell_id);
+        return Err("Player is not active".to_string());
+    };
+    if !caster.alive {
+        return Err("Cannot cast while dead".to_string());
+    }
+    require_armed(ctx, caster_identity, "fire")?;
+    if is_stunned(ctx, caster_identity) {
+        return Err("Cannot fire while stunned".to_string());
+    }
+    if !hand_within_reach(caster.position, hand_position) {
+        return Err("Hand is too far from the caster".to_string());
+    }
+    require_in_progress(ctx, caster.instance_id)?;
+    require_can_cast(ctx, &caster)?;
+    let is_axe = spell.kind == ProjectileKind::Axe;
+    if is_axe && caster.axe_ammo == 0 {
+        return Err("No axes left, pick one up".to_string());
+    }
+
+    // Class global cooldown, then the spell's own cooldown
+    let stats = class_definition_for(ctx, &caster.character_class);
+    let mana_cost = if is_axe { 0 } else { stats.mana_cost_per_cast + spell.mana_cost };
+    check_cast(&caster, ctx.timestamp, stats.cast_cooldown_secs, mana_cost)?;
+    let cooldown = find_cooldown(ctx, caster_identity, &spell.spell_id);
+    if cooldown.as_ref().is_some_and(|c| c.ready_at > ctx.timestamp) {
+        return Err(format!("{} is on cooldown", spell.spell_id));
+    }
+
+    // Server-wide budget, checked last so rejected casts don't consume it
+    consume_global_spawn_budget(ctx)?;
+
+    // Aim built from the caster's yaw and pitch (apply_client_input), widened by bloom
+    let max_spread = spread_radians(caster.spread_heat);
+    let direction = jitter_direction(caster.forward_vector, max_spread, &mut ctx.rng());
+    spawn_projectile_with_spec(ctx, caster_identity, hand_position, direction, spell.kind, spell.projectile_spec(), stats.projectile_damage_multiplier);
+
+    // Pay for the cast; sustained fire builds bloom; axes use ammo
+    if let Some(mut shooter) = ctx.db.player().identity().find(caster_identity) {
+        shooter.mana -= mana_cost;
+        shooter.last_cast_time = Some(ctx.timestamp);
+        shooter.spread_heat = heat_after_shot(shooter.spread_heat);
+        if is_axe {
+            shooter.axe_ammo -= 1;
+        }
+        ctx.db.player().identity().update(shooter);
+    }
+    if spell.cooldown_secs > 0.0 {
+        let ready_at = ctx.timestamp + TimeDuration::from_micros((spell.cooldown_secs * 1_000_000.0) as i64);
+        match cooldown {
+            Some(mut existing) => {
+                existing.ready_at = ready_at;
+                ctx.db.spell_cooldown().id().update(existing);
+            }
+            None => {
+                ctx.db.spell_cooldown().insert(SpellCooldown { id: 0, caster: caster_identity, spell_id: spell.spell_id.clone(), ready_at });
+            }
+        }
+    }
+    break_stealth(ctx, caster_identity); // casting gives away your position
+
+    spacetimedb::log::info!("Player {} cast {} ({:?}) at {:?}", caster_identity, spell.spell_id, spell.kind, hand_position);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+#[allow(clippy::too_many_arguments)]
+pub fn set_spell_definition(
+    ctx: &ReducerContext,
+    spell_id: String,
+    kind: ProjectileKind,
+    projectile_speed: f32,
+    damage: i32,
+    mana_cost: i32,
+    cooldown_secs: f32,
+    aoe_radius: f32,
+    status_effect: Option<StatusOnHit>,
+    gravity_scale: f32,
+    homing_turn_rate: f32,
+    pierce_count: u32,
+    bounce_count: u32,
+) -> Result<(), String> {
+    require_admin(ctx)?;
+    let spell_id = spell_id.trim().to_string();
+    if spell_id.is_empty() {
+        return Err("Spell id is empty".to_string());
+    }
+    if kind == ProjectileKind::Shard {
+        return Err("Shards are not castable".to_string());
+    }
+    if damage < 0 || mana_cost < 0 {
+        return Err("Damage and mana cost must be non-negative".to_string());
+    }
+    if !(projectile_speed.is_finite() && projectile_speed > 0.0) {
+        return Err("Projectile speed must be positive".to_string());
+    }
+    if ![cooldown_secs, aoe_radius, gravity_scale, homing_turn_rate].iter().all(|v| v.is_finite() && *v >= 0.0) {
+        return Err("Cooldown, AOE radius, gravity scale and turn rate must be non-negative".to_string());
+    }
+    if status_effect.is_some_and(|s| !(s.magnitude.is_finite() && s.duration_secs.is_finite() && s.duration_secs > 0.0)) {
+        return Err("Status effect needs a finite magnitude and a positive duration".to_string());
+    }
+
+    let definition = SpellDefinition { spell_id: spell_id.clone(), kind, projectile_speed, damage, mana_cost, cooldown_secs, aoe_radius, status_effect, gravity_scale, homing_turn_rate, pierce_count, bounce_count };
+    if ctx.db.spell_definition().spell_id().find(spell_id.clone()).is_some() {
+        ctx.db.spell_definition().spell_id().update(definition);
+    } else {
+        ctx.db.spell_definition().insert(definition);
+    }
+    spacetimedb::log::info!("Spell definition '{}' saved", spell_id);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_row_decides_the_explosion_and_keeps_the_kind_flight() {
+        let fireball = SpellDefinition::from_kind("fireball", ProjectileKind::Fireball, 15, 1.5);
+        assert_eq!(fireball.aoe_radius, 4.0);
+        assert_eq!(fireball.projectile_spec().explosion_radius, 4.0);
+
+        let dud = SpellDefinition { aoe_radius: 0.0, damage: 3, ..fireball };
+        let spec = dud.projectile_spec();
+        assert_eq!(spec.explosion_radius, 0.0);
+        assert_eq!(spec.damage, 3);
+
+        let bouncer = SpellDefinition { aoe_radius: 2.0, ..SpellDefinition::from_kind("bouncer", ProjectileKind::Bouncer, 5, 0.0) };
+        let spec = bouncer.projectile_spec();
+        assert_eq!(spec.explosion_radius, 2.0);
+        assert_eq!(spec.bounce_count, 3, "still bounces before it explodes");
+    }
+
+    #[test]
+    fn the_row_decides_whether_a_spell_is_lobbed() {
+        let grenade = SpellDefinition::from_kind("grenade", ProjectileKind::Grenade, 0, 0.0);
+        assert_eq!(grenade.gravity_scale, 1.0);
+        assert_eq!(grenade.projectile_spec().gravity_scale, 1.0);
+
+        let straight = SpellDefinition { gravity_scale: 0.0, ..grenade.clone() };
+        assert_eq!(straight.projectile_spec().gravity_scale, 0.0);
+        let floaty = SpellDefinition { gravity_scale: 0.25, ..SpellDefinition::from_kind("bolt", ProjectileKind::Bolt, 0, 0.0) };
+        assert_eq!(floaty.projectile_spec().gravity_scale, 0.25);
+    }
+
+    #[test]
+    fn casts_must_start_at_the_casters_hand() {
+        let feet = Vector3 { x: 10.0, y: 0.0, z: 10.0 };
+        assert!(hand_within_reach(feet, Vector3 { x: 10.5, y: 1.5, z: 10.0 }));
+        assert!(!hand_within_reach(feet, Vector3 { x: 30.0, y: 1.5, z: 10.0 }));
+        assert!(!hand_within_reach(feet, Vector3 { x: f32::NAN, y: 1.5, z: 10.0 }));
+    }
+}
@@ -0,0 +1,276 @@
+/*
+ * Vibe Coding Starter Pack: 3D Multiplayer - status_effects.rs
+ *
+ * Timed effects attached to players (Disarm, Slow, Stun, Burn, HealOverTime).
+ *
+ * Key components:
+ *    - StatusEffect: Public table, one row per (target, kind). Effects never stack: re-applying
+ *      refreshes the duration and keeps the stronger magnitude
+ *    - apply_status_effect: Entry point for abilities/projectiles that inflict effects. Like
+ *      damage, effects only land while a match is in progress, never across a no_pvp zone or
+ *      duel boundary, and never on a duelist from the environment
+ *    - require_armed: Rejects firing while disarmed (check_armed is the pure rule)
+ *    - is_stunned: Stun blocks movement, jumping, casting and melee
+ *    - movement_speed_multiplier: Slow/Stun factor applied by the game_tick movement loop
+ *    - update_status_effects: Per-tick Burn damage and HealOverTime healing (whole points,
+ *      fractions carried in tick_progress), countdown and expiry (called from game_tick)
+ *
+ * Reducers:
+ *    - cast_rejuvenate: Self-cast HealOverTime paid for with mana
+ *    - inflict_status_effect: Admin reducer to apply any effect (testing, scripted encounters)
+ */
+
+use spacetimedb::{Identity, ReducerContext, Table};
+
+use crate::admin::require_admin;
+use crate::arenas::shielded_from_world;
+use crate::combat::{apply_damage, heal_player};
+use crate::common::{
+    StatusEffectKind, MAX_SLOW, MAX_STATUS_EFFECT_SECS, REJUVENATE_HEAL_PER_SEC, REJUVENATE_MANA_COST, REJUVENATE_SECS,
+};
+use crate::matches::{gameplay_enabled, require_in_progress};
+use crate::player;
+use crate::zones::{pvp_blocked, require_can_cast};
+
+#[spacetimedb::table(name = status_effect, public)]
+#[derive(Clone)]
+pub struct StatusEffect {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    #[index(btree)]
+    pub target_identity: Identity,
+    pub kind: StatusEffectKind,
+    pub magnitude: f32,
+    pub remaining_secs: f32,
+    pub source_identity: Option<Identity>,
+    pub tick_progress: f32, // fractional Burn damage / HealOverTime healing not yet applied
+}
+
+pub fn find_status_effect(ctx: &ReducerContext, target: Identity, kind: StatusEffectKind) -> Option<StatusEffect> {
+    ctx.db.status_effect().target_identity().filter(target).find(|e| e.kind == kind)
+}
+
+// Err while `target` is disarmed; `action` names what was attempted in the message
+pub fn require_armed(ctx: &ReducerContext, target: Identity, action: &str) -> Result<(), String> {
+    let effects: Vec<StatusEffect> = ctx.db.status_effect().target_identity().filter(target).collect();
+    check_armed(&effects, action)
+}
+
+fn check_armed(effects: &[StatusEffect], action: &str) -> Result<(), String> {
+    if active(effects, StatusEffectKind::Disarm).is_some() {
+        return Err(format!("Cannot {} while disarmed", action));
+    }
+    Ok(())
+}
+
+// Apply or refresh an effect. Re-applying keeps the longer of the two durations
+// and the stronger magnitude rather than stacking a second row.
+pub fn apply_status_effect(
+    ctx: &ReducerContext,
+    target: Identity,
+    kind: StatusEffectKind,
+    magnitude: f32,
+    duration_secs: f32,
+    source: Option<Identity>,
+) {
+    let Some(player) = ctx.db.player().identity().find(target) else {
+        return;
+    };
+    // Same gates as combat::apply_damage
+    if !gameplay_enabled(ctx, player.instance_id) || pvp_blocked(ctx, source, target) || shielded_from_world(&player, source) {
+        return;
+    }
+    if let Some(mut existing) = find_status_effect(ctx, target, kind) {
+        refresh(&mut existing, magnitude, duration_secs, source);
+        ctx.db.status_effect().id().update(existing);
+    } else {
+        ctx.db.status_effect().insert(StatusEffect {
+            id: 0, // auto_inc
+            target_identity: target,
+            kind,
+            magnitude,
+            remaining_secs: duration_secs,
+            source_identity: source,
+            tick_progress: 0.0,
+        });
+    }
+    spacetimedb::log::info!("Applied {:?} to {} for {:.1}s", kind, target, duration_secs);
+}
+
+fn refresh(existing: &mut StatusEffect, magnitude: f32, duration_secs: f32, source: Option<Identity>) {
+    existing.remaining_secs = existing.remaining_secs.max(duration_secs);
+    existing.magnitude = existing.magnitude.max(magnitude);
+    existing.source_identity = source;
+}
+
+pub fn is_stunned(ctx: &ReducerContext, target: Identity) -> bool {
+    let effects: Vec<StatusEffect> = ctx.db.status_effect().target_identity().filter(target).collect();
+    active(&effects, StatusEffectKind::Stun).is_some()
+}
+
+// Lookup in one target's effects
+fn active(effects: &[StatusEffect], kind: StatusEffectKind) -> Option<&StatusEffect> {
+    effects.iter().find(|e| e.kind == kind)
+}
+
+// Slow and Stun are the only effects that change movement speed
+fn speed_multiplier_of(effects: &[StatusEffect]) -> f32 {
+    if active(effects, StatusEffectKind::Stun).is_some() {
+        return 0.0;
+    }
+    match active(effects, StatusEffectKind::Slow) {
+        Some(slow) => 1.0 - slow.magnitude.clamp(0.0, MAX_SLOW),
+        None => 1.0,
+    }
+}
+
+// Multiplier for movement speed from active Slow and Stun effects
+pub fn movement_speed_multiplier(ctx: &ReducerContext, target: Identity) -> f32 {
+    let effects: Vec<StatusEffect> = ctx.db.status_effect().target_identity().filter(target).collect();
+    speed_multiplier_of(&effects)
+}
+
+// Returns true once the effect has run out
+fn count_down(effect: &mut StatusEffect, delta_time: f32) -> bool {
+    effect.remaining_secs -= delta_time;
+    effect.remaining_secs <= 0.0
+}
+
+pub fn clear_status_effects(ctx: &ReducerContext, target: Identity) {
+    ctx.db.status_effect().target_identity().delete(target);
+}
+
+pub fn update_status_effects(ctx: &ReducerContext, delta_time: f32) {
+    let effects: Vec<StatusEffect> = ctx.db.status_effect().iter().collect();
+    for mut effect in effects {
+        if matches!(effect.kind, StatusEffectKind::Burn | StatusEffectKind::HealOverTime) {
+            // Only the time actually left on the effect counts towards its last tick
+            effect.tick_progress += effect.magnitude * delta_time.min(effect.remaining_secs.max(0.0));
+            let whole = effect.tick_progress.floor() as i32;
+            if whole > 0 {
+                effect.tick_progress -= whole as f32;
+                if effect.kind == StatusEffectKind::Burn {
+                    apply_damage(ctx, effect.target_identity, whole, effect.source_identity);
+                } else {
+                    heal_player(ctx, effect.target_identity, whole);
+                }
+            }
+            // A lethal burn clears the target's effects (combat::apply_damage)
+            if ctx.db.status_effect().id().find(effect.id).is_none() {
+                continue;
+            }
+        }
+
+        if count_down(&mut effect, delta_time) {
+            spacetimedb::log::info!("{:?} expired on {}", effect.kind, effect.target_identity);
+            ctx.db.status_effect().id().delete(effect.id);
+        } else {
+            ctx.db.status_effect().id().update(effect);
+        }
+    }
+}
+
+#[spacetimedb::reducer]
+pub fn cast_rejuvenate(ctx: &ReducerContext) -> Result<(), String> {
+    let Some(mut caster) = ctx.db.player().identity().find(ctx.sender) else {
+        return Err("Player is not active".to_string());
+    };
+    if !caster.alive {
+        return Err("Cannot cast while dead".to_string());
+    }
+    if is_stunned(ctx, caster.identity) {
+        return Err("Cannot cast while stunned".to_string());
+    }
+    require_in_progress(ctx, caster.instance_id)?;
+    require_can_cast(ctx, &caster)?;
+    if caster.mana < REJUVENATE_MANA_COST {
+        return Err("Not enough mana".to_string());
+    }
+    caster.mana -= REJUVENATE_MANA_COST;
+    let caster_identity = caster.identity;
+    ctx.db.player().identity().update(caster);
+
+    apply_status_effect(ctx, caster_identity, StatusEffectKind::HealOverTime, REJUVENATE_HEAL_PER_SEC, REJUVENATE_SECS, Some(caster_identity));
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn inflict_status_effect(
+    ctx: &ReducerContext,
+    target: Identity,
+    kind: StatusEffectKind,
+    magnitude: f32,
+    duration_secs: f32,
+) -> Result<(), String> {
+    require_admin(ctx)?;
+    if !ctx.db.player().identity().find(target).is_some_and(|p| p.alive) {
+        return Err("Target is not an active, living player".to_string());
+    }
+    if !magnitude.is_finite() || magnitude < 0.0 {
+        return Err("Magnitude must be non-negative".to_string());
+    }
+    if !duration_secs.is_finite() || duration_secs <= 0.0 || duration_secs > MAX_STATUS_EFFECT_SECS {
+        return Err(format!("Duration must be between 0 and {}s", MAX_STATUS_EFFECT_SECS));
+    }
+    apply_status_effect(ctx, target, kind, magnitude, duration_secs, Some(ctx.sender));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::{FROST_SLOW_AMOUNT, FROST_SLOW_SECS};
+
+    fn effect(kind: StatusEffectKind, magnitude: f32, remaining_secs: f32) -> StatusEffect {
+        StatusEffect { id: 1, target_identity: Identity::from_byte_array([1; 32]), kind, magnitude, remaining_secs, source_identity: None, tick_progress: 0.0 }
+    }
+
+    // What update_status_effects does to one target over `ticks` ticks
+    fn run(effects: &mut Vec<StatusEffect>, ticks: u32, delta_time: f32) {
+        for _ in 0..ticks {
+            effects.retain_mut(|e| !count_down(e, delta_time));
+        }
+    }
+
+    #[test]
+    fn disarm_blocks_firing_until_it_expires_but_not_movement() {
+        let mut effects = vec![effect(StatusEffectKind::Disarm, 1.0, 2.0)];
+        assert_eq!(check_armed(&effects, "fire"), Err("Cannot fire while disarmed".to_string()));
+        assert_eq!(speed_multiplier_of(&effects), 1.0);
+
+        run(&mut effects, 3, 0.5);
+        assert!(check_armed(&effects, "fire").is_err(), "still disarmed just before the end");
+        run(&mut effects, 1, 0.5);
+        assert_eq!(check_armed(&effects, "fire"), Ok(()));
+    }
+
+    #[test]
+    fn a_second_frost_hit_refreshes_the_slow_instead_of_stacking() {
+        let mut effects = vec![effect(StatusEffectKind::Slow, FROST_SLOW_AMOUNT, FROST_SLOW_SECS)];
+        run(&mut effects, 2, FROST_SLOW_SECS / 4.0);
+        refresh(&mut effects[0], FROST_SLOW_AMOUNT, FROST_SLOW_SECS, Some(Identity::from_byte_array([2; 32])));
+
+        assert_eq!(effects.len(), 1);
+        assert_eq!(effects[0].remaining_secs, FROST_SLOW_SECS);
+        assert_eq!(effects[0].magnitude, FROST_SLOW_AMOUNT);
+        assert!((speed_multiplier_of(&effects) - (1.0 - FROST_SLOW_AMOUNT)).abs() < 1e-6);
+        // The refreshed slow lasts a full duration from the second hit
+        run(&mut effects, 3, FROST_SLOW_SECS / 4.0);
+        assert!(active(&effects, StatusEffectKind::Slow).is_some());
+        run(&mut effects, 1, FROST_SLOW_SECS / 4.0);
+        assert!(active(&effects, StatusEffectKind::Slow).is_none());
+    }
+
+    #[test]
+    fn slows_reduce_movement_up_to_the_cap() {
+        assert!((speed_multiplier_of(&[effect(StatusEffectKind::Slow, 0.4, 1.0)]) - 0.6).abs() < 1e-6);
+        assert!((speed_multiplier_of(&[effect(StatusEffectKind::Slow, 5.0, 1.0)]) - (1.0 - MAX_SLOW)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn a_stun_stops_movement_even_when_slowed() {
+        let effects = vec![effect(StatusEffectKind::Slow, 0.4, 1.0), effect(StatusEffectKind::Stun, 1.0, 1.0)];
+        assert_eq!(speed_multiplier_of(&effects), 0.0);
+    }
+}
@@ -0,0 +1,103 @@
+/*
+ * Vibe Coding Starter Pack: 3D Multiplayer - teams.rs
+ *
+ * Team assignment and team swapping.
+ *
+ * Key components:
+ *    - smallest_team: Team a newly registered player joins (ties go to the lower index)
+ *    - team_sizes: Active player count per team
+ *    - change_team: Player-requested swap, limited by GameConfig::team_swap_cooldown_secs and
+ *      GameConfig::team_imbalance_threshold. A swap is rejected if it would leave the teams
+ *      further apart than the threshold, unless it narrows an existing imbalance. Admins bypass both.
+ */
+
+use spacetimedb::{ReducerContext, Table};
+use std::time::Duration;
+
+use crate::admin::is_admin;
+use crate::common::TEAM_COUNT;
+use crate::config::get_game_config;
+use crate::player;
+
+pub fn team_sizes(ctx: &ReducerContext) -> [u32; TEAM_COUNT as usize] {
+    let mut sizes = [0; TEAM_COUNT as usize];
+    for player in ctx.db.player().iter() {
+        if let Some(size) = sizes.get_mut(player.team as usize) {
+            *size += 1;
+        }
+    }
+    sizes
+}
+
+pub fn smallest_team(ctx: &ReducerContext) -> u8 {
+    let sizes = team_sizes(ctx);
+    (0..TEAM_COUNT).min_by_key(|t| sizes[*t as usize]).unwrap_or(0)
+}
+
+fn imbalance(sizes: &[u32]) -> u32 {
+    let max = sizes.iter().copied().max().unwrap_or(0);
+    let min = sizes.iter().copied().min().unwrap_or(0);
+    max - min
+}
+
+fn check_balance(before: [u32; TEAM_COUNT as usize], from: u8, to: u8, threshold: u32) -> Result<(), String> {
+    let mut after = before;
+    after[from as usize] -= 1;
+    after[to as usize] += 1;
+    let new_imbalance = imbalance(&after);
+    if new_imbalance > threshold && new_imbalance >= imbalance(&before) {
+        return Err("Swapping would unbalance the teams".to_string());
+    }
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn change_team(ctx: &ReducerContext, team: u8) -> Result<(), String> {
+    let Some(mut player) = ctx.db.player().identity().find(ctx.sender) else {
+        return Err("Player is not active".to_string());
+    };
+    if team >= TEAM_COUNT {
+        return Err(format!("Team must be between 0 and {}", TEAM_COUNT - 1));
+    }
+    if player.team == team {
+        return Ok(());
+    }
+
+    if !is_admin(ctx, ctx.sender) {
+        let config = get_game_config(ctx);
+        let cooldown = Duration::from_secs_f32(config.team_swap_cooldown_secs.max(0.0));
+        let on_cooldown = player.last_team_change
+            .and_then(|last| ctx.timestamp.duration_since(last))
+            .is_some_and(|elapsed| elapsed < cooldown);
+        if on_cooldown {
+            return Err("Team swap is on cooldown".to_string());
+        }
+
+        check_balance(team_sizes(ctx), player.team, team, config.team_imbalance_threshold)?;
+    }
+
+    spacetimedb::log::info!("Player {} moved from team {} to team {}", player.username, player.team, team);
+    player.team = team;
+    player.last_team_change = Some(ctx.timestamp);
+    ctx.db.player().identity().update(player);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::DEFAULT_TEAM_IMBALANCE_THRESHOLD;
+
+    #[test]
+    fn a_swap_that_makes_three_versus_one_is_rejected() {
+        assert!(check_balance([2, 2], 1, 0, DEFAULT_TEAM_IMBALANCE_THRESHOLD).is_err());
+    }
+
+    #[test]
+    fn swaps_that_keep_or_restore_balance_are_allowed() {
+        assert!(check_balance([3, 1], 0, 1, DEFAULT_TEAM_IMBALANCE_THRESHOLD).is_ok());
+        assert!(check_balance([2, 1], 0, 1, DEFAULT_TEAM_IMBALANCE_THRESHOLD).is_ok());
+        // Already far apart: narrowing the gap is allowed even if it stays over the threshold
+        assert!(check_balance([5, 1], 0, 1, DEFAULT_TEAM_IMBALANCE_THRESHOLD).is_ok());
+    }
+}
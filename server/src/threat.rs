@@ -0,0 +1,163 @@
+/*
+ * Vibe Coding Starter Pack: 3D Multiplayer - threat.rs
+ *
+ * Threat tables deciding which player each NPC goes after (npcs::update_npcs).
+ *
+ * Key components:
+ *    - Threat: Public table, one row per (NPC, player) with the threat that player has built
+ *      up: THREAT_PER_DAMAGE per point of damage dealt (npcs::damage_npc) plus taunts
+ *    - select_target: A player's score is their stored threat plus a proximity bonus of up to
+ *      THREAT_PROXIMITY_MAX that fades out at NPC_AGGRO_RANGE. Players without threat are only
+ *      noticed inside NPC_AGGRO_RANGE, players with threat are chased up to NPC_LEASH_RANGE.
+ *      The NPC keeps its current target until someone else's score beats it by
+ *      THREAT_SWITCH_RATIO, so it doesn't flip-flop between players with similar threat
+ *    - Rows of players who died, left the instance or broke the leash are dropped; an NPC with
+ *      nobody left to chase forgets its whole table (clear_threat, also called when it dies)
+ *    - taunt_nearby: Used by the taunt ability (abilities.rs). Every NPC within TAUNT_RADIUS
+ *      sets the taunter's threat to THREAT_TAUNT_BONUS above its current top and turns on them
+ */
+
+use spacetimedb::{Identity, ReducerContext, Table};
+
+use crate::common::{NPC_AGGRO_RANGE, NPC_LEASH_RANGE, TAUNT_RADIUS, THREAT_PROXIMITY_MAX, THREAT_SWITCH_RATIO, THREAT_TAUNT_BONUS};
+use crate::npcs::{horizontal_distance, npc, Npc};
+use crate::PlayerData;
+
+#[spacetimedb::table(name = threat, public)]
+#[derive(Clone)]
+pub struct Threat {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    #[index(btree)]
+    pub npc_id: u64,
+    pub identity: Identity,
+    pub amount: f32,
+}
+
+pub fn add_threat(ctx: &ReducerContext, npc_id: u64, identity: Identity, amount: f32) {
+    match ctx.db.threat().npc_id().filter(npc_id).find(|t| t.identity == identity) {
+        Some(mut entry) => {
+            entry.amount += amount;
+            ctx.db.threat().id().update(entry);
+        }
+        None => {
+            ctx.db.threat().insert(Threat { id: 0, npc_id, identity, amount });
+        }
+    }
+}
+
+pub fn clear_threat(ctx: &ReducerContext, npc_id: u64) {
+    ctx.db.threat().npc_id().delete(npc_id);
+}
+
+// Players without threat are only noticed inside the aggro range, players with threat are chased to the leash
+fn threat_score(npc: &Npc, player: &PlayerData, entries: &[Threat]) -> Option<f32> {
+    if player.instance_id != npc.instance_id {
+        return None;
+    }
+    let distance = horizontal_distance(player.position, npc.position);
+    let proximity = THREAT_PROXIMITY_MAX * (1.0 - distance / NPC_AGGRO_RANGE).max(0.0);
+    match entries.iter().find(|t| t.identity == player.identity) {
+        Some(entry) if distance <= NPC_LEASH_RANGE => Some(entry.amount + proximity),
+        None if distance <= NPC_AGGRO_RANGE => Some(proximity),
+        _ => None,
+    }
+}
+
+fn choose_target<'a>(npc: &Npc, players: &'a [PlayerData], entries: &[Threat]) -> Option<&'a PlayerData> {
+    let (best, best_score) = players
+        .iter()
+        .filter_map(|p| threat_score(npc, p, entries).map(|s| (p, s)))
+        .max_by(|a, b| a.1.total_cmp(&b.1))?;
+    let current = npc.target.and_then(|id| players.iter().find(|p| p.identity == id));
+    if let Some(current) = current {
+        if threat_score(npc, current, entries).is_some_and(|s| best_score <= s * THREAT_SWITCH_RATIO) {
+            return Some(current);
+        }
+    }
+    Some(best)
+}
+
+// `players` are the living players; the NPC's current target is `npc.target`
+pub fn select_target<'a>(ctx: &ReducerContext, npc: &Npc, players: &'a [PlayerData]) -> Option<&'a PlayerData> {
+    let entries: Vec<Threat> = ctx.db.threat().npc_id().filter(npc.id).collect();
+    for entry in &entries {
+        if !players.iter().any(|p| p.identity == entry.identity && threat_score(npc, p, &entries).is_some()) {
+            ctx.db.threat().id().delete(entry.id);
+        }
+    }
+    choose_target(npc, players, &entries)
+}
+
+// Returns how many NPCs were taunted
+pub fn taunt_nearby(ctx: &ReducerContext, taunter: &PlayerData) -> usize {
+    let mut taunted = 0;
+    for mut npc in ctx.db.npc().iter() {
+        if npc.instance_id != taunter.instance_id || horizontal_distance(npc.position, taunter.position) > TAUNT_RADIUS {
+            continue;
+        }
+        let top = ctx.db.threat().npc_id().filter(npc.id).map(|t| t.amount).fold(0.0, f32::max);
+        let own = ctx.db.threat().npc_id().filter(npc.id).find(|t| t.identity == taunter.identity).map_or(0.0, |t| t.amount);
+        add_threat(ctx, npc.id, taunter.identity, top + THREAT_TAUNT_BONUS - own);
+        npc.target = Some(taunter.identity);
+        npc.path.clear();
+        ctx.db.npc().id().update(npc);
+        taunted += 1;
+    }
+    taunted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::{Vector3, LOBBY_INSTANCE_ID};
+    use crate::npcs::{NpcState, NpcType};
+    use crate::test_player;
+
+    fn player_at(id: u8, x: f32) -> PlayerData {
+        let mut player = test_player(id);
+        player.position = Vector3 { x, y: 0.0, z: 0.0 };
+        player
+    }
+
+    fn goblin(target: Option<Identity>) -> Npc {
+        Npc { id: 1, npc_type: NpcType::Goblin, position: Vector3::default(), yaw: 0.0, health: 1, max_health: 1, state: NpcState::Idle, target, attack_timer: 0.0, spawner_id: 1, path: Vec::new(), repath_timer: 0.0, instance_id: LOBBY_INSTANCE_ID }
+    }
+
+    fn threat(identity: Identity, amount: f32) -> Threat {
+        Threat { id: 0, npc_id: 1, identity, amount }
+    }
+
+    #[test]
+    fn npcs_notice_players_in_aggro_range_and_chase_players_with_threat_to_the_leash() {
+        let near = player_at(1, 5.0);
+        let chased = player_at(2, NPC_AGGRO_RANGE + 2.0); // out of aggro range, inside the leash
+        let players = vec![near.clone(), chased.clone()];
+
+        assert_eq!(choose_target(&goblin(None), &players, &[]).map(|p| p.identity), Some(near.identity));
+        let entries = [threat(chased.identity, 50.0)];
+        assert_eq!(choose_target(&goblin(None), &players, &entries).map(|p| p.identity), Some(chased.identity));
+
+        let fled = vec![player_at(2, NPC_LEASH_RANGE + 1.0)];
+        assert!(choose_target(&goblin(Some(chased.identity)), &fled, &entries).is_none());
+
+        let elsewhere = vec![PlayerData { instance_id: LOBBY_INSTANCE_ID + 1, ..near.clone() }];
+        assert!(choose_target(&goblin(None), &elsewhere, &[]).is_none(), "players in other instances are ignored");
+        assert!(choose_target(&goblin(None), &[player_at(3, NPC_AGGRO_RANGE + 1.0)], &[]).is_none());
+    }
+
+    #[test]
+    fn npcs_only_switch_targets_when_a_rival_clearly_out_threats_the_current_one() {
+        let current = player_at(1, NPC_AGGRO_RANGE);
+        let rival = player_at(2, NPC_AGGRO_RANGE);
+        let players = vec![current.clone(), rival.clone()];
+        let npc = goblin(Some(current.identity));
+
+        let close = [threat(current.identity, 100.0), threat(rival.identity, 100.0 * THREAT_SWITCH_RATIO)];
+        assert_eq!(choose_target(&npc, &players, &close).map(|p| p.identity), Some(current.identity));
+
+        let clear = [threat(current.identity, 100.0), threat(rival.identity, 100.0 * THREAT_SWITCH_RATIO + 1.0)];
+        assert_eq!(choose_target(&npc, &players, &clear).map(|p| p.identity), Some(rival.identity));
+    }
+}
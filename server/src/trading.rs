@@ -0,0 +1,269 @@
+/*
+ * Vibe Coding Starter Pack: 3D Multiplayer - trading.rs
+ *
+ * Player-to-player trades of items and gold.
+ *
+ * Key components:
+ *    - TradeSession: Public table of running trades. Each side has a TradeOffer (inventory
+ *      stacks and quantities plus gold) and a confirmed flag; a player is in at most one trade
+ *    - Two-phase confirmation (TradePhase):
+ *        - Open: Both sides edit their offers with add_trade_item / set_trade_gold. Any change
+ *          clears both confirmations. Once both confirm_trade, the session moves to Locked
+ *        - Locked: Offers can no longer change. Both confirm_trade again to complete
+ *    - Completion moves everything in one transaction: offers are re-checked against the
+ *      inventories, and if anything is missing or the receiver runs out of inventory space the
+ *      whole reducer fails and nothing changes
+ *    - Both players must stay within TRADE_RANGE of each other to start and confirm
+ *    - cancel_trade: Reducer for either side; logging out cancels too (cancel_trades_for)
+ */
+
+use spacetimedb::{Identity, ReducerContext, SpacetimeType, Table, Timestamp};
+
+use crate::common::TRADE_RANGE;
+use crate::inventory::{add_item_to_inventory, player_inventory, recompute_equipment_stats, PlayerInventory};
+use crate::{player, PlayerData};
+
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq)]
+pub enum TradePhase {
+    Open,
+    Locked,
+}
+
+#[derive(SpacetimeType, Clone, Debug, PartialEq)]
+pub struct TradeItem {
+    pub inventory_id: u64,
+    pub quantity: u32,
+}
+
+#[derive(SpacetimeType, Clone, Debug, PartialEq, Default)]
+pub struct TradeOffer {
+    pub items: Vec<TradeItem>,
+    pub gold: u64,
+}
+
+#[spacetimedb::table(name = trade_session, public)]
+#[derive(Clone)]
+pub struct TradeSession {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    #[index(btree)]
+    pub initiator: Identity,
+    #[index(btree)]
+    pub partner: Identity,
+    pub initiator_offer: TradeOffer,
+    pub partner_offer: TradeOffer,
+    pub initiator_confirmed: bool,
+    pub partner_confirmed: bool,
+    pub phase: TradePhase,
+    pub created_at: Timestamp,
+}
+
+impl TradeSession {
+    fn offer_mut(&mut self, identity: Identity) -> &mut TradeOffer {
+        if identity == self.initiator { &mut self.initiator_offer } else { &mut self.partner_offer }
+    }
+
+    fn other(&self, identity: Identity) -> Identity {
+        if identity == self.initiator { self.partner } else { self.initiator }
+    }
+}
+
+fn session_of(ctx: &ReducerContext, identity: Identity) -> Option<TradeSession> {
+    ctx.db.trade_session().initiator().filter(identity).next()
+        .or_else(|| ctx.db.trade_session().partner().filter(identity).next())
+}
+
+fn my_session(ctx: &ReducerContext) -> Result<TradeSession, String> {
+    session_of(ctx, ctx.sender).ok_or_else(|| "You are not trading".to_string())
+}
+
+fn active_player(ctx: &ReducerContext, identity: Identity) -> Result<PlayerData, String> {
+    ctx.db.player().identity().find(identity).ok_or_else(|| "Player is not active".to_string())
+}
+
+fn require_in_range(ctx: &ReducerContext, a: Identity, b: Identity) -> Result<(), String> {
+    let a = active_player(ctx, a)?;
+    let b = active_player(ctx, b)?;
+    if a.instance_id != b.instance_id || (a.position - b.position).length() > TRADE_RANGE {
+        return Err(format!("You must be within {}m to trade", TRADE_RANGE));
+    }
+    Ok(())
+}
+
+// Any change to an offer needs both sides to look again
+fn edit_offer(ctx: &ReducerContext, edit: impl FnOnce(&mut TradeOffer)) -> Result<(), String> {
+    let mut session = my_session(ctx)?;
+    if session.phase == TradePhase::Locked {
+        return Err("The trade is locked; cancel it to change offers".to_string());
+    }
+    edit(session.offer_mut(ctx.sender));
+    session.initiator_confirmed = false;
+    session.partner_confirmed = false;
+    ctx.db.trade_session().id().update(session);
+    Ok(())
+}
+
+pub fn cancel_trades_for(ctx: &ReducerContext, identity: Identity) {
+    if let Some(session) = session_of(ctx, identity) {
+        ctx.db.trade_session().id().delete(session.id);
+    }
+}
+
+// The stack behind an offered item, as long as `from` still holds enough of it
+fn offered_stack(stack: Option<PlayerInventory>, item: &TradeItem, from: Identity) -> Result<PlayerInventory, String> {
+    let Some(stack) = stack.filter(|s| s.owner == from) else {
+        return Err("An offered item is no longer in its owner's inventory".to_string());
+    };
+    if stack.quantity < item.quantity {
+        return Err(format!("Not enough {} left to trade", stack.item_id));
+    }
+    Ok(stack)
+}
+
+// Moves `offer` from `from` to `to`; any error aborts the whole trade
+fn transfer(ctx: &ReducerContext, offer: &TradeOffer, from: Identity, to: Identity) -> Result<(), String> {
+    let mut touched_equipment = false;
+    for item in &offer.items {
+        let mut stack = offered_stack(ctx.db.player_inventory().id().find(item.inventory_id), item, from)?;
+        touched_equipment |= stack.equipped;
+        let item_id = stack.item_id.clone();
+        if stack.quantity == item.quantity {
+            ctx.db.player_inventory().id().delete(stack.id);
+        } else {
+            stack.quantity -= item.quantity;
+            ctx.db.player_inventory().id().update(stack);
+        }
+        if add_item_to_inventory(ctx, to, &item_id, item.quantity)? > 0 {
+            return Err("Not enough inventory space to complete the trade".to_string());
+        }
+    }
+    if touched_equipment {
+        recompute_equipment_stats(ctx, from);
+    }
+    if offer.gold > 0 {
+        let mut giver = active_player(ctx, from)?;
+        if giver.gold < offer.gold {
+            return Err("Not enough gold to complete the trade".to_string());
+        }
+        giver.gold -= offer.gold;
+        ctx.db.player().identity().update(giver);
+        let mut receiver = active_player(ctx, to)?;
+        receiver.gold += offer.gold;
+        ctx.db.player().identity().update(receiver);
+    }
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn initiate_trade(ctx: &ReducerContext, target: Identity) -> Result<(), String> {
+    if target == ctx.sender {
+        return Err("You can't trade with yourself".to_string());
+    }
+    require_in_range(ctx, ctx.sender, target)?;
+    if session_of(ctx, ctx.sender).is_some() {
+        return Err("You are already trading".to_string());
+    }
+    if session_of(ctx, target).is_some() {
+        return Err("That player is already trading".to_string());
+    }
+    ctx.db.trade_session().insert(TradeSession {
+        id: 0, // auto_inc
+        initiator: ctx.sender,
+        partner: target,
+        initiator_offer: TradeOffer::default(),
+        partner_offer: TradeOffer::default(),
+        initiator_confirmed: false,
+        partner_confirmed: false,
+        phase: TradePhase::Open,
+        created_at: ctx.timestamp,
+    });
+    Ok(())
+}
+
+// quantity 0 takes the stack back out of the offer
+#[spacetimedb::reducer]
+pub fn add_trade_item(ctx: &ReducerContext, inventory_id: u64, quantity: u32) -> Result<(), String> {
+    let Some(stack) = ctx.db.player_inventory().id().find(inventory_id).filter(|s| s.owner == ctx.sender) else {
+        return Err("Item not found in your inventory".to_string());
+    };
+    if stack.equipped {
+        return Err("Unequip the item before trading it".to_string());
+    }
+    if quantity > stack.quantity {
+        return Err(format!("You only have {}", stack.quantity));
+    }
+    edit_offer(ctx, |offer| {
+        offer.items.retain(|i| i.inventory_id != inventory_id);
+        if quantity > 0 {
+            offer.items.push(TradeItem { inventory_id, quantity });
+        }
+    })
+}
+
+#[spacetimedb::reducer]
+pub fn set_trade_gold(ctx: &ReducerContext, gold: u64) -> Result<(), String> {
+    let player = active_player(ctx, ctx.sender)?;
+    if gold > player.gold {
+        return Err(format!("You only have {} gold", player.gold));
+    }
+    edit_offer(ctx, |offer| offer.gold = gold)
+}
+
+#[spacetimedb::reducer]
+pub fn confirm_trade(ctx: &ReducerContext) -> Result<(), String> {
+    let mut session = my_session(ctx)?;
+    require_in_range(ctx, session.initiator, session.partner)?;
+    if ctx.sender == session.initiator {
+        session.initiator_confirmed = true;
+    } else {
+        session.partner_confirmed = true;
+    }
+    if !(session.initiator_confirmed && session.partner_confirmed) {
+        ctx.db.trade_session().id().update(session);
+        return Ok(());
+    }
+    match session.phase {
+        TradePhase::Open => {
+            session.phase = TradePhase::Locked;
+            session.initiator_confirmed = false;
+            session.partner_confirmed = false;
+            ctx.db.trade_session().id().update(session);
+        }
+        TradePhase::Locked => {
+            transfer(ctx, &session.initiator_offer, session.initiator, session.partner)?;
+            transfer(ctx, &session.partner_offer, session.partner, session.initiator)?;
+            ctx.db.trade_session().id().delete(session.id);
+            spacetimedb::log::info!("[TRADE] {} and {} completed trade {}", session.initiator, session.partner, session.id);
+        }
+    }
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn cancel_trade(ctx: &ReducerContext) -> Result<(), String> {
+    let session = my_session(ctx)?;
+    ctx.db.trade_session().id().delete(session.id);
+    spacetimedb::log::info!("[TRADE] {} cancelled trade {} with {}", ctx.sender, session.id, session.other(ctx.sender));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn completing_rejects_stacks_that_changed_since_they_were_offered() {
+        let seller = Identity::from_byte_array([1; 32]);
+        let buyer = Identity::from_byte_array([2; 32]);
+        let stack = PlayerInventory { id: 7, owner: seller, item_id: "health_potion".to_string(), quantity: 5, equipped: false };
+        let offered = TradeItem { inventory_id: 7, quantity: 3 };
+        assert!(offered_stack(Some(stack.clone()), &offered, seller).is_ok());
+
+        let used_some = PlayerInventory { quantity: 2, ..stack.clone() };
+        assert!(offered_stack(Some(used_some), &offered, seller).is_err(), "drank potions after offering");
+        let given_away = PlayerInventory { owner: buyer, ..stack };
+        assert!(offered_stack(Some(given_away), &offered, seller).is_err());
+        assert!(offered_stack(None, &offered, seller).is_err(), "stack consumed or dropped");
+    }
+}
@@ -0,0 +1,397 @@
+/*
+ * Vibe Coding Starter Pack: 3D Multiplayer - vehicles.rs
+ *
+ * Drivable vehicles with a driver seat and passenger seats.
+ *
+ * Key components:
+ *    - VehicleType / VehicleSpec: Handling per vehicle (top speed, acceleration, turn rate,
+ *      size and number of seats)
+ *    - Vehicle: Public table. `driver` steers, `passengers` holds one entry per passenger seat
+ *      (None = free). throttle / steer / brake are the driver's latest drive_input
+ *    - PlayerData::vehicle_id: The vehicle a player is seated in. game_tick skips movement for
+ *      seated players; update_vehicles carries them along instead
+ *    - update_vehicles: Per-tick physics (called from game_tick). Throttle accelerates along the
+ *      vehicle's facing, steering only turns a rolling vehicle, drag and the brake slow it down,
+ *      and static colliders stop it (shedding speed). A vehicle without a driver brakes to a halt.
+ *      Riders who died, logged out or changed instance lose their seat
+ *    - Collision damage: A driven vehicle moving faster than VEHICLE_MIN_IMPACT_SPEED hurts and
+ *      knocks back players on foot it runs into, scaled by speed and credited to the driver
+ *      (no_pvp zones apply), at most once per VEHICLE_IMPACT_COOLDOWN_SECS
+ *    - enter_vehicle / exit_vehicle: Reducers. Entering takes the driver seat if it is free,
+ *      otherwise the first free passenger seat, and dismounts the player (mounts.rs)
+ *    - drive_input: Driver-only reducer for throttle (-1 reverse to 1 forward), steer
+ *      (-1 left to 1 right) and brake
+ *    - leave_vehicle: Frees a player's seat and puts them down beside the vehicle; called from
+ *      log_out_player and instances::move_to_instance
+ *    - add_vehicle / remove_vehicle: Admin reducers
+ */
+
+use spacetimedb::{Identity, ReducerContext, SpacetimeType, Table, Timestamp};
+
+use crate::admin::{record_admin_action, require_admin};
+use crate::combat::{apply_damage, apply_knockback};
+use crate::common::{
+    Vector3, LOBBY_INSTANCE_ID, PLAYER_HEIGHT, VEHICLE_BRAKE_DECELERATION, VEHICLE_DRAG, VEHICLE_ENTER_RANGE, VEHICLE_HEIGHT, VEHICLE_IMPACT_COOLDOWN_SECS,
+    VEHICLE_IMPACT_DAMAGE_PER_SPEED, VEHICLE_IMPACT_SPEED_RETAINED, VEHICLE_MIN_IMPACT_SPEED, VEHICLE_REVERSE_SPEED_FRACTION,
+    VEHICLE_WALL_SPEED_RETAINED,
+};
+use crate::environment::{load_static_colliders, resolve_player_colliders, StaticCollider};
+use crate::instances::instance;
+use crate::mounts::force_dismount;
+use crate::npcs::horizontal_distance;
+use crate::zones::pvp_blocked;
+use crate::{player, PlayerData};
+
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq)]
+pub enum VehicleType {
+    Buggy, // quick two-seater
+    Wagon, // slow, carries a group
+}
+
+pub struct VehicleSpec {
+    pub max_speed: f32,
+    pub acceleration: f32,
+    pub turn_rate: f32, // radians per second at full speed
+    pub hit_radius: f32,
+    pub passenger_seats: usize,
+}
+
+impl VehicleSpec {
+    pub fn for_type(vehicle_type: VehicleType) -> Self {
+        match vehicle_type {
+            VehicleType::Buggy => VehicleSpec { max_speed: 18.0, acceleration: 12.0, turn_rate: 2.2, hit_radius: 1.4, passenger_seats: 1 },
+            VehicleType::Wagon => VehicleSpec { max_speed: 10.0, acceleration: 6.0, turn_rate: 1.5, hit_radius: 1.8, passenger_seats: 3 },
+        }
+    }
+}
+
+#[spacetimedb::table(name = vehicle, public)]
+#[derive(Clone)]
+pub struct Vehicle {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub vehicle_type: VehicleType,
+    pub position: Vector3,
+    pub rotation: f32, // yaw, same convention as players: forward is -Z
+    pub velocity: Vector3,
+    pub driver: Option<Identity>,
+    pub passengers: Vec<Option<Identity>>,
+    pub throttle: f32,
+    pub steer: f32,
+    pub brake: bool,
+    pub last_impact_at: Option<Timestamp>,
+    pub instance_id: u64,
+}
+
+impl Vehicle {
+    fn riders(&self) -> Vec<Identity> {
+        self.driver.into_iter().chain(self.passengers.iter().flatten().copied()).collect()
+    }
+
+    fn free_seat(&mut self, identity: Identity) {
+        if self.driver == Some(identity) {
+            self.driver = None;
+            self.throttle = 0.0;
+            self.steer = 0.0;
+            self.brake = false;
+        }
+        for seat in self.passengers.iter_mut().filter(|s| **s == Some(identity)) {
+            *seat = None;
+        }
+    }
+}
+
+fn forward(rotation: f32) -> Vector3 {
+    Vector3 { x: -rotation.sin(), y: 0.0, z: -rotation.cos() }
+}
+
+fn insert_vehicle(ctx: &ReducerContext, vehicle_type: VehicleType, position: Vector3, rotation: f32, instance_id: u64) -> Vehicle {
+    ctx.db.vehicle().insert(Vehicle {
+        id: 0, // auto_inc
+        vehicle_type,
+        position,
+        rotation,
+        velocity: Vector3::default(),
+        driver: None,
+        passengers: vec![None; VehicleSpec::for_type(vehicle_type).passenger_seats],
+        throttle: 0.0,
+        steer: 0.0,
+        brake: false,
+        last_impact_at: None,
+        instance_id,
+    })
+}
+
+pub fn seed_vehicles(ctx: &ReducerContext) {
+    if ctx.db.vehicle().count() > 0 {
+        return;
+    }
+    insert_vehicle(ctx, VehicleType::Buggy, Vector3 { x: 12.0, y: 1.0, z: 6.0 }, 0.0, LOBBY_INSTANCE_ID);
+    insert_vehicle(ctx, VehicleType::Wagon, Vector3 { x: -12.0, y: 1.0, z: 6.0 }, 0.0, LOBBY_INSTANCE_ID);
+    spacetimedb::log::info!("[INIT] Seeded vehicles.");
+}
+
+// Returns true if the player was seated. Writes the player (and their vehicle) back
+pub fn leave_vehicle(ctx: &ReducerContext, identity: Identity) -> bool {
+    let Some(mut player) = ctx.db.player().identity().find(identity) else {
+        return false;
+    };
+    let Some(vehicle_id) = player.vehicle_id.take() else {
+        return false;
+    };
+    if let Some(mut vehicle) = ctx.db.vehicle().id().find(vehicle_id) {
+        vehicle.free_seat(identity);
+        let spec = VehicleSpec::for_type(vehicle.vehicle_type);
+        let right = Vector3 { x: vehicle.rotation.cos(), y: 0.0, z: -vehicle.rotation.sin() };
+        player.position = vehicle.position + right * (spec.hit_radius + player.hit_radius + 0.2);
+        resolve_player_colliders(&load_static_colliders(ctx), player.instance_id, &mut player.position, player.hit_radius, PLAYER_HEIGHT);
+        ctx.db.vehicle().id().update(vehicle);
+    }
+    ctx.db.player().identity().update(player);
+    true
+}
+
+// Seats whose rider is gone, dead, in another instance or no longer points at this vehicle are freed
+fn release_stale_seats(ctx: &ReducerContext, vehicle: &mut Vehicle) {
+    for identity in vehicle.riders() {
+        let stale = match ctx.db.player().identity().find(identity) {
+            Some(mut rider) if rider.vehicle_id == Some(vehicle.id) => {
+                if rider.alive && rider.instance_id == vehicle.instance_id {
+                    false
+                } else {
+                    rider.vehicle_id = None;
+                    ctx.db.player().identity().update(rider);
+                    true
+                }
+            }
+            _ => true,
+        };
+        if stale {
+            vehicle.free_seat(identity);
+        }
+    }
+}
+
+fn drive(vehicle: &mut Vehicle, spec: &VehicleSpec, colliders: &[StaticCollider], delta_time: f32) {
+    let (throttle, steer, brake) = if vehicle.driver.is_some() { (vehicle.throttle, vehicle.steer, vehicle.brake) } else { (0.0, 0.0, true) };
+    let facing = forward(vehicle.rotation);
+    let mut speed = vehicle.velocity.x * facing.x + vehicle.velocity.z * facing.z;
+
+    // Steering needs the wheels rolling, and reversing turns the other way
+    vehicle.rotation -= steer * spec.turn_rate * delta_time * (speed / spec.max_speed).clamp(-1.0, 1.0);
+    speed += throttle * spec.acceleration * delta_time;
+    if brake {
+        let slowed = (speed.abs() - VEHICLE_BRAKE_DECELERATION * delta_time).max(0.0);
+        speed = slowed.copysign(speed);
+    }
+    speed *= (1.0 - VEHICLE_DRAG * delta_time).max(0.0);
+    speed = speed.clamp(-spec.max_speed * VEHICLE_REVERSE_SPEED_FRACTION, spec.max_speed);
+    vehicle.velocity = forward(vehicle.rotation) * speed;
+
+    let intended = vehicle.position + vehicle.velocity * delta_time;
+    let mut position = intended;
+    resolve_player_colliders(colliders, vehicle.instance_id, &mut position, spec.hit_radius, VEHICLE_HEIGHT);
+    if (position - intended).length() > 0.001 {
+        vehicle.velocity = vehicle.velocity * VEHICLE_WALL_SPEED_RETAINED;
+    }
+    vehicle.position = position;
+}
+
+fn ram_players(ctx: &ReducerContext, vehicle: &mut Vehicle, spec: &VehicleSpec) {
+    let Some(driver) = vehicle.driver else {
+        return;
+    };
+    let speed = vehicle.velocity.length();
+    if speed < VEHICLE_MIN_IMPACT_SPEED {
+        return;
+    }
+    let cooling_down = vehicle
+        .last_impact_at
+        .is_some_and(|t| ctx.timestamp.duration_since(t).is_some_and(|d| d.as_secs_f32() < VEHICLE_IMPACT_COOLDOWN_SECS));
+    if cooling_down {
+        return;
+    }
+    let struck: Vec<PlayerData> = ctx
+        .db
+        .player()
+        .iter()
+        .filter(|p| p.alive && p.vehicle_id.is_none() && p.instance_id == vehicle.instance_id)
+        .filter(|p| horizontal_distance(p.position, vehicle.position) <= spec.hit_radius + p.hit_radius)
+        .filter(|p| (p.position.y - vehicle.position.y).abs() <= VEHICLE_HEIGHT)
+        .filter(|p| !pvp_blocked(ctx, Some(driver), p.identity))
+        .collect();
+    if struck.is_empty() {
+        return;
+    }
+    let damage = (speed * VEHICLE_IMPACT_DAMAGE_PER_SPEED).round() as i32;
+    let push = vehicle.velocity.normalize() * speed;
+    for victim in struck {
+        apply_damage(ctx, victim.identity, damage, Some(driver));
+        apply_knockback(ctx, victim.identity, push);
+    }
+    vehicle.last_impact_at = Some(ctx.timestamp);
+    vehicle.velocity = vehicle.velocity * VEHICLE_IMPACT_SPEED_RETAINED;
+}
+
+fn carry_riders(ctx: &ReducerContext, vehicle: &Vehicle) {
+    for identity in vehicle.riders() {
+        if let Some(mut rider) = ctx.db.player().identity().find(identity) {
+            rider.position = vehicle.position;
+            rider.vertical_velocity = 0.0;
+            rider.fall_distance = 0.0;
+            rider.knockback_velocity = Vector3::default();
+            ctx.db.player().identity().update(rider);
+        }
+    }
+}
+
+pub fn update_vehicles(ctx: &ReducerContext, delta_time: f32) {
+    if ctx.db.vehicle().count() == 0 {
+        return;
+    }
+    let colliders = load_static_colliders(ctx);
+    for mut vehicle in ctx.db.vehicle().iter() {
+        let spec = VehicleSpec::for_type(vehicle.vehicle_type);
+        release_stale_seats(ctx, &mut vehicle);
+        drive(&mut vehicle, &spec, &colliders, delta_time);
+        ram_players(ctx, &mut vehicle, &spec);
+        carry_riders(ctx, &vehicle);
+        ctx.db.vehicle().id().update(vehicle);
+    }
+}
+
+#[spacetimedb::reducer]
+pub fn enter_vehicle(ctx: &ReducerContext, vehicle_id: u64) -> Result<(), String> {
+    let Some(mut player) = ctx.db.player().identity().find(ctx.sender) else {
+        return Err("Player is not active".to_string());
+    };
+    if !player.alive {
+        return Err("Cannot enter a vehicle while dead".to_string());
+    }
+    if player.vehicle_id.is_some() {
+        return Err("You are already in a vehicle".to_string());
+    }
+    let Some(mut vehicle) = ctx.db.vehicle().id().find(vehicle_id).filter(|v| v.instance_id == player.instance_id) else {
+        return Err("Vehicle not found".to_string());
+    };
+    let spec = VehicleSpec::for_type(vehicle.vehicle_type);
+    if horizontal_distance(player.position, vehicle.position) > spec.hit_radius + player.hit_radius + VEHICLE_ENTER_RANGE {
+        return Err("Vehicle is out of reach".to_string());
+    }
+    if vehicle.driver.is_none() {
+        vehicle.driver = Some(player.identity);
+    } else if let Some(seat) = vehicle.passengers.iter_mut().find(|s| s.is_none()) {
+        *seat = Some(player.identity);
+    } else {
+        return Err("Vehicle is full".to_string());
+    }
+    force_dismount(&mut player);
+    player.is_crouching = false;
+    player.vehicle_id = Some(vehicle.id);
+    player.position = vehicle.position;
+    spacetimedb::log::info!("Player {} entered vehicle {}", player.username, vehicle.id);
+    ctx.db.player().identity().update(player);
+    ctx.db.vehicle().id().update(vehicle);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn exit_vehicle(ctx: &ReducerContext) -> Result<(), String> {
+    if !leave_vehicle(ctx, ctx.sender) {
+        return Err("You are not in a vehicle".to_string());
+    }
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn drive_input(ctx: &ReducerContext, throttle: f32, steer: f32, brake: bool) -> Result<(), String> {
+    if !throttle.is_finite() || !steer.is_finite() {
+        return Err("Invalid drive input".to_string());
+    }
+    let Some(player) = ctx.db.player().identity().find(ctx.sender) else {
+        return Err("Player is not active".to_string());
+    };
+    let Some(mut vehicle) = player.vehicle_id.and_then(|id| ctx.db.vehicle().id().find(id)).filter(|v| v.driver == Some(ctx.sender)) else {
+        return Err("You are not driving a vehicle".to_string());
+    };
+    vehicle.throttle = throttle.clamp(-1.0, 1.0);
+    vehicle.steer = steer.clamp(-1.0, 1.0);
+    vehicle.brake = brake;
+    ctx.db.vehicle().id().update(vehicle);
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn add_vehicle(ctx: &ReducerContext, vehicle_type: VehicleType, position: Vector3, rotation: f32, instance_id: u64) -> Result<(), String> {
+    require_admin(ctx)?;
+    if ctx.db.instance().id().find(instance_id).is_none() {
+        return Err("Instance not found".to_string());
+    }
+    if !rotation.is_finite() {
+        return Err("Invalid rotation".to_string());
+    }
+    let vehicle = insert_vehicle(ctx, vehicle_type, position, rotation, instance_id);
+    record_admin_action(ctx, "add_vehicle", None, format!("{} {:?} in instance {}", vehicle.id, vehicle_type, instance_id));
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn remove_vehicle(ctx: &ReducerContext, vehicle_id: u64) -> Result<(), String> {
+    require_admin(ctx)?;
+    let Some(vehicle) = ctx.db.vehicle().id().find(vehicle_id) else {
+        return Err("Vehicle not found".to_string());
+    };
+    for identity in vehicle.riders() {
+        leave_vehicle(ctx, identity);
+    }
+    ctx.db.vehicle().id().delete(vehicle_id);
+    record_admin_action(ctx, "remove_vehicle", None, format!("{}", vehicle_id));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn buggy(driver: Option<Identity>, throttle: f32) -> Vehicle {
+        Vehicle {
+            id: 1,
+            vehicle_type: VehicleType::Buggy,
+            position: Vector3::default(),
+            rotation: 0.0,
+            velocity: Vector3::default(),
+            driver,
+            passengers: vec![None],
+            throttle,
+            steer: 0.0,
+            brake: false,
+            last_impact_at: None,
+            instance_id: LOBBY_INSTANCE_ID,
+        }
+    }
+
+    #[test]
+    fn vehicles_speed_up_to_their_top_speed_and_brake_without_a_driver() {
+        let spec = VehicleSpec::for_type(VehicleType::Buggy);
+        let mut vehicle = buggy(Some(Identity::from_byte_array([1; 32])), 1.0);
+        for _ in 0..600 {
+            drive(&mut vehicle, &spec, &[], 0.05);
+        }
+        assert!(vehicle.position.z < 0.0 && vehicle.position.x.abs() < 1e-3, "drives forward along -Z");
+        let top = vehicle.velocity.length();
+        assert!(top > spec.max_speed * 0.5 && top <= spec.max_speed + 1e-3, "{top}");
+
+        vehicle.driver = None;
+        for _ in 0..200 {
+            drive(&mut vehicle, &spec, &[], 0.05);
+        }
+        assert_eq!(vehicle.velocity.length(), 0.0, "an empty vehicle rolls to a stop");
+
+        let mut reversing = buggy(Some(Identity::from_byte_array([1; 32])), -1.0);
+        for _ in 0..600 {
+            drive(&mut reversing, &spec, &[], 0.05);
+        }
+        assert!(reversing.velocity.length() <= spec.max_speed * VEHICLE_REVERSE_SPEED_FRACTION + 1e-3);
+    }
+}
@@ -0,0 +1,202 @@
+/*
+ * Vibe Coding Starter Pack: 3D Multiplayer - visibility.rs
+ *
+ * Stealth and reveal state. The server doesn't hide rows from clients; instead
+ * PlayerData carries `is_stealthed` and `revealed_until`, and clients should hide
+ * stealthed enemies (nameplate, minimap dot) unless they are currently revealed.
+ *
+ * Key components:
+ *    - toggle_stealth: Ability reducer that enters/leaves stealth
+ *    - break_stealth: Firing drops stealth (called from the spawn reducers)
+ *    - reveal_stealthed_near: Used by Scanner projectiles to flag stealthed enemies in range
+ *    - MinimapPing: Per-viewer reveal of another position (e.g. where a near-miss came from).
+ *      Clients subscribe with `SELECT * FROM minimap_ping WHERE viewer = '<identity>'`
+ *    - ping_near_misses: Called from the projectile loop for enemies a shot passes close to
+ *    - prune_minimap_pings: Drops expired pings (called from game_tick)
+ */
+
+use spacetimedb::{Identity, ReducerContext, Table, TimeDuration, Timestamp};
+
+use crate::common::{Vector3, MAX_PLAYER_HIT_RADIUS, NEAR_MISS_PING_SECS, NEAR_MISS_RADIUS, REVEAL_DURATION_SECS};
+use crate::spatial::SpatialGrid;
+use crate::{player, PlayerData};
+
+#[spacetimedb::table(name = minimap_ping, public)]
+#[derive(Clone)]
+pub struct MinimapPing {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    #[index(btree)]
+    pub viewer: Identity,
+    pub revealed: Identity, // who is shown (the shooter)
+    pub position: Vector3,
+    pub expires_at: Timestamp,
+}
+
+fn secs_from_now(ctx: &ReducerContext, secs: f32) -> Timestamp {
+    ctx.timestamp + TimeDuration::from_micros((secs * 1_000_000.0) as i64)
+}
+
+#[spacetimedb::reducer]
+pub fn toggle_stealth(ctx: &ReducerContext) -> Result<(), String> {
+    let Some(mut player) = ctx.db.player().identity().find(ctx.sender) else {
+        return Err("Player is not active".to_string());
+    };
+    if !player.alive {
+        return Err("Cannot stealth while dead".to_string());
+    }
+    player.is_stealthed = !player.is_stealthed;
+    spacetimedb::log::info!("Player {} stealth: {}", player.username, player.is_stealthed);
+    ctx.db.player().identity().update(player);
+    Ok(())
+}
+
+pub fn break_stealth(ctx: &ReducerContext, identity: Identity) {
+    if let Some(mut player) = ctx.db.player().identity().find(identity) {
+        if player.is_stealthed {
+            player.is_stealthed = false;
+            ctx.db.player().identity().update(player);
+        }
+    }
+}
+
+// Flag every stealthed enemy of `owner` within `radius` of `center` as revealed. Returns how many.
+pub fn reveal_stealthed_near(ctx: &ReducerContext, instance_id: u64, center: Vector3, radius: f32, owner: Identity) -> usize {
+    let reveal_until = secs_from_now(ctx, REVEAL_DURATION_SECS);
+    let mut revealed = 0;
+    for mut target in ctx.db.player().iter() {
+        if target.instance_id != instance_id || !reveal(&mut target, center, radius, owner, reveal_until) {
+            continue;
+        }
+        spacetimedb::log::info!("Player {} revealed by scanner of {}", target.username, owner);
+        ctx.db.player().identity().update(target);
+        revealed += 1;
+    }
+    revealed
+}
+
+// Returns false if the target isn't a stealthed enemy in range, or is already revealed for longer
+fn reveal(target: &mut PlayerData, center: Vector3, radius: f32, owner: Identity, reveal_until: Timestamp) -> bool {
+    if !target.alive || !target.is_stealthed || target.identity == owner {
+        return false;
+    }
+    if (target.position - center).length() > radius + target.hit_radius {
+        return false;
+    }
+    if target.revealed_until.is_some_and(|until| until >= reveal_until) {
+        return false;
+    }
+    target.revealed_until = Some(reveal_until);
+    true
+}
+
+// Show `shooter` on the minimap of every enemy a projectile at `position` just missed.
+// `origin` is used when the shooter isn't a player (e.g. bots). Re-pinging refreshes the
+// existing row instead of adding another.
+pub fn ping_near_misses(ctx: &ReducerContext, grid: &SpatialGrid, instance_id: u64, position: Vector3, shooter: Identity, origin: Vector3, exclude: Option<Identity>) {
+    let shooter_player = ctx.db.player().identity().find(shooter);
+    let shooter_pos = shooter_player.as_ref().map_or(origin, |s| s.position);
+    let shooter_team = shooter_player.as_ref().map(|s| s.team);
+
+    for viewer in grid.query(instance_id, position, NEAR_MISS_RADIUS + MAX_PLAYER_HIT_RADIUS) {
+        if !near_missed(viewer, position, shooter, shooter_team, exclude) {
+            continue;
+        }
+
+        let expires_at = secs_from_now(ctx, NEAR_MISS_PING_SECS);
+        let existing = ctx.db.minimap_ping().viewer().filter(viewer.identity).find(|p| p.revealed == shooter);
+        if let Some(mut ping) = existing {
+            ping.position = shooter_pos;
+            ping.expires_at = expires_at;
+            ctx.db.minimap_ping().id().update(ping);
+        } else {
+            ctx.db.minimap_ping().insert(MinimapPing {
+                id: 0, // auto_inc
+                viewer: viewer.identity,
+                revealed: shooter,
+                position: shooter_pos,
+                expires_at,
+            });
+        }
+    }
+}
+
+// An enemy (not the one it hit) within NEAR_MISS_RADIUS of the projectile
+fn near_missed(viewer: &PlayerData, position: Vector3, shooter: Identity, shooter_team: Option<u8>, exclude: Option<Identity>) -> bool {
+    if viewer.identity == shooter || Some(viewer.identity) == exclude {
+        return false;
+    }
+    if shooter_team == Some(viewer.team) {
+        return false; // friendly fire doesn't give anything away
+    }
+    (viewer.position - position).length() <= NEAR_MISS_RADIUS + viewer.hit_radius
+}
+
+fn ping_expired(ping: &MinimapPing, now: Timestamp) -> bool {
+    ping.expires_at <= now
+}
+
+pub fn prune_minimap_pings(ctx: &ReducerContext) {
+    for ping in ctx.db.minimap_ping().iter() {
+        if ping_expired(&ping, ctx.timestamp) {
+            ctx.db.minimap_ping().id().delete(ping.id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::SCANNER_REVEAL_RADIUS;
+    use crate::test_player;
+
+    fn stealthed_at(id: u8, x: f32) -> PlayerData {
+        PlayerData { is_stealthed: true, position: Vector3 { x, y: 0.0, z: 0.0 }, ..test_player(id) }
+    }
+
+    #[test]
+    fn a_near_miss_briefly_shows_the_shooter_to_the_player_it_missed() {
+        let shooter = PlayerData { team: 0, ..test_player(1) };
+        let missed = PlayerData { team: 1, position: Vector3 { x: NEAR_MISS_RADIUS * 0.5, y: 0.0, z: 0.0 }, ..test_player(2) };
+        let teammate = PlayerData { team: 0, ..missed.clone() };
+        let far = PlayerData { team: 1, position: Vector3 { x: NEAR_MISS_RADIUS * 4.0, y: 0.0, z: 0.0 }, ..test_player(3) };
+        let shot = Vector3::default();
+
+        assert!(near_missed(&missed, shot, shooter.identity, Some(shooter.team), None));
+        assert!(!near_missed(&missed, shot, shooter.identity, Some(shooter.team), Some(missed.identity)), "the player it hit isn't a near miss");
+        assert!(!near_missed(&teammate, shot, shooter.identity, Some(shooter.team), None));
+        assert!(!near_missed(&far, shot, shooter.identity, Some(shooter.team), None));
+        assert!(!near_missed(&shooter, shot, shooter.identity, Some(shooter.team), None));
+
+        let pinged_at = Timestamp::UNIX_EPOCH;
+        let ping = MinimapPing {
+            id: 1,
+            viewer: missed.identity,
+            revealed: shooter.identity,
+            position: shooter.position,
+            expires_at: pinged_at + TimeDuration::from_micros((NEAR_MISS_PING_SECS * 1_000_000.0) as i64),
+        };
+        assert!(!ping_expired(&ping, pinged_at + TimeDuration::from_micros(100_000)));
+        assert!(ping_expired(&ping, ping.expires_at));
+    }
+
+    #[test]
+    fn a_scanner_reveals_stealthed_players_nearby() {
+        let scanner_owner = test_player(1).identity;
+        let until = Timestamp::UNIX_EPOCH + TimeDuration::from_micros(2_000_000);
+        let scan = |target: &mut PlayerData| reveal(target, Vector3::default(), SCANNER_REVEAL_RADIUS, scanner_owner, until);
+
+        let mut nearby = stealthed_at(2, SCANNER_REVEAL_RADIUS * 0.5);
+        assert!(scan(&mut nearby));
+        assert_eq!(nearby.revealed_until, Some(until));
+        assert!(!scan(&mut nearby), "already revealed");
+
+        let mut far = stealthed_at(3, SCANNER_REVEAL_RADIUS * 3.0);
+        assert!(!scan(&mut far));
+        assert_eq!(far.revealed_until, None);
+
+        let mut visible = PlayerData { is_stealthed: false, ..stealthed_at(4, 1.0) };
+        assert!(!scan(&mut visible));
+    }
+}
@@ -0,0 +1,200 @@
+/*
+ * Vibe Coding Starter Pack: 3D Multiplayer - zones.rs
+ *
+ * Named regions of the map with gameplay rules (safe zones, sanctuaries).
+ *
+ * Key components:
+ *    - Zone: Public table of axis-aligned regions keyed by name. Where zones overlap, the
+ *      smallest one containing the point applies (so a small arena inside a big safe zone works)
+ *    - Each zone belongs to one map instance (instances.rs) and only applies to its players
+ *    - zone_at / find_zone_at: Zone containing a point; game_tick stores its name in
+ *      PlayerData.current_zone for the UI
+ *    - pvp_blocked: Player-vs-player harm is cancelled when either side stands in a no_pvp zone.
+ *      Checked by combat::apply_damage (knockback only follows damage that landed) and
+ *      status_effects::apply_status_effect; NPC and environmental damage still apply. Also
+ *      blocks harm across a duel boundary (arenas.rs)
+ *    - require_can_cast: Rejects spells and abilities cast from a no_cast zone
+ *    - regen_boost: Multiplies passive health/mana regen (player_logic::update_players_logic)
+ *    - no_mount: Riders are dismounted on entry and can't mount inside (mounts.rs)
+ *    - set_zone / remove_zone: Admin reducers (set_zone creates or replaces by name)
+ */
+
+use spacetimedb::{Identity, ReducerContext, Table};
+
+use crate::admin::require_admin;
+use crate::common::{Vector3, LOBBY_INSTANCE_ID};
+use crate::instances::instance;
+use crate::{player, PlayerData};
+
+#[spacetimedb::table(name = zone, public)]
+#[derive(Clone)]
+pub struct Zone {
+    #[primary_key]
+    pub name: String,
+    pub center: Vector3,
+    pub half_extents: Vector3,
+    pub no_pvp: bool,
+    pub no_cast: bool,
+    pub regen_boost: f32, // regen multiplier, 1.0 = normal
+    pub no_mount: bool,   // riders are knocked off their mount on entry (mounts.rs)
+    pub instance_id: u64, // map instance the zone belongs to (instances.rs)
+}
+
+impl Zone {
+    fn contains(&self, point: &Vector3) -> bool {
+        (point.x - self.center.x).abs() <= self.half_extents.x
+            && (point.y - self.center.y).abs() <= self.half_extents.y
+            && (point.z - self.center.z).abs() <= self.half_extents.z
+    }
+
+    fn volume(&self) -> f32 {
+        self.half_extents.x * self.half_extents.y * self.half_extents.z
+    }
+}
+
+pub fn seed_zones(ctx: &ReducerContext) {
+    if ctx.db.zone().count() > 0 {
+        return;
+    }
+    ctx.db.zone().insert(Zone {
+        name: "Sanctuary".to_string(),
+        center: Vector3 { x: 0.0, y: 4.0, z: -40.0 },
+        half_extents: Vector3 { x: 8.0, y: 5.0, z: 8.0 },
+        no_pvp: true,
+        no_cast: true,
+        regen_boost: 3.0,
+        no_mount: true,
+        instance_id: LOBBY_INSTANCE_ID,
+    });
+    spacetimedb::log::info!("[INIT] Seeded default zones.");
+}
+
+pub fn load_zones(ctx: &ReducerContext) -> Vec<Zone> {
+    ctx.db.zone().iter().collect()
+}
+
+pub fn zone_at<'a>(zones: &'a [Zone], instance_id: u64, point: &Vector3) -> Option<&'a Zone> {
+    zones.iter().filter(|z| z.instance_id == instance_id && z.contains(point)).min_by(|a, b| a.volume().total_cmp(&b.volume()))
+}
+
+pub fn find_zone_at(ctx: &ReducerContext, instance_id: u64, point: &Vector3) -> Option<Zone> {
+    zone_at(&load_zones(ctx), instance_id, point).cloned()
+}
+
+fn in_no_pvp_zone(zones: &[Zone], player: &PlayerData) -> bool {
+    zone_at(zones, player.instance_id, &player.position).is_some_and(|z| z.no_pvp)
+}
+
+// Whether harm from `attacker` to `target` is cancelled by a no_pvp zone. Only applies
+// between two different players; self-harm and non-player sources always go through.
+pub fn pvp_blocked(ctx: &ReducerContext, attacker: Option<Identity>, target: Identity) -> bool {
+    let Some(attacker) = attacker.filter(|a| *a != target).and_then(|a| ctx.db.player().identity().find(a)) else {
+        return false;
+    };
+    let Some(victim) = ctx.db.player().identity().find(target) else {
+        return false;
+    };
+    harm_blocked(&load_zones(ctx), &attacker, &victim)
+}
+
+// The rule behind pvp_blocked, for two different players
+fn harm_blocked(zones: &[Zone], attacker: &PlayerData, victim: &PlayerData) -> bool {
+    // Duelists and everyone outside their duel can't touch each other (arenas.rs), and
+    // neither can players in different instances
+    attacker.duel_id != victim.duel_id || attacker.instance_id != victim.instance_id || in_no_pvp_zone(zones, attacker) || in_no_pvp_zone(zones, victim)
+}
+
+pub fn require_can_cast(ctx: &ReducerContext, caster: &PlayerData) -> Result<(), String> {
+    match find_zone_at(ctx, caster.instance_id, &caster.position) {
+        Some(zone) if zone.no_cast => Err(format!("Casting is not allowed in {}", zone.name)),
+        _ => Ok(()),
+    }
+}
+
+pub fn regen_boost(zones: &[Zone], player: &PlayerData) -> f32 {
+    zone_at(zones, player.instance_id, &player.position).map_or(1.0, |z| z.regen_boost)
+}
+
+#[spacetimedb::reducer]
+#[allow(clippy::too_many_arguments)]
+pub fn set_zone(ctx: &ReducerContext, name: String, center: Vector3, half_extents: Vector3, no_pvp: bool, no_cast: bool, regen_boost: f32, no_mount: bool, instance_id: u64) -> Result<(), String> {
+    require_admin(ctx)?;
+    if name.trim().is_empty() {
+        return Err("Zone name must not be empty".to_string());
+    }
+    if ![half_extents.x, half_extents.y, half_extents.z].iter().all(|v| v.is_finite() && *v > 0.0) {
+        return Err("Zone extents must be positive".to_string());
+    }
+    if !regen_boost.is_finite() || regen_boost < 0.0 {
+        return Err("Regen boost must be non-negative".to_string());
+    }
+    if ctx.db.instance().id().find(instance_id).is_none() {
+        return Err("Instance not found".to_string());
+    }
+    let zone = Zone { name, center, half_extents, no_pvp, no_cast, regen_boost, no_mount, instance_id };
+    spacetimedb::log::info!("Zone '{}' set (no_pvp: {}, no_cast: {}, regen x{:.1})", zone.name, no_pvp, no_cast, regen_boost);
+    if ctx.db.zone().name().find(zone.name.clone()).is_some() {
+        ctx.db.zone().name().update(zone);
+    } else {
+        ctx.db.zone().insert(zone);
+    }
+    Ok(())
+}
+
+#[spacetimedb::reducer]
+pub fn remove_zone(ctx: &ReducerContext, name: String) -> Result<(), String> {
+    require_admin(ctx)?;
+    if !ctx.db.zone().name().delete(name.clone()) {
+        return Err(format!("Zone '{}' not found", name));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_player;
+
+    fn zone(name: &str, half: f32, no_pvp: bool, regen_boost: f32) -> Zone {
+        let half_extents = Vector3 { x: half, y: half, z: half };
+        Zone { name: name.to_string(), center: Vector3 { x: 0.0, y: 0.0, z: 0.0 }, half_extents, no_pvp, no_cast: false, regen_boost, no_mount: false, instance_id: LOBBY_INSTANCE_ID }
+    }
+
+    #[test]
+    fn the_smallest_zone_containing_a_player_sets_the_rules() {
+        let zones = [zone("Sanctuary", 20.0, true, 3.0), zone("Arena", 5.0, false, 1.0)];
+        let mut player = test_player(1);
+        player.position = Vector3 { x: 2.0, y: 0.0, z: 2.0 };
+        assert_eq!(zone_at(&zones, LOBBY_INSTANCE_ID, &player.position).map(|z| z.name.as_str()), Some("Arena"));
+        assert!(!in_no_pvp_zone(&zones, &player), "fighting allowed in the arena");
+        assert_eq!(regen_boost(&zones, &player), 1.0);
+
+        player.position.x = 10.0;
+        assert!(in_no_pvp_zone(&zones, &player));
+        assert_eq!(regen_boost(&zones, &player), 3.0);
+
+        player.instance_id = LOBBY_INSTANCE_ID + 1;
+        assert_eq!(regen_boost(&zones, &player), 1.0, "zones only apply in their own instance");
+        player.instance_id = LOBBY_INSTANCE_ID;
+
+        player.position.x = 30.0;
+        assert!(zone_at(&zones, LOBBY_INSTANCE_ID, &player.position).is_none());
+        assert_eq!(regen_boost(&zones, &player), 1.0, "no zone, normal regen");
+    }
+
+    #[test]
+    fn duelists_and_outsiders_cannot_harm_each_other() {
+        let zones = [zone("Sanctuary", 20.0, true, 3.0)];
+        let far = Vector3 { x: 100.0, y: 0.0, z: 0.0 };
+        let duelist = PlayerData { duel_id: Some(7), position: far, ..test_player(1) };
+        let opponent = PlayerData { duel_id: Some(7), position: far, ..test_player(2) };
+        let outsider = PlayerData { position: far, ..test_player(3) };
+        assert!(!harm_blocked(&zones, &duelist, &opponent));
+        assert!(harm_blocked(&zones, &outsider, &duelist), "can't interfere with a duel");
+        assert!(harm_blocked(&zones, &duelist, &outsider), "or be hit from one");
+        assert!(harm_blocked(&zones, &PlayerData { duel_id: Some(8), ..opponent }, &duelist), "another duel");
+
+        let safe = PlayerData { position: Vector3 { x: 0.0, y: 0.0, z: 0.0 }, ..test_player(4) };
+        assert!(harm_blocked(&zones, &outsider, &safe), "no_pvp zone");
+    }
+}